@@ -0,0 +1,258 @@
+//! Object-safe trait over the OSS operations.
+//!
+//! Application code can depend on `Arc<dyn OssApi>` instead of [`OssClient`],
+//! so tests can substitute a hand-written double that needs no network.
+//! Every operation has a default implementation returning
+//! [`OssError::Unsupported`], so a double only implements what it uses.
+//!
+//! # Examples
+//! ```
+//! use std::sync::Arc;
+//! use rs_ali_oss::OssApi;
+//! use rs_ali_oss::api::OssFuture;
+//! use rs_ali_oss::types::request::DeleteObjectRequest;
+//! use rs_ali_oss::types::response::DeleteObjectResponse;
+//!
+//! struct AlwaysDeletes;
+//!
+//! impl OssApi for AlwaysDeletes {
+//!     fn delete_object(&self, _: DeleteObjectRequest) -> OssFuture<'_, DeleteObjectResponse> {
+//!         Box::pin(async { Ok(DeleteObjectResponse::default()) })
+//!     }
+//! }
+//!
+//! let api: Arc<dyn OssApi> = Arc::new(AlwaysDeletes);
+//! ```
+
+use futures_util::future::BoxFuture;
+
+use crate::client::OssClient;
+use crate::error::{OssError, Result};
+use crate::types::{request, response};
+
+/// Future returned by [`OssApi`] operations.
+pub type OssFuture<'a, T> = BoxFuture<'a, Result<T>>;
+
+macro_rules! oss_api {
+    ($($name:ident($request:ident) -> $response:ident;)*) => {
+        /// The OSS operations, implemented by [`OssClient`].
+        ///
+        /// Each method behaves like the [`OssClient`] method of the same name.
+        pub trait OssApi: Send + Sync {
+            $(
+                #[doc = concat!("See [`OssClient::", stringify!($name), "`].")]
+                fn $name(
+                    &self,
+                    request: request::$request,
+                ) -> OssFuture<'_, response::$response> {
+                    let _ = request;
+                    unsupported(stringify!($name))
+                }
+            )*
+
+            /// See [`OssClient::presign_get_object`].
+            fn presign_get_object(&self, request: request::PresignedUrlRequest) -> Result<String> {
+                let _ = request;
+                Err(OssError::Unsupported("presign_get_object".into()))
+            }
+
+            /// See [`OssClient::presign_put_object`].
+            fn presign_put_object(&self, request: request::PresignedUrlRequest) -> Result<String> {
+                let _ = request;
+                Err(OssError::Unsupported("presign_put_object".into()))
+            }
+        }
+
+        impl OssApi for OssClient {
+            $(
+                fn $name(
+                    &self,
+                    request: request::$request,
+                ) -> OssFuture<'_, response::$response> {
+                    Box::pin(OssClient::$name(self, request))
+                }
+            )*
+
+            fn presign_get_object(&self, request: request::PresignedUrlRequest) -> Result<String> {
+                OssClient::presign_get_object(self, request)
+            }
+
+            fn presign_put_object(&self, request: request::PresignedUrlRequest) -> Result<String> {
+                OssClient::presign_put_object(self, request)
+            }
+        }
+    };
+}
+
+fn unsupported<T>(operation: &str) -> OssFuture<'static, T> {
+    let err = OssError::Unsupported(operation.to_string());
+    Box::pin(async move { Err(err) })
+}
+
+oss_api! {
+    // Buckets
+    create_bucket(CreateBucketRequest) -> CreateBucketResponse;
+    delete_bucket(DeleteBucketRequest) -> DeleteBucketResponse;
+    list_buckets(ListBucketsRequest) -> ListBucketsResponse;
+    get_bucket_info(GetBucketInfoRequest) -> GetBucketInfoResponse;
+    get_bucket_location(GetBucketLocationRequest) -> GetBucketLocationResponse;
+    put_bucket_acl(PutBucketAclRequest) -> PutBucketAclResponse;
+    get_bucket_acl(GetBucketAclRequest) -> GetBucketAclResponse;
+    put_bucket_cors(PutBucketCorsRequest) -> PutBucketCorsResponse;
+    get_bucket_cors(GetBucketCorsRequest) -> GetBucketCorsResponse;
+    delete_bucket_cors(DeleteBucketCorsRequest) -> DeleteBucketCorsResponse;
+    put_bucket_referer(PutBucketRefererRequest) -> PutBucketRefererResponse;
+    get_bucket_referer(GetBucketRefererRequest) -> GetBucketRefererResponse;
+    put_bucket_policy(PutBucketPolicyRequest) -> PutBucketPolicyResponse;
+    get_bucket_policy(GetBucketPolicyRequest) -> GetBucketPolicyResponse;
+    delete_bucket_policy(DeleteBucketPolicyRequest) -> DeleteBucketPolicyResponse;
+    put_bucket_versioning(PutBucketVersioningRequest) -> PutBucketVersioningResponse;
+    get_bucket_versioning(GetBucketVersioningRequest) -> GetBucketVersioningResponse;
+    put_bucket_lifecycle(PutBucketLifecycleRequest) -> PutBucketLifecycleResponse;
+    get_bucket_lifecycle(GetBucketLifecycleRequest) -> GetBucketLifecycleResponse;
+    delete_bucket_lifecycle(DeleteBucketLifecycleRequest) -> DeleteBucketLifecycleResponse;
+    put_bucket_encryption(PutBucketEncryptionRequest) -> PutBucketEncryptionResponse;
+    get_bucket_encryption(GetBucketEncryptionRequest) -> GetBucketEncryptionResponse;
+    delete_bucket_encryption(DeleteBucketEncryptionRequest) -> DeleteBucketEncryptionResponse;
+    put_bucket_logging(PutBucketLoggingRequest) -> PutBucketLoggingResponse;
+    get_bucket_logging(GetBucketLoggingRequest) -> GetBucketLoggingResponse;
+    delete_bucket_logging(DeleteBucketLoggingRequest) -> DeleteBucketLoggingResponse;
+    put_bucket_transfer_acceleration(PutBucketTransferAccelerationRequest)
+        -> PutBucketTransferAccelerationResponse;
+    get_bucket_transfer_acceleration(GetBucketTransferAccelerationRequest)
+        -> GetBucketTransferAccelerationResponse;
+    create_bucket_data_redundancy_transition(CreateBucketDataRedundancyTransitionRequest)
+        -> CreateBucketDataRedundancyTransitionResponse;
+    get_bucket_data_redundancy_transition(GetBucketDataRedundancyTransitionRequest)
+        -> GetBucketDataRedundancyTransitionResponse;
+    list_bucket_data_redundancy_transition(ListBucketDataRedundancyTransitionRequest)
+        -> ListBucketDataRedundancyTransitionResponse;
+    delete_bucket_data_redundancy_transition(DeleteBucketDataRedundancyTransitionRequest)
+        -> DeleteBucketDataRedundancyTransitionResponse;
+    put_cname(PutCnameRequest) -> PutCnameResponse;
+
+    // Objects
+    put_object(PutObjectRequest) -> PutObjectResponse;
+    get_object(GetObjectRequest) -> GetObjectResponse;
+    delete_object(DeleteObjectRequest) -> DeleteObjectResponse;
+    head_object(HeadObjectRequest) -> HeadObjectResponse;
+    list_objects_v2(ListObjectsV2Request) -> ListObjectsV2Response;
+    list_object_versions(ListObjectVersionsRequest) -> ListObjectVersionsResponse;
+    copy_object(CopyObjectRequest) -> CopyObjectResponse;
+    delete_multiple_objects(DeleteMultipleObjectsRequest) -> DeleteMultipleObjectsResponse;
+    restore_object(RestoreObjectRequest) -> RestoreObjectResponse;
+    append_object(AppendObjectRequest) -> AppendObjectResponse;
+    get_object_acl(GetObjectAclRequest) -> GetObjectAclResponse;
+    put_object_acl(PutObjectAclRequest) -> PutObjectAclResponse;
+    get_object_tagging(GetObjectTaggingRequest) -> GetObjectTaggingResponse;
+    put_object_tagging(PutObjectTaggingRequest) -> PutObjectTaggingResponse;
+    delete_object_tagging(DeleteObjectTaggingRequest) -> DeleteObjectTaggingResponse;
+
+    // Multipart uploads
+    initiate_multipart_upload(InitiateMultipartUploadRequest) -> InitiateMultipartUploadResponse;
+    upload_part(UploadPartRequest) -> UploadPartResponse;
+    upload_part_copy(UploadPartCopyRequest) -> UploadPartCopyResponse;
+    complete_multipart_upload(CompleteMultipartUploadRequest) -> CompleteMultipartUploadResponse;
+    abort_multipart_upload(AbortMultipartUploadRequest) -> AbortMultipartUploadResponse;
+    list_parts(ListPartsRequest) -> ListPartsResponse;
+    list_multipart_uploads(ListMultipartUploadsRequest) -> ListMultipartUploadsResponse;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::config::ClientBuilder;
+    use crate::types::common::{BucketName, ObjectKey};
+    use crate::types::request::{DeleteObjectRequestBuilder, HeadObjectRequestBuilder};
+    use crate::types::response::HeadObjectResponse;
+
+    struct FixedHead;
+
+    impl OssApi for FixedHead {
+        fn head_object(&self, _: request::HeadObjectRequest) -> OssFuture<'_, HeadObjectResponse> {
+            Box::pin(async {
+                Ok(HeadObjectResponse {
+                    content_type: None,
+                    content_length: Some(42),
+                    cache_control: None,
+                    content_disposition: None,
+                    content_encoding: None,
+                    expires: None,
+                    etag: None,
+                    last_modified: None,
+                    crc64: None,
+                    storage_class: None,
+                    object_type: None,
+                    version_id: None,
+                    server_side_encryption: None,
+                    kms_key_id: None,
+                    restore: None,
+                    expiration: None,
+                    metadata: Default::default(),
+                    request_id: None,
+                })
+            })
+        }
+    }
+
+    fn bucket() -> BucketName {
+        BucketName::new("my-bucket").unwrap()
+    }
+
+    fn key() -> ObjectKey {
+        ObjectKey::new("a.txt").unwrap()
+    }
+
+    #[tokio::test]
+    async fn doubles_implement_only_what_they_need() {
+        let api: Arc<dyn OssApi> = Arc::new(FixedHead);
+        let head = api
+            .head_object(
+                HeadObjectRequestBuilder::new()
+                    .bucket(bucket())
+                    .key(key())
+                    .build()
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(head.content_length, Some(42));
+
+        let err = api
+            .delete_object(
+                DeleteObjectRequestBuilder::new()
+                    .bucket(bucket())
+                    .key(key())
+                    .build()
+                    .unwrap(),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, OssError::Unsupported(ref op) if op == "delete_object"));
+    }
+
+    #[test]
+    fn client_is_an_oss_api() {
+        let client = OssClient::from_builder(
+            ClientBuilder::new()
+                .access_key_id("test-id")
+                .access_key_secret("test-secret")
+                .region("cn-hangzhou"),
+        )
+        .unwrap();
+        let api: Arc<dyn OssApi> = Arc::new(client);
+        let url = api
+            .presign_get_object(
+                request::PresignedUrlRequestBuilder::new()
+                    .bucket(bucket())
+                    .key(key())
+                    .expires(std::time::Duration::from_secs(60))
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+        assert!(url.contains("a.txt"));
+    }
+}
@@ -1,5 +1,167 @@
 //! Authentication and request signing for Alibaba Cloud OSS.
 
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use futures_util::future::BoxFuture;
+
+use crate::config::{Credentials, PayloadSigning};
+use crate::error::Result;
+use crate::types::Region;
+
+pub mod v1;
 pub mod v4;
 
 pub use v4::sign_request;
+
+/// Intermediate values computed while signing a request.
+///
+/// When OSS answers `SignatureDoesNotMatch`, its error body echoes the
+/// canonical request / string-to-sign it computed; comparing them with these
+/// values pinpoints the mismatch (typically a header rewritten by a proxy or
+/// gateway). Enable with
+/// [`ClientBuilder::expose_signing_details`](crate::config::ClientBuilder::expose_signing_details)
+/// to receive them in [`InterceptorContext::signing`](crate::middleware::InterceptorContext::signing).
+///
+/// The canonical request includes signed header values such as the STS
+/// security token, so avoid logging it in production.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SigningDetails {
+    /// Signature version that produced these values.
+    pub version: SignatureVersion,
+    /// Canonical request (V4) or canonicalized resource (V1).
+    pub canonical_request: String,
+    /// Lowercase names of the headers covered by the signature, sorted.
+    pub signed_headers: Vec<String>,
+    /// The exact string that was signed.
+    pub string_to_sign: String,
+}
+
+/// Signature algorithm used to authenticate requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SignatureVersion {
+    /// Legacy header-based HMAC-SHA1 signature, for OSS-compatible
+    /// deployments that do not accept V4.
+    V1,
+    /// OSS4-HMAC-SHA256 signature (default).
+    #[default]
+    V4,
+}
+
+/// What a [`Signer`] needs to sign one request attempt, besides the request.
+#[derive(Debug)]
+pub struct SigningContext<'a> {
+    /// Credentials resolved for this attempt.
+    pub credentials: &'a Credentials,
+    /// Region of the client.
+    pub region: &'a Region,
+    /// Time to sign with.
+    pub datetime: DateTime<Utc>,
+    /// Unencoded `/{bucket}/{key}` (or `/{bucket}/`, `/`) being accessed.
+    pub resource_path: &'a str,
+    /// Extra headers to sign (V4 `AdditionalHeaders`), lowercase and sorted.
+    pub additional_headers: &'a [String],
+    /// Whether V4 signatures cover the request body.
+    pub payload_signing: PayloadSigning,
+}
+
+/// Signs outgoing requests.
+///
+/// The client calls it once per attempt, after interceptors have throttled
+/// and before they see the request, so alternative signing (hardware-backed
+/// keys, a signing proxy, a compatibility shim) can be plugged in with
+/// [`ClientBuilder::signer`](crate::config::ClientBuilder::signer) without
+/// reimplementing the request pipeline. Presigned URLs are not affected.
+///
+/// # Examples
+/// ```
+/// use rs_ali_oss::auth::{Signer, SigningContext, SigningDetails, V4Signer};
+///
+/// /// Signs with V4 and tags every request with the signing host.
+/// struct TaggingSigner;
+///
+/// impl Signer for TaggingSigner {
+///     fn sign(
+///         &self,
+///         request: &mut reqwest::Request,
+///         context: &SigningContext<'_>,
+///     ) -> rs_ali_oss::Result<Option<SigningDetails>> {
+///         request
+///             .headers_mut()
+///             .insert("x-signed-by", "build-42".parse().unwrap());
+///         V4Signer.sign(request, context)
+///     }
+/// }
+/// ```
+pub trait Signer: Send + Sync {
+    /// Sign `request` in place, returning the signing details if the signer
+    /// has them (see
+    /// [`ClientBuilder::expose_signing_details`](crate::config::ClientBuilder::expose_signing_details)).
+    fn sign(
+        &self,
+        request: &mut reqwest::Request,
+        context: &SigningContext<'_>,
+    ) -> Result<Option<SigningDetails>>;
+
+    /// Async variant of [`sign`](Self::sign); this is what the client calls.
+    ///
+    /// Override it when signing needs to await, e.g. a remote key service or
+    /// signing proxy. The default implementation calls [`sign`](Self::sign)
+    /// inline.
+    fn sign_async<'a>(
+        &'a self,
+        request: &'a mut reqwest::Request,
+        context: &'a SigningContext<'a>,
+    ) -> BoxFuture<'a, Result<Option<SigningDetails>>> {
+        Box::pin(std::future::ready(self.sign(request, context)))
+    }
+}
+
+impl fmt::Debug for dyn Signer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Signer")
+    }
+}
+
+/// The OSS V4 signer ([`SignatureVersion::V4`]).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct V4Signer;
+
+impl Signer for V4Signer {
+    fn sign(
+        &self,
+        request: &mut reqwest::Request,
+        context: &SigningContext<'_>,
+    ) -> Result<Option<SigningDetails>> {
+        v4::sign_request_with_payload(
+            request,
+            context.credentials,
+            context.region,
+            context.datetime,
+            context.resource_path,
+            context.additional_headers,
+            context.payload_signing,
+        )
+        .map(Some)
+    }
+}
+
+/// The legacy OSS V1 signer ([`SignatureVersion::V1`]).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct V1Signer;
+
+impl Signer for V1Signer {
+    fn sign(
+        &self,
+        request: &mut reqwest::Request,
+        context: &SigningContext<'_>,
+    ) -> Result<Option<SigningDetails>> {
+        v1::sign_request_with_details(
+            request,
+            context.credentials,
+            context.datetime,
+            context.resource_path,
+        )
+        .map(Some)
+    }
+}
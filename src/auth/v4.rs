@@ -11,7 +11,8 @@ use reqwest::header::HeaderMap;
 use sha2::{Digest, Sha256};
 use zeroize::Zeroizing;
 
-use crate::config::Credentials;
+use super::{SignatureVersion, SigningDetails};
+use crate::config::{Credentials, PayloadSigning};
 use crate::encoding::{QUERY_ENCODE_SET, URI_ENCODE_SET};
 use crate::error::OssError;
 use crate::types::Region;
@@ -59,10 +60,51 @@ fn canonical_query_string(url: &url::Url) -> String {
         .join("&")
 }
 
-fn canonical_and_signed_headers(headers: &HeaderMap) -> (String, String) {
+/// Normalize user-requested additional signed headers: lowercase, sorted,
+/// deduplicated, and without headers that are signed by default.
+///
+/// # Errors
+///
+/// Returns [`OssError::InvalidParameter`] if a name is not a valid header name.
+pub(crate) fn normalize_additional_headers<I, S>(names: I) -> crate::error::Result<Vec<String>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut normalized = Vec::new();
+    for name in names {
+        let name = name.as_ref().trim().to_lowercase();
+        if reqwest::header::HeaderName::from_bytes(name.as_bytes()).is_err() {
+            return Err(OssError::InvalidParameter {
+                field: "sign_additional_headers".into(),
+                reason: format!("`{name}` is not a valid header name"),
+            });
+        }
+        if !is_default_signed_header(&name) {
+            normalized.push(name);
+        }
+    }
+    normalized.sort();
+    normalized.dedup();
+    Ok(normalized)
+}
+
+/// Returns the canonical headers, the additional-headers list and the names of
+/// all signed headers.
+///
+/// `additional` must already be normalized; names absent from the request are
+/// skipped. `host` is taken from the URL because the HTTP client only adds the
+/// header when sending.
+fn canonical_and_signed_headers(
+    url: &url::Url,
+    headers: &HeaderMap,
+    additional: &[String],
+) -> (String, String, Vec<String>) {
     let mut header_list: Vec<(String, String)> = headers
         .iter()
-        .filter(|(name, _)| is_default_signed_header(name.as_str()))
+        .filter(|(name, _)| {
+            is_default_signed_header(name.as_str()) || additional.iter().any(|a| a == name.as_str())
+        })
         .map(|(name, value)| {
             (
                 name.as_str().to_lowercase(),
@@ -70,6 +112,16 @@ fn canonical_and_signed_headers(headers: &HeaderMap) -> (String, String) {
             )
         })
         .collect();
+    if additional.iter().any(|a| a == "host")
+        && !headers.contains_key("host")
+        && let Some(host) = url.host_str()
+    {
+        let value = match url.port() {
+            Some(port) => format!("{host}:{port}"),
+            None => host.to_string(),
+        };
+        header_list.push(("host".to_string(), value));
+    }
     header_list.sort_by(|a, b| a.0.cmp(&b.0));
 
     let canonical = header_list
@@ -77,10 +129,16 @@ fn canonical_and_signed_headers(headers: &HeaderMap) -> (String, String) {
         .map(|(k, v)| format!("{k}:{v}\n"))
         .collect::<String>();
 
-    // No user-specified additional headers
-    let additional = String::new();
+    let mut additional_signed: Vec<&str> = header_list
+        .iter()
+        .map(|(k, _)| k.as_str())
+        .filter(|k| !is_default_signed_header(k))
+        .collect();
+    additional_signed.dedup();
+    let additional = additional_signed.join(";");
+    let names = header_list.into_iter().map(|(k, _)| k).collect();
 
-    (canonical, additional)
+    (canonical, additional, names)
 }
 
 fn build_canonical_request(
@@ -88,17 +146,20 @@ fn build_canonical_request(
     resource_path: &str,
     url: &url::Url,
     headers: &HeaderMap,
-) -> (String, String) {
+    additional: &[String],
+    payload_hash: &str,
+) -> (String, String, Vec<String>) {
     let uri = canonical_uri(resource_path);
     let query = canonical_query_string(url);
-    let (canonical_hdrs, additional_hdrs) = canonical_and_signed_headers(headers);
+    let (canonical_hdrs, additional_hdrs, signed_names) =
+        canonical_and_signed_headers(url, headers, additional);
 
     let canonical_request = format!(
         "{}\n{}\n{}\n{}\n{}\n{}",
-        method, uri, query, canonical_hdrs, additional_hdrs, UNSIGNED_PAYLOAD
+        method, uri, query, canonical_hdrs, additional_hdrs, payload_hash
     );
 
-    (canonical_request, additional_hdrs)
+    (canonical_request, additional_hdrs, signed_names)
 }
 
 /// Build the string-to-sign from the datetime, scope, and canonical request.
@@ -153,6 +214,27 @@ pub(crate) fn calculate_signature(
     Ok(hex::encode(sig_bytes))
 }
 
+/// The `x-oss-content-sha256` value for the request's body.
+fn payload_hash(
+    req: &reqwest::Request,
+    payload_signing: PayloadSigning,
+) -> crate::error::Result<String> {
+    let body = match req.body() {
+        Some(body) => body.as_bytes(),
+        None => Some(&[][..]),
+    };
+    match (payload_signing, body) {
+        (PayloadSigning::Unsigned, _) | (PayloadSigning::InMemory, None) => {
+            Ok(UNSIGNED_PAYLOAD.to_string())
+        }
+        (_, Some(bytes)) => Ok(hex::encode(Sha256::digest(bytes))),
+        (PayloadSigning::Required, None) => Err(OssError::InvalidParameter {
+            field: "body".into(),
+            reason: "payload signing is required but a streaming body cannot be hashed".into(),
+        }),
+    }
+}
+
 /// Sign a request using OSS V4 signature (OSS4-HMAC-SHA256).
 ///
 /// Adds `x-oss-date`, `x-oss-content-sha256`, and `Authorization` headers
@@ -168,6 +250,52 @@ pub fn sign_request(
     datetime: DateTime<Utc>,
     resource_path: &str,
 ) -> crate::error::Result<()> {
+    sign_request_with_details(req, credentials, region, datetime, resource_path, &[]).map(|_| ())
+}
+
+/// Like [`sign_request`], but also signs the `additional_headers` present on
+/// the request (listed in `AdditionalHeaders`) and returns the canonical
+/// request and string-to-sign for debugging signature mismatches.
+///
+/// Header names in `additional_headers` are matched case-insensitively;
+/// headers signed by default (`x-oss-*`, `content-type`, `content-md5`) are
+/// ignored.
+///
+/// # Errors
+///
+/// Returns [`OssError::Auth`] if any header value cannot be constructed.
+pub fn sign_request_with_details(
+    req: &mut reqwest::Request,
+    credentials: &Credentials,
+    region: &Region,
+    datetime: DateTime<Utc>,
+    resource_path: &str,
+    additional_headers: &[String],
+) -> crate::error::Result<SigningDetails> {
+    sign_request_with_payload(
+        req,
+        credentials,
+        region,
+        datetime,
+        resource_path,
+        additional_headers,
+        PayloadSigning::Unsigned,
+    )
+}
+
+/// Like [`sign_request_with_details`], but hashes the body into
+/// `x-oss-content-sha256` as `payload_signing` requires.
+pub(crate) fn sign_request_with_payload(
+    req: &mut reqwest::Request,
+    credentials: &Credentials,
+    region: &Region,
+    datetime: DateTime<Utc>,
+    resource_path: &str,
+    additional_headers: &[String],
+    payload_signing: PayloadSigning,
+) -> crate::error::Result<SigningDetails> {
+    let additional_headers = normalize_additional_headers(additional_headers)?;
+    let payload_hash = payload_hash(req, payload_signing)?;
     let datetime_str = datetime.format("%Y%m%dT%H%M%SZ").to_string();
     let date_str = datetime.format("%Y%m%d").to_string();
     let region_str: &str = region.as_ref();
@@ -182,7 +310,7 @@ pub fn sign_request(
     );
     headers.insert(
         "x-oss-content-sha256",
-        UNSIGNED_PAYLOAD
+        payload_hash
             .parse()
             .map_err(|_| OssError::Auth("failed to set x-oss-content-sha256 header".to_string()))?,
     );
@@ -199,8 +327,14 @@ pub fn sign_request(
     // Build canonical request
     let method = req.method().as_str().to_string();
     let url = req.url().clone();
-    let (canonical_request, additional_headers) =
-        build_canonical_request(&method, resource_path, &url, req.headers());
+    let (canonical_request, additional_headers, signed_headers) = build_canonical_request(
+        &method,
+        resource_path,
+        &url,
+        req.headers(),
+        &additional_headers,
+        &payload_hash,
+    );
 
     // Build string to sign
     let string_to_sign =
@@ -236,7 +370,12 @@ pub fn sign_request(
             .map_err(|_| OssError::Auth("failed to set authorization header".to_string()))?,
     );
 
-    Ok(())
+    Ok(SigningDetails {
+        version: SignatureVersion::V4,
+        canonical_request,
+        signed_headers,
+        string_to_sign,
+    })
 }
 
 #[cfg(test)]
@@ -395,6 +534,81 @@ mod tests {
         assert_eq!(auth, auth2);
     }
 
+    #[tokio::test]
+    async fn test_sign_request_payload_signing_modes() {
+        let client = reqwest::Client::new();
+        let creds = crate::config::Credentials::new("ak", "sk");
+        let region = crate::types::Region::new("cn-hangzhou").unwrap();
+        let url = "https://examplebucket.oss-cn-hangzhou.aliyuncs.com/exampleobject";
+        let content_sha = |req: &reqwest::Request| {
+            req.headers()["x-oss-content-sha256"]
+                .to_str()
+                .unwrap()
+                .to_string()
+        };
+
+        let mut req = client.put(url).body(b"Hello OSS".to_vec()).build().unwrap();
+        sign_request_with_payload(
+            &mut req,
+            &creds,
+            &region,
+            Utc::now(),
+            "/examplebucket/exampleobject",
+            &[],
+            PayloadSigning::InMemory,
+        )
+        .unwrap();
+        assert_eq!(content_sha(&req), hex::encode(Sha256::digest(b"Hello OSS")));
+
+        let mut req = client.get(url).build().unwrap();
+        sign_request_with_payload(
+            &mut req,
+            &creds,
+            &region,
+            Utc::now(),
+            "/examplebucket/exampleobject",
+            &[],
+            PayloadSigning::Required,
+        )
+        .unwrap();
+        assert_eq!(content_sha(&req), hex::encode(Sha256::digest(b"")));
+
+        let stream = futures_util::stream::iter(vec![Ok::<_, std::io::Error>(
+            bytes::Bytes::from_static(b"chunk"),
+        )]);
+        let mut req = client
+            .put(url)
+            .body(reqwest::Body::wrap_stream(stream))
+            .build()
+            .unwrap();
+        sign_request_with_payload(
+            &mut req,
+            &creds,
+            &region,
+            Utc::now(),
+            "/examplebucket/exampleobject",
+            &[],
+            PayloadSigning::InMemory,
+        )
+        .unwrap();
+        assert_eq!(content_sha(&req), UNSIGNED_PAYLOAD);
+
+        let err = sign_request_with_payload(
+            &mut req,
+            &creds,
+            &region,
+            Utc::now(),
+            "/examplebucket/exampleobject",
+            &[],
+            PayloadSigning::Required,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            OssError::InvalidParameter { field, .. } if field == "body"
+        ));
+    }
+
     #[test]
     fn test_string_to_sign_format() {
         let result = build_string_to_sign(
@@ -484,6 +698,98 @@ mod tests {
         assert_eq!(token, "sts-token-value");
     }
 
+    #[tokio::test]
+    async fn test_sign_request_with_details_matches_authorization() {
+        let client = reqwest::Client::new();
+        let mut req = client
+            .put("https://my-bucket.oss-cn-hangzhou.aliyuncs.com/test.txt?acl")
+            .header("content-type", "text/plain")
+            .build()
+            .unwrap();
+        let creds = crate::config::Credentials::new("id", "secret");
+        let region = crate::types::Region::new("cn-hangzhou").unwrap();
+        let dt = chrono::Utc::now();
+
+        let details =
+            sign_request_with_details(&mut req, &creds, &region, dt, "/my-bucket/test.txt", &[])
+                .unwrap();
+
+        assert_eq!(details.version, SignatureVersion::V4);
+        assert!(
+            details
+                .canonical_request
+                .starts_with("PUT\n/my-bucket/test.txt\nacl\n")
+        );
+        assert_eq!(
+            details.signed_headers,
+            ["content-type", "x-oss-content-sha256", "x-oss-date"]
+        );
+        assert_eq!(
+            details.string_to_sign,
+            build_string_to_sign(
+                &dt.format("%Y%m%dT%H%M%SZ").to_string(),
+                &dt.format("%Y%m%d").to_string(),
+                "cn-hangzhou",
+                &details.canonical_request,
+            )
+        );
+        let key =
+            derive_signing_key("secret", &dt.format("%Y%m%d").to_string(), "cn-hangzhou").unwrap();
+        let signature = calculate_signature(&key, &details.string_to_sign).unwrap();
+        let auth = req.headers()["authorization"].to_str().unwrap();
+        assert!(auth.ends_with(&format!("Signature={signature}")));
+    }
+
+    #[test]
+    fn test_normalize_additional_headers() {
+        let names = normalize_additional_headers(["X-Custom", "Host", "content-type", "host"]);
+        assert_eq!(names.unwrap(), ["host", "x-custom"]);
+        assert!(matches!(
+            normalize_additional_headers(["bad header"]),
+            Err(OssError::InvalidParameter { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sign_request_with_additional_headers() {
+        let client = reqwest::Client::new();
+        let mut req = client
+            .get("https://my-bucket.oss-cn-hangzhou.aliyuncs.com:8443/test.txt")
+            .header("x-custom", " pinned ")
+            .build()
+            .unwrap();
+        let creds = crate::config::Credentials::new("id", "secret");
+        let region = crate::types::Region::new("cn-hangzhou").unwrap();
+        let additional = [
+            "x-custom".to_string(),
+            "host".to_string(),
+            "range".to_string(),
+        ];
+
+        let details = sign_request_with_details(
+            &mut req,
+            &creds,
+            &region,
+            chrono::Utc::now(),
+            "/my-bucket/test.txt",
+            &additional,
+        )
+        .unwrap();
+
+        assert!(details.canonical_request.contains(
+            "host:my-bucket.oss-cn-hangzhou.aliyuncs.com:8443\n\
+             x-custom:pinned\n\
+             x-oss-content-sha256:UNSIGNED-PAYLOAD\n"
+        ));
+        assert!(
+            details
+                .canonical_request
+                .contains("\n\nhost;x-custom\nUNSIGNED-PAYLOAD")
+        );
+        let auth = req.headers()["authorization"].to_str().unwrap();
+        assert!(auth.contains(",AdditionalHeaders=host;x-custom,Signature="));
+    }
+
     #[test]
     fn test_percent_encoding_special_chars() {
         let result = canonical_uri("/bucket/file name+test=value&other");
@@ -0,0 +1,366 @@
+//! Blocking (synchronous) client, enabled with the `blocking` feature.
+//!
+//! [`OssBlockingClient`] wraps an [`OssClient`] and drives it on an internal
+//! single-threaded tokio runtime, so CLI tools and non-async codebases can use
+//! the SDK without setting up a runtime themselves.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::io::{self, Read};
+use std::sync::Arc;
+
+use bytes::{Buf, Bytes};
+
+use crate::client::OssClient;
+use crate::config::{ClientBuilder, Config, OperationOptions};
+use crate::error::Result;
+use crate::types::common::{BucketName, ObjectKey, ObjectType, StorageClass};
+use crate::types::request::{self, PresignedUrlRequest};
+use crate::types::response;
+
+/// Blocking counterpart of [`OssClient`].
+///
+/// Every operation blocks the calling thread until it completes. Clones share
+/// the runtime and connection pool; the client can be used from several
+/// threads at once.
+///
+/// # Panics
+///
+/// Operations panic when called from within an async runtime, because they
+/// would block one of its worker threads. Use [`OssClient`] there instead.
+///
+/// # Examples
+/// ```no_run
+/// use rs_ali_oss::blocking::OssBlockingClient;
+/// use rs_ali_oss::types::request::PutObjectRequestBuilder;
+/// use rs_ali_oss::{BucketName, ClientBuilder, ObjectKey};
+///
+/// # fn example() -> rs_ali_oss::Result<()> {
+/// let client = OssBlockingClient::from_builder(
+///     ClientBuilder::new()
+///         .access_key_id("your-key-id")
+///         .access_key_secret("your-key-secret")
+///         .region("cn-hangzhou"),
+/// )?;
+/// let request = PutObjectRequestBuilder::new()
+///     .bucket(BucketName::new("my-bucket")?)
+///     .key(ObjectKey::new("hello.txt")?)
+///     .body(b"Hello, OSS!".to_vec())
+///     .build()?;
+/// client.put_object(request)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct OssBlockingClient {
+    inner: OssClient,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+macro_rules! blocking_ops {
+    ($($name:ident($request:ty) -> $response:ty;)*) => {
+        $(
+            #[doc = concat!("Blocking version of [`OssClient::", stringify!($name), "`].")]
+            pub fn $name(&self, request: $request) -> Result<$response> {
+                self.block_on(self.inner.$name(request))
+            }
+        )*
+    };
+}
+
+impl OssBlockingClient {
+    /// Create a new blocking client with the given configuration.
+    pub fn new(config: Config) -> Result<Self> {
+        Self::from_async(OssClient::new(config)?)
+    }
+
+    /// Create a blocking client from a builder.
+    pub fn from_builder(builder: ClientBuilder) -> Result<Self> {
+        Self::new(builder.build()?)
+    }
+
+    /// Wrap an existing async client, keeping its interceptors, circuit
+    /// breaker and operation options.
+    pub fn from_async(client: OssClient) -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        Ok(Self {
+            inner: client,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// Returns the wrapped async client.
+    pub fn async_client(&self) -> &OssClient {
+        &self.inner
+    }
+
+    /// Returns a reference to the underlying configuration.
+    pub fn config(&self) -> &Config {
+        self.inner.config()
+    }
+
+    /// Blocking version of [`OssClient::with_options`].
+    pub fn with_options(&self, options: OperationOptions) -> Self {
+        Self {
+            inner: self.inner.with_options(options),
+            runtime: Arc::clone(&self.runtime),
+        }
+    }
+
+    /// Run a future on the client's runtime, e.g. to drive a paginator or a
+    /// [`TransferManager`](crate::TransferManager) built from
+    /// [`async_client`](Self::async_client).
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Blocking version of [`OssClient::get_object`].
+    ///
+    /// The body is streamed: read it through [`BlockingObjectBody`]'s
+    /// [`Read`] implementation or buffer it with [`BlockingObjectBody::bytes`].
+    pub fn get_object(&self, request: request::GetObjectRequest) -> Result<GetObjectResponse> {
+        let response = self.block_on(self.inner.get_object(request))?;
+        Ok(self.wrap_get_object(response))
+    }
+
+    /// Blocking version of [`OssClient::get_object_with_options`].
+    pub fn get_object_with_options(
+        &self,
+        request: request::GetObjectRequest,
+        options: OperationOptions,
+    ) -> Result<GetObjectResponse> {
+        self.with_options(options).get_object(request)
+    }
+
+    /// Blocking version of [`OssClient::put_object_with_options`].
+    pub fn put_object_with_options(
+        &self,
+        request: request::PutObjectRequest,
+        options: OperationOptions,
+    ) -> Result<response::PutObjectResponse> {
+        self.with_options(options).put_object(request)
+    }
+
+    /// Blocking version of [`OssClient::warm_up`].
+    pub fn warm_up(&self, bucket: &BucketName) -> Result<()> {
+        self.block_on(self.inner.warm_up(bucket))
+    }
+
+    /// Blocking version of [`OssClient::list_all_parts`].
+    pub fn list_all_parts(
+        &self,
+        bucket: BucketName,
+        key: ObjectKey,
+        upload_id: impl Into<String>,
+    ) -> Result<Vec<response::PartInfo>> {
+        self.block_on(self.inner.list_all_parts(bucket, key, upload_id))
+    }
+
+    /// Blocking version of [`OssClient::presign_get_object`].
+    pub fn presign_get_object(&self, request: PresignedUrlRequest) -> Result<String> {
+        self.inner.presign_get_object(request)
+    }
+
+    /// Blocking version of [`OssClient::presign_put_object`].
+    pub fn presign_put_object(&self, request: PresignedUrlRequest) -> Result<String> {
+        self.inner.presign_put_object(request)
+    }
+
+    fn wrap_get_object(&self, response: response::GetObjectResponse) -> GetObjectResponse {
+        GetObjectResponse {
+            body: BlockingObjectBody {
+                body: response.body,
+                chunk: Bytes::new(),
+                runtime: Arc::clone(&self.runtime),
+            },
+            content_type: response.content_type,
+            content_length: response.content_length,
+            etag: response.etag,
+            storage_class: response.storage_class,
+            object_type: response.object_type,
+            tagging_count: response.tagging_count,
+            restore: response.restore,
+            expiration: response.expiration,
+            metadata: response.metadata,
+            request_id: response.request_id,
+        }
+    }
+
+    blocking_ops! {
+        put_object(request::PutObjectRequest) -> response::PutObjectResponse;
+        delete_object(request::DeleteObjectRequest) -> response::DeleteObjectResponse;
+        head_object(request::HeadObjectRequest) -> response::HeadObjectResponse;
+        list_objects_v2(request::ListObjectsV2Request) -> response::ListObjectsV2Response;
+        list_object_versions(request::ListObjectVersionsRequest)
+            -> response::ListObjectVersionsResponse;
+        copy_object(request::CopyObjectRequest) -> response::CopyObjectResponse;
+        delete_multiple_objects(request::DeleteMultipleObjectsRequest)
+            -> response::DeleteMultipleObjectsResponse;
+        restore_object(request::RestoreObjectRequest) -> response::RestoreObjectResponse;
+        append_object(request::AppendObjectRequest) -> response::AppendObjectResponse;
+        get_object_acl(request::GetObjectAclRequest) -> response::GetObjectAclResponse;
+        put_object_acl(request::PutObjectAclRequest) -> response::PutObjectAclResponse;
+        get_object_tagging(request::GetObjectTaggingRequest) -> response::GetObjectTaggingResponse;
+        put_object_tagging(request::PutObjectTaggingRequest) -> response::PutObjectTaggingResponse;
+        delete_object_tagging(request::DeleteObjectTaggingRequest)
+            -> response::DeleteObjectTaggingResponse;
+        initiate_multipart_upload(request::InitiateMultipartUploadRequest)
+            -> response::InitiateMultipartUploadResponse;
+        upload_part(request::UploadPartRequest) -> response::UploadPartResponse;
+        upload_part_copy(request::UploadPartCopyRequest) -> response::UploadPartCopyResponse;
+        complete_multipart_upload(request::CompleteMultipartUploadRequest)
+            -> response::CompleteMultipartUploadResponse;
+        abort_multipart_upload(request::AbortMultipartUploadRequest)
+            -> response::AbortMultipartUploadResponse;
+        list_parts(request::ListPartsRequest) -> response::ListPartsResponse;
+        list_multipart_uploads(request::ListMultipartUploadsRequest)
+            -> response::ListMultipartUploadsResponse;
+        create_bucket(request::CreateBucketRequest) -> response::CreateBucketResponse;
+        delete_bucket(request::DeleteBucketRequest) -> response::DeleteBucketResponse;
+        list_buckets(request::ListBucketsRequest) -> response::ListBucketsResponse;
+        get_bucket_info(request::GetBucketInfoRequest) -> response::GetBucketInfoResponse;
+        get_bucket_location(request::GetBucketLocationRequest)
+            -> response::GetBucketLocationResponse;
+        put_bucket_acl(request::PutBucketAclRequest) -> response::PutBucketAclResponse;
+        get_bucket_acl(request::GetBucketAclRequest) -> response::GetBucketAclResponse;
+        put_bucket_cors(request::PutBucketCorsRequest) -> response::PutBucketCorsResponse;
+        get_bucket_cors(request::GetBucketCorsRequest) -> response::GetBucketCorsResponse;
+        delete_bucket_cors(request::DeleteBucketCorsRequest) -> response::DeleteBucketCorsResponse;
+        put_bucket_referer(request::PutBucketRefererRequest) -> response::PutBucketRefererResponse;
+        get_bucket_referer(request::GetBucketRefererRequest) -> response::GetBucketRefererResponse;
+        put_bucket_policy(request::PutBucketPolicyRequest) -> response::PutBucketPolicyResponse;
+        get_bucket_policy(request::GetBucketPolicyRequest) -> response::GetBucketPolicyResponse;
+        delete_bucket_policy(request::DeleteBucketPolicyRequest)
+            -> response::DeleteBucketPolicyResponse;
+        put_bucket_versioning(request::PutBucketVersioningRequest)
+            -> response::PutBucketVersioningResponse;
+        get_bucket_versioning(request::GetBucketVersioningRequest)
+            -> response::GetBucketVersioningResponse;
+        put_bucket_lifecycle(request::PutBucketLifecycleRequest)
+            -> response::PutBucketLifecycleResponse;
+        get_bucket_lifecycle(request::GetBucketLifecycleRequest)
+            -> response::GetBucketLifecycleResponse;
+        delete_bucket_lifecycle(request::DeleteBucketLifecycleRequest)
+            -> response::DeleteBucketLifecycleResponse;
+        put_bucket_encryption(request::PutBucketEncryptionRequest)
+            -> response::PutBucketEncryptionResponse;
+        get_bucket_encryption(request::GetBucketEncryptionRequest)
+            -> response::GetBucketEncryptionResponse;
+        delete_bucket_encryption(request::DeleteBucketEncryptionRequest)
+            -> response::DeleteBucketEncryptionResponse;
+        put_bucket_logging(request::PutBucketLoggingRequest) -> response::PutBucketLoggingResponse;
+        get_bucket_logging(request::GetBucketLoggingRequest) -> response::GetBucketLoggingResponse;
+        delete_bucket_logging(request::DeleteBucketLoggingRequest)
+            -> response::DeleteBucketLoggingResponse;
+        put_bucket_transfer_acceleration(request::PutBucketTransferAccelerationRequest)
+            -> response::PutBucketTransferAccelerationResponse;
+        get_bucket_transfer_acceleration(request::GetBucketTransferAccelerationRequest)
+            -> response::GetBucketTransferAccelerationResponse;
+        create_bucket_data_redundancy_transition(
+            request::CreateBucketDataRedundancyTransitionRequest
+        ) -> response::CreateBucketDataRedundancyTransitionResponse;
+        get_bucket_data_redundancy_transition(request::GetBucketDataRedundancyTransitionRequest)
+            -> response::GetBucketDataRedundancyTransitionResponse;
+        list_bucket_data_redundancy_transition(request::ListBucketDataRedundancyTransitionRequest)
+            -> response::ListBucketDataRedundancyTransitionResponse;
+        delete_bucket_data_redundancy_transition(
+            request::DeleteBucketDataRedundancyTransitionRequest
+        ) -> response::DeleteBucketDataRedundancyTransitionResponse;
+        put_cname(request::PutCnameRequest) -> response::PutCnameResponse;
+    }
+}
+
+impl fmt::Debug for OssBlockingClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OssBlockingClient")
+            .field("config", self.inner.config())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Response from a blocking GetObject operation.
+#[derive(Debug)]
+pub struct GetObjectResponse {
+    /// The response body.
+    pub body: BlockingObjectBody,
+    /// Content type of the object.
+    pub content_type: Option<String>,
+    /// Content length in bytes.
+    pub content_length: Option<u64>,
+    /// ETag of the object.
+    pub etag: Option<String>,
+    /// Storage class of the object.
+    pub storage_class: Option<StorageClass>,
+    /// How the object was created.
+    pub object_type: Option<ObjectType>,
+    /// Number of tags on the object (`x-oss-tagging-count`).
+    pub tagging_count: Option<u32>,
+    /// Restore state, for archived objects that have had a restore requested.
+    pub restore: Option<response::RestoreStatus>,
+    /// When a lifecycle rule will delete the object.
+    pub expiration: Option<response::ObjectExpiration>,
+    /// Custom metadata (x-oss-meta-* headers).
+    pub metadata: HashMap<String, String>,
+    /// OSS request ID.
+    pub request_id: Option<String>,
+}
+
+/// Streaming object body for the blocking client.
+///
+/// Implements [`Read`], so it can be passed to [`std::io::copy`] to stream an
+/// object into a file without buffering it in memory.
+pub struct BlockingObjectBody {
+    body: response::ObjectBody,
+    chunk: Bytes,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl BlockingObjectBody {
+    /// Consume the body and return all remaining bytes.
+    pub fn bytes(self) -> std::result::Result<Bytes, reqwest::Error> {
+        let Self {
+            mut body,
+            chunk,
+            runtime,
+        } = self;
+        let mut all = chunk.to_vec();
+        while let Some(next) = runtime.block_on(body.chunk())? {
+            all.extend_from_slice(&next);
+        }
+        Ok(all.into())
+    }
+
+    /// Consume the body and return it as a UTF-8 string (lossy).
+    pub fn text(self) -> std::result::Result<String, reqwest::Error> {
+        let bytes = self.bytes()?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+impl Read for BlockingObjectBody {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.chunk.is_empty() {
+            match self
+                .runtime
+                .block_on(self.body.chunk())
+                .map_err(io::Error::other)?
+            {
+                Some(chunk) => self.chunk = chunk,
+                None => return Ok(0),
+            }
+        }
+        let n = buf.len().min(self.chunk.len());
+        buf[..n].copy_from_slice(&self.chunk[..n]);
+        self.chunk.advance(n);
+        Ok(n)
+    }
+}
+
+impl fmt::Debug for BlockingObjectBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<streaming body>")
+    }
+}
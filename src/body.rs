@@ -0,0 +1,187 @@
+//! Request bodies that can be sent more than once.
+//!
+//! In-memory bodies are replayed automatically when a request is retried.
+//! Streaming bodies can only be read once, so by default a request with a
+//! streaming body is attempted a single time. A [`ReplayableBody`] lifts that
+//! restriction by recreating the stream for every attempt; alternatively,
+//! [`ClientBuilder::max_replay_buffer_size`](crate::config::ClientBuilder::max_replay_buffer_size)
+//! buffers small streams in memory.
+
+use std::fmt;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures_util::{StreamExt, TryStreamExt, stream};
+use http_body_util::{BodyExt, BodyStream};
+
+use crate::error::Result;
+
+/// Creates a fresh request body for each attempt of a request.
+///
+/// Implemented for closures, so most callers never implement it by hand.
+pub trait BodyProvider: Send + Sync {
+    /// Create a body positioned at the start of the payload.
+    fn body(&self) -> Result<reqwest::Body>;
+}
+
+impl<F> BodyProvider for F
+where
+    F: Fn() -> Result<reqwest::Body> + Send + Sync,
+{
+    fn body(&self) -> Result<reqwest::Body> {
+        self()
+    }
+}
+
+/// A streaming body that can be recreated, so requests using it are retried
+/// like requests with in-memory bodies.
+///
+/// The provider is called on the async runtime, so it must not block: return
+/// a stream that opens and reads the source lazily, as below, rather than
+/// reading it up front.
+///
+/// # Examples
+/// ```no_run
+/// use futures_util::{StreamExt, TryStreamExt};
+/// use rs_ali_oss::ReplayableBody;
+/// use tokio_util::io::ReaderStream;
+///
+/// let path = std::path::PathBuf::from("/var/backups/db.tar");
+/// let body = ReplayableBody::new(move || {
+///     let file = tokio::fs::File::open(path.clone());
+///     let chunks = futures_util::stream::once(file)
+///         .map_ok(ReaderStream::new)
+///         .try_flatten();
+///     Ok(reqwest::Body::wrap_stream(chunks))
+/// })
+/// .content_length(1024 * 1024);
+/// ```
+#[derive(Clone)]
+pub struct ReplayableBody {
+    provider: Arc<dyn BodyProvider>,
+    content_length: Option<u64>,
+}
+
+impl ReplayableBody {
+    /// Create a body from a provider, called once per attempt.
+    pub fn new(provider: impl BodyProvider + 'static) -> Self {
+        Self {
+            provider: Arc::new(provider),
+            content_length: None,
+        }
+    }
+
+    /// Declare the body length, sent as `Content-Length` instead of using
+    /// chunked transfer encoding.
+    pub fn content_length(mut self, length: u64) -> Self {
+        self.content_length = Some(length);
+        self
+    }
+
+    /// Returns the declared body length, if any.
+    pub fn len(&self) -> Option<u64> {
+        self.content_length
+    }
+
+    /// Returns whether the body is declared to be empty.
+    pub fn is_empty(&self) -> bool {
+        self.content_length == Some(0)
+    }
+
+    pub(crate) fn body(&self) -> Result<reqwest::Body> {
+        self.provider.body()
+    }
+}
+
+impl fmt::Debug for ReplayableBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReplayableBody")
+            .field("content_length", &self.content_length)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Body of an upload request: sent once, or recreated per attempt.
+#[derive(Debug)]
+pub(crate) enum RequestBody {
+    Once(reqwest::Body),
+    Replayable(ReplayableBody),
+}
+
+/// Take the contents of an in-memory body. The buffer is moved out of the
+/// body, not copied.
+pub(crate) async fn into_bytes(body: reqwest::Body) -> Result<Bytes> {
+    Ok(body.collect().await?.to_bytes())
+}
+
+/// Read a streaming body into memory if it is at most `limit` bytes long.
+///
+/// Longer bodies are returned as a stream that yields the already-read prefix
+/// followed by the rest, so nothing is lost; they just stay non-replayable.
+pub(crate) async fn buffer_if_small(body: reqwest::Body, limit: u64) -> Result<reqwest::Body> {
+    let mut frames = BodyStream::new(body)
+        .try_filter_map(|frame| std::future::ready(Ok(frame.into_data().ok())))
+        .boxed();
+    let mut prefix: Vec<Bytes> = Vec::new();
+    let mut size = 0u64;
+    while let Some(chunk) = frames.try_next().await? {
+        size += chunk.len() as u64;
+        prefix.push(chunk);
+        if size > limit {
+            let replayed = stream::iter(prefix.into_iter().map(Ok));
+            return Ok(reqwest::Body::wrap_stream(replayed.chain(frames)));
+        }
+    }
+    Ok(reqwest::Body::from(prefix.concat()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunked(chunks: &[&'static [u8]]) -> reqwest::Body {
+        let chunks: Vec<std::result::Result<Bytes, std::io::Error>> =
+            chunks.iter().map(|c| Ok(Bytes::from_static(c))).collect();
+        reqwest::Body::wrap_stream(stream::iter(chunks))
+    }
+
+    async fn collect(body: reqwest::Body) -> Vec<u8> {
+        use http_body_util::BodyExt;
+        body.collect().await.unwrap().to_bytes().to_vec()
+    }
+
+    #[tokio::test]
+    async fn small_streams_are_buffered() {
+        let body = buffer_if_small(chunked(&[b"hello, ", b"oss"]), 16)
+            .await
+            .unwrap();
+        assert_eq!(body.as_bytes(), Some(&b"hello, oss"[..]));
+    }
+
+    #[tokio::test]
+    async fn large_streams_are_passed_through() {
+        let body = buffer_if_small(chunked(&[b"hello, ", b"oss", b"!"]), 8)
+            .await
+            .unwrap();
+        assert!(body.as_bytes().is_none());
+        assert_eq!(collect(body).await, b"hello, oss!");
+    }
+
+    #[tokio::test]
+    async fn in_memory_bodies_are_not_copied() {
+        let data = Bytes::from(vec![7u8; 4096]);
+        let ptr = data.as_ptr();
+        let bytes = into_bytes(reqwest::Body::from(data)).await.unwrap();
+        assert_eq!(bytes.as_ptr(), ptr);
+        assert_eq!(bytes.len(), 4096);
+    }
+
+    #[test]
+    fn replayable_body_recreates_stream() {
+        let body = ReplayableBody::new(|| Ok(reqwest::Body::from("data"))).content_length(4);
+        assert_eq!(body.len(), Some(4));
+        for _ in 0..2 {
+            assert_eq!(body.body().unwrap().as_bytes(), Some(&b"data"[..]));
+        }
+    }
+}
@@ -1,11 +1,19 @@
 //! Configuration types for the Alibaba Cloud OSS client.
 
 use std::fmt;
+use std::net::SocketAddr;
+use std::sync::Arc;
 use std::time::Duration;
 
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
+use crate::auth::{SignatureVersion, Signer, V1Signer, V4Signer};
+use crate::credential::{CredentialProvider, StaticProvider};
 use crate::error::{OssError, Result};
+use crate::retry::{DefaultRetryClassifier, RetryClassifier};
 use crate::types::Region;
 
 /// OSS access credentials.
@@ -24,6 +32,7 @@ pub struct Credentials {
     access_key_id: String,
     access_key_secret: SecretString,
     security_token: Option<SecretString>,
+    expiration: Option<DateTime<Utc>>,
 }
 
 /// A string that is zeroed on drop and redacted in Debug.
@@ -43,6 +52,7 @@ impl Credentials {
             access_key_id: access_key_id.into(),
             access_key_secret: SecretString(access_key_secret.into()),
             security_token: None,
+            expiration: None,
         }
     }
 
@@ -56,9 +66,19 @@ impl Credentials {
             access_key_id: access_key_id.into(),
             access_key_secret: SecretString(access_key_secret.into()),
             security_token: Some(SecretString(security_token.into())),
+            expiration: None,
         }
     }
 
+    /// Attach the time at which these credentials stop being valid.
+    ///
+    /// Temporary credentials issued by STS carry an expiration; providers use it
+    /// to decide when a refresh is due.
+    pub fn with_expiration(mut self, expiration: DateTime<Utc>) -> Self {
+        self.expiration = Some(expiration);
+        self
+    }
+
     /// Construct credentials from an STS `Credentials` response.
     #[cfg(feature = "sts")]
     pub fn from_sts(sts_creds: &rs_ali_sts::Credentials) -> Self {
@@ -83,6 +103,26 @@ impl Credentials {
     pub fn security_token(&self) -> Option<&str> {
         self.security_token.as_ref().map(|s| s.0.as_str())
     }
+
+    /// Returns the expiration time, if these are temporary credentials.
+    pub fn expiration(&self) -> Option<DateTime<Utc>> {
+        self.expiration
+    }
+
+    /// Returns `true` if the credentials expire within `window` from now.
+    ///
+    /// Credentials without an expiration never expire.
+    pub fn expires_within(&self, window: Duration) -> bool {
+        match self.expiration {
+            Some(expiration) => {
+                let window = chrono::Duration::from_std(window).unwrap_or(chrono::Duration::MAX);
+                Utc::now()
+                    .checked_add_signed(window)
+                    .is_none_or(|t| t >= expiration)
+            }
+            None => false,
+        }
+    }
 }
 
 impl fmt::Debug for Credentials {
@@ -94,6 +134,7 @@ impl fmt::Debug for Credentials {
                 "security_token",
                 &self.security_token.as_ref().map(|_| "****"),
             )
+            .field("expiration", &self.expiration)
             .finish()
     }
 }
@@ -107,6 +148,25 @@ pub struct RetryConfig {
     pub base_delay: Duration,
     /// Maximum delay between retries (default: 30s).
     pub max_delay: Duration,
+    /// Streaming bodies of at most this many bytes are buffered in memory so
+    /// the request can be retried (default: 0, streaming bodies are sent once).
+    pub max_replay_buffer_size: u64,
+    /// HTTP statuses retried by the [`DefaultRetryClassifier`] in addition
+    /// to 5xx (default: 408 and 429).
+    pub retryable_status_codes: Vec<u16>,
+    /// OSS error codes retried by the [`DefaultRetryClassifier`] regardless
+    /// of the HTTP status (default: [`DEFAULT_RETRYABLE_ERROR_CODES`](Self::DEFAULT_RETRYABLE_ERROR_CODES)).
+    pub retryable_error_codes: Vec<String>,
+}
+
+impl RetryConfig {
+    /// OSS error codes for timeouts and throttling that succeed on retry.
+    pub const DEFAULT_RETRYABLE_ERROR_CODES: &'static [&'static str] = &[
+        "RequestTimeout",
+        "SlowDown",
+        "Throttling",
+        "QpsLimitExceeded",
+    ];
 }
 
 impl Default for RetryConfig {
@@ -115,6 +175,12 @@ impl Default for RetryConfig {
             max_retries: 3,
             base_delay: Duration::from_millis(200),
             max_delay: Duration::from_secs(30),
+            max_replay_buffer_size: 0,
+            retryable_status_codes: vec![408, 429],
+            retryable_error_codes: Self::DEFAULT_RETRYABLE_ERROR_CODES
+                .iter()
+                .map(|code| code.to_string())
+                .collect(),
         }
     }
 }
@@ -126,6 +192,9 @@ pub struct PoolConfig {
     pub max_idle_per_host: Option<usize>,
     /// Idle connection timeout (default: 90 seconds).
     pub idle_timeout: Option<Duration>,
+    /// Maximum requests in flight at once across all clones of a client
+    /// (default: no limit).
+    pub max_in_flight_requests: Option<usize>,
 }
 
 impl Default for PoolConfig {
@@ -133,6 +202,7 @@ impl Default for PoolConfig {
         Self {
             max_idle_per_host: None,
             idle_timeout: Some(Duration::from_secs(90)),
+            max_in_flight_requests: None,
         }
     }
 }
@@ -158,22 +228,353 @@ impl Default for TimeoutConfig {
     }
 }
 
+/// TLS configuration for private OSS gateways.
+#[derive(Debug, Clone, Default)]
+pub struct TlsConfig {
+    /// Extra trusted root certificates, in addition to the system roots.
+    pub root_certificates: Vec<reqwest::Certificate>,
+    /// Client certificate and key presented for mutual TLS.
+    pub identity: Option<reqwest::Identity>,
+    /// Skip server certificate validation entirely (default: false).
+    pub danger_accept_invalid_certs: bool,
+}
+
+/// HTTP protocol version used to talk to OSS.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HttpVersion {
+    /// Negotiate via TLS ALPN: HTTP/2 when the server offers it, HTTP/1.1
+    /// otherwise (default).
+    #[default]
+    Auto,
+    /// Only use HTTP/1.1, e.g. behind proxies that mishandle HTTP/2.
+    Http1Only,
+    /// Assume HTTP/2 without negotiation ("prior knowledge"). Only works
+    /// with servers known to speak HTTP/2.
+    Http2PriorKnowledge,
+}
+
+/// Whether V4 signatures cover the request body through
+/// `x-oss-content-sha256`.
+///
+/// Hashing lets OSS reject a body altered in transit, at the cost of a
+/// SHA-256 pass over every upload. V1 signatures never cover the body.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PayloadSigning {
+    /// Always send `UNSIGNED-PAYLOAD` (default).
+    #[default]
+    Unsigned,
+    /// Hash in-memory bodies; send `UNSIGNED-PAYLOAD` for streaming ones.
+    InMemory,
+    /// Hash every body. Requests with a streaming body fail before they are
+    /// sent, since their payload cannot be hashed up front.
+    Required,
+}
+
+/// How user metadata (`x-oss-meta-*`) values outside printable ASCII are sent.
+///
+/// OSS only accepts ASCII header values. The chosen encoding is applied to
+/// values sent with `PutObject` and `CopyObject`, and reversed for the
+/// metadata returned by `HeadObject` and `GetObject`, so values round-trip
+/// through the same client configuration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MetadataEncoding {
+    /// Fail the request before it is sent (default).
+    #[default]
+    Reject,
+    /// Percent-encode UTF-8 bytes, control characters and `%` (e.g. `caf%C3%A9`).
+    PercentEncode,
+    /// Send values that are not printable ASCII as an RFC 2047 encoded word
+    /// (`=?UTF-8?B?...?=`), the format used by several other OSS clients.
+    Rfc2047,
+}
+
+impl MetadataEncoding {
+    /// Encode the value of metadata entry `key` for sending.
+    pub(crate) fn encode(self, key: &str, value: &str) -> Result<String> {
+        let printable = value.bytes().all(|b| (0x20..0x7f).contains(&b));
+        match self {
+            MetadataEncoding::Reject if printable => Ok(value.to_string()),
+            MetadataEncoding::Reject => Err(OssError::InvalidParameter {
+                field: format!("metadata `{key}`"),
+                reason: "value must be printable ASCII; set ClientBuilder::metadata_encoding \
+                         to send other values encoded"
+                    .into(),
+            }),
+            MetadataEncoding::PercentEncode => Ok(percent_encoding::utf8_percent_encode(
+                value,
+                crate::encoding::METADATA_ENCODE_SET,
+            )
+            .to_string()),
+            MetadataEncoding::Rfc2047 if printable => Ok(value.to_string()),
+            MetadataEncoding::Rfc2047 => Ok(format!(
+                "=?UTF-8?B?{}?=",
+                base64::engine::general_purpose::STANDARD.encode(value)
+            )),
+        }
+    }
+
+    /// Decode a received metadata value. Values that are not in the expected
+    /// encoding are returned unchanged.
+    pub(crate) fn decode(self, value: &str) -> String {
+        match self {
+            MetadataEncoding::Reject => value.to_string(),
+            MetadataEncoding::PercentEncode => percent_encoding::percent_decode_str(value)
+                .decode_utf8()
+                .map(|decoded| decoded.into_owned())
+                .unwrap_or_else(|_| value.to_string()),
+            MetadataEncoding::Rfc2047 => value
+                .strip_prefix("=?UTF-8?B?")
+                .or_else(|| value.strip_prefix("=?utf-8?b?"))
+                .and_then(|rest| rest.strip_suffix("?="))
+                .and_then(|encoded| {
+                    base64::engine::general_purpose::STANDARD
+                        .decode(encoded)
+                        .ok()
+                })
+                .and_then(|bytes| String::from_utf8(bytes).ok())
+                .unwrap_or_else(|| value.to_string()),
+        }
+    }
+}
+
+/// Configuration for the HTTP protocol.
+#[derive(Debug, Clone, Default)]
+pub struct ProtocolConfig {
+    /// Protocol version selection (default: [`HttpVersion::Auto`]).
+    pub version: HttpVersion,
+    /// Use adaptive flow-control windows for HTTP/2 (default: false).
+    pub http2_adaptive_window: bool,
+    /// HTTP/2 per-stream flow-control window in bytes (default: hyper's default).
+    pub http2_initial_stream_window_size: Option<u32>,
+    /// HTTP/2 per-connection flow-control window in bytes (default: hyper's default).
+    pub http2_initial_connection_window_size: Option<u32>,
+}
+
+/// Per-operation overrides of client-wide settings.
+///
+/// Attach to a client with [`OssClient::with_options`](crate::OssClient::with_options)
+/// to apply them to every operation issued through it, or pass them to a
+/// `*_with_options` method for a single call. Unset fields fall back to the
+/// client configuration.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+/// use rs_ali_oss::config::OperationOptions;
+///
+/// # fn example() -> rs_ali_oss::Result<()> {
+/// let options = OperationOptions::builder()
+///     .timeout(Duration::from_secs(3600))
+///     .max_retries(0)
+///     .traffic_limit(100 * 1024 * 1024 * 8)
+///     .header("x-oss-request-payer", "requester")
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OperationOptions {
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) max_retries: Option<u32>,
+    pub(crate) traffic_limit: Option<u64>,
+    pub(crate) headers: HeaderMap,
+}
+
+impl OperationOptions {
+    /// Create a builder.
+    pub fn builder() -> OperationOptionsBuilder {
+        OperationOptionsBuilder::new()
+    }
+
+    /// Overall deadline for the operation, replacing
+    /// [`TimeoutConfig::request_timeout`].
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Maximum retry attempts, replacing [`RetryConfig::max_retries`].
+    pub fn max_retries(&self) -> Option<u32> {
+        self.max_retries
+    }
+
+    /// Bandwidth limit in bits per second, sent as `x-oss-traffic-limit`.
+    pub fn traffic_limit(&self) -> Option<u64> {
+        self.traffic_limit
+    }
+
+    /// Extra headers added to every request of the operation.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+}
+
+/// Builder for [`OperationOptions`].
+#[derive(Debug, Default)]
+pub struct OperationOptionsBuilder {
+    timeout: Option<Duration>,
+    max_retries: Option<u32>,
+    traffic_limit: Option<u64>,
+    headers: Vec<(String, String)>,
+}
+
+impl OperationOptionsBuilder {
+    /// Minimum `x-oss-traffic-limit` accepted by OSS: 100 KB/s.
+    pub const MIN_TRAFFIC_LIMIT: u64 = 819_200;
+    /// Maximum `x-oss-traffic-limit` accepted by OSS: 100 MB/s.
+    pub const MAX_TRAFFIC_LIMIT: u64 = 838_860_800;
+
+    /// Create a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overall deadline for the operation, including retries.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Maximum retry attempts for the operation (`0` disables retries).
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    /// Limit the bandwidth of uploads and downloads, in bits per second.
+    ///
+    /// OSS honours the limit on object data transfers (PutObject, GetObject,
+    /// AppendObject, UploadPart, CopyObject) and accepts values between
+    /// [`MIN_TRAFFIC_LIMIT`](Self::MIN_TRAFFIC_LIMIT) and
+    /// [`MAX_TRAFFIC_LIMIT`](Self::MAX_TRAFFIC_LIMIT).
+    pub fn traffic_limit(mut self, bits_per_second: u64) -> Self {
+        self.traffic_limit = Some(bits_per_second);
+        self
+    }
+
+    /// Add a header to every request of the operation, replacing any value
+    /// the operation sets itself.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Build the options.
+    pub fn build(self) -> Result<OperationOptions> {
+        if let Some(limit) = self.traffic_limit
+            && !(Self::MIN_TRAFFIC_LIMIT..=Self::MAX_TRAFFIC_LIMIT).contains(&limit)
+        {
+            return Err(OssError::InvalidParameter {
+                field: "traffic_limit".into(),
+                reason: format!(
+                    "must be between {} and {} bits per second",
+                    Self::MIN_TRAFFIC_LIMIT,
+                    Self::MAX_TRAFFIC_LIMIT
+                ),
+            });
+        }
+
+        Ok(OperationOptions {
+            timeout: self.timeout,
+            max_retries: self.max_retries,
+            traffic_limit: self.traffic_limit,
+            headers: parse_headers(self.headers, "header")?,
+        })
+    }
+}
+
+/// Parse `(name, value)` pairs into a header map, keeping every value of a
+/// name that appears more than once.
+fn parse_headers(headers: Vec<(String, String)>, field: &str) -> Result<HeaderMap> {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        let header_name =
+            HeaderName::try_from(name.as_str()).map_err(|_| OssError::InvalidParameter {
+                field: field.into(),
+                reason: format!("invalid header name: {name}"),
+            })?;
+        let header_value =
+            HeaderValue::try_from(value).map_err(|_| OssError::InvalidParameter {
+                field: field.into(),
+                reason: format!("invalid value for header {name}"),
+            })?;
+        map.append(header_name, header_value);
+    }
+    Ok(map)
+}
+
+/// Global transfer acceleration endpoint to route requests through.
+///
+/// Transfer acceleration must be enabled on the bucket, see
+/// [`OssClient::put_bucket_transfer_acceleration`](crate::OssClient::put_bucket_transfer_acceleration).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccelerateEndpoint {
+    /// `oss-accelerate.aliyuncs.com`: the nearest acceleration point worldwide.
+    Global,
+    /// `oss-accelerate-overseas.aliyuncs.com`: acceleration points outside
+    /// the Chinese mainland only.
+    Overseas,
+}
+
+impl AccelerateEndpoint {
+    /// Host name of the endpoint.
+    pub fn host(&self) -> &'static str {
+        match self {
+            Self::Global => "oss-accelerate.aliyuncs.com",
+            Self::Overseas => "oss-accelerate-overseas.aliyuncs.com",
+        }
+    }
+}
+
 /// Configuration for the OSS client.
 #[derive(Debug, Clone)]
 pub struct Config {
-    pub(crate) credentials: Credentials,
+    pub(crate) credential_provider: Arc<dyn CredentialProvider>,
     pub(crate) region: Region,
     pub(crate) endpoint: Option<String>,
     pub(crate) use_path_style: bool,
+    pub(crate) cname: bool,
+    pub(crate) use_internal_endpoint: bool,
+    pub(crate) accelerate_endpoint: Option<AccelerateEndpoint>,
+    pub(crate) resolve_overrides: Vec<(String, SocketAddr)>,
     pub(crate) retry_config: RetryConfig,
     pub(crate) pool_config: PoolConfig,
     pub(crate) timeout_config: TimeoutConfig,
+    pub(crate) protocol_config: ProtocolConfig,
+    pub(crate) tls_config: TlsConfig,
+    pub(crate) signature_version: SignatureVersion,
+    pub(crate) signer: Arc<dyn Signer>,
+    pub(crate) payload_signing: PayloadSigning,
+    pub(crate) expose_signing_details: bool,
+    pub(crate) additional_signed_headers: Vec<String>,
+    pub(crate) user_agent: String,
+    pub(crate) default_headers: HeaderMap,
+    pub(crate) detect_content_type: bool,
+    pub(crate) metadata_encoding: MetadataEncoding,
+    pub(crate) multipart_copy_threshold: Option<u64>,
+    pub(crate) retry_classifier: Arc<dyn RetryClassifier>,
 }
 
 impl Config {
-    /// Returns the credentials.
-    pub fn credentials(&self) -> &Credentials {
-        &self.credentials
+    /// Build a configuration purely from `OSS_*` environment variables.
+    ///
+    /// Shorthand for `ClientBuilder::from_env().build()`; see
+    /// [`ClientBuilder::from_env`] for the variables read.
+    pub fn from_env() -> Result<Self> {
+        ClientBuilder::from_env().build()
+    }
+
+    /// Resolve the current credentials from the configured provider.
+    ///
+    /// This may block while a network-backed provider refreshes; async code
+    /// should prefer [`CredentialProvider::resolve_async`] on
+    /// [`credential_provider`](Self::credential_provider).
+    pub fn credentials(&self) -> Result<Credentials> {
+        self.credential_provider.resolve()
+    }
+
+    /// Returns the credential provider used to sign requests.
+    pub fn credential_provider(&self) -> &Arc<dyn CredentialProvider> {
+        &self.credential_provider
     }
 
     /// Returns the region.
@@ -191,11 +592,37 @@ impl Config {
         self.use_path_style
     }
 
+    /// Returns whether the custom endpoint is a domain bound to the bucket
+    /// (CNAME).
+    pub fn cname(&self) -> bool {
+        self.cname
+    }
+
+    /// Returns whether the internal (VPC) endpoint of the region is used.
+    pub fn use_internal_endpoint(&self) -> bool {
+        self.use_internal_endpoint
+    }
+
+    /// Returns the transfer acceleration endpoint, if enabled.
+    pub fn accelerate_endpoint(&self) -> Option<AccelerateEndpoint> {
+        self.accelerate_endpoint
+    }
+
+    /// Returns the host names pinned to fixed addresses, bypassing DNS.
+    pub fn resolve_overrides(&self) -> &[(String, SocketAddr)] {
+        &self.resolve_overrides
+    }
+
     /// Returns the retry configuration.
     pub fn retry_config(&self) -> &RetryConfig {
         &self.retry_config
     }
 
+    /// Returns the classifier deciding which failed attempts are retried.
+    pub fn retry_classifier(&self) -> &Arc<dyn RetryClassifier> {
+        &self.retry_classifier
+    }
+
     /// Returns the connection pool configuration.
     pub fn pool_config(&self) -> &PoolConfig {
         &self.pool_config
@@ -205,6 +632,70 @@ impl Config {
     pub fn timeout_config(&self) -> &TimeoutConfig {
         &self.timeout_config
     }
+
+    /// Returns the HTTP protocol configuration.
+    pub fn protocol_config(&self) -> &ProtocolConfig {
+        &self.protocol_config
+    }
+
+    /// Returns the TLS configuration.
+    pub fn tls_config(&self) -> &TlsConfig {
+        &self.tls_config
+    }
+
+    /// Returns the signature version used to sign requests.
+    pub fn signature_version(&self) -> SignatureVersion {
+        self.signature_version
+    }
+
+    /// Returns the signer applied to every request.
+    pub fn signer(&self) -> &Arc<dyn Signer> {
+        &self.signer
+    }
+
+    /// Returns whether V4 signatures cover the request body.
+    pub fn payload_signing(&self) -> PayloadSigning {
+        self.payload_signing
+    }
+
+    /// Returns whether signing details are passed to interceptors.
+    pub fn expose_signing_details(&self) -> bool {
+        self.expose_signing_details
+    }
+
+    /// Returns the extra headers signed via V4 `AdditionalHeaders`
+    /// (lowercase, sorted).
+    pub fn additional_signed_headers(&self) -> &[String] {
+        &self.additional_signed_headers
+    }
+
+    /// Returns the `User-Agent` sent with every request:
+    /// `rs-ali-oss/{version}`, followed by the configured suffix if any.
+    pub fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+
+    /// Returns the headers added to every request.
+    pub fn default_headers(&self) -> &HeaderMap {
+        &self.default_headers
+    }
+
+    /// Returns whether uploads without a content type get one inferred from
+    /// the object key's extension (requires the `mime_guess` feature).
+    pub fn detect_content_type(&self) -> bool {
+        self.detect_content_type
+    }
+
+    /// Returns how non-ASCII user metadata values are encoded.
+    pub fn metadata_encoding(&self) -> MetadataEncoding {
+        self.metadata_encoding
+    }
+
+    /// Returns the source size above which `CopyObject` switches to a
+    /// multipart copy, or `None` if the fallback is disabled.
+    pub fn multipart_copy_threshold(&self) -> Option<u64> {
+        self.multipart_copy_threshold
+    }
 }
 
 /// Builder for constructing an OSS [`Config`].
@@ -223,26 +714,108 @@ pub struct ClientBuilder {
     access_key_id: Option<String>,
     access_key_secret: Option<Zeroizing<String>>,
     security_token: Option<Zeroizing<String>>,
+    credential_provider: Option<Arc<dyn CredentialProvider>>,
     region: Option<String>,
     endpoint: Option<String>,
     use_path_style: bool,
+    cname: bool,
+    use_internal_endpoint: bool,
+    accelerate_endpoint: Option<AccelerateEndpoint>,
+    resolve_overrides: Vec<(String, SocketAddr)>,
     max_retries: Option<u32>,
     base_retry_delay: Option<Duration>,
     max_retry_delay: Option<Duration>,
+    max_replay_buffer_size: Option<u64>,
+    retryable_status_codes: Option<Vec<u16>>,
+    retryable_error_codes: Option<Vec<String>>,
     pool_max_idle_per_host: Option<usize>,
     pool_idle_timeout: Option<Duration>,
+    max_in_flight_requests: Option<usize>,
     connect_timeout: Option<Duration>,
     read_timeout: Option<Duration>,
     request_timeout: Option<Duration>,
+    http_version: HttpVersion,
+    http2_adaptive_window: bool,
+    http2_initial_stream_window_size: Option<u32>,
+    http2_initial_connection_window_size: Option<u32>,
+    root_certificates: Vec<reqwest::Certificate>,
+    identity: Option<reqwest::Identity>,
+    danger_accept_invalid_certs: bool,
     allow_insecure: bool,
+    signature_version: SignatureVersion,
+    signer: Option<Arc<dyn Signer>>,
+    payload_signing: PayloadSigning,
+    expose_signing_details: bool,
+    additional_signed_headers: Vec<String>,
+    user_agent_suffix: Option<String>,
+    default_headers: Vec<(String, String)>,
+    detect_content_type: Option<bool>,
+    metadata_encoding: MetadataEncoding,
+    multipart_copy: Option<bool>,
+    multipart_copy_threshold: Option<u64>,
+    retry_classifier: Option<Arc<dyn RetryClassifier>>,
 }
 
 impl ClientBuilder {
+    /// Default [`multipart_copy_threshold`](Self::multipart_copy_threshold):
+    /// 1 GiB, the largest source `CopyObject` accepts.
+    pub const DEFAULT_MULTIPART_COPY_THRESHOLD: u64 = 1024 * 1024 * 1024;
+
     /// Create a new builder with default settings.
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Create a builder pre-populated from `OSS_*` environment variables.
+    ///
+    /// Reads the following variables, ignoring any that are unset or empty:
+    /// - `OSS_ACCESS_KEY_ID`
+    /// - `OSS_ACCESS_KEY_SECRET`
+    /// - `OSS_SESSION_TOKEN`
+    /// - `OSS_REGION`
+    /// - `OSS_ENDPOINT` (`https://` is assumed when no scheme is given)
+    ///
+    /// Setters called on the returned builder take precedence over the
+    /// environment, so code can supply defaults for anything a deployment
+    /// leaves unset. Missing required values are reported by
+    /// [`build`](Self::build) as usual. To combine environment settings with a
+    /// [`credential_provider`](Self::credential_provider), leave the key
+    /// variables unset.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use rs_ali_oss::config::ClientBuilder;
+    /// # fn example() -> rs_ali_oss::Result<()> {
+    /// let config = ClientBuilder::from_env().max_retries(5).build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+
+        let mut builder = Self::new();
+        if let Some(id) = var("OSS_ACCESS_KEY_ID") {
+            builder = builder.access_key_id(id);
+        }
+        if let Some(secret) = var("OSS_ACCESS_KEY_SECRET") {
+            builder = builder.access_key_secret(secret);
+        }
+        if let Some(token) = var("OSS_SESSION_TOKEN") {
+            builder = builder.security_token(token);
+        }
+        if let Some(region) = var("OSS_REGION") {
+            builder = builder.region(region);
+        }
+        if let Some(endpoint) = var("OSS_ENDPOINT") {
+            builder = if endpoint.contains("://") {
+                builder.endpoint(endpoint)
+            } else {
+                builder.endpoint(format!("https://{endpoint}"))
+            };
+        }
+        builder
+    }
+
     /// Set the access key ID.
     pub fn access_key_id(mut self, id: impl Into<String>) -> Self {
         self.access_key_id = Some(id.into());
@@ -261,6 +834,18 @@ impl ClientBuilder {
         self
     }
 
+    /// Resolve credentials from a provider instead of fixed access keys.
+    ///
+    /// Credentials are resolved before every request attempt, so rotating or
+    /// expiring STS tokens are picked up without rebuilding the client. Wrap
+    /// slow providers in a [`CachingProvider`](crate::credential::CachingProvider)
+    /// unless they cache on their own. Cannot be combined with
+    /// [`access_key_id`](Self::access_key_id) / [`access_key_secret`](Self::access_key_secret).
+    pub fn credential_provider(mut self, provider: Arc<dyn CredentialProvider>) -> Self {
+        self.credential_provider = Some(provider);
+        self
+    }
+
     /// Set the region (e.g., "cn-hangzhou").
     pub fn region(mut self, region: impl Into<String>) -> Self {
         self.region = Some(region.into());
@@ -279,6 +864,59 @@ impl ClientBuilder {
         self
     }
 
+    /// Treat the custom [`endpoint`](Self::endpoint) as a domain bound to the
+    /// bucket (CNAME), e.g. `https://static.example.com` (default: false).
+    ///
+    /// Requests and object URLs then use the endpoint as-is, without the
+    /// bucket as a subdomain or path segment. Requires a custom endpoint.
+    pub fn cname(mut self, cname: bool) -> Self {
+        self.cname = cname;
+        self
+    }
+
+    /// Use the region's internal endpoint, `oss-{region}-internal.aliyuncs.com`
+    /// (default: false).
+    ///
+    /// Traffic over the internal endpoint stays inside Alibaba Cloud and is not
+    /// billed as public egress, but it is only reachable from ECS instances and
+    /// other services in the same region. Cannot be combined with a custom
+    /// [`endpoint`](Self::endpoint).
+    pub fn use_internal_endpoint(mut self, use_internal_endpoint: bool) -> Self {
+        self.use_internal_endpoint = use_internal_endpoint;
+        self
+    }
+
+    /// Route requests through the global transfer acceleration endpoint,
+    /// `oss-accelerate.aliyuncs.com` (default: false).
+    ///
+    /// Speeds up cross-continent transfers for buckets with transfer
+    /// acceleration enabled. Requests are still signed for the bucket's
+    /// region. Cannot be combined with a custom [`endpoint`](Self::endpoint)
+    /// or the internal endpoint.
+    pub fn use_accelerate_endpoint(mut self, use_accelerate_endpoint: bool) -> Self {
+        self.accelerate_endpoint = use_accelerate_endpoint.then_some(AccelerateEndpoint::Global);
+        self
+    }
+
+    /// Route requests through a specific transfer acceleration endpoint,
+    /// e.g. [`AccelerateEndpoint::Overseas`].
+    pub fn accelerate_endpoint(mut self, endpoint: AccelerateEndpoint) -> Self {
+        self.accelerate_endpoint = Some(endpoint);
+        self
+    }
+
+    /// Resolve `host` to `addr` instead of querying DNS. Can be called
+    /// repeatedly to pin several hosts.
+    ///
+    /// The host must match exactly, including the bucket prefix of
+    /// virtual-hosted URLs (e.g. `my-bucket.oss-cn-hangzhou.aliyuncs.com`).
+    /// The port of `addr` is ignored; connections use the port of the
+    /// endpoint URL.
+    pub fn resolve(mut self, host: impl Into<String>, addr: SocketAddr) -> Self {
+        self.resolve_overrides.push((host.into(), addr));
+        self
+    }
+
     /// Set the maximum number of retry attempts for transient errors.
     pub fn max_retries(mut self, max_retries: u32) -> Self {
         self.max_retries = Some(max_retries);
@@ -297,6 +935,44 @@ impl ClientBuilder {
         self
     }
 
+    /// Buffer streaming request bodies of at most `bytes` in memory so they
+    /// can be retried (default: 0, disabled).
+    ///
+    /// Without buffering, a request with a streaming body is attempted once.
+    /// Larger bodies are still streamed, without retries; use a
+    /// [`ReplayableBody`](crate::body::ReplayableBody) to retry those.
+    pub fn max_replay_buffer_size(mut self, bytes: u64) -> Self {
+        self.max_replay_buffer_size = Some(bytes);
+        self
+    }
+
+    /// Replace the HTTP statuses retried in addition to 5xx (default: 408, 429).
+    pub fn retryable_status_codes(mut self, statuses: impl IntoIterator<Item = u16>) -> Self {
+        self.retryable_status_codes = Some(statuses.into_iter().collect());
+        self
+    }
+
+    /// Replace the OSS error codes that are always retried (default:
+    /// [`RetryConfig::DEFAULT_RETRYABLE_ERROR_CODES`]).
+    pub fn retryable_error_codes<I, S>(mut self, codes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.retryable_error_codes = Some(codes.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Decide which failed attempts are retried (default: [`DefaultRetryClassifier`],
+    /// which retries 5xx responses, the
+    /// [`retryable_status_codes`](Self::retryable_status_codes) (408 and 429 by
+    /// default), the [`retryable_error_codes`](Self::retryable_error_codes),
+    /// and connect/timeout errors).
+    pub fn retry_classifier(mut self, classifier: Arc<dyn RetryClassifier>) -> Self {
+        self.retry_classifier = Some(classifier);
+        self
+    }
+
     /// Set the maximum number of idle connections kept alive per host.
     pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
         self.pool_max_idle_per_host = Some(max);
@@ -309,6 +985,18 @@ impl ClientBuilder {
         self
     }
 
+    /// Cap the number of requests in flight at once (default: no limit).
+    ///
+    /// Further requests wait for a free slot, so thousands of concurrently
+    /// spawned tasks cannot exhaust sockets or file descriptors. A slot is
+    /// held from sending an attempt until its response headers arrive;
+    /// streamed response bodies are not counted. Clones of the client share
+    /// the limit; see [`OssClient::in_flight_requests`](crate::OssClient::in_flight_requests).
+    pub fn max_in_flight_requests(mut self, max: usize) -> Self {
+        self.max_in_flight_requests = Some(max);
+        self
+    }
+
     /// Set the TCP connect timeout (default: 10 seconds).
     pub fn connect_timeout(mut self, timeout: Duration) -> Self {
         self.connect_timeout = Some(timeout);
@@ -327,6 +1015,135 @@ impl ClientBuilder {
         self
     }
 
+    /// Select the HTTP protocol version (default: [`HttpVersion::Auto`]).
+    pub fn http_version(mut self, version: HttpVersion) -> Self {
+        self.http_version = version;
+        self
+    }
+
+    /// Enable adaptive HTTP/2 flow-control windows, which grow with the
+    /// measured bandwidth-delay product (default: false).
+    ///
+    /// Overrides the initial window sizes.
+    pub fn http2_adaptive_window(mut self, enabled: bool) -> Self {
+        self.http2_adaptive_window = enabled;
+        self
+    }
+
+    /// Set the initial HTTP/2 per-stream flow-control window in bytes.
+    pub fn http2_initial_stream_window_size(mut self, size: u32) -> Self {
+        self.http2_initial_stream_window_size = Some(size);
+        self
+    }
+
+    /// Set the initial HTTP/2 per-connection flow-control window in bytes.
+    pub fn http2_initial_connection_window_size(mut self, size: u32) -> Self {
+        self.http2_initial_connection_window_size = Some(size);
+        self
+    }
+
+    /// Trust an additional root certificate, e.g. the internal CA of a
+    /// private OSS gateway. Can be called repeatedly.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use rs_ali_oss::config::ClientBuilder;
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let ca = reqwest::Certificate::from_pem(&std::fs::read("internal-ca.pem")?)?;
+    /// let builder = ClientBuilder::new()
+    ///     .endpoint("https://oss-gateway.internal")
+    ///     .add_root_certificate(ca);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_root_certificate(mut self, certificate: reqwest::Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Present a client certificate for mutual TLS, e.g. one created with
+    /// [`reqwest::Identity::from_pkcs8_pem`] or
+    /// [`reqwest::Identity::from_pkcs12_der`].
+    pub fn identity(mut self, identity: reqwest::Identity) -> Self {
+        self.identity = Some(identity);
+        self
+    }
+
+    /// **Dangerous:** accept any server certificate, including expired,
+    /// self-signed or wrong-host ones (default: false).
+    ///
+    /// This disables protection against man-in-the-middle attacks and
+    /// exposes credentials and data. Only use it against disposable test
+    /// gateways; prefer [`add_root_certificate`](Self::add_root_certificate)
+    /// for private CAs.
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.danger_accept_invalid_certs = accept;
+        self
+    }
+
+    /// Append an application identifier to the default `User-Agent`, e.g.
+    /// `"myapp/1.2.3"` produces `rs-ali-oss/{version} myapp/1.2.3`.
+    ///
+    /// Makes the application's traffic identifiable in OSS access logs while
+    /// keeping the SDK attribution.
+    pub fn user_agent_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.user_agent_suffix = Some(suffix.into());
+        self
+    }
+
+    /// Add a header to every request, e.g. a gateway routing header or a
+    /// cost-center tag. Call repeatedly to add several headers; repeating a
+    /// name sends every value.
+    ///
+    /// The header is added before signing, so `x-oss-*` headers are covered
+    /// by V4 signatures. A header an operation sets itself, or one set via
+    /// [`OperationOptionsBuilder::header`], takes precedence.
+    pub fn default_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.default_headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Infer the `Content-Type` of `PutObject`, `AppendObject` and
+    /// `InitiateMultipartUpload` requests that do not set one from the
+    /// object key's extension, e.g. `text/html` for `index.html`
+    /// (default: true).
+    ///
+    /// Only takes effect with the `mime_guess` feature; without it, or for
+    /// unknown extensions, OSS stores such objects as
+    /// `application/octet-stream`.
+    pub fn detect_content_type(mut self, enabled: bool) -> Self {
+        self.detect_content_type = Some(enabled);
+        self
+    }
+
+    /// Set how user metadata values that are not printable ASCII are handled
+    /// (default: [`MetadataEncoding::Reject`]).
+    pub fn metadata_encoding(mut self, encoding: MetadataEncoding) -> Self {
+        self.metadata_encoding = encoding;
+        self
+    }
+
+    /// Transparently copy sources larger than the
+    /// [multipart copy threshold](Self::multipart_copy_threshold) with
+    /// `UploadPartCopy` when calling `copy_object` (default: false).
+    ///
+    /// When enabled, every `copy_object` looks up the source size with a
+    /// `HeadObject` request first; if that lookup fails, the object is
+    /// copied with a single `CopyObject`. `CopyObject` itself only accepts
+    /// sources up to 1 GiB.
+    pub fn multipart_copy(mut self, enabled: bool) -> Self {
+        self.multipart_copy = Some(enabled);
+        self
+    }
+
+    /// Source size in bytes above which `copy_object` falls back to a
+    /// multipart copy, once enabled with
+    /// [`multipart_copy`](Self::multipart_copy) (default: 1 GiB).
+    pub fn multipart_copy_threshold(mut self, bytes: u64) -> Self {
+        self.multipart_copy_threshold = Some(bytes);
+        self
+    }
+
     /// Allow insecure HTTP endpoints (default: false).
     ///
     /// By default, custom endpoints must use HTTPS. Enable this for local development
@@ -336,22 +1153,107 @@ impl ClientBuilder {
         self
     }
 
+    /// Set the signature version (default: [`SignatureVersion::V4`]).
+    ///
+    /// Only use [`SignatureVersion::V1`] for OSS-compatible deployments that
+    /// reject V4 signatures.
+    pub fn signature_version(mut self, version: SignatureVersion) -> Self {
+        self.signature_version = version;
+        self
+    }
+
+    /// Sign requests with a custom [`Signer`] instead of the built-in one
+    /// chosen by [`signature_version`](Self::signature_version).
+    ///
+    /// Useful for hardware-backed keys, signing proxies or compatibility
+    /// shims. Presigned URLs are still signed by the SDK.
+    pub fn signer(mut self, signer: Arc<dyn Signer>) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Choose whether V4 signatures cover the request body
+    /// (default: [`PayloadSigning::Unsigned`]).
+    pub fn payload_signing(mut self, signing: PayloadSigning) -> Self {
+        self.payload_signing = signing;
+        self
+    }
+
+    /// Pass each attempt's canonical request and string-to-sign to interceptors
+    /// via [`InterceptorContext::signing`](crate::middleware::InterceptorContext::signing)
+    /// (default: false).
+    ///
+    /// Intended for diagnosing `SignatureDoesNotMatch` errors. The canonical
+    /// request contains signed header values, including the STS security token.
+    pub fn expose_signing_details(mut self, enabled: bool) -> Self {
+        self.expose_signing_details = enabled;
+        self
+    }
+
+    /// Include extra request headers in the V4 signature (`AdditionalHeaders`).
+    ///
+    /// Some bucket policies pin header values and require them to be signed,
+    /// e.g. `host` or a custom header. Names are case-insensitive; headers
+    /// already signed by default (`x-oss-*`, `Content-Type`, `Content-MD5`)
+    /// need not be listed. Headers missing from a request are skipped. Has no
+    /// effect with [`SignatureVersion::V1`] or on presigned URLs.
+    pub fn sign_additional_headers<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.additional_signed_headers = names.into_iter().map(Into::into).collect();
+        self
+    }
+
     /// Build the [`Config`], validating all required fields.
     pub fn build(self) -> Result<Config> {
-        let access_key_id = self
-            .access_key_id
-            .ok_or_else(|| OssError::MissingField("access_key_id".to_string()))?;
+        let credential_provider: Arc<dyn CredentialProvider> = match self.credential_provider {
+            Some(provider) => {
+                if self.access_key_id.is_some()
+                    || self.access_key_secret.is_some()
+                    || self.security_token.is_some()
+                {
+                    return Err(OssError::InvalidParameter {
+                        field: "credential_provider".into(),
+                        reason:
+                            "cannot be combined with access_key_id/access_key_secret/security_token"
+                                .into(),
+                    });
+                }
+                provider
+            }
+            None => {
+                let access_key_id = self
+                    .access_key_id
+                    .ok_or_else(|| OssError::MissingField("access_key_id".to_string()))?;
+
+                if access_key_id.trim().is_empty() {
+                    return Err(OssError::InvalidParameter {
+                        field: "access_key_id".into(),
+                        reason: "must not be empty or whitespace-only".into(),
+                    });
+                }
 
-        if access_key_id.trim().is_empty() {
-            return Err(OssError::InvalidParameter {
-                field: "access_key_id".into(),
-                reason: "must not be empty or whitespace-only".into(),
-            });
-        }
+                let mut access_key_secret = self
+                    .access_key_secret
+                    .ok_or_else(|| OssError::MissingField("access_key_secret".to_string()))?;
+
+                // Move the inner String out of Zeroizing to avoid creating an
+                // intermediate, unzeroized copy on the heap.
+                let secret_str = Zeroizing::new(std::mem::take(&mut *access_key_secret));
+
+                let credentials = match self.security_token {
+                    Some(mut token) => {
+                        let token_str = Zeroizing::new(std::mem::take(&mut *token));
+                        Credentials::with_security_token(access_key_id, &*secret_str, &*token_str)
+                    }
+                    None => Credentials::new(access_key_id, &*secret_str),
+                };
+                Arc::new(StaticProvider::from_credentials(credentials))
+            }
+        };
 
-        let mut access_key_secret = self
-            .access_key_secret
-            .ok_or_else(|| OssError::MissingField("access_key_secret".to_string()))?;
         let region_str = self
             .region
             .ok_or_else(|| OssError::MissingField("region".to_string()))?;
@@ -369,6 +1271,45 @@ impl ClientBuilder {
             });
         }
 
+        if self.cname && self.endpoint.is_none() {
+            return Err(OssError::InvalidParameter {
+                field: "cname".into(),
+                reason: "requires a custom endpoint".into(),
+            });
+        }
+
+        if self.use_internal_endpoint && self.endpoint.is_some() {
+            return Err(OssError::InvalidParameter {
+                field: "use_internal_endpoint".into(),
+                reason: "cannot be combined with a custom endpoint".into(),
+            });
+        }
+
+        if self.accelerate_endpoint.is_some()
+            && (self.use_internal_endpoint || self.endpoint.is_some())
+        {
+            return Err(OssError::InvalidParameter {
+                field: "accelerate_endpoint".into(),
+                reason: "cannot be combined with a custom or internal endpoint".into(),
+            });
+        }
+
+        let additional_signed_headers =
+            crate::auth::v4::normalize_additional_headers(&self.additional_signed_headers)?;
+
+        let mut user_agent = format!("rs-ali-oss/{}", env!("CARGO_PKG_VERSION"));
+        if let Some(suffix) = &self.user_agent_suffix {
+            let suffix = suffix.trim();
+            if suffix.is_empty() || HeaderValue::from_str(suffix).is_err() {
+                return Err(OssError::InvalidParameter {
+                    field: "user_agent_suffix".into(),
+                    reason: "must be a non-empty, valid header value".into(),
+                });
+            }
+            user_agent.push(' ');
+            user_agent.push_str(suffix);
+        }
+
         let mut retry_config = RetryConfig::default();
         if let Some(max_retries) = self.max_retries {
             retry_config.max_retries = max_retries;
@@ -379,6 +1320,15 @@ impl ClientBuilder {
         if let Some(max_delay) = self.max_retry_delay {
             retry_config.max_delay = max_delay;
         }
+        if let Some(size) = self.max_replay_buffer_size {
+            retry_config.max_replay_buffer_size = size;
+        }
+        if let Some(statuses) = self.retryable_status_codes {
+            retry_config.retryable_status_codes = statuses;
+        }
+        if let Some(codes) = self.retryable_error_codes {
+            retry_config.retryable_error_codes = codes;
+        }
 
         let mut pool_config = PoolConfig::default();
         if let Some(max) = self.pool_max_idle_per_host {
@@ -387,6 +1337,18 @@ impl ClientBuilder {
         if let Some(timeout) = self.pool_idle_timeout {
             pool_config.idle_timeout = Some(timeout);
         }
+        if let Some(max) = self.max_in_flight_requests {
+            if max == 0 || max > tokio::sync::Semaphore::MAX_PERMITS {
+                return Err(OssError::InvalidParameter {
+                    field: "max_in_flight_requests".into(),
+                    reason: format!(
+                        "must be between 1 and {}",
+                        tokio::sync::Semaphore::MAX_PERMITS
+                    ),
+                });
+            }
+            pool_config.max_in_flight_requests = Some(max);
+        }
 
         let mut timeout_config = TimeoutConfig::default();
         if let Some(t) = self.connect_timeout {
@@ -397,24 +1359,52 @@ impl ClientBuilder {
         }
         timeout_config.request_timeout = self.request_timeout;
 
-        // Move the inner String out of Zeroizing to avoid creating an
-        // intermediate, unzeroized copy on the heap.
-        let secret_str = Zeroizing::new(std::mem::take(&mut *access_key_secret));
+        let tls_config = TlsConfig {
+            root_certificates: self.root_certificates,
+            identity: self.identity,
+            danger_accept_invalid_certs: self.danger_accept_invalid_certs,
+        };
+
+        let protocol_config = ProtocolConfig {
+            version: self.http_version,
+            http2_adaptive_window: self.http2_adaptive_window,
+            http2_initial_stream_window_size: self.http2_initial_stream_window_size,
+            http2_initial_connection_window_size: self.http2_initial_connection_window_size,
+        };
 
         Ok(Config {
-            credentials: match self.security_token {
-                Some(mut token) => {
-                    let token_str = Zeroizing::new(std::mem::take(&mut *token));
-                    Credentials::with_security_token(access_key_id, &*secret_str, &*token_str)
-                }
-                None => Credentials::new(access_key_id, &*secret_str),
-            },
+            credential_provider,
             region,
             endpoint: self.endpoint,
             use_path_style: self.use_path_style,
+            cname: self.cname,
+            use_internal_endpoint: self.use_internal_endpoint,
+            accelerate_endpoint: self.accelerate_endpoint,
+            resolve_overrides: self.resolve_overrides,
             retry_config,
             pool_config,
             timeout_config,
+            protocol_config,
+            tls_config,
+            signature_version: self.signature_version,
+            signer: self.signer.unwrap_or_else(|| match self.signature_version {
+                SignatureVersion::V4 => Arc::new(V4Signer),
+                SignatureVersion::V1 => Arc::new(V1Signer),
+            }),
+            payload_signing: self.payload_signing,
+            expose_signing_details: self.expose_signing_details,
+            additional_signed_headers,
+            user_agent,
+            default_headers: parse_headers(self.default_headers, "default_header")?,
+            detect_content_type: self.detect_content_type.unwrap_or(true),
+            metadata_encoding: self.metadata_encoding,
+            multipart_copy_threshold: self.multipart_copy.unwrap_or(false).then(|| {
+                self.multipart_copy_threshold
+                    .unwrap_or(Self::DEFAULT_MULTIPART_COPY_THRESHOLD)
+            }),
+            retry_classifier: self
+                .retry_classifier
+                .unwrap_or_else(|| Arc::new(DefaultRetryClassifier)),
         })
     }
 }
@@ -431,18 +1421,58 @@ impl fmt::Debug for ClientBuilder {
                 "security_token",
                 &self.security_token.as_ref().map(|_| "****"),
             )
+            .field(
+                "credential_provider",
+                &self.credential_provider.as_ref().map(|p| p.provider_name()),
+            )
             .field("region", &self.region)
             .field("endpoint", &self.endpoint)
             .field("use_path_style", &self.use_path_style)
+            .field("cname", &self.cname)
+            .field("use_internal_endpoint", &self.use_internal_endpoint)
+            .field("accelerate_endpoint", &self.accelerate_endpoint)
+            .field("resolve_overrides", &self.resolve_overrides)
             .field("max_retries", &self.max_retries)
             .field("base_retry_delay", &self.base_retry_delay)
             .field("max_retry_delay", &self.max_retry_delay)
+            .field("max_replay_buffer_size", &self.max_replay_buffer_size)
+            .field("retryable_status_codes", &self.retryable_status_codes)
+            .field("retryable_error_codes", &self.retryable_error_codes)
             .field("pool_max_idle_per_host", &self.pool_max_idle_per_host)
             .field("pool_idle_timeout", &self.pool_idle_timeout)
+            .field("max_in_flight_requests", &self.max_in_flight_requests)
             .field("connect_timeout", &self.connect_timeout)
             .field("read_timeout", &self.read_timeout)
             .field("request_timeout", &self.request_timeout)
+            .field("http_version", &self.http_version)
+            .field("http2_adaptive_window", &self.http2_adaptive_window)
+            .field(
+                "http2_initial_stream_window_size",
+                &self.http2_initial_stream_window_size,
+            )
+            .field(
+                "http2_initial_connection_window_size",
+                &self.http2_initial_connection_window_size,
+            )
+            .field("root_certificates", &self.root_certificates.len())
+            .field("identity", &self.identity.as_ref().map(|_| "****"))
+            .field(
+                "danger_accept_invalid_certs",
+                &self.danger_accept_invalid_certs,
+            )
             .field("allow_insecure", &self.allow_insecure)
+            .field("signature_version", &self.signature_version)
+            .field("signer", &self.signer.is_some())
+            .field("payload_signing", &self.payload_signing)
+            .field("expose_signing_details", &self.expose_signing_details)
+            .field("additional_signed_headers", &self.additional_signed_headers)
+            .field("user_agent_suffix", &self.user_agent_suffix)
+            .field("default_headers", &self.default_headers)
+            .field("detect_content_type", &self.detect_content_type)
+            .field("metadata_encoding", &self.metadata_encoding)
+            .field("multipart_copy", &self.multipart_copy)
+            .field("multipart_copy_threshold", &self.multipart_copy_threshold)
+            .field("retry_classifier", &self.retry_classifier)
             .finish()
     }
 }
@@ -460,8 +1490,9 @@ mod tests {
             .build();
         assert!(config.is_ok());
         let config = config.unwrap();
-        assert_eq!(config.credentials().access_key_id(), "test-id");
-        assert_eq!(config.credentials().access_key_secret(), "test-secret");
+        let credentials = config.credentials().unwrap();
+        assert_eq!(credentials.access_key_id(), "test-id");
+        assert_eq!(credentials.access_key_secret(), "test-secret");
         assert_eq!(config.region().as_ref(), "cn-hangzhou");
     }
 
@@ -550,6 +1581,19 @@ mod tests {
         assert_eq!(creds.security_token(), Some("sts-token"));
     }
 
+    #[test]
+    fn credentials_expiration() {
+        let creds = Credentials::new("id", "secret");
+        assert!(creds.expiration().is_none());
+        assert!(!creds.expires_within(Duration::from_secs(3600)));
+
+        let soon = Utc::now() + chrono::Duration::seconds(60);
+        let creds = Credentials::with_security_token("id", "secret", "token").with_expiration(soon);
+        assert_eq!(creds.expiration(), Some(soon));
+        assert!(creds.expires_within(Duration::from_secs(120)));
+        assert!(!creds.expires_within(Duration::from_secs(10)));
+    }
+
     #[test]
     fn credentials_debug_redacts_security_token() {
         let creds = Credentials::with_security_token("id", "my-secret-value", "my-token-value");
@@ -568,8 +1612,70 @@ mod tests {
             .region("cn-hangzhou")
             .build()
             .unwrap();
-        assert_eq!(config.credentials().access_key_id(), "sts-id");
-        assert_eq!(config.credentials().security_token(), Some("sts-token"));
+        let credentials = config.credentials().unwrap();
+        assert_eq!(credentials.access_key_id(), "sts-id");
+        assert_eq!(credentials.security_token(), Some("sts-token"));
+    }
+
+    #[test]
+    fn builder_signature_version_defaults_to_v4() {
+        let builder = ClientBuilder::new()
+            .access_key_id("id")
+            .access_key_secret("secret")
+            .region("cn-hangzhou");
+        let config = builder.build().unwrap();
+        assert_eq!(config.signature_version(), SignatureVersion::V4);
+
+        let config = ClientBuilder::new()
+            .access_key_id("id")
+            .access_key_secret("secret")
+            .region("cn-hangzhou")
+            .signature_version(SignatureVersion::V1)
+            .build()
+            .unwrap();
+        assert_eq!(config.signature_version(), SignatureVersion::V1);
+    }
+
+    #[test]
+    fn builder_normalizes_additional_signed_headers() {
+        let config = ClientBuilder::new()
+            .access_key_id("id")
+            .access_key_secret("secret")
+            .region("cn-hangzhou")
+            .sign_additional_headers(["X-Pinned", "Host", "Content-MD5"])
+            .build()
+            .unwrap();
+        assert_eq!(config.additional_signed_headers(), ["host", "x-pinned"]);
+
+        let result = ClientBuilder::new()
+            .access_key_id("id")
+            .access_key_secret("secret")
+            .region("cn-hangzhou")
+            .sign_additional_headers(["not valid"])
+            .build();
+        assert!(matches!(result, Err(OssError::InvalidParameter { .. })));
+    }
+
+    #[test]
+    fn builder_with_credential_provider() {
+        let config = ClientBuilder::new()
+            .credential_provider(Arc::new(StaticProvider::new("provider-id", "secret")))
+            .region("cn-hangzhou")
+            .build()
+            .unwrap();
+        assert_eq!(config.credential_provider().provider_name(), "static");
+        assert_eq!(config.credentials().unwrap().access_key_id(), "provider-id");
+    }
+
+    #[test]
+    fn builder_rejects_provider_with_static_keys() {
+        let result = ClientBuilder::new()
+            .access_key_id("id")
+            .access_key_secret("secret")
+            .credential_provider(Arc::new(StaticProvider::new("provider-id", "secret")))
+            .region("cn-hangzhou")
+            .build();
+        assert!(matches!(result, Err(OssError::InvalidParameter { .. })));
     }
 
     #[test]
@@ -649,4 +1755,317 @@ mod tests {
             .build();
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn builder_cname_requires_custom_endpoint() {
+        let result = ClientBuilder::new()
+            .access_key_id("id")
+            .access_key_secret("secret")
+            .region("cn-hangzhou")
+            .cname(true)
+            .build();
+        assert!(matches!(
+            result,
+            Err(OssError::InvalidParameter { field, .. }) if field == "cname"
+        ));
+    }
+
+    #[test]
+    fn builder_rejects_internal_endpoint_with_custom_endpoint() {
+        let result = ClientBuilder::new()
+            .access_key_id("id")
+            .access_key_secret("secret")
+            .region("cn-hangzhou")
+            .endpoint("https://custom.oss.example.com")
+            .use_internal_endpoint(true)
+            .build();
+        assert!(matches!(
+            result,
+            Err(OssError::InvalidParameter { field, .. }) if field == "use_internal_endpoint"
+        ));
+    }
+
+    #[test]
+    fn builder_accelerate_endpoint() {
+        let config = ClientBuilder::new()
+            .access_key_id("id")
+            .access_key_secret("secret")
+            .region("cn-hangzhou")
+            .use_accelerate_endpoint(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.accelerate_endpoint(),
+            Some(AccelerateEndpoint::Global)
+        );
+
+        let config = ClientBuilder::new()
+            .access_key_id("id")
+            .access_key_secret("secret")
+            .region("cn-hangzhou")
+            .accelerate_endpoint(AccelerateEndpoint::Overseas)
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.accelerate_endpoint().map(|e| e.host()),
+            Some("oss-accelerate-overseas.aliyuncs.com")
+        );
+    }
+
+    #[test]
+    fn builder_rejects_accelerate_with_other_endpoints() {
+        let base = || {
+            ClientBuilder::new()
+                .access_key_id("id")
+                .access_key_secret("secret")
+                .region("cn-hangzhou")
+                .use_accelerate_endpoint(true)
+        };
+        for builder in [
+            base().use_internal_endpoint(true),
+            base().endpoint("https://custom.oss.example.com"),
+        ] {
+            assert!(matches!(
+                builder.build(),
+                Err(OssError::InvalidParameter { field, .. }) if field == "accelerate_endpoint"
+            ));
+        }
+    }
+
+    #[test]
+    fn builder_protocol_config() {
+        let config = ClientBuilder::new()
+            .access_key_id("id")
+            .access_key_secret("secret")
+            .region("cn-hangzhou")
+            .build()
+            .unwrap();
+        assert_eq!(config.protocol_config().version, HttpVersion::Auto);
+        assert!(!config.protocol_config().http2_adaptive_window);
+
+        let config = ClientBuilder::new()
+            .access_key_id("id")
+            .access_key_secret("secret")
+            .region("cn-hangzhou")
+            .http_version(HttpVersion::Http1Only)
+            .http2_adaptive_window(true)
+            .http2_initial_stream_window_size(1 << 20)
+            .http2_initial_connection_window_size(4 << 20)
+            .build()
+            .unwrap();
+        let pc = config.protocol_config();
+        assert_eq!(pc.version, HttpVersion::Http1Only);
+        assert!(pc.http2_adaptive_window);
+        assert_eq!(pc.http2_initial_stream_window_size, Some(1 << 20));
+        assert_eq!(pc.http2_initial_connection_window_size, Some(4 << 20));
+    }
+
+    const OSS_VARS: [&str; 5] = [
+        "OSS_ACCESS_KEY_ID",
+        "OSS_ACCESS_KEY_SECRET",
+        "OSS_SESSION_TOKEN",
+        "OSS_REGION",
+        "OSS_ENDPOINT",
+    ];
+
+    #[test]
+    fn from_env_reads_variables() {
+        temp_env::with_vars(
+            [
+                ("OSS_ACCESS_KEY_ID", Some("env-id")),
+                ("OSS_ACCESS_KEY_SECRET", Some("env-secret")),
+                ("OSS_SESSION_TOKEN", Some("env-token")),
+                ("OSS_REGION", Some("cn-shanghai")),
+                ("OSS_ENDPOINT", Some("oss.example.com")),
+            ],
+            || {
+                let config = Config::from_env().unwrap();
+                let credentials = config.credentials().unwrap();
+                assert_eq!(credentials.access_key_id(), "env-id");
+                assert_eq!(credentials.access_key_secret(), "env-secret");
+                assert_eq!(credentials.security_token(), Some("env-token"));
+                assert_eq!(config.region().as_ref(), "cn-shanghai");
+                assert_eq!(config.endpoint(), Some("https://oss.example.com"));
+            },
+        );
+    }
+
+    #[test]
+    fn from_env_setters_take_precedence() {
+        temp_env::with_vars(
+            [
+                ("OSS_ACCESS_KEY_ID", Some("env-id")),
+                ("OSS_ACCESS_KEY_SECRET", Some("env-secret")),
+                ("OSS_SESSION_TOKEN", None),
+                ("OSS_REGION", Some("")),
+                ("OSS_ENDPOINT", None),
+            ],
+            || {
+                let config = ClientBuilder::from_env()
+                    .access_key_id("explicit-id")
+                    .region("cn-beijing")
+                    .build()
+                    .unwrap();
+                let credentials = config.credentials().unwrap();
+                assert_eq!(credentials.access_key_id(), "explicit-id");
+                assert_eq!(credentials.access_key_secret(), "env-secret");
+                assert_eq!(credentials.security_token(), None);
+                assert_eq!(config.region().as_ref(), "cn-beijing");
+                assert_eq!(config.endpoint(), None);
+            },
+        );
+    }
+
+    #[test]
+    fn from_env_missing_vars_fails() {
+        temp_env::with_vars_unset(OSS_VARS, || {
+            assert!(matches!(Config::from_env(), Err(OssError::MissingField(_))));
+        });
+    }
+
+    #[test]
+    fn operation_options_builder_validates() {
+        let options = OperationOptions::builder()
+            .timeout(Duration::from_secs(5))
+            .max_retries(1)
+            .traffic_limit(OperationOptionsBuilder::MIN_TRAFFIC_LIMIT)
+            .header("x-oss-request-payer", "requester")
+            .build()
+            .unwrap();
+        assert_eq!(options.timeout(), Some(Duration::from_secs(5)));
+        assert_eq!(options.max_retries(), Some(1));
+        assert_eq!(options.headers()["x-oss-request-payer"], "requester");
+
+        for limit in [
+            OperationOptionsBuilder::MIN_TRAFFIC_LIMIT - 1,
+            OperationOptionsBuilder::MAX_TRAFFIC_LIMIT + 1,
+        ] {
+            assert!(matches!(
+                OperationOptions::builder().traffic_limit(limit).build(),
+                Err(OssError::InvalidParameter { .. })
+            ));
+        }
+        assert!(matches!(
+            OperationOptions::builder()
+                .header("bad header", "v")
+                .build(),
+            Err(OssError::InvalidParameter { .. })
+        ));
+        assert!(matches!(
+            OperationOptions::builder()
+                .header("x-ok", "bad\nvalue")
+                .build(),
+            Err(OssError::InvalidParameter { .. })
+        ));
+    }
+
+    #[test]
+    fn builder_parses_default_headers() {
+        let config = ClientBuilder::new()
+            .access_key_id("test-id")
+            .access_key_secret("test-secret")
+            .region("cn-hangzhou")
+            .default_header("X-Gateway-Route", "blue")
+            .default_header("x-cost-center", "storage")
+            .default_header("x-cost-center", "analytics")
+            .build()
+            .unwrap();
+        assert_eq!(config.default_headers().len(), 3);
+        assert_eq!(config.default_headers()["x-gateway-route"], "blue");
+        let cost_centers: Vec<_> = config
+            .default_headers()
+            .get_all("x-cost-center")
+            .iter()
+            .collect();
+        assert_eq!(cost_centers, ["storage", "analytics"]);
+
+        let result = ClientBuilder::new()
+            .access_key_id("test-id")
+            .access_key_secret("test-secret")
+            .region("cn-hangzhou")
+            .default_header("bad header", "x")
+            .build();
+        assert!(matches!(
+            result,
+            Err(OssError::InvalidParameter { ref field, .. }) if field == "default_header"
+        ));
+    }
+
+    #[test]
+    fn builder_appends_user_agent_suffix() {
+        let base = format!("rs-ali-oss/{}", env!("CARGO_PKG_VERSION"));
+        let config = ClientBuilder::new()
+            .access_key_id("test-id")
+            .access_key_secret("test-secret")
+            .region("cn-hangzhou")
+            .build()
+            .unwrap();
+        assert_eq!(config.user_agent(), base);
+
+        let config = ClientBuilder::new()
+            .access_key_id("test-id")
+            .access_key_secret("test-secret")
+            .region("cn-hangzhou")
+            .user_agent_suffix("myapp/1.2.3")
+            .build()
+            .unwrap();
+        assert_eq!(config.user_agent(), format!("{base} myapp/1.2.3"));
+
+        for suffix in ["  ", "bad\napp"] {
+            let result = ClientBuilder::new()
+                .access_key_id("test-id")
+                .access_key_secret("test-secret")
+                .region("cn-hangzhou")
+                .user_agent_suffix(suffix)
+                .build();
+            assert!(matches!(result, Err(OssError::InvalidParameter { .. })));
+        }
+    }
+
+    #[test]
+    fn builder_rejects_zero_in_flight_limit() {
+        let result = ClientBuilder::new()
+            .access_key_id("test-id")
+            .access_key_secret("test-secret")
+            .region("cn-hangzhou")
+            .max_in_flight_requests(0)
+            .build();
+        assert!(matches!(result, Err(OssError::InvalidParameter { .. })));
+    }
+
+    #[test]
+    fn metadata_reject_allows_only_printable_ascii() {
+        let encoding = MetadataEncoding::Reject;
+        assert_eq!(encoding.encode("k", "plain value").unwrap(), "plain value");
+        for value in ["café", "line\r\nbreak"] {
+            assert!(matches!(
+                encoding.encode("k", value),
+                Err(OssError::InvalidParameter { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn metadata_percent_encoding_round_trips() {
+        let encoding = MetadataEncoding::PercentEncode;
+        for value in ["café", "100%", "a\nb", "plain"] {
+            let encoded = encoding.encode("k", value).unwrap();
+            assert!(encoded.is_ascii() && !encoded.contains('\n'), "{encoded}");
+            assert_eq!(encoding.decode(&encoded), value);
+        }
+        assert_eq!(encoding.encode("k", "café").unwrap(), "caf%C3%A9");
+    }
+
+    #[test]
+    fn metadata_rfc2047_round_trips() {
+        let encoding = MetadataEncoding::Rfc2047;
+        assert_eq!(encoding.encode("k", "plain").unwrap(), "plain");
+        let encoded = encoding.encode("k", "日本語").unwrap();
+        assert_eq!(encoded, "=?UTF-8?B?5pel5pys6Kqe?=");
+        assert_eq!(encoding.decode(&encoded), "日本語");
+        assert_eq!(
+            encoding.decode("=?UTF-8?B?not base64?="),
+            "=?UTF-8?B?not base64?="
+        );
+    }
 }
@@ -3,6 +3,20 @@
 //! Alibaba Cloud OSS uses CRC64-ECMA-182 (polynomial `0x42F0E1EBA9EA3693`)
 //! to verify data integrity on uploads and downloads. The server returns the
 //! checksum in the `x-oss-hash-crc64ecma` response header.
+//!
+//! # Examples
+//! ```
+//! use rs_ali_oss::crc64::{self, Crc64};
+//!
+//! let mut hasher = Crc64::new();
+//! hasher.update(b"hello, ");
+//! hasher.update(b"oss");
+//! assert_eq!(hasher.finalize(), crc64::checksum(b"hello, oss"));
+//!
+//! // The CRC of a concatenation follows from the CRCs of its parts.
+//! let combined = crc64::combine(crc64::checksum(b"hello, "), crc64::checksum(b"oss"), 3);
+//! assert_eq!(combined, hasher.finalize());
+//! ```
 
 // Reversed/reflected form of 0x42F0E1EBA9EA3693, matching Go's crc64.ECMA
 const POLY: u64 = 0xC96C5795D7870F42;
@@ -30,6 +44,52 @@ const fn make_table() -> [u64; 256] {
 
 static TABLE: [u64; 256] = make_table();
 
+/// Streaming CRC64-ECMA hasher producing the values OSS reports in
+/// `x-oss-hash-crc64ecma`.
+///
+/// Feed data with [`update`](Self::update) (or through [`std::io::Write`],
+/// e.g. with [`std::io::copy`]) and read the checksum with
+/// [`finalize`](Self::finalize).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Crc64 {
+    crc: u64,
+}
+
+impl Crc64 {
+    /// Create a hasher for empty input.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resume from the checksum of data hashed earlier.
+    pub fn with_initial(crc: u64) -> Self {
+        Self { crc }
+    }
+
+    /// Add data to the checksum.
+    pub fn update(&mut self, data: &[u8]) {
+        self.crc = update(self.crc, data);
+    }
+
+    /// The checksum of all data added so far.
+    ///
+    /// Does not consume the hasher; more data may be added afterwards.
+    pub fn finalize(&self) -> u64 {
+        self.crc
+    }
+}
+
+impl std::io::Write for Crc64 {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
 /// Compute the CRC64-ECMA checksum of a byte slice.
 pub fn checksum(data: &[u8]) -> u64 {
     update(0, data)
@@ -37,12 +97,14 @@ pub fn checksum(data: &[u8]) -> u64 {
 
 /// Update a running CRC64-ECMA checksum with additional data.
 pub fn update(crc: u64, data: &[u8]) -> u64 {
-    let mut crc = crc;
+    // The register is inverted before and after, as in CRC-64/XZ (Go's
+    // `crc64.ECMA`), so checksums match the values OSS returns.
+    let mut crc = !crc;
     for &byte in data {
         let index = ((crc ^ byte as u64) & 0xFF) as usize;
         crc = TABLE[index] ^ (crc >> 8);
     }
-    crc
+    !crc
 }
 
 /// Combine two CRC64 checksums (for multipart uploads).
@@ -117,7 +179,9 @@ fn gf2_matrix_square(square: &mut [u64; 64], mat: &[u64; 64]) {
 
 /// Verify a CRC64 checksum against a value from the `x-oss-hash-crc64ecma` header.
 ///
-/// Returns `Ok(())` if the checksums match, or an error if they don't.
+/// Returns `Ok(())` if the checksums match, or
+/// [`OssError::ChecksumMismatch`](crate::error::OssError::ChecksumMismatch)
+/// if they don't.
 pub fn verify(computed: u64, header_value: &str) -> crate::error::Result<()> {
     let expected: u64 =
         header_value
@@ -127,9 +191,9 @@ pub fn verify(computed: u64, header_value: &str) -> crate::error::Result<()> {
                 reason: format!("invalid CRC64 value: '{header_value}'"),
             })?;
     if computed != expected {
-        return Err(crate::error::OssError::InvalidParameter {
-            field: "crc64".into(),
-            reason: format!("CRC64 mismatch: computed {computed}, server returned {expected}"),
+        return Err(crate::error::OssError::ChecksumMismatch {
+            computed,
+            server: expected,
         });
     }
     Ok(())
@@ -203,7 +267,10 @@ mod tests {
     #[test]
     fn verify_mismatch() {
         let crc = checksum(b"test data");
-        assert!(verify(crc, "12345").is_err());
+        assert!(matches!(
+            verify(crc, "12345"),
+            Err(crate::error::OssError::ChecksumMismatch { server: 12345, .. })
+        ));
     }
 
     #[test]
@@ -213,9 +280,30 @@ mod tests {
 
     #[test]
     fn known_vector_123456789() {
-        let crc = checksum(b"123456789");
-        assert_ne!(crc, 0);
-        assert_eq!(crc, checksum(b"123456789"));
+        // CRC-64/XZ check value, as returned by OSS for this content.
+        assert_eq!(checksum(b"123456789"), 0x995D_C9BB_DF19_39FA);
+    }
+
+    #[test]
+    fn hasher_matches_one_shot() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        let mut hasher = Crc64::new();
+        for chunk in data.chunks(5) {
+            hasher.update(chunk);
+        }
+        assert_eq!(hasher.finalize(), checksum(data));
+
+        let mut resumed = Crc64::with_initial(checksum(&data[..10]));
+        resumed.update(&data[10..]);
+        assert_eq!(resumed, hasher);
+    }
+
+    #[test]
+    fn hasher_implements_write() {
+        let data = vec![7u8; 100_000];
+        let mut hasher = Crc64::new();
+        std::io::copy(&mut data.as_slice(), &mut hasher).unwrap();
+        assert_eq!(hasher.finalize(), checksum(&data));
     }
 
     #[test]
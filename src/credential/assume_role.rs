@@ -0,0 +1,281 @@
+//! STS AssumeRole credential provider.
+
+use std::fmt;
+use std::time::Duration;
+
+use futures_util::future::BoxFuture;
+
+use super::CredentialProvider;
+use super::sts::{self, ExpiringCache};
+use crate::config::Credentials;
+use crate::error::{OssError, Result};
+
+/// Parameters of an STS `AssumeRole` call.
+#[derive(Debug, Clone)]
+pub(crate) struct AssumeRoleParams {
+    pub(crate) role_arn: String,
+    pub(crate) role_session_name: String,
+    pub(crate) policy: Option<String>,
+    pub(crate) external_id: Option<String>,
+    pub(crate) duration: Duration,
+    pub(crate) sts_endpoint: String,
+}
+
+impl AssumeRoleParams {
+    /// Call `AssumeRole`, signing the request with `base` credentials.
+    pub(crate) async fn assume(
+        &self,
+        http: &reqwest::Client,
+        base: &Credentials,
+    ) -> Result<Credentials> {
+        let mut params = vec![
+            ("RoleArn", self.role_arn.clone()),
+            ("RoleSessionName", self.role_session_name.clone()),
+            ("DurationSeconds", self.duration.as_secs().to_string()),
+        ];
+        if let Some(ref policy) = self.policy {
+            params.push(("Policy", policy.clone()));
+        }
+        if let Some(ref external_id) = self.external_id {
+            params.push(("ExternalId", external_id.clone()));
+        }
+        sts::request_credentials(http, &self.sts_endpoint, "AssumeRole", &params, Some(base)).await
+    }
+}
+
+/// Assumes a RAM role using credentials from another provider.
+///
+/// The source provider supplies the base keys used to sign the STS `AssumeRole`
+/// call; the resulting temporary credentials are cached and refreshed shortly
+/// before they expire. This lets one set of base keys fan out to several
+/// narrowly scoped roles, and providers can be nested to chain roles.
+///
+/// # Examples
+/// ```no_run
+/// use rs_ali_oss::credential::{AssumeRoleProvider, EnvironmentProvider};
+///
+/// # fn example() -> rs_ali_oss::Result<()> {
+/// let provider = AssumeRoleProvider::builder(EnvironmentProvider::new())
+///     .role_arn("acs:ram::123456789012****:role/uploader")
+///     .role_session_name("nightly-upload")
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct AssumeRoleProvider {
+    source: Box<dyn CredentialProvider>,
+    params: AssumeRoleParams,
+    http_client: reqwest::Client,
+    cache: ExpiringCache,
+}
+
+impl AssumeRoleProvider {
+    /// Create a builder that assumes a role using credentials from `source`.
+    pub fn builder(source: impl CredentialProvider + 'static) -> AssumeRoleProviderBuilder {
+        AssumeRoleProviderBuilder::new(source)
+    }
+
+    /// Force-clear the cached credentials so the next resolution calls STS again.
+    ///
+    /// Also invalidates the source provider.
+    pub fn invalidate(&self) {
+        self.cache.invalidate();
+        self.source.invalidate();
+    }
+}
+
+impl CredentialProvider for AssumeRoleProvider {
+    fn resolve(&self) -> Result<Credentials> {
+        if let Some(credentials) = self.cache.fresh() {
+            return Ok(credentials);
+        }
+        let base = self.source.resolve()?;
+        self.cache
+            .get_or_refresh_blocking(|http| async move { self.params.assume(&http, &base).await })
+    }
+
+    fn resolve_async(&self) -> BoxFuture<'_, Result<Credentials>> {
+        Box::pin(self.cache.get_or_refresh(move || async move {
+            let base = self.source.resolve_async().await?;
+            self.params.assume(&self.http_client, &base).await
+        }))
+    }
+
+    fn provider_name(&self) -> &str {
+        "assume_role"
+    }
+
+    fn invalidate(&self) {
+        AssumeRoleProvider::invalidate(self);
+    }
+}
+
+impl fmt::Debug for AssumeRoleProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AssumeRoleProvider")
+            .field("source", &self.source.provider_name())
+            .field("role_arn", &self.params.role_arn)
+            .field("role_session_name", &self.params.role_session_name)
+            .field("duration", &self.params.duration)
+            .field("sts_endpoint", &self.params.sts_endpoint)
+            .finish()
+    }
+}
+
+/// Builder for [`AssumeRoleProvider`].
+pub struct AssumeRoleProviderBuilder {
+    source: Box<dyn CredentialProvider>,
+    role_arn: Option<String>,
+    role_session_name: Option<String>,
+    policy: Option<String>,
+    external_id: Option<String>,
+    duration: Option<Duration>,
+    refresh_before: Option<Duration>,
+    sts_endpoint: Option<String>,
+}
+
+impl AssumeRoleProviderBuilder {
+    /// Create a builder that assumes a role using credentials from `source`.
+    pub fn new(source: impl CredentialProvider + 'static) -> Self {
+        Self {
+            source: Box::new(source),
+            role_arn: None,
+            role_session_name: None,
+            policy: None,
+            external_id: None,
+            duration: None,
+            refresh_before: None,
+            sts_endpoint: None,
+        }
+    }
+
+    /// Set the ARN of the RAM role to assume.
+    pub fn role_arn(mut self, role_arn: impl Into<String>) -> Self {
+        self.role_arn = Some(role_arn.into());
+        self
+    }
+
+    /// Set the role session name (default: `rs-ali-oss-{timestamp}`).
+    pub fn role_session_name(mut self, name: impl Into<String>) -> Self {
+        self.role_session_name = Some(name.into());
+        self
+    }
+
+    /// Restrict the assumed role further with an inline policy document.
+    pub fn policy(mut self, policy: impl Into<String>) -> Self {
+        self.policy = Some(policy.into());
+        self
+    }
+
+    /// Set the external ID required by the role's trust policy.
+    pub fn external_id(mut self, external_id: impl Into<String>) -> Self {
+        self.external_id = Some(external_id.into());
+        self
+    }
+
+    /// Set the lifetime of the issued credentials (900s-43200s, default: 3600s).
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Set how long before expiry the credentials are refreshed (default: 5 minutes).
+    pub fn refresh_before(mut self, refresh_before: Duration) -> Self {
+        self.refresh_before = Some(refresh_before);
+        self
+    }
+
+    /// Override the STS endpoint (default: `https://sts.aliyuncs.com`).
+    pub fn sts_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.sts_endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Build the provider.
+    pub fn build(self) -> Result<AssumeRoleProvider> {
+        let duration = self.duration.unwrap_or(sts::DEFAULT_DURATION);
+        sts::validate_duration(duration)?;
+        let refresh_before = self.refresh_before.unwrap_or(sts::DEFAULT_REFRESH_BEFORE);
+        if refresh_before >= duration {
+            return Err(OssError::InvalidParameter {
+                field: "refresh_before".into(),
+                reason: "must be shorter than the credential duration".into(),
+            });
+        }
+        Ok(AssumeRoleProvider {
+            source: self.source,
+            params: AssumeRoleParams {
+                role_arn: self
+                    .role_arn
+                    .ok_or_else(|| OssError::MissingField("role_arn".into()))?,
+                role_session_name: self
+                    .role_session_name
+                    .unwrap_or_else(sts::default_session_name),
+                policy: self.policy,
+                external_id: self.external_id,
+                duration,
+                sts_endpoint: self
+                    .sts_endpoint
+                    .unwrap_or_else(|| sts::DEFAULT_STS_ENDPOINT.to_string()),
+            },
+            http_client: sts::http_client()?,
+            cache: ExpiringCache::new(refresh_before),
+        })
+    }
+}
+
+impl fmt::Debug for AssumeRoleProviderBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AssumeRoleProviderBuilder")
+            .field("source", &self.source.provider_name())
+            .field("role_arn", &self.role_arn)
+            .field("role_session_name", &self.role_session_name)
+            .field("duration", &self.duration)
+            .field("refresh_before", &self.refresh_before)
+            .field("sts_endpoint", &self.sts_endpoint)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::credential::StaticProvider;
+
+    #[test]
+    fn builder_requires_role_arn() {
+        let result = AssumeRoleProviderBuilder::new(StaticProvider::new("id", "secret")).build();
+        assert!(matches!(result, Err(OssError::MissingField(f)) if f == "role_arn"));
+    }
+
+    #[test]
+    fn builder_rejects_refresh_window_longer_than_duration() {
+        let result = AssumeRoleProviderBuilder::new(StaticProvider::new("id", "secret"))
+            .role_arn("acs:ram::123:role/app")
+            .duration(Duration::from_secs(900))
+            .refresh_before(Duration::from_secs(900))
+            .build();
+        assert!(matches!(result, Err(OssError::InvalidParameter { .. })));
+    }
+
+    #[test]
+    fn source_error_propagates() {
+        let provider = AssumeRoleProviderBuilder::new(crate::credential::ProviderChain::new())
+            .role_arn("acs:ram::123:role/app")
+            .build()
+            .unwrap();
+        assert!(matches!(provider.resolve(), Err(OssError::Auth(_))));
+    }
+
+    #[test]
+    fn debug_shows_source_and_role() {
+        let provider = AssumeRoleProviderBuilder::new(StaticProvider::new("id", "secret"))
+            .role_arn("acs:ram::123:role/app")
+            .build()
+            .unwrap();
+        let debug = format!("{provider:?}");
+        assert!(debug.contains("static"));
+        assert!(debug.contains("acs:ram::123:role/app"));
+        assert!(!debug.contains("secret"));
+    }
+}
@@ -0,0 +1,1035 @@
+//! Credential providers for flexible authentication.
+//!
+//! Provides a [`CredentialProvider`] trait and built-in implementations for
+//! loading credentials from various sources.
+
+mod assume_role;
+mod oidc;
+mod profile;
+pub(crate) mod sts;
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use futures_util::future::BoxFuture;
+
+use crate::config::Credentials;
+use crate::error::{OssError, Result};
+
+pub use assume_role::{AssumeRoleProvider, AssumeRoleProviderBuilder};
+pub use oidc::{OidcRoleArnProvider, OidcRoleArnProviderBuilder};
+pub use profile::{ProfileProvider, ProfileProviderBuilder};
+
+/// A source of OSS credentials.
+///
+/// Implement this trait to provide credentials from custom sources such as
+/// vaults, metadata services, or credential files.
+pub trait CredentialProvider: Send + Sync {
+    /// Resolve credentials from this provider.
+    fn resolve(&self) -> Result<Credentials>;
+
+    /// Resolve credentials without blocking the async runtime.
+    ///
+    /// The default implementation delegates to [`resolve`](Self::resolve).
+    /// Providers that perform network I/O override this to fetch asynchronously.
+    fn resolve_async(&self) -> BoxFuture<'_, Result<Credentials>> {
+        Box::pin(std::future::ready(self.resolve()))
+    }
+
+    /// Provider name for diagnostics.
+    fn provider_name(&self) -> &str;
+
+    /// Discard any cached credentials so the next resolution fetches fresh ones.
+    ///
+    /// Called by the client when OSS rejects the credentials it signed with.
+    /// The default implementation does nothing.
+    fn invalidate(&self) {}
+}
+
+impl fmt::Debug for dyn CredentialProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CredentialProvider({})", self.provider_name())
+    }
+}
+
+/// Provides fixed credentials that never change.
+#[derive(Clone)]
+pub struct StaticProvider {
+    credentials: Credentials,
+}
+
+impl StaticProvider {
+    /// Create a provider with permanent access keys.
+    pub fn new(access_key_id: impl Into<String>, access_key_secret: impl Into<String>) -> Self {
+        Self {
+            credentials: Credentials::new(access_key_id, access_key_secret),
+        }
+    }
+
+    /// Create a provider with temporary STS credentials.
+    pub fn with_security_token(
+        access_key_id: impl Into<String>,
+        access_key_secret: impl Into<String>,
+        security_token: impl Into<String>,
+    ) -> Self {
+        Self {
+            credentials: Credentials::with_security_token(
+                access_key_id,
+                access_key_secret,
+                security_token,
+            ),
+        }
+    }
+
+    /// Create a provider from existing credentials.
+    pub fn from_credentials(credentials: Credentials) -> Self {
+        Self { credentials }
+    }
+}
+
+impl fmt::Debug for StaticProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StaticProvider")
+            .field("credentials", &self.credentials)
+            .finish()
+    }
+}
+
+impl CredentialProvider for StaticProvider {
+    fn resolve(&self) -> Result<Credentials> {
+        Ok(self.credentials.clone())
+    }
+
+    fn provider_name(&self) -> &str {
+        "static"
+    }
+}
+
+/// Loads credentials from environment variables.
+///
+/// Reads the following variables:
+/// - `ALIBABA_CLOUD_ACCESS_KEY_ID` (required)
+/// - `ALIBABA_CLOUD_ACCESS_KEY_SECRET` (required)
+/// - `ALIBABA_CLOUD_SECURITY_TOKEN` (optional, for STS)
+#[derive(Debug, Clone, Default)]
+pub struct EnvironmentProvider;
+
+impl EnvironmentProvider {
+    /// Create a new environment credential provider.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CredentialProvider for EnvironmentProvider {
+    fn resolve(&self) -> Result<Credentials> {
+        credentials_from_env()
+    }
+
+    fn provider_name(&self) -> &str {
+        "environment"
+    }
+}
+
+fn credentials_from_env() -> Result<Credentials> {
+    let access_key_id = std::env::var("ALIBABA_CLOUD_ACCESS_KEY_ID").map_err(|_| {
+        OssError::MissingField(
+            "ALIBABA_CLOUD_ACCESS_KEY_ID environment variable not set".to_string(),
+        )
+    })?;
+
+    let access_key_secret = std::env::var("ALIBABA_CLOUD_ACCESS_KEY_SECRET").map_err(|_| {
+        OssError::MissingField(
+            "ALIBABA_CLOUD_ACCESS_KEY_SECRET environment variable not set".to_string(),
+        )
+    })?;
+
+    if access_key_id.trim().is_empty() {
+        return Err(OssError::InvalidParameter {
+            field: "ALIBABA_CLOUD_ACCESS_KEY_ID".into(),
+            reason: "must not be empty".into(),
+        });
+    }
+
+    match std::env::var("ALIBABA_CLOUD_SECURITY_TOKEN") {
+        Ok(token) if !token.is_empty() => Ok(Credentials::with_security_token(
+            access_key_id,
+            access_key_secret,
+            token,
+        )),
+        _ => Ok(Credentials::new(access_key_id, access_key_secret)),
+    }
+}
+
+/// Loads the per-invocation credentials of Function Compute and SAE runtimes.
+///
+/// These runtimes inject temporary STS credentials through the same variables
+/// read by [`EnvironmentProvider`] and replace them as they rotate, so the
+/// security token is required and the credentials are stamped as expiring at
+/// resolution time. A [`CachingProvider`] wrapping this provider (directly or
+/// through a chain) therefore re-reads the environment on every resolution.
+///
+/// [`ProviderChain::default_chain`] tries this provider first when running in
+/// Function Compute (`FC_FUNCTION_NAME` is set) or SAE (`SAE_APP_ID` or
+/// `SAE_INSTANCE_ID` is set).
+#[derive(Debug, Clone, Default)]
+pub struct ServerlessProvider;
+
+/// Environment variables set by the Function Compute and SAE runtimes.
+/// How long [`ProviderChain::default_chain`] reuses credentials loaded from the
+/// shared credentials file before reading it again.
+pub const PROFILE_CACHE_TTL: Duration = Duration::from_secs(300);
+
+const SERVERLESS_RUNTIME_VARS: [&str; 3] = ["FC_FUNCTION_NAME", "SAE_APP_ID", "SAE_INSTANCE_ID"];
+
+impl ServerlessProvider {
+    /// Create a new serverless runtime credential provider.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Whether the process appears to run inside Function Compute or SAE.
+    fn detected() -> bool {
+        SERVERLESS_RUNTIME_VARS
+            .iter()
+            .any(|var| std::env::var_os(var).is_some())
+    }
+}
+
+impl CredentialProvider for ServerlessProvider {
+    fn resolve(&self) -> Result<Credentials> {
+        let credentials = credentials_from_env()?;
+        if credentials.security_token().is_none() {
+            return Err(OssError::MissingField(
+                "ALIBABA_CLOUD_SECURITY_TOKEN environment variable not set".to_string(),
+            ));
+        }
+        Ok(credentials.with_expiration(Utc::now()))
+    }
+
+    fn provider_name(&self) -> &str {
+        "serverless"
+    }
+}
+
+/// Tries multiple providers in order, returning the first successful result.
+pub struct ProviderChain {
+    providers: Vec<Box<dyn CredentialProvider>>,
+}
+
+impl ProviderChain {
+    /// Create an empty provider chain.
+    pub fn new() -> Self {
+        Self {
+            providers: Vec::new(),
+        }
+    }
+
+    /// Create a chain with the default provider order:
+    /// 1. Serverless runtime credentials (only inside Function Compute or SAE)
+    /// 2. Environment variables
+    /// 3. RRSA OIDC token exchange (only when the RRSA environment variables are set)
+    /// 4. Shared credentials file profile (cached for [`PROFILE_CACHE_TTL`])
+    pub fn default_chain() -> Self {
+        let mut chain = Self::new();
+        if ServerlessProvider::detected() {
+            chain.push(ServerlessProvider::new());
+        }
+        chain.push(EnvironmentProvider::new());
+        if let Ok(oidc) = OidcRoleArnProvider::from_env() {
+            chain.push(oidc);
+        }
+        if let Ok(profile) = ProfileProvider::builder().build() {
+            chain.push(CachingProvider::new(profile, PROFILE_CACHE_TTL));
+        }
+        chain
+    }
+
+    /// Append a provider to the chain.
+    pub fn push(&mut self, provider: impl CredentialProvider + 'static) {
+        self.providers.push(Box::new(provider));
+    }
+
+    /// Append a provider to the chain (builder pattern).
+    pub fn with(mut self, provider: impl CredentialProvider + 'static) -> Self {
+        self.push(provider);
+        self
+    }
+}
+
+impl Default for ProviderChain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for ProviderChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = self.providers.iter().map(|p| p.provider_name()).collect();
+        f.debug_struct("ProviderChain")
+            .field("providers", &names)
+            .finish()
+    }
+}
+
+impl CredentialProvider for ProviderChain {
+    fn resolve(&self) -> Result<Credentials> {
+        let mut last_err = None;
+        for provider in &self.providers {
+            match provider.resolve() {
+                Ok(creds) => return Ok(creds),
+                Err(e) => last_err = Some(e),
+            }
+        }
+        Err(last_err
+            .unwrap_or_else(|| OssError::Auth("no credential providers configured".to_string())))
+    }
+
+    fn resolve_async(&self) -> BoxFuture<'_, Result<Credentials>> {
+        Box::pin(async move {
+            let mut last_err = None;
+            for provider in &self.providers {
+                match provider.resolve_async().await {
+                    Ok(creds) => return Ok(creds),
+                    Err(e) => last_err = Some(e),
+                }
+            }
+            Err(last_err.unwrap_or_else(|| {
+                OssError::Auth("no credential providers configured".to_string())
+            }))
+        })
+    }
+
+    fn provider_name(&self) -> &str {
+        "chain"
+    }
+
+    fn invalidate(&self) {
+        for provider in &self.providers {
+            provider.invalidate();
+        }
+    }
+}
+
+/// Wraps another provider and caches its credentials for a configurable TTL.
+///
+/// When [`resolve`](CredentialProvider::resolve) is called, returns the cached
+/// credentials if they are still valid. Otherwise, calls the inner provider to
+/// obtain fresh credentials and caches the result. Credentials that carry an
+/// [`expiration`](Credentials::expiration) are never cached past it.
+///
+/// Once an entry enters its refresh window ([`refresh_before`](Self::refresh_before),
+/// minus a random [`jitter`](Self::jitter)), callers keep receiving the cached
+/// credentials while a single background task fetches new ones, so requests do
+/// not stall on the refresh and concurrent callers do not stampede the source.
+/// [`stale_while_revalidate`](Self::stale_while_revalidate) extends this to a
+/// grace period after the TTL for credentials without a hard expiration.
+///
+/// This is especially useful for STS temporary credentials that are expensive
+/// to obtain and have a limited lifetime.
+///
+/// # Examples
+/// ```
+/// use rs_ali_oss::credential::{CachingProvider, EnvironmentProvider};
+/// use std::time::Duration;
+///
+/// let provider = CachingProvider::new(EnvironmentProvider::new(), Duration::from_secs(900))
+///     .refresh_before(Duration::from_secs(120))
+///     .jitter(Duration::from_secs(30));
+/// ```
+pub struct CachingProvider {
+    shared: Arc<CacheShared>,
+    policy: RefreshPolicy,
+}
+
+struct CacheShared {
+    inner: Box<dyn CredentialProvider>,
+    entry: RwLock<Option<CachedEntry>>,
+    refreshing: AtomicBool,
+    /// Held while `resolve_async` fetches missing or expired credentials, so
+    /// concurrent callers wait for one fetch instead of each starting one.
+    fetch_lock: tokio::sync::Mutex<()>,
+}
+
+struct CachedEntry {
+    credentials: Credentials,
+    refresh_at: Instant,
+    stale_until: Instant,
+}
+
+enum CacheState {
+    Fresh(Credentials),
+    Stale(Credentials),
+    Expired,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RefreshPolicy {
+    ttl: Duration,
+    refresh_before: Duration,
+    jitter: Duration,
+    stale_while_revalidate: Duration,
+}
+
+impl RefreshPolicy {
+    fn entry(&self, credentials: Credentials) -> CachedEntry {
+        let now = Instant::now();
+        // Never trust credentials past their own expiration, whatever the TTL.
+        let hard_expiry = credentials.expiration().map(|expiration| {
+            let remaining = (expiration - Utc::now()).to_std().unwrap_or_default();
+            now + remaining
+        });
+        let mut expires_at = now + self.ttl;
+        if let Some(hard_expiry) = hard_expiry {
+            expires_at = expires_at.min(hard_expiry);
+        }
+        let lead = self.refresh_before + random_duration(self.jitter);
+        let refresh_at = expires_at.checked_sub(lead).unwrap_or(now).max(now);
+        let stale_until = match hard_expiry {
+            Some(hard_expiry) => hard_expiry.min(expires_at + self.stale_while_revalidate),
+            None => expires_at + self.stale_while_revalidate,
+        };
+        CachedEntry {
+            credentials,
+            refresh_at,
+            stale_until,
+        }
+    }
+}
+
+/// Uniformly random duration in `[0, max]`, used to spread refreshes out.
+fn random_duration(max: Duration) -> Duration {
+    use std::hash::{BuildHasher, Hasher};
+
+    if max.is_zero() {
+        return Duration::ZERO;
+    }
+    let random = std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish();
+    let nanos = max.as_nanos().min(u64::MAX as u128) as u64;
+    Duration::from_nanos(random % (nanos + 1))
+}
+
+/// Clears the in-flight flag when a background refresh finishes or is dropped.
+struct RefreshGuard(Arc<CacheShared>);
+
+impl Drop for RefreshGuard {
+    fn drop(&mut self) {
+        self.0.refreshing.store(false, Ordering::Release);
+    }
+}
+
+impl CacheShared {
+    fn state(&self) -> CacheState {
+        let guard = self.entry.read().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        match guard.as_ref() {
+            Some(entry) if now < entry.refresh_at => CacheState::Fresh(entry.credentials.clone()),
+            Some(entry) if now < entry.stale_until => CacheState::Stale(entry.credentials.clone()),
+            _ => CacheState::Expired,
+        }
+    }
+
+    fn store(&self, credentials: Credentials, policy: &RefreshPolicy) {
+        let mut guard = self.entry.write().unwrap_or_else(|e| e.into_inner());
+        *guard = Some(policy.entry(credentials));
+    }
+
+    fn finish_background(&self, result: Result<Credentials>, policy: &RefreshPolicy) {
+        match result {
+            Ok(credentials) => self.store(credentials, policy),
+            Err(e) => tracing::warn!(
+                provider = self.inner.provider_name(),
+                error = %e,
+                "background credential refresh failed, keeping cached credentials"
+            ),
+        }
+    }
+}
+
+impl CachingProvider {
+    /// Create a caching wrapper around `inner` that refreshes every `ttl`.
+    ///
+    /// By default the background refresh starts `ttl / 5` (at most 5 minutes)
+    /// before the entry expires, without jitter or a stale grace period.
+    pub fn new(inner: impl CredentialProvider + 'static, ttl: Duration) -> Self {
+        Self {
+            shared: Arc::new(CacheShared {
+                inner: Box::new(inner),
+                entry: RwLock::new(None),
+                refreshing: AtomicBool::new(false),
+                fetch_lock: tokio::sync::Mutex::new(()),
+            }),
+            policy: RefreshPolicy {
+                ttl,
+                refresh_before: (ttl / 5).min(sts::DEFAULT_REFRESH_BEFORE),
+                jitter: Duration::ZERO,
+                stale_while_revalidate: Duration::ZERO,
+            },
+        }
+    }
+
+    /// Set how long before expiry a background refresh is started.
+    pub fn refresh_before(mut self, refresh_before: Duration) -> Self {
+        self.policy.refresh_before = refresh_before;
+        self
+    }
+
+    /// Start each background refresh up to `jitter` earlier, chosen at random,
+    /// so that many clients sharing a source do not refresh in lockstep.
+    pub fn jitter(mut self, jitter: Duration) -> Self {
+        self.policy.jitter = jitter;
+        self
+    }
+
+    /// Keep serving credentials for up to `window` after the TTL while a
+    /// background refresh runs. Ignored past a credential's own expiration.
+    pub fn stale_while_revalidate(mut self, window: Duration) -> Self {
+        self.policy.stale_while_revalidate = window;
+        self
+    }
+
+    /// Force-clear the cached credentials so the next `resolve` fetches fresh ones.
+    ///
+    /// Also invalidates the wrapped provider.
+    pub fn invalidate(&self) {
+        let mut guard = self.shared.entry.write().unwrap_or_else(|e| e.into_inner());
+        *guard = None;
+        drop(guard);
+        self.shared.inner.invalidate();
+    }
+
+    #[cfg(test)]
+    fn cached(&self) -> Option<Credentials> {
+        match self.shared.state() {
+            CacheState::Fresh(credentials) | CacheState::Stale(credentials) => Some(credentials),
+            CacheState::Expired => None,
+        }
+    }
+
+    /// Return usable cached credentials, kicking off a background refresh if
+    /// they are inside the refresh window.
+    fn cached_or_revalidate(&self) -> Option<Credentials> {
+        match self.shared.state() {
+            CacheState::Fresh(credentials) => Some(credentials),
+            CacheState::Stale(credentials) => {
+                self.spawn_refresh();
+                Some(credentials)
+            }
+            CacheState::Expired => None,
+        }
+    }
+
+    /// Refresh in the background unless a refresh is already in flight.
+    ///
+    /// Uses the ambient tokio runtime when there is one and a short-lived
+    /// thread otherwise.
+    fn spawn_refresh(&self) {
+        if self.shared.refreshing.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let guard = RefreshGuard(Arc::clone(&self.shared));
+        let policy = self.policy;
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn(async move {
+                    let result = guard.0.inner.resolve_async().await;
+                    guard.0.finish_background(result, &policy);
+                });
+            }
+            Err(_) => {
+                // If the thread cannot be spawned the guard is dropped with the
+                // closure and the next caller retries.
+                let _ = std::thread::Builder::new()
+                    .name("oss-credential-refresh".into())
+                    .spawn(move || {
+                        let result = guard.0.inner.resolve();
+                        guard.0.finish_background(result, &policy);
+                    });
+            }
+        }
+    }
+}
+
+impl CredentialProvider for CachingProvider {
+    fn resolve(&self) -> Result<Credentials> {
+        // Fast path: read lock, return cached if still usable
+        if let Some(credentials) = self.cached_or_revalidate() {
+            return Ok(credentials);
+        }
+
+        // Slow path: write lock, double-check, then refresh
+        let mut guard = self.shared.entry.write().unwrap_or_else(|e| e.into_inner());
+        if let Some(ref entry) = *guard
+            && Instant::now() < entry.stale_until
+        {
+            return Ok(entry.credentials.clone());
+        }
+
+        let credentials = self.shared.inner.resolve()?;
+        *guard = Some(self.policy.entry(credentials.clone()));
+        Ok(credentials)
+    }
+
+    fn resolve_async(&self) -> BoxFuture<'_, Result<Credentials>> {
+        Box::pin(async move {
+            if let Some(credentials) = self.cached_or_revalidate() {
+                return Ok(credentials);
+            }
+            let _fetch = self.shared.fetch_lock.lock().await;
+            // Another caller may have fetched while this one waited.
+            if let Some(credentials) = self.cached_or_revalidate() {
+                return Ok(credentials);
+            }
+            let credentials = self.shared.inner.resolve_async().await?;
+            self.shared.store(credentials.clone(), &self.policy);
+            Ok(credentials)
+        })
+    }
+
+    fn provider_name(&self) -> &str {
+        "caching"
+    }
+
+    fn invalidate(&self) {
+        CachingProvider::invalidate(self);
+    }
+}
+
+impl fmt::Debug for CachingProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CachingProvider")
+            .field("inner", &self.shared.inner.provider_name())
+            .field("ttl", &self.policy.ttl)
+            .field("refresh_before", &self.policy.refresh_before)
+            .field("jitter", &self.policy.jitter)
+            .field(
+                "stale_while_revalidate",
+                &self.policy.stale_while_revalidate,
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn static_provider_resolves() {
+        let provider = StaticProvider::new("ak-id", "ak-secret");
+        let creds = provider.resolve().unwrap();
+        assert_eq!(creds.access_key_id(), "ak-id");
+        assert_eq!(creds.access_key_secret(), "ak-secret");
+        assert!(creds.security_token().is_none());
+    }
+
+    #[test]
+    fn static_provider_with_sts() {
+        let provider = StaticProvider::with_security_token("ak-id", "ak-secret", "token");
+        let creds = provider.resolve().unwrap();
+        assert_eq!(creds.security_token(), Some("token"));
+    }
+
+    #[test]
+    fn static_provider_from_credentials() {
+        let creds = Credentials::new("id", "secret");
+        let provider = StaticProvider::from_credentials(creds);
+        let resolved = provider.resolve().unwrap();
+        assert_eq!(resolved.access_key_id(), "id");
+    }
+
+    #[test]
+    fn static_provider_debug_redacts() {
+        let provider = StaticProvider::new("LTAI5tXXXX", "super-secret");
+        let debug = format!("{provider:?}");
+        assert!(debug.contains("LTAI5tXXXX"));
+        assert!(!debug.contains("super-secret"));
+    }
+
+    #[test]
+    fn chain_empty_fails() {
+        let chain = ProviderChain::new();
+        assert!(chain.resolve().is_err());
+    }
+
+    #[test]
+    fn chain_returns_first_success() {
+        let chain = ProviderChain::new()
+            .with(StaticProvider::new("first-id", "first-secret"))
+            .with(StaticProvider::new("second-id", "second-secret"));
+        let creds = chain.resolve().unwrap();
+        assert_eq!(creds.access_key_id(), "first-id");
+    }
+
+    #[test]
+    fn chain_skips_failures() {
+        let chain = ProviderChain::new()
+            .with(EnvironmentProvider::new())
+            .with(StaticProvider::new("fallback-id", "fallback-secret"));
+        let creds = chain.resolve().unwrap();
+        assert_eq!(creds.access_key_id(), "fallback-id");
+    }
+
+    #[test]
+    fn chain_debug_shows_provider_names() {
+        let chain = ProviderChain::new()
+            .with(EnvironmentProvider::new())
+            .with(StaticProvider::new("id", "secret"));
+        let debug = format!("{chain:?}");
+        assert!(debug.contains("environment"));
+        assert!(debug.contains("static"));
+    }
+
+    #[test]
+    fn environment_provider_missing_vars_fails() {
+        temp_env::with_vars_unset(
+            [
+                "ALIBABA_CLOUD_ACCESS_KEY_ID",
+                "ALIBABA_CLOUD_ACCESS_KEY_SECRET",
+                "ALIBABA_CLOUD_SECURITY_TOKEN",
+            ],
+            || {
+                let provider = EnvironmentProvider::new();
+                assert!(provider.resolve().is_err());
+            },
+        );
+    }
+
+    #[test]
+    fn serverless_provider_marks_credentials_short_lived() {
+        temp_env::with_vars(
+            [
+                ("ALIBABA_CLOUD_ACCESS_KEY_ID", Some("STS.fc-id")),
+                ("ALIBABA_CLOUD_ACCESS_KEY_SECRET", Some("fc-secret")),
+                ("ALIBABA_CLOUD_SECURITY_TOKEN", Some("fc-token")),
+            ],
+            || {
+                let creds = ServerlessProvider::new().resolve().unwrap();
+                assert_eq!(creds.access_key_id(), "STS.fc-id");
+                assert_eq!(creds.security_token(), Some("fc-token"));
+                assert!(creds.expiration().is_some_and(|e| e <= Utc::now()));
+            },
+        );
+    }
+
+    #[test]
+    fn serverless_provider_requires_security_token() {
+        temp_env::with_vars(
+            [
+                ("ALIBABA_CLOUD_ACCESS_KEY_ID", Some("id")),
+                ("ALIBABA_CLOUD_ACCESS_KEY_SECRET", Some("secret")),
+                ("ALIBABA_CLOUD_SECURITY_TOKEN", None),
+            ],
+            || {
+                let result = ServerlessProvider::new().resolve();
+                assert!(matches!(result, Err(OssError::MissingField(_))));
+            },
+        );
+    }
+
+    #[test]
+    fn caching_provider_rereads_serverless_credentials() {
+        let provider = CachingProvider::new(ServerlessProvider::new(), Duration::from_secs(300));
+        for token in ["token-1", "token-2"] {
+            temp_env::with_vars(
+                [
+                    ("ALIBABA_CLOUD_ACCESS_KEY_ID", Some("STS.fc-id")),
+                    ("ALIBABA_CLOUD_ACCESS_KEY_SECRET", Some("fc-secret")),
+                    ("ALIBABA_CLOUD_SECURITY_TOKEN", Some(token)),
+                ],
+                || assert_eq!(provider.resolve().unwrap().security_token(), Some(token)),
+            );
+        }
+    }
+
+    #[test]
+    fn default_chain_prefers_serverless_inside_function_compute() {
+        temp_env::with_vars(
+            [
+                ("FC_FUNCTION_NAME", Some("handler")),
+                ("SAE_APP_ID", None),
+                ("SAE_INSTANCE_ID", None),
+            ],
+            || {
+                let debug = format!("{:?}", ProviderChain::default_chain());
+                assert!(debug.contains("serverless"));
+            },
+        );
+        temp_env::with_vars_unset(SERVERLESS_RUNTIME_VARS, || {
+            let debug = format!("{:?}", ProviderChain::default_chain());
+            assert!(!debug.contains("serverless"));
+        });
+    }
+
+    #[test]
+    fn default_chain_prefers_serverless_inside_sae() {
+        for var in ["SAE_APP_ID", "SAE_INSTANCE_ID"] {
+            temp_env::with_vars_unset(SERVERLESS_RUNTIME_VARS, || {
+                temp_env::with_var(var, Some("sae-app"), || {
+                    let debug = format!("{:?}", ProviderChain::default_chain());
+                    assert!(debug.contains("serverless"), "{var}");
+                });
+            });
+        }
+    }
+
+    #[test]
+    fn provider_name_correct() {
+        assert_eq!(StaticProvider::new("a", "b").provider_name(), "static");
+        assert_eq!(EnvironmentProvider::new().provider_name(), "environment");
+        assert_eq!(ProviderChain::new().provider_name(), "chain");
+        assert_eq!(
+            CachingProvider::new(StaticProvider::new("a", "b"), Duration::from_secs(60))
+                .provider_name(),
+            "caching"
+        );
+    }
+
+    #[test]
+    fn caching_provider_returns_credentials() {
+        let provider = CachingProvider::new(
+            StaticProvider::new("cached-id", "cached-secret"),
+            Duration::from_secs(300),
+        );
+        let creds = provider.resolve().unwrap();
+        assert_eq!(creds.access_key_id(), "cached-id");
+        assert_eq!(creds.access_key_secret(), "cached-secret");
+    }
+
+    #[test]
+    fn caching_provider_returns_same_credentials_within_ttl() {
+        let provider = CachingProvider::new(
+            StaticProvider::new("id", "secret"),
+            Duration::from_secs(300),
+        );
+        let creds1 = provider.resolve().unwrap();
+        let creds2 = provider.resolve().unwrap();
+        assert_eq!(creds1.access_key_id(), creds2.access_key_id());
+    }
+
+    #[test]
+    fn caching_provider_refreshes_after_ttl() {
+        let provider = CachingProvider::new(
+            StaticProvider::new("id", "secret"),
+            Duration::from_millis(0),
+        );
+        let creds = provider.resolve().unwrap();
+        assert_eq!(creds.access_key_id(), "id");
+        std::thread::sleep(Duration::from_millis(1));
+        let creds2 = provider.resolve().unwrap();
+        assert_eq!(creds2.access_key_id(), "id");
+    }
+
+    #[test]
+    fn caching_provider_invalidate_clears_cache() {
+        let provider = CachingProvider::new(
+            StaticProvider::new("id", "secret"),
+            Duration::from_secs(300),
+        );
+        provider.resolve().unwrap();
+        provider.invalidate();
+        let creds = provider.resolve().unwrap();
+        assert_eq!(creds.access_key_id(), "id");
+    }
+
+    #[test]
+    fn caching_provider_debug_shows_inner_name() {
+        let provider =
+            CachingProvider::new(StaticProvider::new("id", "secret"), Duration::from_secs(60));
+        let debug = format!("{provider:?}");
+        assert!(debug.contains("CachingProvider"));
+        assert!(debug.contains("static"));
+        assert!(debug.contains("60"));
+    }
+
+    #[tokio::test]
+    async fn resolve_async_defaults_to_resolve() {
+        let provider = StaticProvider::new("async-id", "secret");
+        let creds = provider.resolve_async().await.unwrap();
+        assert_eq!(creds.access_key_id(), "async-id");
+    }
+
+    #[tokio::test]
+    async fn chain_resolve_async_skips_failures() {
+        let chain = ProviderChain::new()
+            .with(ProviderChain::new())
+            .with(StaticProvider::new("fallback-id", "fallback-secret"));
+        let creds = chain.resolve_async().await.unwrap();
+        assert_eq!(creds.access_key_id(), "fallback-id");
+    }
+
+    #[tokio::test]
+    async fn caching_provider_resolve_async_caches() {
+        let provider = CachingProvider::new(
+            StaticProvider::new("id", "secret"),
+            Duration::from_secs(300),
+        );
+        provider.resolve_async().await.unwrap();
+        assert!(provider.cached().is_some());
+    }
+
+    #[test]
+    fn caching_provider_propagates_inner_error() {
+        let provider = CachingProvider::new(ProviderChain::new(), Duration::from_secs(300));
+        assert!(provider.resolve().is_err());
+    }
+
+    /// Hands out `key-0`, `key-1`, ... and fails once `fail_from` is reached.
+    #[derive(Clone, Default)]
+    struct CountingProvider {
+        calls: Arc<std::sync::atomic::AtomicUsize>,
+        fail_from: Option<usize>,
+    }
+
+    impl CredentialProvider for CountingProvider {
+        fn resolve(&self) -> Result<Credentials> {
+            let n = self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.fail_from.is_some_and(|limit| n >= limit) {
+                return Err(OssError::Auth("source unavailable".into()));
+            }
+            Ok(Credentials::new(format!("key-{n}"), "secret"))
+        }
+
+        fn provider_name(&self) -> &str {
+            "counting"
+        }
+    }
+
+    fn wait_for(mut condition: impl FnMut() -> bool) {
+        for _ in 0..200 {
+            if condition() {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        panic!("condition not met in time");
+    }
+
+    #[test]
+    fn caching_provider_refreshes_in_background_within_window() {
+        let source = CountingProvider::default();
+        let provider = CachingProvider::new(source.clone(), Duration::from_millis(300))
+            .refresh_before(Duration::from_millis(250));
+        assert_eq!(provider.resolve().unwrap().access_key_id(), "key-0");
+
+        std::thread::sleep(Duration::from_millis(80));
+        // Inside the refresh window: served from cache, refreshed off the hot path.
+        assert_eq!(provider.resolve().unwrap().access_key_id(), "key-0");
+        wait_for(|| provider.cached().unwrap().access_key_id() == "key-1");
+        assert_eq!(source.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn caching_provider_serves_stale_while_revalidating() {
+        let source = CountingProvider::default();
+        let provider = CachingProvider::new(source.clone(), Duration::ZERO)
+            .stale_while_revalidate(Duration::from_secs(60));
+        assert_eq!(provider.resolve().unwrap().access_key_id(), "key-0");
+        assert_eq!(provider.resolve().unwrap().access_key_id(), "key-0");
+        wait_for(|| source.calls.load(Ordering::SeqCst) >= 2);
+    }
+
+    #[test]
+    fn caching_provider_keeps_credentials_when_background_refresh_fails() {
+        let source = CountingProvider {
+            fail_from: Some(1),
+            ..Default::default()
+        };
+        let provider = CachingProvider::new(source.clone(), Duration::ZERO)
+            .stale_while_revalidate(Duration::from_secs(60));
+        provider.resolve().unwrap();
+        provider.resolve().unwrap();
+        wait_for(|| !provider.shared.refreshing.load(Ordering::Acquire));
+        assert_eq!(provider.resolve().unwrap().access_key_id(), "key-0");
+    }
+
+    #[test]
+    fn caching_provider_honours_credential_expiration() {
+        let expiring = Credentials::new("expiring", "secret")
+            .with_expiration(Utc::now() - chrono::Duration::seconds(1));
+        let provider = CachingProvider::new(
+            StaticProvider::from_credentials(expiring),
+            Duration::from_secs(300),
+        )
+        .stale_while_revalidate(Duration::from_secs(300));
+        provider.resolve().unwrap();
+        assert!(provider.cached().is_none());
+    }
+
+    #[test]
+    fn random_duration_is_bounded() {
+        assert_eq!(random_duration(Duration::ZERO), Duration::ZERO);
+        for _ in 0..100 {
+            assert!(random_duration(Duration::from_millis(10)) <= Duration::from_millis(10));
+        }
+    }
+
+    #[tokio::test]
+    async fn caching_provider_fetches_once_for_concurrent_callers() {
+        /// Counts fetches, each of which takes a while.
+        #[derive(Clone, Default)]
+        struct SlowProvider(Arc<std::sync::atomic::AtomicUsize>);
+
+        impl CredentialProvider for SlowProvider {
+            fn resolve(&self) -> Result<Credentials> {
+                unreachable!("only resolved asynchronously")
+            }
+
+            fn resolve_async(&self) -> BoxFuture<'_, Result<Credentials>> {
+                Box::pin(async move {
+                    let n = self.0.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    Ok(Credentials::new(format!("key-{n}"), "secret"))
+                })
+            }
+
+            fn provider_name(&self) -> &str {
+                "slow"
+            }
+        }
+
+        let source = SlowProvider::default();
+        let provider = CachingProvider::new(source.clone(), Duration::from_secs(300));
+        let resolve_all =
+            || futures_util::future::join_all((0..8).map(|_| provider.resolve_async()));
+
+        for result in resolve_all().await {
+            assert_eq!(result.unwrap().access_key_id(), "key-0");
+        }
+        assert_eq!(source.0.load(Ordering::SeqCst), 1);
+
+        // Rejected credentials are refetched once, too.
+        provider.invalidate();
+        for result in resolve_all().await {
+            assert_eq!(result.unwrap().access_key_id(), "key-1");
+        }
+        assert_eq!(source.0.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn caching_provider_refreshes_on_runtime_in_background() {
+        let source = CountingProvider::default();
+        let provider = CachingProvider::new(source.clone(), Duration::ZERO)
+            .stale_while_revalidate(Duration::from_secs(60));
+        assert_eq!(
+            provider.resolve_async().await.unwrap().access_key_id(),
+            "key-0"
+        );
+        assert_eq!(
+            provider.resolve_async().await.unwrap().access_key_id(),
+            "key-0"
+        );
+        for _ in 0..200 {
+            if provider.cached().unwrap().access_key_id() == "key-1" {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        panic!("background refresh did not complete");
+    }
+}
@@ -0,0 +1,347 @@
+//! RRSA (RAM Roles for Service Accounts) credential provider.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use futures_util::future::BoxFuture;
+
+use super::CredentialProvider;
+use super::sts::{self, ExpiringCache};
+use crate::config::Credentials;
+use crate::error::{OssError, Result};
+
+/// Exchanges an OIDC token for temporary credentials via STS `AssumeRoleWithOIDC`.
+///
+/// This is how workloads on ACK clusters with RRSA enabled obtain credentials:
+/// the cluster projects a service-account token into the pod and sets the
+/// role ARN, OIDC provider ARN and token file path as environment variables.
+///
+/// The token file is re-read on every exchange, since it is rotated on disk.
+/// Credentials are cached and refreshed automatically five minutes before
+/// they expire.
+///
+/// # Examples
+/// ```no_run
+/// use rs_ali_oss::credential::{CredentialProvider, OidcRoleArnProvider};
+///
+/// # fn example() -> rs_ali_oss::Result<()> {
+/// let provider = OidcRoleArnProvider::from_env()?;
+/// let credentials = provider.resolve()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct OidcRoleArnProvider {
+    role_arn: String,
+    oidc_provider_arn: String,
+    oidc_token_file: PathBuf,
+    role_session_name: String,
+    policy: Option<String>,
+    duration: Duration,
+    sts_endpoint: String,
+    http_client: reqwest::Client,
+    cache: ExpiringCache,
+}
+
+impl OidcRoleArnProvider {
+    /// Create a builder for configuring the provider explicitly.
+    pub fn builder() -> OidcRoleArnProviderBuilder {
+        OidcRoleArnProviderBuilder::new()
+    }
+
+    /// Create a provider from the RRSA environment variables.
+    ///
+    /// Reads the following variables:
+    /// - `ALIBABA_CLOUD_ROLE_ARN` (required)
+    /// - `ALIBABA_CLOUD_OIDC_PROVIDER_ARN` (required)
+    /// - `ALIBABA_CLOUD_OIDC_TOKEN_FILE` (required)
+    /// - `ALIBABA_CLOUD_ROLE_SESSION_NAME` (optional)
+    /// - `ALIBABA_CLOUD_STS_REGION` (optional, selects a regional STS endpoint)
+    /// - `ALIBABA_CLOUD_VPC_ENDPOINT_ENABLED` (optional, `true` selects the VPC endpoint)
+    pub fn from_env() -> Result<Self> {
+        let required = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .filter(|v| !v.trim().is_empty())
+                .ok_or_else(|| {
+                    OssError::MissingField(format!("{name} environment variable not set"))
+                })
+        };
+
+        let mut builder = OidcRoleArnProviderBuilder::new()
+            .role_arn(required("ALIBABA_CLOUD_ROLE_ARN")?)
+            .oidc_provider_arn(required("ALIBABA_CLOUD_OIDC_PROVIDER_ARN")?)
+            .oidc_token_file(required("ALIBABA_CLOUD_OIDC_TOKEN_FILE")?);
+
+        if let Ok(name) = std::env::var("ALIBABA_CLOUD_ROLE_SESSION_NAME")
+            && !name.is_empty()
+        {
+            builder = builder.role_session_name(name);
+        }
+        if let Ok(region) = std::env::var("ALIBABA_CLOUD_STS_REGION")
+            && !region.is_empty()
+        {
+            let vpc = std::env::var("ALIBABA_CLOUD_VPC_ENDPOINT_ENABLED")
+                .is_ok_and(|v| v.eq_ignore_ascii_case("true"));
+            builder = builder.sts_endpoint(sts::regional_endpoint(&region, vpc));
+        }
+        builder.build()
+    }
+
+    /// Force-clear the cached credentials so the next resolution performs a new exchange.
+    pub fn invalidate(&self) {
+        self.cache.invalidate();
+    }
+
+    async fn fetch(&self, http: &reqwest::Client) -> Result<Credentials> {
+        // Read off the runtime thread; the file may sit on a slow volume.
+        let path = self.oidc_token_file.clone();
+        let token = tokio::task::spawn_blocking(move || std::fs::read_to_string(path))
+            .await
+            .map_err(|e| OssError::Auth(format!("OIDC token file read failed: {e}")))?
+            .map_err(|e| {
+                OssError::Auth(format!(
+                    "failed to read OIDC token file {}: {e}",
+                    self.oidc_token_file.display()
+                ))
+            })?;
+        let token = token.trim();
+        if token.is_empty() {
+            return Err(OssError::Auth(format!(
+                "OIDC token file {} is empty",
+                self.oidc_token_file.display()
+            )));
+        }
+
+        let mut params = vec![
+            ("RoleArn", self.role_arn.clone()),
+            ("OIDCProviderArn", self.oidc_provider_arn.clone()),
+            ("OIDCToken", token.to_string()),
+            ("RoleSessionName", self.role_session_name.clone()),
+            ("DurationSeconds", self.duration.as_secs().to_string()),
+        ];
+        if let Some(ref policy) = self.policy {
+            params.push(("Policy", policy.clone()));
+        }
+
+        sts::request_credentials(
+            http,
+            &self.sts_endpoint,
+            "AssumeRoleWithOIDC",
+            &params,
+            None,
+        )
+        .await
+    }
+}
+
+impl CredentialProvider for OidcRoleArnProvider {
+    fn resolve(&self) -> Result<Credentials> {
+        self.cache
+            .get_or_refresh_blocking(|http| async move { self.fetch(&http).await })
+    }
+
+    fn resolve_async(&self) -> BoxFuture<'_, Result<Credentials>> {
+        Box::pin(self.cache.get_or_refresh(|| self.fetch(&self.http_client)))
+    }
+
+    fn provider_name(&self) -> &str {
+        "oidc_role_arn"
+    }
+
+    fn invalidate(&self) {
+        OidcRoleArnProvider::invalidate(self);
+    }
+}
+
+impl fmt::Debug for OidcRoleArnProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OidcRoleArnProvider")
+            .field("role_arn", &self.role_arn)
+            .field("oidc_provider_arn", &self.oidc_provider_arn)
+            .field("oidc_token_file", &self.oidc_token_file)
+            .field("role_session_name", &self.role_session_name)
+            .field("duration", &self.duration)
+            .field("sts_endpoint", &self.sts_endpoint)
+            .finish()
+    }
+}
+
+/// Builder for [`OidcRoleArnProvider`].
+#[derive(Debug, Default)]
+pub struct OidcRoleArnProviderBuilder {
+    role_arn: Option<String>,
+    oidc_provider_arn: Option<String>,
+    oidc_token_file: Option<PathBuf>,
+    role_session_name: Option<String>,
+    policy: Option<String>,
+    duration: Option<Duration>,
+    sts_endpoint: Option<String>,
+}
+
+impl OidcRoleArnProviderBuilder {
+    /// Create a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the ARN of the RAM role to assume.
+    pub fn role_arn(mut self, role_arn: impl Into<String>) -> Self {
+        self.role_arn = Some(role_arn.into());
+        self
+    }
+
+    /// Set the ARN of the OIDC identity provider.
+    pub fn oidc_provider_arn(mut self, arn: impl Into<String>) -> Self {
+        self.oidc_provider_arn = Some(arn.into());
+        self
+    }
+
+    /// Set the path of the projected OIDC token file.
+    pub fn oidc_token_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.oidc_token_file = Some(path.into());
+        self
+    }
+
+    /// Set the role session name (default: `rs-ali-oss-{timestamp}`).
+    pub fn role_session_name(mut self, name: impl Into<String>) -> Self {
+        self.role_session_name = Some(name.into());
+        self
+    }
+
+    /// Restrict the assumed role further with an inline policy document.
+    pub fn policy(mut self, policy: impl Into<String>) -> Self {
+        self.policy = Some(policy.into());
+        self
+    }
+
+    /// Set the lifetime of the issued credentials (900s-43200s, default: 3600s).
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Override the STS endpoint (default: `https://sts.aliyuncs.com`).
+    pub fn sts_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.sts_endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Build the provider.
+    pub fn build(self) -> Result<OidcRoleArnProvider> {
+        let duration = self.duration.unwrap_or(sts::DEFAULT_DURATION);
+        sts::validate_duration(duration)?;
+        Ok(OidcRoleArnProvider {
+            role_arn: self
+                .role_arn
+                .ok_or_else(|| OssError::MissingField("role_arn".into()))?,
+            oidc_provider_arn: self
+                .oidc_provider_arn
+                .ok_or_else(|| OssError::MissingField("oidc_provider_arn".into()))?,
+            oidc_token_file: self
+                .oidc_token_file
+                .ok_or_else(|| OssError::MissingField("oidc_token_file".into()))?,
+            role_session_name: self
+                .role_session_name
+                .unwrap_or_else(sts::default_session_name),
+            policy: self.policy,
+            duration,
+            sts_endpoint: self
+                .sts_endpoint
+                .unwrap_or_else(|| sts::DEFAULT_STS_ENDPOINT.to_string()),
+            http_client: sts::http_client()?,
+            cache: ExpiringCache::new(sts::DEFAULT_REFRESH_BEFORE),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RRSA_VARS: [&str; 6] = [
+        "ALIBABA_CLOUD_ROLE_ARN",
+        "ALIBABA_CLOUD_OIDC_PROVIDER_ARN",
+        "ALIBABA_CLOUD_OIDC_TOKEN_FILE",
+        "ALIBABA_CLOUD_ROLE_SESSION_NAME",
+        "ALIBABA_CLOUD_STS_REGION",
+        "ALIBABA_CLOUD_VPC_ENDPOINT_ENABLED",
+    ];
+
+    #[test]
+    fn builder_requires_role_arn() {
+        let result = OidcRoleArnProviderBuilder::new()
+            .oidc_provider_arn("acs:ram::123:oidc-provider/ack")
+            .oidc_token_file("/var/run/token")
+            .build();
+        assert!(matches!(result, Err(OssError::MissingField(f)) if f == "role_arn"));
+    }
+
+    #[test]
+    fn builder_rejects_short_duration() {
+        let result = OidcRoleArnProviderBuilder::new()
+            .role_arn("acs:ram::123:role/app")
+            .oidc_provider_arn("acs:ram::123:oidc-provider/ack")
+            .oidc_token_file("/var/run/token")
+            .duration(Duration::from_secs(60))
+            .build();
+        assert!(matches!(result, Err(OssError::InvalidParameter { .. })));
+    }
+
+    #[test]
+    fn from_env_missing_vars_fails() {
+        temp_env::with_vars_unset(RRSA_VARS, || {
+            assert!(OidcRoleArnProvider::from_env().is_err());
+        });
+    }
+
+    #[test]
+    fn from_env_reads_variables() {
+        temp_env::with_vars(
+            [
+                ("ALIBABA_CLOUD_ROLE_ARN", Some("acs:ram::123:role/app")),
+                (
+                    "ALIBABA_CLOUD_OIDC_PROVIDER_ARN",
+                    Some("acs:ram::123:oidc-provider/ack"),
+                ),
+                ("ALIBABA_CLOUD_OIDC_TOKEN_FILE", Some("/var/run/token")),
+                ("ALIBABA_CLOUD_ROLE_SESSION_NAME", Some("my-session")),
+                ("ALIBABA_CLOUD_STS_REGION", Some("cn-beijing")),
+                ("ALIBABA_CLOUD_VPC_ENDPOINT_ENABLED", Some("true")),
+            ],
+            || {
+                let provider = OidcRoleArnProvider::from_env().unwrap();
+                assert_eq!(provider.role_arn, "acs:ram::123:role/app");
+                assert_eq!(provider.role_session_name, "my-session");
+                assert_eq!(
+                    provider.sts_endpoint,
+                    "https://sts-vpc.cn-beijing.aliyuncs.com"
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn missing_token_file_is_auth_error() {
+        let provider = OidcRoleArnProviderBuilder::new()
+            .role_arn("acs:ram::123:role/app")
+            .oidc_provider_arn("acs:ram::123:oidc-provider/ack")
+            .oidc_token_file("/nonexistent/rs-ali-oss/token")
+            .build()
+            .unwrap();
+        assert!(matches!(provider.resolve(), Err(OssError::Auth(_))));
+    }
+
+    #[test]
+    fn debug_shows_role_configuration() {
+        let provider = OidcRoleArnProviderBuilder::new()
+            .role_arn("acs:ram::123:role/app")
+            .oidc_provider_arn("acs:ram::123:oidc-provider/ack")
+            .oidc_token_file("/var/run/token")
+            .build()
+            .unwrap();
+        let debug = format!("{provider:?}");
+        assert!(debug.contains("OidcRoleArnProvider"));
+        assert!(debug.contains("acs:ram::123:role/app"));
+    }
+}
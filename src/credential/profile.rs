@@ -0,0 +1,493 @@
+//! Shared credentials file provider.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use futures_util::future::BoxFuture;
+
+use super::CredentialProvider;
+use super::assume_role::AssumeRoleParams;
+use super::sts::{self, ExpiringCache};
+use crate::config::Credentials;
+use crate::error::{OssError, Result};
+
+/// Loads credentials from a profile in the Alibaba Cloud shared credentials file.
+///
+/// The file is located, in order of precedence, at the path set on the builder,
+/// at `ALIBABA_CLOUD_CREDENTIALS_FILE`, or at `~/.alibabacloud/credentials`.
+/// The profile is the one set on the builder, else `ALIBABA_CLOUD_PROFILE`,
+/// else `default`. The file is re-read on every resolution, so wrap the
+/// provider in a [`CachingProvider`](super::CachingProvider) to reuse it, as
+/// [`ProviderChain::default_chain`](super::ProviderChain::default_chain) does.
+///
+/// Supported profile types:
+/// - `access_key`: `access_key_id`, `access_key_secret`
+/// - `sts`: `access_key_id`, `access_key_secret`, `security_token`
+/// - `ram_role_arn`: `access_key_id`, `access_key_secret`, `role_arn`, and
+///   optionally `role_session_name`, `policy`, `external_id`,
+///   `role_session_expiration` (seconds) and `sts_region`
+///
+/// ```ini
+/// [default]
+/// type = access_key
+/// access_key_id = LTAI5tXXXX
+/// access_key_secret = your-secret
+///
+/// [deploy]
+/// type = ram_role_arn
+/// access_key_id = LTAI5tXXXX
+/// access_key_secret = your-secret
+/// role_arn = acs:ram::123456789012****:role/deployer
+/// ```
+///
+/// Temporary credentials obtained for `ram_role_arn` profiles are cached and
+/// refreshed five minutes before they expire.
+pub struct ProfileProvider {
+    path: Option<PathBuf>,
+    profile: Option<String>,
+    sts_endpoint: Option<String>,
+    http_client: reqwest::Client,
+    cache: ExpiringCache,
+}
+
+/// A parsed profile section.
+type Profile = HashMap<String, String>;
+
+/// What a profile resolves to.
+enum ProfileKind {
+    Direct(Credentials),
+    RamRoleArn(RamRoleArn),
+}
+
+/// Parameters of a `ram_role_arn` profile.
+struct RamRoleArn {
+    base: Credentials,
+    params: AssumeRoleParams,
+}
+
+impl ProfileProvider {
+    /// Create a builder for configuring the provider.
+    pub fn builder() -> ProfileProviderBuilder {
+        ProfileProviderBuilder::new()
+    }
+
+    /// Force-clear cached role credentials so the next resolution calls STS again.
+    pub fn invalidate(&self) {
+        self.cache.invalidate();
+    }
+
+    fn file_path(&self) -> Result<PathBuf> {
+        if let Some(ref path) = self.path {
+            return Ok(path.clone());
+        }
+        if let Ok(path) = std::env::var("ALIBABA_CLOUD_CREDENTIALS_FILE")
+            && !path.is_empty()
+        {
+            return Ok(PathBuf::from(path));
+        }
+        let home = std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .ok_or_else(|| {
+                OssError::MissingField("HOME directory for the credentials file".into())
+            })?;
+        Ok(PathBuf::from(home)
+            .join(".alibabacloud")
+            .join("credentials"))
+    }
+
+    fn profile_name(&self) -> String {
+        self.profile
+            .clone()
+            .or_else(|| {
+                std::env::var("ALIBABA_CLOUD_PROFILE")
+                    .ok()
+                    .filter(|p| !p.is_empty())
+            })
+            .unwrap_or_else(|| "default".to_string())
+    }
+
+    fn load(&self) -> Result<ProfileKind> {
+        let path = self.file_path()?;
+        let content = read_file(&path)?;
+        self.parse(&path, &content)
+    }
+
+    /// Like [`load`](Self::load), reading the file off the async runtime thread.
+    async fn load_async(&self) -> Result<ProfileKind> {
+        let path = self.file_path()?;
+        let file = path.clone();
+        let content = tokio::task::spawn_blocking(move || read_file(&file))
+            .await
+            .map_err(|e| OssError::Auth(format!("credentials file read failed: {e}")))??;
+        self.parse(&path, &content)
+    }
+
+    fn parse(&self, path: &Path, content: &str) -> Result<ProfileKind> {
+        let name = self.profile_name();
+        let mut profiles = parse_profiles(content);
+        let profile = profiles.remove(&name).ok_or_else(|| {
+            OssError::Auth(format!("profile '{name}' not found in {}", path.display()))
+        })?;
+        self.interpret(&name, profile)
+    }
+
+    fn interpret(&self, name: &str, profile: Profile) -> Result<ProfileKind> {
+        if profile
+            .get("enable")
+            .is_some_and(|v| v.eq_ignore_ascii_case("false"))
+        {
+            return Err(OssError::Auth(format!("profile '{name}' is disabled")));
+        }
+
+        let field = |key: &str| {
+            profile
+                .get(key)
+                .filter(|v| !v.is_empty())
+                .cloned()
+                .ok_or_else(|| OssError::MissingField(format!("{key} in profile '{name}'")))
+        };
+
+        let kind = profile
+            .get("type")
+            .map(String::as_str)
+            .unwrap_or("access_key");
+        match kind {
+            "access_key" => Ok(ProfileKind::Direct(Credentials::new(
+                field("access_key_id")?,
+                field("access_key_secret")?,
+            ))),
+            "sts" => Ok(ProfileKind::Direct(Credentials::with_security_token(
+                field("access_key_id")?,
+                field("access_key_secret")?,
+                field("security_token")?,
+            ))),
+            "ram_role_arn" => {
+                let duration = match profile.get("role_session_expiration") {
+                    Some(secs) => Duration::from_secs(secs.parse().map_err(|_| {
+                        OssError::InvalidParameter {
+                            field: format!("role_session_expiration in profile '{name}'"),
+                            reason: "must be a number of seconds".into(),
+                        }
+                    })?),
+                    None => sts::DEFAULT_DURATION,
+                };
+                sts::validate_duration(duration)?;
+                let sts_endpoint = match (&self.sts_endpoint, profile.get("sts_region")) {
+                    (Some(endpoint), _) => endpoint.clone(),
+                    (None, Some(region)) => sts::regional_endpoint(region, false),
+                    (None, None) => sts::DEFAULT_STS_ENDPOINT.to_string(),
+                };
+                Ok(ProfileKind::RamRoleArn(RamRoleArn {
+                    base: Credentials::new(field("access_key_id")?, field("access_key_secret")?),
+                    params: AssumeRoleParams {
+                        role_arn: field("role_arn")?,
+                        role_session_name: profile
+                            .get("role_session_name")
+                            .cloned()
+                            .unwrap_or_else(sts::default_session_name),
+                        policy: profile.get("policy").cloned(),
+                        external_id: profile.get("external_id").cloned(),
+                        duration,
+                        sts_endpoint,
+                    },
+                }))
+            }
+            other => Err(OssError::InvalidParameter {
+                field: format!("type in profile '{name}'"),
+                reason: format!("unsupported profile type '{other}'"),
+            }),
+        }
+    }
+}
+
+impl RamRoleArn {
+    async fn assume(&self, http: &reqwest::Client) -> Result<Credentials> {
+        self.params.assume(http, &self.base).await
+    }
+}
+
+impl CredentialProvider for ProfileProvider {
+    fn resolve(&self) -> Result<Credentials> {
+        match self.load()? {
+            ProfileKind::Direct(credentials) => Ok(credentials),
+            ProfileKind::RamRoleArn(role) => self
+                .cache
+                .get_or_refresh_blocking(|http| async move { role.assume(&http).await }),
+        }
+    }
+
+    fn resolve_async(&self) -> BoxFuture<'_, Result<Credentials>> {
+        Box::pin(async move {
+            match self.load_async().await? {
+                ProfileKind::Direct(credentials) => Ok(credentials),
+                ProfileKind::RamRoleArn(role) => {
+                    self.cache
+                        .get_or_refresh(|| role.assume(&self.http_client))
+                        .await
+                }
+            }
+        })
+    }
+
+    fn provider_name(&self) -> &str {
+        "profile"
+    }
+
+    fn invalidate(&self) {
+        ProfileProvider::invalidate(self);
+    }
+}
+
+impl fmt::Debug for ProfileProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProfileProvider")
+            .field("path", &self.path)
+            .field("profile", &self.profile)
+            .field("sts_endpoint", &self.sts_endpoint)
+            .finish()
+    }
+}
+
+/// Builder for [`ProfileProvider`].
+#[derive(Debug, Default)]
+pub struct ProfileProviderBuilder {
+    path: Option<PathBuf>,
+    profile: Option<String>,
+    sts_endpoint: Option<String>,
+}
+
+impl ProfileProviderBuilder {
+    /// Create a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read credentials from this file instead of the default location.
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Select the profile to load (default: `ALIBABA_CLOUD_PROFILE` or `default`).
+    pub fn profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// Override the STS endpoint used for `ram_role_arn` profiles.
+    pub fn sts_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.sts_endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Build the provider.
+    pub fn build(self) -> Result<ProfileProvider> {
+        Ok(ProfileProvider {
+            path: self.path,
+            profile: self.profile,
+            sts_endpoint: self.sts_endpoint,
+            http_client: sts::http_client()?,
+            cache: ExpiringCache::new(sts::DEFAULT_REFRESH_BEFORE),
+        })
+    }
+}
+
+/// Parse an INI-style credentials file into profiles keyed by section name.
+fn read_file(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).map_err(|e| {
+        OssError::Auth(format!(
+            "failed to read credentials file {}: {e}",
+            path.display()
+        ))
+    })
+}
+
+fn parse_profiles(content: &str) -> HashMap<String, Profile> {
+    let mut profiles: HashMap<String, Profile> = HashMap::new();
+    let mut current: Option<String> = None;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let name = section.trim().to_string();
+            profiles.entry(name.clone()).or_default();
+            current = Some(name);
+            continue;
+        }
+        if let (Some(section), Some((key, value))) = (&current, line.split_once('=')) {
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(value);
+            profiles
+                .entry(section.clone())
+                .or_default()
+                .insert(key.trim().to_string(), value.to_string());
+        }
+    }
+    profiles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = r#"
+# Shared credentials
+[default]
+enable = true
+type = access_key
+access_key_id = default-id
+access_key_secret = default-secret
+
+[temp]
+type = sts
+access_key_id = "sts-id"
+access_key_secret = sts-secret
+security_token = sts-token
+
+[role]
+type = ram_role_arn
+access_key_id = base-id
+access_key_secret = base-secret
+role_arn = acs:ram::123:role/app
+role_session_name = session
+role_session_expiration = 1800
+sts_region = cn-shanghai
+
+[ecs]
+type = ecs_ram_role
+role_name = EcsRole
+
+[off]
+enable = false
+access_key_id = id
+access_key_secret = secret
+"#;
+
+    fn provider(profile: &str) -> ProfileProvider {
+        ProfileProviderBuilder::new()
+            .profile(profile)
+            .build()
+            .unwrap()
+    }
+
+    fn interpret(profile: &str) -> Result<ProfileKind> {
+        let mut profiles = parse_profiles(FILE);
+        provider(profile).interpret(profile, profiles.remove(profile).unwrap())
+    }
+
+    #[test]
+    fn parses_sections_and_quotes() {
+        let profiles = parse_profiles(FILE);
+        assert_eq!(profiles.len(), 5);
+        assert_eq!(profiles["temp"]["access_key_id"], "sts-id");
+        assert_eq!(profiles["default"]["type"], "access_key");
+    }
+
+    #[test]
+    fn access_key_profile() {
+        match interpret("default").unwrap() {
+            ProfileKind::Direct(creds) => {
+                assert_eq!(creds.access_key_id(), "default-id");
+                assert!(creds.security_token().is_none());
+            }
+            ProfileKind::RamRoleArn(_) => panic!("expected direct credentials"),
+        }
+    }
+
+    #[test]
+    fn sts_profile() {
+        match interpret("temp").unwrap() {
+            ProfileKind::Direct(creds) => assert_eq!(creds.security_token(), Some("sts-token")),
+            ProfileKind::RamRoleArn(_) => panic!("expected direct credentials"),
+        }
+    }
+
+    #[test]
+    fn ram_role_arn_profile() {
+        match interpret("role").unwrap() {
+            ProfileKind::RamRoleArn(role) => {
+                assert_eq!(role.base.access_key_id(), "base-id");
+                assert_eq!(role.params.role_arn, "acs:ram::123:role/app");
+                assert_eq!(role.params.role_session_name, "session");
+                assert_eq!(role.params.duration, Duration::from_secs(1800));
+                assert_eq!(
+                    role.params.sts_endpoint,
+                    "https://sts.cn-shanghai.aliyuncs.com"
+                );
+            }
+            ProfileKind::Direct(_) => panic!("expected ram_role_arn"),
+        }
+    }
+
+    #[test]
+    fn unsupported_type_fails() {
+        assert!(matches!(
+            interpret("ecs"),
+            Err(OssError::InvalidParameter { .. })
+        ));
+    }
+
+    #[test]
+    fn disabled_profile_fails() {
+        assert!(matches!(interpret("off"), Err(OssError::Auth(_))));
+    }
+
+    #[test]
+    fn resolves_from_file_and_env() {
+        let path =
+            std::env::temp_dir().join(format!("rs-ali-oss-profile-test-{}", std::process::id()));
+        std::fs::write(&path, FILE).unwrap();
+        temp_env::with_vars(
+            [
+                (
+                    "ALIBABA_CLOUD_CREDENTIALS_FILE",
+                    Some(path.to_str().unwrap()),
+                ),
+                ("ALIBABA_CLOUD_PROFILE", Some("temp")),
+            ],
+            || {
+                let provider = ProfileProviderBuilder::new().build().unwrap();
+                let creds = provider.resolve().unwrap();
+                assert_eq!(creds.access_key_id(), "sts-id");
+            },
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn resolves_async_from_file() {
+        let path =
+            std::env::temp_dir().join(format!("rs-ali-oss-profile-async-{}", std::process::id()));
+        std::fs::write(&path, FILE).unwrap();
+        let provider = ProfileProviderBuilder::new()
+            .path(&path)
+            .profile("temp")
+            .build()
+            .unwrap();
+        let creds = provider.resolve_async().await.unwrap();
+        assert_eq!(creds.access_key_id(), "sts-id");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn missing_profile_fails() {
+        let path =
+            std::env::temp_dir().join(format!("rs-ali-oss-profile-missing-{}", std::process::id()));
+        std::fs::write(&path, FILE).unwrap();
+        let provider = ProfileProviderBuilder::new()
+            .path(&path)
+            .profile("nope")
+            .build()
+            .unwrap();
+        assert!(matches!(provider.resolve(), Err(OssError::Auth(_))));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
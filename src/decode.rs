@@ -0,0 +1,180 @@
+//! Decompression of object bodies stored with `Content-Encoding: gzip` or
+//! `deflate`.
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use async_compression::tokio::bufread::{GzipDecoder, ZlibDecoder};
+use bytes::Bytes;
+use tokio::io::{AsyncBufRead, AsyncRead, ReadBuf};
+
+use crate::crc64::Crc64;
+use crate::error::OssError;
+use crate::types::response::ObjectBody;
+
+/// Size of the buffer decoded data is read into.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// A content coding the SDK can decode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ContentCoding {
+    Gzip,
+    Deflate,
+}
+
+impl ContentCoding {
+    /// Parse a `Content-Encoding` value; `None` for codings that are not
+    /// decoded, including `identity`.
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "gzip" | "x-gzip" => Some(Self::Gzip),
+            "deflate" => Some(Self::Deflate),
+            _ => None,
+        }
+    }
+}
+
+/// Hashes the encoded bytes as the decoder reads them and, at the end of
+/// the body, compares the result with the CRC64 OSS reported.
+struct CrcCheckedReader {
+    inner: ObjectBody,
+    crc: Crc64,
+    expected: Option<u64>,
+    /// Bytes at the front of `inner`'s buffer that were already hashed.
+    hashed: usize,
+}
+
+impl AsyncRead for CrcCheckedReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let available = ready!(self.as_mut().poll_fill_buf(cx))?;
+        let n = available.len().min(buf.remaining());
+        buf.put_slice(&available[..n]);
+        self.consume(n);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncBufRead for CrcCheckedReader {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+        let buf = ready!(Pin::new(&mut this.inner).poll_fill_buf(cx))?;
+        if buf.len() > this.hashed {
+            this.crc.update(&buf[this.hashed..]);
+            this.hashed = buf.len();
+        } else if buf.is_empty()
+            && let Some(server) = this.expected.take()
+        {
+            let computed = this.crc.finalize();
+            if computed != server {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    OssError::ChecksumMismatch { computed, server },
+                )));
+            }
+        }
+        Poll::Ready(Ok(buf))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        this.hashed = this.hashed.saturating_sub(amt);
+        Pin::new(&mut this.inner).consume(amt);
+    }
+}
+
+enum Decoder {
+    Gzip(GzipDecoder<CrcCheckedReader>),
+    Deflate(ZlibDecoder<CrcCheckedReader>),
+}
+
+impl Decoder {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        match self {
+            Self::Gzip(decoder) => Pin::new(decoder).poll_read(cx, buf),
+            Self::Deflate(decoder) => Pin::new(decoder).poll_read(cx, buf),
+        }
+    }
+
+    fn encoded(&mut self) -> &mut CrcCheckedReader {
+        match self {
+            Self::Gzip(decoder) => decoder.get_mut(),
+            Self::Deflate(decoder) => decoder.get_mut(),
+        }
+    }
+}
+
+/// Decompress `body`.
+///
+/// When `expected_crc64` is given, the encoded bytes are checked against it
+/// once the body has been read, and a mismatch fails the read.
+pub(crate) fn decode(
+    body: ObjectBody,
+    coding: ContentCoding,
+    expected_crc64: Option<u64>,
+) -> ObjectBody {
+    let reader = CrcCheckedReader {
+        inner: body,
+        crc: Crc64::new(),
+        expected: expected_crc64,
+        hashed: 0,
+    };
+    let mut decoder = match coding {
+        ContentCoding::Gzip => {
+            let mut decoder = GzipDecoder::new(reader);
+            decoder.multiple_members(true);
+            Decoder::Gzip(decoder)
+        }
+        ContentCoding::Deflate => Decoder::Deflate(ZlibDecoder::new(reader)),
+    };
+    let mut buf = vec![0; CHUNK_SIZE];
+    let mut decoded_all = false;
+    let stream = futures_util::stream::poll_fn(move |cx| {
+        if !decoded_all {
+            let mut read_buf = ReadBuf::new(&mut buf);
+            if let Err(e) = ready!(decoder.poll_read(cx, &mut read_buf)) {
+                return Poll::Ready(Some(Err(e)));
+            }
+            if !read_buf.filled().is_empty() {
+                return Poll::Ready(Some(Ok(Bytes::copy_from_slice(read_buf.filled()))));
+            }
+            decoded_all = true;
+        }
+        // Read past the end of the compressed data, so the checksum covers
+        // the whole body.
+        let encoded = decoder.encoded();
+        loop {
+            let len = match ready!(Pin::new(&mut *encoded).poll_fill_buf(cx)) {
+                Ok(rest) => rest.len(),
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            };
+            if len == 0 {
+                return Poll::Ready(None);
+            }
+            Pin::new(&mut *encoded).consume(len);
+        }
+    });
+    let response = http::Response::new(reqwest::Body::wrap_stream(stream));
+    ObjectBody::new(reqwest::Response::from(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_decodable_codings() {
+        assert_eq!(ContentCoding::parse("gzip"), Some(ContentCoding::Gzip));
+        assert_eq!(ContentCoding::parse(" X-GZIP "), Some(ContentCoding::Gzip));
+        assert_eq!(
+            ContentCoding::parse("deflate"),
+            Some(ContentCoding::Deflate)
+        );
+        assert_eq!(ContentCoding::parse("identity"), None);
+        assert_eq!(ContentCoding::parse("br"), None);
+    }
+}
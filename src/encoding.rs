@@ -1,6 +1,6 @@
 //! Shared percent-encoding sets for OSS request signing and URL construction.
 
-use percent_encoding::{AsciiSet, NON_ALPHANUMERIC};
+use percent_encoding::{AsciiSet, CONTROLS, NON_ALPHANUMERIC, utf8_percent_encode};
 
 /// Encode everything except unreserved chars (RFC 3986) and forward slash.
 /// Used for URI paths where `/` separators must be preserved.
@@ -18,3 +18,21 @@ pub(crate) const QUERY_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
     .remove(b'.')
     .remove(b'_')
     .remove(b'~');
+
+/// Encode control characters and `%` (non-ASCII bytes are always encoded).
+/// Used for user metadata values, which OSS requires to be ASCII.
+pub(crate) const METADATA_ENCODE_SET: &AsciiSet = &CONTROLS.add(b'%');
+
+/// Format tags as an `x-oss-tagging` header value (`k1=v1&k2=v2`).
+pub(crate) fn encode_tagging(tags: &[(String, String)]) -> String {
+    tags.iter()
+        .map(|(k, v)| {
+            format!(
+                "{}={}",
+                utf8_percent_encode(k, QUERY_ENCODE_SET),
+                utf8_percent_encode(v, QUERY_ENCODE_SET)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
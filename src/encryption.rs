@@ -0,0 +1,511 @@
+//! Client-side encryption (CSE).
+//!
+//! [`EncryptedOssClient`] encrypts object data before it is uploaded and
+//! decrypts it as it is downloaded, so OSS only ever stores ciphertext. Each
+//! object gets a fresh AES-256 data key used in CTR mode. The data key and IV
+//! are wrapped by a [`MasterKey`] — an RSA key pair ([`RsaMasterKey`]) or a
+//! KMS CMK (implement the trait on top of your KMS client) — and stored with
+//! the object as `x-oss-meta-client-side-encryption-*` metadata, the format
+//! the official OSS SDKs read and write.
+//!
+//! Requires the `cse` feature.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use aes::Aes256;
+use base64::Engine;
+use bytes::Bytes;
+use ctr::cipher::{KeyIvInit, StreamCipher, StreamCipherSeek};
+use rand::RngCore;
+use rand::rngs::OsRng;
+use rsa::pkcs1::DecodeRsaPrivateKey;
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::{Pkcs1v15Encrypt, RsaPrivateKey, RsaPublicKey};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::api::{OssApi, OssFuture};
+use crate::body::RequestBody;
+use crate::error::{OssError, Result};
+use crate::types::request::{
+    GetObjectRequest, InitiateMultipartUploadRequest, PutObjectRequest, UploadPartRequest,
+};
+use crate::types::response::{GetObjectResponse, PutObjectResponse, UploadPartResponse};
+
+/// Content encryption algorithm recorded in the object metadata.
+pub const CEK_ALGORITHM: &str = "AES/CTR/NoPadding";
+
+/// Wrap algorithm of [`RsaMasterKey`].
+pub const RSA_WRAP_ALGORITHM: &str = "RSA/NONE/PKCS1Padding";
+
+/// Wrap algorithm the official SDKs use for KMS master keys.
+pub const KMS_WRAP_ALGORITHM: &str = "KMS/ALICLOUD";
+
+/// Metadata keys, without the `x-oss-meta-` prefix.
+const META_KEY: &str = "client-side-encryption-key";
+const META_START: &str = "client-side-encryption-start";
+const META_CEK_ALG: &str = "client-side-encryption-cek-alg";
+const META_WRAP_ALG: &str = "client-side-encryption-wrap-alg";
+const META_MATDESC: &str = "client-side-encryption-matdesc";
+const META_CONTENT_LENGTH: &str = "client-side-encryption-unencrypted-content-length";
+const META_DATA_SIZE: &str = "client-side-encryption-data-size";
+const META_PART_SIZE: &str = "client-side-encryption-part-size";
+
+/// AES block size; multipart part sizes must be a multiple of it.
+const BLOCK_SIZE: u64 = 16;
+
+type Aes256Ctr = ctr::Ctr128BE<Aes256>;
+
+/// A key that wraps (encrypts) and unwraps the per-object data keys.
+///
+/// A KMS implementation sends the base64 of `plaintext` to the KMS `Encrypt`
+/// API and returns the decoded `CiphertextBlob`, reporting
+/// [`KMS_WRAP_ALGORITHM`] as its wrap algorithm.
+pub trait MasterKey: Send + Sync {
+    /// Wrap algorithm recorded in the object metadata, e.g.
+    /// [`RSA_WRAP_ALGORITHM`]. Objects wrapped with another algorithm are
+    /// rejected on download.
+    fn wrap_algorithm(&self) -> &str;
+
+    /// Material description (a JSON object) recorded with each object to
+    /// identify the master key. Empty by default, in which case none is
+    /// recorded.
+    fn description(&self) -> &str {
+        ""
+    }
+
+    /// Wrap a data key or IV.
+    fn encrypt<'a>(&'a self, plaintext: &'a [u8]) -> OssFuture<'a, Vec<u8>>;
+
+    /// Unwrap a data key or IV wrapped by [`encrypt`](Self::encrypt).
+    fn decrypt<'a>(&'a self, ciphertext: &'a [u8]) -> OssFuture<'a, Vec<u8>>;
+}
+
+/// A [`MasterKey`] backed by an RSA key pair, using PKCS#1 v1.5 padding.
+pub struct RsaMasterKey {
+    private_key: RsaPrivateKey,
+    public_key: RsaPublicKey,
+    description: String,
+}
+
+impl RsaMasterKey {
+    /// Create a master key from an RSA private key.
+    pub fn new(private_key: RsaPrivateKey) -> Self {
+        let public_key = RsaPublicKey::from(&private_key);
+        Self {
+            private_key,
+            public_key,
+            description: String::new(),
+        }
+    }
+
+    /// Parse a PEM-encoded private key, in PKCS#1 (`RSA PRIVATE KEY`) or
+    /// PKCS#8 (`PRIVATE KEY`) form.
+    pub fn from_pem(pem: &str) -> Result<Self> {
+        let private_key = RsaPrivateKey::from_pkcs1_pem(pem)
+            .or_else(|_| RsaPrivateKey::from_pkcs8_pem(pem))
+            .map_err(|e| OssError::InvalidParameter {
+                field: "pem".into(),
+                reason: e.to_string(),
+            })?;
+        Ok(Self::new(private_key))
+    }
+
+    /// Set the material description recorded with each object, e.g.
+    /// `{"key":"backup-2024"}`.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+}
+
+impl MasterKey for RsaMasterKey {
+    fn wrap_algorithm(&self) -> &str {
+        RSA_WRAP_ALGORITHM
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn encrypt<'a>(&'a self, plaintext: &'a [u8]) -> OssFuture<'a, Vec<u8>> {
+        let result = self
+            .public_key
+            .encrypt(&mut OsRng, Pkcs1v15Encrypt, plaintext)
+            .map_err(|e| OssError::Encryption(e.to_string()));
+        Box::pin(std::future::ready(result))
+    }
+
+    fn decrypt<'a>(&'a self, ciphertext: &'a [u8]) -> OssFuture<'a, Vec<u8>> {
+        let result = self
+            .private_key
+            .decrypt(Pkcs1v15Encrypt, ciphertext)
+            .map_err(|e| OssError::Encryption(e.to_string()));
+        Box::pin(std::future::ready(result))
+    }
+}
+
+impl fmt::Debug for RsaMasterKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RsaMasterKey")
+            .field("description", &self.description)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A per-object AES-256 data key and CTR IV, zeroized on drop.
+#[derive(Zeroize, ZeroizeOnDrop)]
+struct ContentCipher {
+    key: [u8; 32],
+    iv: [u8; 16],
+}
+
+impl ContentCipher {
+    fn generate() -> Self {
+        let mut cipher = Self {
+            key: [0; 32],
+            iv: [0; 16],
+        };
+        OsRng.fill_bytes(&mut cipher.key);
+        OsRng.fill_bytes(&mut cipher.iv);
+        cipher
+    }
+
+    /// A keystream positioned at byte `offset` of the object.
+    fn keystream_at(&self, offset: u64) -> Result<Aes256Ctr> {
+        let mut keystream = Aes256Ctr::new(&self.key.into(), &self.iv.into());
+        keystream
+            .try_seek(offset)
+            .map_err(|e| OssError::Encryption(e.to_string()))?;
+        Ok(keystream)
+    }
+
+    /// Encrypt `data`, which starts at byte `offset` of the object.
+    fn apply(&self, offset: u64, data: &[u8]) -> Result<Vec<u8>> {
+        let mut output = data.to_vec();
+        self.keystream_at(offset)?.apply_keystream(&mut output);
+        Ok(output)
+    }
+
+    /// Wrap the key and IV with `master_key` into object metadata.
+    async fn wrap(
+        &self,
+        master_key: &dyn MasterKey,
+        metadata: &mut HashMap<String, String>,
+    ) -> Result<()> {
+        let engine = base64::engine::general_purpose::STANDARD;
+        let key = master_key.encrypt(&self.key).await?;
+        let iv = master_key.encrypt(&self.iv).await?;
+        metadata.insert(META_KEY.into(), engine.encode(key));
+        metadata.insert(META_START.into(), engine.encode(iv));
+        metadata.insert(META_CEK_ALG.into(), CEK_ALGORITHM.into());
+        metadata.insert(
+            META_WRAP_ALG.into(),
+            master_key.wrap_algorithm().to_string(),
+        );
+        if !master_key.description().is_empty() {
+            metadata.insert(META_MATDESC.into(), master_key.description().to_string());
+        }
+        Ok(())
+    }
+
+    /// Unwrap the key and IV from object metadata, or `None` when the object
+    /// was not encrypted on the client.
+    async fn unwrap(
+        master_key: &dyn MasterKey,
+        metadata: &HashMap<String, String>,
+    ) -> Result<Option<Self>> {
+        let Some(wrapped_key) = metadata.get(META_KEY) else {
+            return Ok(None);
+        };
+        let field = |name: &str| {
+            metadata
+                .get(name)
+                .ok_or_else(|| OssError::Encryption(format!("missing metadata {name}")))
+        };
+        let cek_algorithm = field(META_CEK_ALG)?;
+        if cek_algorithm != CEK_ALGORITHM {
+            return Err(OssError::Encryption(format!(
+                "unsupported content encryption algorithm {cek_algorithm}"
+            )));
+        }
+        let wrap_algorithm = field(META_WRAP_ALG)?;
+        if wrap_algorithm != master_key.wrap_algorithm() {
+            return Err(OssError::Encryption(format!(
+                "object key is wrapped with {wrap_algorithm}, master key uses {}",
+                master_key.wrap_algorithm()
+            )));
+        }
+
+        let engine = base64::engine::general_purpose::STANDARD;
+        let decode = |name: &str, value: &str| {
+            engine
+                .decode(value)
+                .map_err(|e| OssError::Encryption(format!("invalid metadata {name}: {e}")))
+        };
+        let key = Zeroizing::new(master_key.decrypt(&decode(META_KEY, wrapped_key)?).await?);
+        let iv = master_key
+            .decrypt(&decode(META_START, field(META_START)?)?)
+            .await?;
+        Ok(Some(Self {
+            key: key
+                .as_slice()
+                .try_into()
+                .map_err(|_| OssError::Encryption("data key is not 32 bytes".into()))?,
+            iv: iv
+                .try_into()
+                .map_err(|_| OssError::Encryption("IV is not 16 bytes".into()))?,
+        }))
+    }
+}
+
+/// An OSS client that encrypts object data on the client.
+///
+/// Objects written with [`put_object`](Self::put_object) or a multipart
+/// upload are stored encrypted; [`get_object`](Self::get_object) decrypts
+/// them, including ranged reads, and passes objects without encryption
+/// metadata through unchanged. Other operations, such as completing or
+/// aborting a multipart upload, do not touch object data and go through
+/// [`client`](Self::client).
+///
+/// Bodies are encrypted in memory, so uploads need in-memory bodies.
+///
+/// # Examples
+/// ```no_run
+/// use rs_ali_oss::encryption::{EncryptedOssClient, RsaMasterKey};
+/// use rs_ali_oss::types::common::{BucketName, ObjectKey};
+/// use rs_ali_oss::types::request::PutObjectRequestBuilder;
+///
+/// # async fn example(client: rs_ali_oss::OssClient, pem: &str) -> rs_ali_oss::Result<()> {
+/// let client = EncryptedOssClient::new(client, RsaMasterKey::from_pem(pem)?);
+/// let request = PutObjectRequestBuilder::new()
+///     .bucket(BucketName::new("my-bucket")?)
+///     .key(ObjectKey::new("records/2024.csv")?)
+///     .body(b"id,name\n".to_vec())
+///     .build()?;
+/// client.put_object(request).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct EncryptedOssClient {
+    client: Arc<dyn OssApi>,
+    master_key: Arc<dyn MasterKey>,
+}
+
+impl EncryptedOssClient {
+    /// Wrap `client`, protecting data keys with `master_key`.
+    pub fn new(client: impl OssApi + 'static, master_key: impl MasterKey + 'static) -> Self {
+        Self {
+            client: Arc::new(client),
+            master_key: Arc::new(master_key),
+        }
+    }
+
+    /// The underlying client, for operations that do not touch object data.
+    pub fn client(&self) -> &dyn OssApi {
+        self.client.as_ref()
+    }
+
+    /// Encrypt and upload an object.
+    pub async fn put_object(&self, mut request: PutObjectRequest) -> Result<PutObjectResponse> {
+        let plaintext = in_memory(&request.body)?;
+        let cipher = ContentCipher::generate();
+        let ciphertext = cipher.apply(0, plaintext)?;
+        cipher
+            .wrap(self.master_key.as_ref(), &mut request.metadata)
+            .await?;
+        request
+            .metadata
+            .insert(META_CONTENT_LENGTH.into(), plaintext.len().to_string());
+        request.body = RequestBody::Once(ciphertext.into());
+        self.client.put_object(request).await
+    }
+
+    /// Download an object, decrypting it as the body is read.
+    ///
+    /// Any byte range is honoured; the keystream is positioned at the start
+    /// of the returned range.
+    pub async fn get_object(&self, request: GetObjectRequest) -> Result<GetObjectResponse> {
+        let mut response = self.client.get_object(request).await?;
+        let Some(cipher) =
+            ContentCipher::unwrap(self.master_key.as_ref(), &response.metadata).await?
+        else {
+            return Ok(response);
+        };
+        let mut keystream = cipher.keystream_at(response.body.range_start())?;
+        response.body = response.body.with_transform(Box::new(move |chunk: Bytes| {
+            let mut data = chunk.to_vec();
+            keystream.apply_keystream(&mut data);
+            Bytes::from(data)
+        }));
+        Ok(response)
+    }
+
+    /// Initiate an encrypted multipart upload of `data_size` bytes split into
+    /// parts of `part_size` bytes (the last part may be shorter).
+    ///
+    /// `part_size` must be a multiple of 16 so that every part starts on an
+    /// AES block boundary, as the official SDKs require.
+    pub async fn initiate_multipart_upload(
+        &self,
+        mut request: InitiateMultipartUploadRequest,
+        data_size: u64,
+        part_size: u64,
+    ) -> Result<EncryptedMultipartUpload> {
+        if part_size == 0 || !part_size.is_multiple_of(BLOCK_SIZE) {
+            return Err(OssError::InvalidParameter {
+                field: "part_size".into(),
+                reason: format!("must be a non-zero multiple of {BLOCK_SIZE}"),
+            });
+        }
+        let cipher = ContentCipher::generate();
+        cipher
+            .wrap(self.master_key.as_ref(), &mut request.metadata)
+            .await?;
+        request
+            .metadata
+            .insert(META_DATA_SIZE.into(), data_size.to_string());
+        request
+            .metadata
+            .insert(META_PART_SIZE.into(), part_size.to_string());
+        let response = self.client.initiate_multipart_upload(request).await?;
+        Ok(EncryptedMultipartUpload {
+            upload_id: response.upload_id,
+            data_size,
+            part_size,
+            cipher,
+        })
+    }
+
+    /// Encrypt and upload one part of `upload`.
+    ///
+    /// Every part but the last must be exactly the part size given at
+    /// initiation. Parts may be uploaded in any order and concurrently.
+    pub async fn upload_part(
+        &self,
+        upload: &EncryptedMultipartUpload,
+        mut request: UploadPartRequest,
+    ) -> Result<UploadPartResponse> {
+        if request.upload_id != upload.upload_id {
+            return Err(OssError::InvalidParameter {
+                field: "upload_id".into(),
+                reason: "does not match the encrypted upload".into(),
+            });
+        }
+        let plaintext = request.body.as_bytes().ok_or_else(not_in_memory)?;
+        let offset = u64::from(request.part_number - 1) * upload.part_size;
+        let end = offset + plaintext.len() as u64;
+        if end > upload.data_size
+            || (plaintext.len() as u64 != upload.part_size && end != upload.data_size)
+        {
+            return Err(OssError::InvalidParameter {
+                field: "body".into(),
+                reason: format!(
+                    "part {} must be {} bytes, or end the {}-byte object",
+                    request.part_number, upload.part_size, upload.data_size
+                ),
+            });
+        }
+        let ciphertext = upload.cipher.apply(offset, plaintext)?;
+        request.body = ciphertext.into();
+        self.client.upload_part(request).await
+    }
+}
+
+impl fmt::Debug for EncryptedOssClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncryptedOssClient")
+            .field("wrap_algorithm", &self.master_key.wrap_algorithm())
+            .finish_non_exhaustive()
+    }
+}
+
+/// An encrypted multipart upload started by
+/// [`EncryptedOssClient::initiate_multipart_upload`].
+///
+/// Holds the upload's data key, so keep it in memory only for the duration
+/// of the upload.
+pub struct EncryptedMultipartUpload {
+    upload_id: String,
+    data_size: u64,
+    part_size: u64,
+    cipher: ContentCipher,
+}
+
+impl EncryptedMultipartUpload {
+    /// The upload ID, for the part, complete and abort requests.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+
+    /// Total plaintext size of the object.
+    pub fn data_size(&self) -> u64 {
+        self.data_size
+    }
+
+    /// Plaintext size of every part but the last.
+    pub fn part_size(&self) -> u64 {
+        self.part_size
+    }
+}
+
+impl fmt::Debug for EncryptedMultipartUpload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncryptedMultipartUpload")
+            .field("upload_id", &self.upload_id)
+            .field("data_size", &self.data_size)
+            .field("part_size", &self.part_size)
+            .finish_non_exhaustive()
+    }
+}
+
+fn in_memory(body: &RequestBody) -> Result<&[u8]> {
+    match body {
+        RequestBody::Once(body) => body.as_bytes().ok_or_else(not_in_memory),
+        RequestBody::Replayable(_) => Err(not_in_memory()),
+    }
+}
+
+fn not_in_memory() -> OssError {
+    OssError::InvalidParameter {
+        field: "body".into(),
+        reason: "client-side encryption requires an in-memory body".into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keystream_can_start_mid_object() {
+        let cipher = ContentCipher::generate();
+        let data: Vec<u8> = (0..100).collect();
+        let whole = cipher.apply(0, &data).unwrap();
+        assert_ne!(whole, data);
+        assert_eq!(cipher.apply(37, &data[37..]).unwrap(), whole[37..]);
+        assert_eq!(cipher.apply(0, &whole).unwrap(), data);
+    }
+
+    #[test]
+    fn unencrypted_objects_have_no_cipher() {
+        struct NoKey;
+        impl MasterKey for NoKey {
+            fn wrap_algorithm(&self) -> &str {
+                KMS_WRAP_ALGORITHM
+            }
+            fn encrypt<'a>(&'a self, _: &'a [u8]) -> OssFuture<'a, Vec<u8>> {
+                unreachable!()
+            }
+            fn decrypt<'a>(&'a self, _: &'a [u8]) -> OssFuture<'a, Vec<u8>> {
+                unreachable!()
+            }
+        }
+        let metadata = HashMap::from([("author".to_string(), "alice".to_string())]);
+        let cipher =
+            futures_util::FutureExt::now_or_never(ContentCipher::unwrap(&NoKey, &metadata));
+        assert!(matches!(cipher, Some(Ok(None))));
+    }
+}
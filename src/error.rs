@@ -1,11 +1,15 @@
 //! Error types for the Alibaba Cloud OSS SDK.
 
+use std::fmt;
 use std::time::Duration;
 
 use reqwest::StatusCode;
 use serde::Deserialize;
 use thiserror::Error;
 
+use crate::config::RetryConfig;
+use crate::retry::{DefaultRetryClassifier, RetryAttempt, RetryClassifier, RetryDecision};
+
 /// Errors that can occur when interacting with Alibaba Cloud OSS.
 #[derive(Debug, Error)]
 pub enum OssError {
@@ -17,8 +21,15 @@ pub enum OssError {
     #[error("XML parsing error: {0}")]
     XmlParse(String),
 
+    /// JSON parsing error.
+    #[error("JSON parsing error: {0}")]
+    JsonParse(String),
+
     /// OSS service returned an error response.
-    #[error("OSS service error (HTTP {status}): {code} - {message}")]
+    #[error(
+        "OSS service error (HTTP {status}): {code} - {message}{}",
+        diagnostics(.request_id, .host_id, .ec.as_deref())
+    )]
     ServerError {
         /// HTTP status code.
         status: u16,
@@ -30,6 +41,13 @@ pub enum OssError {
         request_id: String,
         /// Host that generated the error.
         host_id: String,
+        /// OSS diagnostic error code (`EC`), used to look up the cause in the
+        /// OSS troubleshooting docs.
+        ec: Option<String>,
+        /// The response body as received, truncated to
+        /// [`OssError::MAX_RAW_BODY_LEN`] bytes, for logging error formats the
+        /// SDK cannot parse.
+        raw_body: String,
     },
 
     /// Invalid bucket name.
@@ -56,7 +74,11 @@ pub enum OssError {
     #[error("missing required field: {0}")]
     MissingField(String),
 
-    /// Operation timed out.
+    /// The overall request deadline (the configured request timeout or a
+    /// per-operation timeout) passed, including time spent on retries.
+    ///
+    /// Connect and read timeouts surface as [`OssError::Http`]; use
+    /// [`OssError::timeout_kind`] to tell all three apart.
     #[error("operation timed out after {0:?}")]
     Timeout(Duration),
 
@@ -78,14 +100,209 @@ pub enum OssError {
         last_error: Box<OssError>,
     },
 
+    /// The CRC64 of the data sent or received differs from the one OSS
+    /// reported, meaning the data was corrupted or parts were misassembled.
+    #[error("CRC64 mismatch: computed {computed}, server returned {server}")]
+    ChecksumMismatch {
+        /// CRC64 computed locally.
+        computed: u64,
+        /// CRC64 reported by OSS in `x-oss-hash-crc64ecma`.
+        server: u64,
+    },
+
     /// Invalid URL construction.
     #[error("invalid URL: {0}")]
     InvalidUrl(String),
+
+    /// The circuit breaker for the endpoint host is open; the request was not sent.
+    #[error("circuit open for {host}, retry after {retry_after:?}")]
+    CircuitOpen {
+        /// Endpoint host whose circuit is open.
+        host: String,
+        /// Time until the circuit lets a probe request through.
+        retry_after: Duration,
+    },
+
+    /// The operation is not implemented by this [`OssApi`](crate::OssApi)
+    /// implementation, e.g. a test double.
+    #[error("operation not supported: {0}")]
+    Unsupported(String),
+
+    /// Client-side encryption or decryption failed, e.g. the data key could
+    /// not be unwrapped or the object's encryption metadata is malformed.
+    #[error("client-side encryption error: {0}")]
+    Encryption(String),
+}
+
+/// The longest prefix of `body` that fits in `max` bytes without splitting a
+/// character.
+fn truncate_body(body: &str, max: usize) -> &str {
+    if body.len() <= max {
+        return body;
+    }
+    let mut end = max;
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    &body[..end]
+}
+
+/// The stage of a request at which a timeout fired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeoutKind {
+    /// No connection to the endpoint could be established within the connect
+    /// timeout; the endpoint is likely unreachable.
+    Connect,
+    /// The connection was established but the server sent no data within the
+    /// read timeout.
+    Read,
+    /// The overall request deadline passed, e.g. because a slow transfer or
+    /// retries took too long.
+    Request,
+}
+
+impl fmt::Display for TimeoutKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TimeoutKind::Connect => "connect",
+            TimeoutKind::Read => "read",
+            TimeoutKind::Request => "request",
+        })
+    }
 }
 
 /// A specialized `Result` type for OSS operations.
 pub type Result<T> = std::result::Result<T, OssError>;
 
+macro_rules! error_codes {
+    ($($(#[$doc:meta])* $variant:ident,)+) => {
+        /// Error code returned by the OSS service in the `Code` element of an
+        /// error response.
+        ///
+        /// Codes the SDK does not know about are kept verbatim in
+        /// [`ErrorCode::Unknown`].
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum ErrorCode {
+            $($(#[$doc])* $variant,)+
+            /// Any other error code.
+            Unknown(String),
+        }
+
+        impl ErrorCode {
+            /// The code as it appears in the error response.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(ErrorCode::$variant => stringify!($variant),)+
+                    ErrorCode::Unknown(code) => code,
+                }
+            }
+        }
+
+        impl From<&str> for ErrorCode {
+            fn from(code: &str) -> Self {
+                match code {
+                    $(stringify!($variant) => ErrorCode::$variant,)+
+                    other => ErrorCode::Unknown(other.to_string()),
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    /// Access to the resource was denied.
+    AccessDenied,
+    /// The bucket name is already taken.
+    BucketAlreadyExists,
+    /// The bucket still contains objects and cannot be deleted.
+    BucketNotEmpty,
+    /// The upload succeeded but OSS could not deliver its callback.
+    CallbackFailed,
+    /// The request body is larger than allowed.
+    EntityTooLarge,
+    /// The request body is smaller than allowed, e.g. a non-final multipart part.
+    EntityTooSmall,
+    /// The access key ID does not exist.
+    InvalidAccessKeyId,
+    /// A request parameter is invalid.
+    InvalidArgument,
+    /// The bucket name is invalid.
+    InvalidBucketName,
+    /// The Content-MD5 header is invalid.
+    InvalidDigest,
+    /// The object key is invalid.
+    InvalidObjectName,
+    /// A multipart part is missing or its ETag does not match.
+    InvalidPart,
+    /// The parts of a multipart upload are not in ascending order.
+    InvalidPartOrder,
+    /// The requested byte range cannot be satisfied.
+    InvalidRange,
+    /// The object is archived and must be restored before it can be read.
+    InvalidObjectState,
+    /// The request body is not well-formed XML.
+    MalformedXML,
+    /// The request is missing the Content-Length header.
+    MissingContentLength,
+    /// The bucket does not exist.
+    NoSuchBucket,
+    /// The bucket has no CORS configuration.
+    NoSuchCORSConfiguration,
+    /// The object does not exist.
+    NoSuchKey,
+    /// The bucket has no lifecycle configuration.
+    NoSuchLifecycle,
+    /// The bucket has no policy.
+    NoSuchBucketPolicy,
+    /// The object or bucket has no tags.
+    NoSuchTagSet,
+    /// The multipart upload does not exist or was already completed.
+    NoSuchUpload,
+    /// The object version does not exist.
+    NoSuchVersion,
+    /// The requested operation is not implemented.
+    NotImplemented,
+    /// A precondition header such as If-Match did not hold.
+    PreconditionFailed,
+    /// The request rate for the bucket exceeded its QPS limit.
+    QpsLimitExceeded,
+    /// The request did not finish in time.
+    RequestTimeout,
+    /// The request time is too far from the server time.
+    RequestTimeTooSkewed,
+    /// A restore of the archived object is already in progress.
+    RestoreAlreadyInProgress,
+    /// The security token has expired.
+    SecurityTokenExpired,
+    /// The request signature does not match.
+    SignatureDoesNotMatch,
+    /// The service is temporarily unavailable.
+    ServiceUnavailable,
+    /// Requests are being throttled; slow down.
+    SlowDown,
+    /// Requests are being throttled.
+    Throttling,
+    /// The account already owns the maximum number of buckets.
+    TooManyBuckets,
+    /// An internal server error occurred.
+    InternalError,
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ErrorCode {
+    type Err = std::convert::Infallible;
+
+    fn from_str(code: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(ErrorCode::from(code))
+    }
+}
+
 /// Raw OSS error response XML structure.
 #[derive(Debug, Deserialize)]
 #[serde(rename = "Error")]
@@ -94,18 +311,44 @@ struct OssErrorResponse {
     code: String,
     #[serde(rename = "Message")]
     message: String,
-    #[serde(rename = "RequestId")]
+    #[serde(rename = "RequestId", default)]
     request_id: String,
     #[serde(rename = "HostId", default)]
     host_id: String,
+    #[serde(rename = "EC", default)]
+    ec: Option<String>,
+}
+
+/// Render the non-empty identifiers of a service error for its `Display` output.
+fn diagnostics(request_id: &str, host_id: &str, ec: Option<&str>) -> String {
+    let parts: Vec<String> = [
+        ("request id", request_id),
+        ("host id", host_id),
+        ("EC", ec.unwrap_or_default()),
+    ]
+    .into_iter()
+    .filter(|(_, value)| !value.is_empty())
+    .map(|(name, value)| format!("{name}: {value}"))
+    .collect();
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", parts.join(", "))
+    }
 }
 
 impl OssError {
+    /// Maximum number of bytes of the response body kept in the `raw_body`
+    /// of [`OssError::ServerError`].
+    pub const MAX_RAW_BODY_LEN: usize = 8 * 1024;
+
     /// Parse an OSS error response from HTTP status and body.
     ///
     /// Attempts to parse the body as OSS XML error format. Falls back to
-    /// a raw message if XML parsing fails.
+    /// a raw message if XML parsing fails. Either way the (truncated) body is
+    /// kept in `raw_body`.
     pub fn from_response_body(status: StatusCode, body: &str) -> Self {
+        let raw_body = truncate_body(body, Self::MAX_RAW_BODY_LEN).to_string();
         match quick_xml::de::from_str::<OssErrorResponse>(body) {
             Ok(err_resp) => OssError::ServerError {
                 status: status.as_u16(),
@@ -113,6 +356,8 @@ impl OssError {
                 message: err_resp.message,
                 request_id: err_resp.request_id,
                 host_id: err_resp.host_id,
+                ec: err_resp.ec.filter(|ec| !ec.is_empty()),
+                raw_body,
             },
             Err(_) => OssError::ServerError {
                 status: status.as_u16(),
@@ -120,9 +365,167 @@ impl OssError {
                 message: body.to_string(),
                 request_id: String::new(),
                 host_id: String::new(),
+                ec: None,
+                raw_body,
             },
         }
     }
+
+    /// Fill in the request ID and EC of a service error from the
+    /// `x-oss-request-id` and `x-oss-ec` response headers when the body did not
+    /// carry them, as with error responses to `HEAD` requests.
+    pub(crate) fn with_response_headers(mut self, headers: &reqwest::header::HeaderMap) -> Self {
+        if let OssError::ServerError { request_id, ec, .. } = &mut self {
+            let header = |name: &str| {
+                headers
+                    .get(name)
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_string)
+            };
+            if request_id.is_empty()
+                && let Some(value) = header("x-oss-request-id")
+            {
+                *request_id = value;
+            }
+            if ec.is_none() {
+                *ec = header("x-oss-ec");
+            }
+        }
+        self
+    }
+
+    /// The typed OSS error code of a service error.
+    ///
+    /// Returns `None` for errors that did not come from an OSS error response,
+    /// and for error responses without a code.
+    ///
+    /// # Examples
+    /// ```
+    /// use rs_ali_oss::error::{ErrorCode, OssError};
+    ///
+    /// fn object_missing(err: &OssError) -> bool {
+    ///     err.error_code() == Some(ErrorCode::NoSuchKey)
+    /// }
+    /// ```
+    pub fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            OssError::ServerError { code, .. } if !code.is_empty() => {
+                Some(ErrorCode::from(code.as_str()))
+            }
+            OssError::RetryExhausted { last_error, .. } => last_error.error_code(),
+            _ => None,
+        }
+    }
+
+    /// The raw body of the OSS error response, truncated to
+    /// [`MAX_RAW_BODY_LEN`](Self::MAX_RAW_BODY_LEN) bytes.
+    ///
+    /// Useful for logging responses from OSS-compatible gateways whose error
+    /// format differs from OSS. Returns `None` for errors without a response.
+    pub fn raw_body(&self) -> Option<&str> {
+        match self {
+            OssError::ServerError { raw_body, .. } => Some(raw_body),
+            OssError::RetryExhausted { last_error, .. } => last_error.raw_body(),
+            _ => None,
+        }
+    }
+
+    /// Which timeout, if any, caused the error.
+    ///
+    /// # Examples
+    /// ```
+    /// use rs_ali_oss::error::{OssError, TimeoutKind};
+    ///
+    /// fn metric_label(err: &OssError) -> &'static str {
+    ///     match err.timeout_kind() {
+    ///         Some(TimeoutKind::Connect) => "endpoint_unreachable",
+    ///         Some(TimeoutKind::Read | TimeoutKind::Request) => "deadline_exceeded",
+    ///         None => "other",
+    ///     }
+    /// }
+    /// ```
+    pub fn timeout_kind(&self) -> Option<TimeoutKind> {
+        match self {
+            OssError::Timeout(_) => Some(TimeoutKind::Request),
+            OssError::Http(e) if e.is_timeout() => Some(if e.is_connect() {
+                TimeoutKind::Connect
+            } else {
+                TimeoutKind::Read
+            }),
+            OssError::RetryExhausted { last_error, .. } => last_error.timeout_kind(),
+            _ => None,
+        }
+    }
+
+    /// The HTTP status code of the failed response, if a response was received.
+    ///
+    /// For [`OssError::RetryExhausted`], the status of the last attempt.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            OssError::ServerError { status, .. } => Some(*status),
+            OssError::Http(e) => e.status().map(|status| status.as_u16()),
+            OssError::RetryExhausted { last_error, .. } => last_error.status(),
+            _ => None,
+        }
+    }
+
+    /// Whether the error is transient, so the same request may succeed later.
+    ///
+    /// Uses the same rules as the default retry policy: 5xx responses,
+    /// 408/429, OSS timeout and throttling codes, connect and timeout
+    /// transport errors, operation timeouts and open circuits.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            OssError::ServerError { .. } | OssError::Http(_) => {
+                let code = self.error_code();
+                let attempt = RetryAttempt {
+                    method: "GET",
+                    attempt: 0,
+                    status: self.status(),
+                    code: code.as_ref().map(ErrorCode::as_str),
+                    error: self,
+                    config: &RetryConfig::default(),
+                };
+                DefaultRetryClassifier.classify(&attempt) == RetryDecision::Retry
+            }
+            OssError::Timeout(_) | OssError::CircuitOpen { .. } => true,
+            OssError::RetryExhausted { last_error, .. } => last_error.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Whether the bucket, object, upload or version does not exist (HTTP 404).
+    pub fn is_not_found(&self) -> bool {
+        self.status() == Some(404)
+    }
+
+    /// Whether OSS refused the request (HTTP 403), e.g. for missing
+    /// permissions or an invalid signature.
+    pub fn is_access_denied(&self) -> bool {
+        self.status() == Some(403)
+    }
+
+    /// Whether OSS throttled the request (HTTP 429, or a `SlowDown`,
+    /// `Throttling` or `QpsLimitExceeded` error code).
+    pub fn is_throttled(&self) -> bool {
+        self.status() == Some(429)
+            || matches!(
+                self.error_code(),
+                Some(ErrorCode::SlowDown | ErrorCode::Throttling | ErrorCode::QpsLimitExceeded)
+            )
+    }
+
+    /// Whether OSS rejected the request's access key or security token, which
+    /// usually means the credentials were rotated or expired.
+    pub(crate) fn is_credential_error(&self) -> bool {
+        match self {
+            OssError::ServerError { .. } => matches!(
+                self.error_code(),
+                Some(ErrorCode::InvalidAccessKeyId | ErrorCode::SecurityTokenExpired)
+            ),
+            _ => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -213,9 +616,245 @@ mod tests {
         assert_eq!(err.to_string(), "retry exhausted after 4 attempt(s)");
     }
 
+    #[test]
+    fn credential_errors_are_detected_by_code() {
+        for code in ["InvalidAccessKeyId", "SecurityTokenExpired"] {
+            let xml = format!(
+                "<Error><Code>{code}</Code><Message>m</Message>\
+                 <RequestId>r</RequestId><HostId>h</HostId></Error>"
+            );
+            let err = OssError::from_response_body(StatusCode::FORBIDDEN, &xml);
+            assert!(err.is_credential_error(), "{code}");
+        }
+        let xml = "<Error><Code>AccessDenied</Code><Message>m</Message>\
+                   <RequestId>r</RequestId><HostId>h</HostId></Error>";
+        let err = OssError::from_response_body(StatusCode::FORBIDDEN, xml);
+        assert!(!err.is_credential_error());
+        assert!(!OssError::Auth("x".into()).is_credential_error());
+    }
+
+    #[test]
+    fn parses_and_displays_diagnostics() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Error>
+    <Code>AccessDenied</Code>
+    <Message>You have no right to access this object.</Message>
+    <RequestId>5C3D9175B6FC201293AD****</RequestId>
+    <HostId>bucket.oss-cn-hangzhou.aliyuncs.com</HostId>
+    <EC>0003-00000001</EC>
+</Error>"#;
+        let err = OssError::from_response_body(StatusCode::FORBIDDEN, xml);
+        match &err {
+            OssError::ServerError { ec, host_id, .. } => {
+                assert_eq!(ec.as_deref(), Some("0003-00000001"));
+                assert_eq!(host_id, "bucket.oss-cn-hangzhou.aliyuncs.com");
+            }
+            other => panic!("expected ServerError, got: {other:?}"),
+        }
+        assert_eq!(
+            err.to_string(),
+            "OSS service error (HTTP 403): AccessDenied - You have no right to access \
+             this object. (request id: 5C3D9175B6FC201293AD****, host id: \
+             bucket.oss-cn-hangzhou.aliyuncs.com, EC: 0003-00000001)"
+        );
+
+        let err = OssError::from_response_body(StatusCode::BAD_GATEWAY, "bad gateway");
+        assert_eq!(
+            err.to_string(),
+            "OSS service error (HTTP 502):  - bad gateway"
+        );
+    }
+
+    #[test]
+    fn response_headers_fill_missing_diagnostics() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("x-oss-request-id", "REQ-FROM-HEADER".parse().unwrap());
+        headers.insert("x-oss-ec", "0026-00000001".parse().unwrap());
+
+        let err =
+            OssError::from_response_body(StatusCode::NOT_FOUND, "").with_response_headers(&headers);
+        match err {
+            OssError::ServerError { request_id, ec, .. } => {
+                assert_eq!(request_id, "REQ-FROM-HEADER");
+                assert_eq!(ec.as_deref(), Some("0026-00000001"));
+            }
+            other => panic!("expected ServerError, got: {other:?}"),
+        }
+
+        let xml = "<Error><Code>NoSuchKey</Code><Message>m</Message>\
+                   <RequestId>REQ-FROM-BODY</RequestId></Error>";
+        let err = OssError::from_response_body(StatusCode::NOT_FOUND, xml)
+            .with_response_headers(&headers);
+        match err {
+            OssError::ServerError { request_id, .. } => assert_eq!(request_id, "REQ-FROM-BODY"),
+            other => panic!("expected ServerError, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn error_code_is_parsed() {
+        let xml = "<Error><Code>NoSuchBucket</Code><Message>m</Message>\
+                   <RequestId>r</RequestId><HostId>h</HostId></Error>";
+        let err = OssError::from_response_body(StatusCode::NOT_FOUND, xml);
+        assert_eq!(err.error_code(), Some(ErrorCode::NoSuchBucket));
+
+        let xml = "<Error><Code>SomethingNew</Code><Message>m</Message>\
+                   <RequestId>r</RequestId></Error>";
+        let err = OssError::from_response_body(StatusCode::BAD_REQUEST, xml);
+        let code = err.error_code().unwrap();
+        assert_eq!(code, ErrorCode::Unknown("SomethingNew".into()));
+        assert_eq!(code.as_str(), "SomethingNew");
+
+        let err = OssError::from_response_body(StatusCode::BAD_GATEWAY, "not xml");
+        assert_eq!(err.error_code(), None);
+        assert_eq!(OssError::InvalidUrl("x".into()).error_code(), None);
+    }
+
+    #[test]
+    fn error_code_round_trips() {
+        for code in [
+            ErrorCode::AccessDenied,
+            ErrorCode::SignatureDoesNotMatch,
+            ErrorCode::BucketNotEmpty,
+            ErrorCode::MalformedXML,
+            ErrorCode::NoSuchCORSConfiguration,
+        ] {
+            assert_eq!(code.as_str().parse::<ErrorCode>().unwrap(), code);
+            assert_eq!(code.to_string(), code.as_str());
+        }
+    }
+
+    #[test]
+    fn error_code_looks_through_retry_exhausted() {
+        let xml = "<Error><Code>SlowDown</Code><Message>m</Message>\
+                   <RequestId>r</RequestId></Error>";
+        let err = OssError::RetryExhausted {
+            attempts: 3,
+            last_error: Box::new(OssError::from_response_body(
+                StatusCode::SERVICE_UNAVAILABLE,
+                xml,
+            )),
+        };
+        assert_eq!(err.error_code(), Some(ErrorCode::SlowDown));
+    }
+
+    fn service_error(status: StatusCode, code: &str) -> OssError {
+        let xml = format!(
+            "<Error><Code>{code}</Code><Message>m</Message><RequestId>r</RequestId></Error>"
+        );
+        OssError::from_response_body(status, &xml)
+    }
+
+    #[test]
+    fn classification_helpers() {
+        let not_found = service_error(StatusCode::NOT_FOUND, "NoSuchKey");
+        assert_eq!(not_found.status(), Some(404));
+        assert!(not_found.is_not_found());
+        assert!(!not_found.is_access_denied());
+        assert!(!not_found.is_retryable());
+
+        let denied = service_error(StatusCode::FORBIDDEN, "AccessDenied");
+        assert!(denied.is_access_denied());
+        assert!(!denied.is_retryable());
+
+        let slow_down = service_error(StatusCode::SERVICE_UNAVAILABLE, "SlowDown");
+        assert!(slow_down.is_throttled());
+        assert!(slow_down.is_retryable());
+        assert!(service_error(StatusCode::TOO_MANY_REQUESTS, "").is_throttled());
+        assert!(!service_error(StatusCode::INTERNAL_SERVER_ERROR, "InternalError").is_throttled());
+        assert!(service_error(StatusCode::INTERNAL_SERVER_ERROR, "InternalError").is_retryable());
+        assert!(service_error(StatusCode::BAD_REQUEST, "RequestTimeout").is_retryable());
+    }
+
+    #[test]
+    fn classification_of_non_service_errors() {
+        let timeout = OssError::Timeout(Duration::from_secs(1));
+        assert_eq!(timeout.status(), None);
+        assert!(timeout.is_retryable());
+        assert!(!timeout.is_not_found());
+
+        let invalid = OssError::InvalidObjectKey("".into());
+        assert!(!invalid.is_retryable());
+        assert!(!invalid.is_throttled());
+
+        let exhausted = OssError::RetryExhausted {
+            attempts: 3,
+            last_error: Box::new(service_error(StatusCode::SERVICE_UNAVAILABLE, "SlowDown")),
+        };
+        assert_eq!(exhausted.status(), Some(503));
+        assert!(exhausted.is_throttled());
+        assert!(exhausted.is_retryable());
+    }
+
+    #[test]
+    fn raw_body_is_kept() {
+        let xml = "<Error><Code>NoSuchKey</Code><Message>m</Message>\
+                   <RequestId>r</RequestId></Error>";
+        let err = OssError::from_response_body(StatusCode::NOT_FOUND, xml);
+        assert_eq!(err.raw_body(), Some(xml));
+
+        let json = r#"{"error":{"code":"NotFound","message":"no such object"}}"#;
+        let err = OssError::from_response_body(StatusCode::NOT_FOUND, json);
+        assert_eq!(err.raw_body(), Some(json));
+        assert_eq!(OssError::InvalidUrl("x".into()).raw_body(), None);
+    }
+
+    #[test]
+    fn raw_body_is_truncated_on_char_boundary() {
+        let body = "é".repeat(OssError::MAX_RAW_BODY_LEN);
+        let err = OssError::from_response_body(StatusCode::BAD_GATEWAY, &body);
+        let raw = err.raw_body().unwrap();
+        assert_eq!(raw.len(), OssError::MAX_RAW_BODY_LEN);
+        assert!(raw.chars().all(|c| c == 'é'));
+
+        let body = format!("a{}", "é".repeat(OssError::MAX_RAW_BODY_LEN));
+        let err = OssError::from_response_body(StatusCode::BAD_GATEWAY, &body);
+        assert_eq!(
+            err.raw_body().unwrap().len(),
+            OssError::MAX_RAW_BODY_LEN - 1
+        );
+    }
+
+    #[test]
+    fn timeout_kind_of_deadline() {
+        let err = OssError::Timeout(Duration::from_secs(3));
+        assert_eq!(err.timeout_kind(), Some(TimeoutKind::Request));
+        let err = OssError::RetryExhausted {
+            attempts: 2,
+            last_error: Box::new(err),
+        };
+        assert_eq!(err.timeout_kind(), Some(TimeoutKind::Request));
+        assert_eq!(OssError::Auth("x".into()).timeout_kind(), None);
+        assert_eq!(TimeoutKind::Connect.to_string(), "connect");
+    }
+
+    #[test]
+    fn display_checksum_mismatch() {
+        let err = OssError::ChecksumMismatch {
+            computed: 1,
+            server: 2,
+        };
+        assert_eq!(
+            err.to_string(),
+            "CRC64 mismatch: computed 1, server returned 2"
+        );
+    }
+
     #[test]
     fn display_invalid_url() {
         let err = OssError::InvalidUrl("missing scheme".to_string());
         assert_eq!(err.to_string(), "invalid URL: missing scheme");
     }
+
+    #[test]
+    fn display_circuit_open() {
+        let err = OssError::CircuitOpen {
+            host: "bucket.oss-cn-hangzhou.aliyuncs.com".to_string(),
+            retry_after: Duration::from_secs(5),
+        };
+        assert_eq!(
+            err.to_string(),
+            "circuit open for bucket.oss-cn-hangzhou.aliyuncs.com, retry after 5s"
+        );
+    }
 }
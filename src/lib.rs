@@ -1,36 +1,81 @@
 //! Alibaba Cloud OSS SDK for Rust.
 #![deny(missing_docs)]
 
+pub mod api;
 pub mod auth;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod body;
 pub mod client;
 pub mod config;
 pub mod crc64;
 pub mod credential;
+#[cfg(feature = "decompression")]
+pub(crate) mod decode;
 pub(crate) mod encoding;
+#[cfg(feature = "cse")]
+pub mod encryption;
 pub mod error;
 pub mod middleware;
+#[cfg(feature = "test-util")]
+pub mod mock;
 pub mod ops;
 pub mod progress;
+pub mod retry;
 pub mod types;
 
+pub use api::OssApi;
+pub use auth::{SignatureVersion, Signer, SigningContext, SigningDetails, V1Signer, V4Signer};
+#[cfg(feature = "blocking")]
+pub use blocking::OssBlockingClient;
+pub use body::{BodyProvider, ReplayableBody};
 pub use client::OssClient;
-pub use config::{ClientBuilder, Config, Credentials, PoolConfig, RetryConfig, TimeoutConfig};
+pub use config::{
+    AccelerateEndpoint, ClientBuilder, Config, Credentials, HttpVersion, MetadataEncoding,
+    OperationOptions, OperationOptionsBuilder, PayloadSigning, PoolConfig, ProtocolConfig,
+    RetryConfig, TimeoutConfig, TlsConfig,
+};
+pub use crc64::Crc64;
 pub use credential::{
-    CachingProvider, CredentialProvider, EnvironmentProvider, ProviderChain, StaticProvider,
+    AssumeRoleProvider, AssumeRoleProviderBuilder, CachingProvider, CredentialProvider,
+    EnvironmentProvider, OidcRoleArnProvider, OidcRoleArnProviderBuilder, ProfileProvider,
+    ProfileProviderBuilder, ProviderChain, ServerlessProvider, StaticProvider,
+};
+pub use error::{ErrorCode, OssError, Result, TimeoutKind};
+pub use middleware::{
+    CircuitBreaker, CircuitState, Hedging, HedgingBuilder, Interceptor, InterceptorContext,
+    MetricsInterceptor, MetricsSnapshot, OperationClass, RateLimitInterceptor,
+    RateLimitInterceptorBuilder, RequestOutcome,
+};
+pub use ops::bulk::{
+    AbortFailure, CopyFailure, CopyPrefixBuilder, CopyPrefixResponse, DeleteBucketForceResponse,
+    DeleteFailure, DeletePrefixBuilder, DeletePrefixResponse, PrefixCopy, RestoreFailure,
+    RestorePrefixBuilder, RestorePrefixResponse, VersionedDeletion,
+};
+pub use ops::manifest::{
+    Manifest, ManifestBuilder, ManifestEntry, VerifyIssue, VerifyIssueKind, VerifyReport,
 };
-pub use error::{OssError, Result};
-pub use middleware::{Interceptor, InterceptorContext, RequestOutcome};
 pub use ops::paginator::{
     ListBucketsPaginator, ListBucketsPaginatorBuilder, ListObjectsV2Paginator,
-    ListObjectsV2PaginatorBuilder,
+    ListObjectsV2PaginatorBuilder, ResumeState,
 };
 pub use ops::transfer::{
-    TransferManager, TransferManagerBuilder, TransferUploadRequest, TransferUploadRequestBuilder,
-    TransferUploadResponse,
+    TransferDownloadRequest, TransferDownloadRequestBuilder, TransferDownloadResponse,
+    TransferEvent, TransferListener, TransferManager, TransferManagerBuilder, TransferTarget,
+    TransferUploadRequest, TransferUploadRequestBuilder, TransferUploadResponse,
+    TransferWriteResponse,
+};
+pub use ops::walk::{WalkEntry, Walker, WalkerBuilder};
+pub use progress::{
+    NoopProgressListener, ProgressListener, ProgressThrottle, ThrottledProgressListener,
+    TransferKind, TransferProgress,
+};
+pub use retry::{
+    DefaultRetryClassifier, RetryAttempt, RetryBudget, RetryBudgetBuilder, RetryClassifier,
+    RetryDecision,
 };
-pub use progress::{NoopProgressListener, ProgressListener, TransferKind, TransferProgress};
 pub use types::common::{
-    BucketAcl, BucketName, CorsHttpMethod, MetadataDirective, ObjectAcl, ObjectKey, Region,
-    ServerSideEncryption, StorageClass, VersioningStatus,
+    BucketAcl, BucketName, Callback, CorsHttpMethod, DataRedundancyType, MetadataDirective,
+    ObjectAcl, ObjectKey, Region, ServerSideEncryption, StorageClass, VersioningStatus,
 };
 pub use types::response::ObjectBody;
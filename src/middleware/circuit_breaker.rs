@@ -0,0 +1,433 @@
+//! Per-host circuit breaker.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::error::{OssError, Result};
+
+/// State of the circuit for one endpoint host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests flow normally.
+    Closed,
+    /// Requests are rejected with [`OssError::CircuitOpen`] until the cooldown ends.
+    Open,
+    /// The cooldown ended; a single probe request decides whether to close again.
+    HalfOpen,
+}
+
+#[derive(Debug)]
+enum HostCircuit {
+    Closed,
+    Open { until: Instant },
+    HalfOpen { probe_started: Instant },
+}
+
+#[derive(Debug)]
+struct HostState {
+    circuit: HostCircuit,
+    consecutive_failures: u32,
+    /// Outcomes of the most recent attempts, `true` for failures.
+    window: VecDeque<bool>,
+}
+
+impl HostState {
+    fn new() -> Self {
+        Self {
+            circuit: HostCircuit::Closed,
+            consecutive_failures: 0,
+            window: VecDeque::new(),
+        }
+    }
+
+    fn close(&mut self) {
+        self.circuit = HostCircuit::Closed;
+        self.consecutive_failures = 0;
+        self.window.clear();
+    }
+}
+
+/// Circuit breaker that short-circuits requests to an endpoint host during an
+/// outage instead of letting every caller retry into it.
+///
+/// Attempts that fail with a transport error or a 5xx status count as
+/// failures. The circuit of a host opens when either the consecutive failure
+/// threshold or the error-rate threshold over a sliding window is reached;
+/// requests then fail fast with [`OssError::CircuitOpen`]. After the cooldown
+/// one probe request is let through: success closes the circuit, failure
+/// opens it for another cooldown.
+///
+/// Register it with [`OssClient::circuit_breaker`](crate::OssClient::circuit_breaker).
+/// Share one instance between clients through `Arc` to pool their health data.
+///
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+/// use std::time::Duration;
+/// use rs_ali_oss::middleware::CircuitBreaker;
+///
+/// # fn example(client: rs_ali_oss::OssClient) -> rs_ali_oss::Result<()> {
+/// let breaker = CircuitBreaker::builder()
+///     .error_rate_threshold(0.5)
+///     .window_size(40)
+///     .cooldown(Duration::from_secs(10))
+///     .build()?;
+/// let client = client.circuit_breaker(Arc::new(breaker));
+/// # Ok(())
+/// # }
+/// ```
+pub struct CircuitBreaker {
+    failure_threshold: Option<u32>,
+    error_rate_threshold: Option<f64>,
+    window_size: usize,
+    minimum_requests: usize,
+    cooldown: Duration,
+    hosts: Mutex<HashMap<String, HostState>>,
+}
+
+impl CircuitBreaker {
+    /// Create a builder.
+    pub fn builder() -> CircuitBreakerBuilder {
+        CircuitBreakerBuilder::new()
+    }
+
+    /// Current state of the circuit for `host`.
+    pub fn state(&self, host: &str) -> CircuitState {
+        let hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        match hosts.get(host).map(|state| &state.circuit) {
+            None | Some(HostCircuit::Closed) => CircuitState::Closed,
+            Some(HostCircuit::Open { until }) if *until <= Instant::now() => CircuitState::HalfOpen,
+            Some(HostCircuit::Open { .. }) => CircuitState::Open,
+            Some(HostCircuit::HalfOpen { .. }) => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Close all circuits and forget the recorded outcomes.
+    pub fn reset(&self) {
+        self.hosts.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    /// Check whether an attempt to `host` may proceed.
+    pub(crate) fn acquire(&self, host: &str, now: Instant) -> Result<()> {
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(state) = hosts.get_mut(host) else {
+            return Ok(());
+        };
+        let retry_after = match state.circuit {
+            HostCircuit::Closed => return Ok(()),
+            HostCircuit::Open { until } if until <= now => {
+                state.circuit = HostCircuit::HalfOpen { probe_started: now };
+                return Ok(());
+            }
+            HostCircuit::Open { until } => until - now,
+            // A probe that never reported back (e.g. its future was dropped)
+            // must not keep the circuit half-open forever.
+            HostCircuit::HalfOpen { probe_started } if probe_started + self.cooldown <= now => {
+                state.circuit = HostCircuit::HalfOpen { probe_started: now };
+                return Ok(());
+            }
+            HostCircuit::HalfOpen { probe_started } => probe_started + self.cooldown - now,
+        };
+        Err(OssError::CircuitOpen {
+            host: host.to_string(),
+            retry_after,
+        })
+    }
+
+    /// Record the outcome of an attempt to `host`.
+    pub(crate) fn record(&self, host: &str, failure: bool, now: Instant) {
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        if !failure && !hosts.contains_key(host) {
+            return;
+        }
+        let state = hosts.entry(host.to_string()).or_insert_with(HostState::new);
+        match state.circuit {
+            HostCircuit::Open { .. } => {}
+            HostCircuit::HalfOpen { .. } if failure => {
+                tracing::warn!(host, "circuit breaker probe failed, reopening");
+                state.circuit = HostCircuit::Open {
+                    until: now + self.cooldown,
+                };
+            }
+            HostCircuit::HalfOpen { .. } => {
+                tracing::info!(host, "circuit breaker probe succeeded, closing");
+                state.close();
+            }
+            HostCircuit::Closed => {
+                state.consecutive_failures = if failure {
+                    state.consecutive_failures + 1
+                } else {
+                    0
+                };
+                if state.window.len() == self.window_size {
+                    state.window.pop_front();
+                }
+                state.window.push_back(failure);
+                if self.should_trip(state) {
+                    tracing::warn!(host, cooldown = ?self.cooldown, "circuit breaker opened");
+                    state.circuit = HostCircuit::Open {
+                        until: now + self.cooldown,
+                    };
+                    state.consecutive_failures = 0;
+                    state.window.clear();
+                }
+            }
+        }
+    }
+
+    fn should_trip(&self, state: &HostState) -> bool {
+        let consecutive = self
+            .failure_threshold
+            .is_some_and(|threshold| state.consecutive_failures >= threshold);
+        let rate = self.error_rate_threshold.is_some_and(|threshold| {
+            let total = state.window.len();
+            let failures = state.window.iter().filter(|f| **f).count();
+            total >= self.minimum_requests && failures as f64 / total as f64 >= threshold
+        });
+        consecutive || rate
+    }
+}
+
+impl fmt::Debug for CircuitBreaker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CircuitBreaker")
+            .field("failure_threshold", &self.failure_threshold)
+            .field("error_rate_threshold", &self.error_rate_threshold)
+            .field("window_size", &self.window_size)
+            .field("minimum_requests", &self.minimum_requests)
+            .field("cooldown", &self.cooldown)
+            .finish()
+    }
+}
+
+/// Builder for [`CircuitBreaker`].
+///
+/// Without any threshold, the circuit opens after 5 consecutive failures.
+#[derive(Debug, Default)]
+pub struct CircuitBreakerBuilder {
+    failure_threshold: Option<u32>,
+    error_rate_threshold: Option<f64>,
+    window_size: Option<usize>,
+    minimum_requests: Option<usize>,
+    cooldown: Option<Duration>,
+}
+
+impl CircuitBreakerBuilder {
+    /// Create a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open the circuit after this many consecutive failed attempts.
+    pub fn failure_threshold(mut self, failures: u32) -> Self {
+        self.failure_threshold = Some(failures);
+        self
+    }
+
+    /// Open the circuit once the fraction of failed attempts in the window
+    /// reaches this value (between 0 and 1).
+    pub fn error_rate_threshold(mut self, rate: f64) -> Self {
+        self.error_rate_threshold = Some(rate);
+        self
+    }
+
+    /// Number of most recent attempts the error rate is computed over (default: 20).
+    pub fn window_size(mut self, size: usize) -> Self {
+        self.window_size = Some(size);
+        self
+    }
+
+    /// Minimum attempts in the window before the error rate is evaluated
+    /// (default: half the window size).
+    pub fn minimum_requests(mut self, requests: usize) -> Self {
+        self.minimum_requests = Some(requests);
+        self
+    }
+
+    /// How long an open circuit rejects requests before probing (default: 30s).
+    pub fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = Some(cooldown);
+        self
+    }
+
+    /// Build the circuit breaker.
+    pub fn build(self) -> Result<CircuitBreaker> {
+        let invalid = |field: &str, reason: &str| OssError::InvalidParameter {
+            field: field.into(),
+            reason: reason.into(),
+        };
+        if self.failure_threshold == Some(0) {
+            return Err(invalid("failure_threshold", "must be at least 1"));
+        }
+        if let Some(rate) = self.error_rate_threshold
+            && !(rate > 0.0 && rate <= 1.0)
+        {
+            return Err(invalid("error_rate_threshold", "must be in (0, 1]"));
+        }
+        let window_size = self.window_size.unwrap_or(20);
+        if window_size == 0 {
+            return Err(invalid("window_size", "must be at least 1"));
+        }
+        let minimum_requests = self.minimum_requests.unwrap_or(window_size.div_ceil(2));
+        if minimum_requests == 0 || minimum_requests > window_size {
+            return Err(invalid(
+                "minimum_requests",
+                "must be between 1 and the window size",
+            ));
+        }
+        let cooldown = self.cooldown.unwrap_or(Duration::from_secs(30));
+        if cooldown.is_zero() {
+            return Err(invalid("cooldown", "must be greater than zero"));
+        }
+        let failure_threshold = match (self.failure_threshold, self.error_rate_threshold) {
+            (None, None) => Some(5),
+            (threshold, _) => threshold,
+        };
+        Ok(CircuitBreaker {
+            failure_threshold,
+            error_rate_threshold: self.error_rate_threshold,
+            window_size,
+            minimum_requests,
+            cooldown,
+            hosts: Mutex::new(HashMap::new()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOST: &str = "bucket.oss-cn-hangzhou.aliyuncs.com";
+
+    fn breaker(builder: CircuitBreakerBuilder) -> CircuitBreaker {
+        builder.cooldown(Duration::from_secs(10)).build().unwrap()
+    }
+
+    #[test]
+    fn opens_after_consecutive_failures() {
+        let cb = breaker(CircuitBreaker::builder().failure_threshold(3));
+        let now = Instant::now();
+        cb.record(HOST, true, now);
+        cb.record(HOST, true, now);
+        // A success resets the streak.
+        cb.record(HOST, false, now);
+        cb.record(HOST, true, now);
+        cb.record(HOST, true, now);
+        assert!(cb.acquire(HOST, now).is_ok());
+        cb.record(HOST, true, now);
+        assert_eq!(cb.state(HOST), CircuitState::Open);
+
+        match cb.acquire(HOST, now + Duration::from_secs(4)) {
+            Err(OssError::CircuitOpen { host, retry_after }) => {
+                assert_eq!(host, HOST);
+                assert_eq!(retry_after, Duration::from_secs(6));
+            }
+            other => panic!("expected CircuitOpen, got {other:?}"),
+        }
+        // Other hosts are unaffected.
+        assert!(cb.acquire("other.example.com", now).is_ok());
+    }
+
+    #[test]
+    fn opens_on_error_rate() {
+        let cb = breaker(
+            CircuitBreaker::builder()
+                .error_rate_threshold(0.5)
+                .window_size(4)
+                .minimum_requests(4),
+        );
+        let now = Instant::now();
+        cb.record(HOST, true, now);
+        cb.record(HOST, true, now);
+        cb.record(HOST, false, now);
+        // Not enough samples yet.
+        assert!(cb.acquire(HOST, now).is_ok());
+        cb.record(HOST, false, now);
+        assert_eq!(cb.state(HOST), CircuitState::Open);
+    }
+
+    #[test]
+    fn error_rate_uses_sliding_window() {
+        let cb = breaker(
+            CircuitBreaker::builder()
+                .error_rate_threshold(0.75)
+                .window_size(4)
+                .minimum_requests(4),
+        );
+        let now = Instant::now();
+        for failure in [true, true, false, false, false, true, true] {
+            cb.record(HOST, failure, now);
+        }
+        // Window is [false, false, true, true]: 50% < 75%.
+        assert_eq!(cb.state(HOST), CircuitState::Closed);
+        cb.record(HOST, true, now);
+        assert_eq!(cb.state(HOST), CircuitState::Open);
+    }
+
+    #[test]
+    fn half_open_probe_closes_or_reopens() {
+        let cb = breaker(CircuitBreaker::builder().failure_threshold(1));
+        let now = Instant::now();
+        cb.record(HOST, true, now);
+
+        let after = now + Duration::from_secs(10);
+        assert!(cb.acquire(HOST, after).is_ok());
+        // Only one probe at a time.
+        assert!(cb.acquire(HOST, after).is_err());
+        cb.record(HOST, true, after);
+        assert_eq!(cb.state(HOST), CircuitState::Open);
+        assert!(cb.acquire(HOST, after + Duration::from_secs(1)).is_err());
+
+        let later = after + Duration::from_secs(10);
+        assert!(cb.acquire(HOST, later).is_ok());
+        cb.record(HOST, false, later);
+        assert_eq!(cb.state(HOST), CircuitState::Closed);
+        assert!(cb.acquire(HOST, later).is_ok());
+    }
+
+    #[test]
+    fn abandoned_probe_is_replaced_after_cooldown() {
+        let cb = breaker(CircuitBreaker::builder().failure_threshold(1));
+        let now = Instant::now();
+        cb.record(HOST, true, now);
+        let probe = now + Duration::from_secs(10);
+        assert!(cb.acquire(HOST, probe).is_ok());
+        assert!(cb.acquire(HOST, probe + Duration::from_secs(9)).is_err());
+        assert!(cb.acquire(HOST, probe + Duration::from_secs(10)).is_ok());
+    }
+
+    #[test]
+    fn default_trips_after_five_failures() {
+        let cb = CircuitBreaker::builder().build().unwrap();
+        let now = Instant::now();
+        for _ in 0..4 {
+            cb.record(HOST, true, now);
+        }
+        assert_eq!(cb.state(HOST), CircuitState::Closed);
+        cb.record(HOST, true, now);
+        assert_eq!(cb.state(HOST), CircuitState::Open);
+        cb.reset();
+        assert_eq!(cb.state(HOST), CircuitState::Closed);
+    }
+
+    #[test]
+    fn builder_validates() {
+        let invalid = |builder: CircuitBreakerBuilder| {
+            matches!(builder.build(), Err(OssError::InvalidParameter { .. }))
+        };
+        assert!(invalid(CircuitBreaker::builder().failure_threshold(0)));
+        assert!(invalid(CircuitBreaker::builder().error_rate_threshold(0.0)));
+        assert!(invalid(CircuitBreaker::builder().error_rate_threshold(1.5)));
+        assert!(invalid(
+            CircuitBreaker::builder().error_rate_threshold(f64::NAN)
+        ));
+        assert!(invalid(CircuitBreaker::builder().window_size(0)));
+        assert!(invalid(
+            CircuitBreaker::builder().window_size(5).minimum_requests(6)
+        ));
+        assert!(invalid(CircuitBreaker::builder().cooldown(Duration::ZERO)));
+    }
+}
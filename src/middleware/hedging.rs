@@ -0,0 +1,252 @@
+//! Hedged requests for latency-sensitive reads.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::error::{OssError, Result};
+
+/// Hedging policy for idempotent `GET`/`HEAD` requests.
+///
+/// When a read has not completed after the configured latency percentile of
+/// recent reads, a second, identical request is sent; whichever succeeds first
+/// is returned and the other is cancelled. This trims the tail latency caused
+/// by rare slow connections at the cost of a few extra requests. Until enough
+/// latencies have been observed, the initial delay is used.
+///
+/// Register it with [`OssClient::hedging`](crate::OssClient::hedging). Requests
+/// with a body are never hedged.
+///
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+/// use std::time::Duration;
+/// use rs_ali_oss::middleware::Hedging;
+///
+/// # fn example(client: rs_ali_oss::OssClient) -> rs_ali_oss::Result<()> {
+/// let hedging = Hedging::builder()
+///     .percentile(0.95)
+///     .initial_delay(Duration::from_millis(200))
+///     .build()?;
+/// let client = client.hedging(Arc::new(hedging));
+/// # Ok(())
+/// # }
+/// ```
+pub struct Hedging {
+    percentile: f64,
+    window_size: usize,
+    minimum_samples: usize,
+    initial_delay: Duration,
+    min_delay: Duration,
+    latencies: Mutex<VecDeque<Duration>>,
+    hedged: AtomicU64,
+}
+
+impl Hedging {
+    /// Create a builder.
+    pub fn builder() -> HedgingBuilder {
+        HedgingBuilder::new()
+    }
+
+    /// How long a read may run before a hedge request is sent.
+    pub fn delay(&self) -> Duration {
+        let latencies = self.latencies.lock().unwrap_or_else(|e| e.into_inner());
+        if latencies.len() < self.minimum_samples {
+            return self.initial_delay;
+        }
+        let mut sorted: Vec<Duration> = latencies.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = (self.percentile * sorted.len() as f64).ceil() as usize;
+        let index = rank.clamp(1, sorted.len()) - 1;
+        sorted[index].max(self.min_delay)
+    }
+
+    /// Number of hedge requests sent so far.
+    pub fn hedged_requests(&self) -> u64 {
+        self.hedged.load(Ordering::Relaxed)
+    }
+
+    /// Record the latency of a completed read.
+    pub(crate) fn record(&self, latency: Duration) {
+        let mut latencies = self.latencies.lock().unwrap_or_else(|e| e.into_inner());
+        if latencies.len() == self.window_size {
+            latencies.pop_front();
+        }
+        latencies.push_back(latency);
+    }
+
+    /// Count a hedge request.
+    pub(crate) fn record_hedge(&self) {
+        self.hedged.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl fmt::Debug for Hedging {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hedging")
+            .field("percentile", &self.percentile)
+            .field("window_size", &self.window_size)
+            .field("minimum_samples", &self.minimum_samples)
+            .field("initial_delay", &self.initial_delay)
+            .field("min_delay", &self.min_delay)
+            .field("hedged_requests", &self.hedged_requests())
+            .finish()
+    }
+}
+
+/// Builder for [`Hedging`].
+#[derive(Debug, Default)]
+pub struct HedgingBuilder {
+    percentile: Option<f64>,
+    window_size: Option<usize>,
+    minimum_samples: Option<usize>,
+    initial_delay: Option<Duration>,
+    min_delay: Option<Duration>,
+}
+
+impl HedgingBuilder {
+    /// Create a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Latency percentile of recent reads after which a hedge is sent, between
+    /// 0 and 1 (default: 0.95).
+    pub fn percentile(mut self, percentile: f64) -> Self {
+        self.percentile = Some(percentile);
+        self
+    }
+
+    /// Number of most recent read latencies the percentile is computed over
+    /// (default: 100).
+    pub fn window_size(mut self, size: usize) -> Self {
+        self.window_size = Some(size);
+        self
+    }
+
+    /// Latencies to observe before the percentile is used (default: 20, or
+    /// the window size if smaller).
+    pub fn minimum_samples(mut self, samples: usize) -> Self {
+        self.minimum_samples = Some(samples);
+        self
+    }
+
+    /// Hedge delay used until enough latencies have been observed
+    /// (default: 100ms).
+    pub fn initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = Some(delay);
+        self
+    }
+
+    /// Lower bound for the hedge delay, so a run of fast reads cannot make
+    /// every read hedged (default: 10ms).
+    pub fn min_delay(mut self, delay: Duration) -> Self {
+        self.min_delay = Some(delay);
+        self
+    }
+
+    /// Build the hedging policy.
+    pub fn build(self) -> Result<Hedging> {
+        let invalid = |field: &str, reason: &str| OssError::InvalidParameter {
+            field: field.into(),
+            reason: reason.into(),
+        };
+        let percentile = self.percentile.unwrap_or(0.95);
+        if !(percentile > 0.0 && percentile < 1.0) {
+            return Err(invalid("percentile", "must be in (0, 1)"));
+        }
+        let window_size = self.window_size.unwrap_or(100);
+        if window_size == 0 {
+            return Err(invalid("window_size", "must be at least 1"));
+        }
+        let minimum_samples = self.minimum_samples.unwrap_or(window_size.min(20));
+        if minimum_samples == 0 || minimum_samples > window_size {
+            return Err(invalid(
+                "minimum_samples",
+                "must be between 1 and the window size",
+            ));
+        }
+        Ok(Hedging {
+            percentile,
+            window_size,
+            minimum_samples,
+            initial_delay: self.initial_delay.unwrap_or(Duration::from_millis(100)),
+            min_delay: self.min_delay.unwrap_or(Duration::from_millis(10)),
+            latencies: Mutex::new(VecDeque::with_capacity(window_size)),
+            hedged: AtomicU64::new(0),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn uses_initial_delay_until_enough_samples() {
+        let hedging = Hedging::builder()
+            .minimum_samples(3)
+            .initial_delay(ms(250))
+            .build()
+            .unwrap();
+        hedging.record(ms(20));
+        hedging.record(ms(30));
+        assert_eq!(hedging.delay(), ms(250));
+        hedging.record(ms(40));
+        assert_eq!(hedging.delay(), ms(40));
+    }
+
+    #[test]
+    fn delay_tracks_percentile_of_window() {
+        let hedging = Hedging::builder()
+            .percentile(0.9)
+            .window_size(10)
+            .minimum_samples(10)
+            .build()
+            .unwrap();
+        for latency in 1..=10 {
+            hedging.record(ms(latency * 100));
+        }
+        assert_eq!(hedging.delay(), ms(900));
+        // Old samples fall out of the window.
+        for _ in 0..10 {
+            hedging.record(ms(50));
+        }
+        assert_eq!(hedging.delay(), ms(50));
+    }
+
+    #[test]
+    fn delay_is_bounded_below() {
+        let hedging = Hedging::builder()
+            .minimum_samples(1)
+            .min_delay(ms(25))
+            .build()
+            .unwrap();
+        hedging.record(ms(1));
+        assert_eq!(hedging.delay(), ms(25));
+    }
+
+    #[test]
+    fn builder_validates() {
+        for percentile in [0.0, 1.0, f64::NAN] {
+            assert!(matches!(
+                Hedging::builder().percentile(percentile).build(),
+                Err(OssError::InvalidParameter { .. })
+            ));
+        }
+        assert!(matches!(
+            Hedging::builder().window_size(0).build(),
+            Err(OssError::InvalidParameter { .. })
+        ));
+        assert!(matches!(
+            Hedging::builder().window_size(5).minimum_samples(6).build(),
+            Err(OssError::InvalidParameter { .. })
+        ));
+    }
+}
@@ -0,0 +1,451 @@
+//! Request metrics aggregated per operation.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use super::{Interceptor, InterceptorContext, RequestOutcome};
+
+/// Upper bounds of the latency histogram buckets.
+const LATENCY_BUCKETS: &[Duration] = &[
+    Duration::from_millis(5),
+    Duration::from_millis(10),
+    Duration::from_millis(25),
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_millis(2500),
+    Duration::from_secs(5),
+    Duration::from_secs(10),
+];
+
+/// Query parameters that identify the OSS operation of a request, in priority order.
+const OPERATION_SUBRESOURCES: &[&str] = &[
+    "uploads",
+    "uploadId",
+    "append",
+    "delete",
+    "restore",
+    "symlink",
+    "acl",
+    "tagging",
+    "cors",
+    "lifecycle",
+    "location",
+    "logging",
+    "policy",
+    "referer",
+    "website",
+    "versioning",
+    "versions",
+    "encryption",
+    "bucketInfo",
+    "stat",
+    "objectMeta",
+    "x-oss-process",
+];
+
+/// Name of the operation a request belongs to: the HTTP method plus the
+/// sub-resource query parameter that selects the OSS API, if any
+/// (e.g. `GET`, `PUT?uploadId`, `POST?delete`).
+pub fn operation_name(method: &str, url: &str) -> String {
+    let subresource = url::Url::parse(url).ok().and_then(|url| {
+        let keys: Vec<String> = url.query_pairs().map(|(k, _)| k.into_owned()).collect();
+        OPERATION_SUBRESOURCES
+            .iter()
+            .find(|name| keys.iter().any(|k| k == *name))
+            .copied()
+    });
+    match subresource {
+        Some(subresource) => format!("{method}?{subresource}"),
+        None => method.to_string(),
+    }
+}
+
+/// Latency distribution of request attempts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LatencyHistogram {
+    /// Cumulative counts: the number of attempts that took at most each bound.
+    pub buckets: Vec<(Duration, u64)>,
+    /// Total number of recorded attempts.
+    pub count: u64,
+    /// Sum of all recorded durations.
+    pub sum: Duration,
+}
+
+impl LatencyHistogram {
+    fn record(&mut self, duration: Duration) {
+        if self.buckets.is_empty() {
+            self.buckets = LATENCY_BUCKETS.iter().map(|b| (*b, 0)).collect();
+        }
+        for (bound, count) in &mut self.buckets {
+            if duration <= *bound {
+                *count += 1;
+            }
+        }
+        self.count += 1;
+        self.sum += duration;
+    }
+
+    /// Mean latency, or `None` if nothing was recorded.
+    pub fn mean(&self) -> Option<Duration> {
+        u32::try_from(self.count)
+            .ok()
+            .filter(|count| *count > 0)
+            .map(|count| self.sum / count)
+    }
+}
+
+/// Aggregated metrics of a single operation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OperationMetrics {
+    /// Logical requests (first attempts).
+    pub requests: u64,
+    /// All attempts, including retries.
+    pub attempts: u64,
+    /// Retry attempts.
+    pub retries: u64,
+    /// Attempts that failed with a non-2xx status or a transport error.
+    pub errors: u64,
+    /// Attempts that failed without receiving a response.
+    pub transport_errors: u64,
+    /// Attempt counts by HTTP status code.
+    pub status_codes: BTreeMap<u16, u64>,
+    /// Request body bytes sent (attempts with an unknown size are not counted).
+    pub bytes_sent: u64,
+    /// Response body bytes received, as reported in
+    /// [`RequestOutcome::bytes_received`].
+    pub bytes_received: u64,
+    /// Attempt latency distribution.
+    pub latency: LatencyHistogram,
+}
+
+impl OperationMetrics {
+    /// Fraction of attempts that failed (0.0 when there were none).
+    pub fn error_rate(&self) -> f64 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.attempts as f64
+        }
+    }
+}
+
+/// Point-in-time copy of the metrics collected by a [`MetricsInterceptor`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MetricsSnapshot {
+    /// Metrics keyed by [`operation_name`].
+    pub operations: BTreeMap<String, OperationMetrics>,
+}
+
+impl MetricsSnapshot {
+    /// Metrics summed over all operations.
+    pub fn total(&self) -> OperationMetrics {
+        let mut total = OperationMetrics::default();
+        for metrics in self.operations.values() {
+            total.requests += metrics.requests;
+            total.attempts += metrics.attempts;
+            total.retries += metrics.retries;
+            total.errors += metrics.errors;
+            total.transport_errors += metrics.transport_errors;
+            total.bytes_sent += metrics.bytes_sent;
+            total.bytes_received += metrics.bytes_received;
+            for (status, count) in &metrics.status_codes {
+                *total.status_codes.entry(*status).or_default() += count;
+            }
+            if total.latency.buckets.is_empty() {
+                total.latency.buckets = LATENCY_BUCKETS.iter().map(|b| (*b, 0)).collect();
+            }
+            for (i, (_, count)) in metrics.latency.buckets.iter().enumerate() {
+                total.latency.buckets[i].1 += count;
+            }
+            total.latency.count += metrics.latency.count;
+            total.latency.sum += metrics.latency.sum;
+        }
+        total
+    }
+
+    /// Render the snapshot in the Prometheus text exposition format.
+    #[cfg(feature = "prometheus")]
+    pub fn to_prometheus(&self) -> String {
+        use std::fmt::Write;
+
+        let mut out = String::new();
+        type Counter = (&'static str, &'static str, fn(&OperationMetrics) -> u64);
+        let counters: [Counter; 7] = [
+            ("oss_requests_total", "Logical OSS requests.", |m| {
+                m.requests
+            }),
+            (
+                "oss_request_attempts_total",
+                "OSS request attempts, including retries.",
+                |m| m.attempts,
+            ),
+            ("oss_request_retries_total", "OSS request retries.", |m| {
+                m.retries
+            }),
+            (
+                "oss_request_errors_total",
+                "Failed OSS request attempts.",
+                |m| m.errors,
+            ),
+            (
+                "oss_request_transport_errors_total",
+                "OSS request attempts that received no response.",
+                |m| m.transport_errors,
+            ),
+            (
+                "oss_request_sent_bytes_total",
+                "OSS request body bytes sent.",
+                |m| m.bytes_sent,
+            ),
+            (
+                "oss_response_received_bytes_total",
+                "OSS response body bytes received.",
+                |m| m.bytes_received,
+            ),
+        ];
+        for (name, help, value) in counters {
+            let _ = writeln!(out, "# HELP {name} {help}\n# TYPE {name} counter");
+            for (operation, metrics) in &self.operations {
+                let _ = writeln!(
+                    out,
+                    "{name}{{operation=\"{operation}\"}} {}",
+                    value(metrics)
+                );
+            }
+        }
+
+        let name = "oss_request_duration_seconds";
+        let _ = writeln!(
+            out,
+            "# HELP {name} OSS request attempt latency.\n# TYPE {name} histogram"
+        );
+        for (operation, metrics) in &self.operations {
+            for (bound, count) in &metrics.latency.buckets {
+                let _ = writeln!(
+                    out,
+                    "{name}_bucket{{operation=\"{operation}\",le=\"{}\"}} {count}",
+                    bound.as_secs_f64()
+                );
+            }
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{operation=\"{operation}\",le=\"+Inf\"}} {}",
+                metrics.latency.count
+            );
+            let _ = writeln!(
+                out,
+                "{name}_sum{{operation=\"{operation}\"}} {}",
+                metrics.latency.sum.as_secs_f64()
+            );
+            let _ = writeln!(
+                out,
+                "{name}_count{{operation=\"{operation}\"}} {}",
+                metrics.latency.count
+            );
+        }
+        out
+    }
+}
+
+/// Interceptor that aggregates request counts, error rates, retries and
+/// latency histograms per operation.
+///
+/// Register one instance (shared through `Arc`) and read it with
+/// [`snapshot`](Self::snapshot). With the `prometheus` feature,
+/// [`MetricsSnapshot::to_prometheus`] renders the Prometheus text format.
+///
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+/// use rs_ali_oss::middleware::MetricsInterceptor;
+///
+/// # fn example(client: rs_ali_oss::OssClient) {
+/// let metrics = Arc::new(MetricsInterceptor::new());
+/// let client = client.interceptor(metrics.clone());
+/// // ... issue requests ...
+/// let total = metrics.snapshot().total();
+/// println!("{} requests, {:.1}% errors", total.requests, total.error_rate() * 100.0);
+/// # }
+/// ```
+#[derive(Default)]
+pub struct MetricsInterceptor {
+    operations: Mutex<BTreeMap<String, OperationMetrics>>,
+}
+
+impl MetricsInterceptor {
+    /// Create an empty metrics interceptor.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copy the metrics collected so far.
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let operations = self.operations.lock().unwrap_or_else(|e| e.into_inner());
+        MetricsSnapshot {
+            operations: operations.clone(),
+        }
+    }
+
+    /// Discard all collected metrics.
+    pub fn reset(&self) {
+        self.operations
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+impl Interceptor for MetricsInterceptor {
+    fn name(&self) -> &str {
+        "metrics"
+    }
+
+    fn after_request(&self, ctx: &InterceptorContext, outcome: &RequestOutcome) {
+        let operation = operation_name(&ctx.method, &ctx.url);
+        let mut operations = self.operations.lock().unwrap_or_else(|e| e.into_inner());
+        let metrics = operations.entry(operation).or_default();
+        metrics.attempts += 1;
+        if ctx.attempt == 0 {
+            metrics.requests += 1;
+        } else {
+            metrics.retries += 1;
+        }
+        if !outcome.success {
+            metrics.errors += 1;
+        }
+        match outcome.status {
+            Some(status) => *metrics.status_codes.entry(status).or_default() += 1,
+            None => metrics.transport_errors += 1,
+        }
+        metrics.bytes_sent += outcome.bytes_sent.unwrap_or(0);
+        metrics.bytes_received += outcome.bytes_received.unwrap_or(0);
+        metrics.latency.record(outcome.duration);
+    }
+}
+
+impl fmt::Debug for MetricsInterceptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operations = self.operations.lock().unwrap_or_else(|e| e.into_inner());
+        f.debug_struct("MetricsInterceptor")
+            .field("operations", &operations.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(method: &str, url: &str, attempt: u32) -> InterceptorContext {
+        InterceptorContext {
+            method: method.to_string(),
+            url: url.to_string(),
+            attempt,
+            signing: None,
+        }
+    }
+
+    fn outcome(status: Option<u16>, millis: u64) -> RequestOutcome {
+        RequestOutcome {
+            status,
+            duration: Duration::from_millis(millis),
+            success: status.is_some_and(|s| (200..300).contains(&s)),
+            error: status.is_none().then(|| "connection reset".to_string()),
+            request_id: None,
+            error_code: None,
+            headers: Default::default(),
+            bytes_sent: Some(100),
+            bytes_received: status.map(|_| 10),
+        }
+    }
+
+    #[test]
+    fn operation_name_uses_subresource() {
+        let base = "https://b.oss-cn-hangzhou.aliyuncs.com/key";
+        assert_eq!(operation_name("GET", base), "GET");
+        assert_eq!(
+            operation_name("PUT", &format!("{base}?partNumber=1&uploadId=abc")),
+            "PUT?uploadId"
+        );
+        assert_eq!(
+            operation_name("POST", &format!("{base}?uploads")),
+            "POST?uploads"
+        );
+        assert_eq!(operation_name("GET", &format!("{base}?acl")), "GET?acl");
+        assert_eq!(operation_name("GET", &format!("{base}?max-keys=5")), "GET");
+    }
+
+    #[test]
+    fn records_requests_retries_and_errors() {
+        let metrics = MetricsInterceptor::new();
+        let url = "https://b.oss-cn-hangzhou.aliyuncs.com/key";
+        metrics.after_request(&ctx("GET", url, 0), &outcome(Some(503), 20));
+        metrics.after_request(&ctx("GET", url, 1), &outcome(None, 5));
+        metrics.after_request(&ctx("GET", url, 2), &outcome(Some(200), 40));
+        metrics.after_request(&ctx("PUT", url, 0), &outcome(Some(200), 3000));
+
+        let snapshot = metrics.snapshot();
+        let get = &snapshot.operations["GET"];
+        assert_eq!(get.requests, 1);
+        assert_eq!(get.attempts, 3);
+        assert_eq!(get.retries, 2);
+        assert_eq!(get.errors, 2);
+        assert_eq!(get.transport_errors, 1);
+        assert_eq!(get.status_codes[&503], 1);
+        assert_eq!(get.status_codes[&200], 1);
+        assert_eq!(get.bytes_sent, 300);
+        assert_eq!(get.bytes_received, 20);
+        assert!((get.error_rate() - 2.0 / 3.0).abs() < f64::EPSILON);
+        assert_eq!(get.latency.count, 3);
+        assert_eq!(get.latency.sum, Duration::from_millis(65));
+        assert_eq!(get.latency.mean(), Some(Duration::from_millis(65) / 3));
+        // 5ms bucket holds one attempt; 50ms bucket is cumulative.
+        assert_eq!(get.latency.buckets[0], (Duration::from_millis(5), 1));
+        assert_eq!(get.latency.buckets[3], (Duration::from_millis(50), 3));
+
+        let total = snapshot.total();
+        assert_eq!(total.requests, 2);
+        assert_eq!(total.attempts, 4);
+        assert_eq!(total.bytes_sent, 400);
+        assert_eq!(total.latency.count, 4);
+        assert_eq!(total.latency.buckets[8], (Duration::from_millis(2500), 3));
+    }
+
+    #[test]
+    fn reset_clears_metrics() {
+        let metrics = MetricsInterceptor::new();
+        metrics.after_request(&ctx("GET", "https://e.com/k", 0), &outcome(Some(200), 1));
+        metrics.reset();
+        assert!(metrics.snapshot().operations.is_empty());
+        assert_eq!(metrics.snapshot().total().error_rate(), 0.0);
+    }
+
+    #[cfg(feature = "prometheus")]
+    #[test]
+    fn renders_prometheus_text() {
+        let metrics = MetricsInterceptor::new();
+        metrics.after_request(
+            &ctx("GET", "https://e.com/k?acl", 0),
+            &outcome(Some(200), 30),
+        );
+        let text = metrics.snapshot().to_prometheus();
+        assert!(text.contains("# TYPE oss_requests_total counter"));
+        assert!(text.contains("oss_requests_total{operation=\"GET?acl\"} 1"));
+        assert!(
+            text.contains(
+                "oss_request_duration_seconds_bucket{operation=\"GET?acl\",le=\"0.05\"} 1"
+            )
+        );
+        assert!(
+            text.contains(
+                "oss_request_duration_seconds_bucket{operation=\"GET?acl\",le=\"0.025\"} 0"
+            )
+        );
+        assert!(text.contains("oss_request_duration_seconds_count{operation=\"GET?acl\"} 1"));
+    }
+}
@@ -0,0 +1,514 @@
+//! HTTP middleware/interceptor abstraction for request lifecycle hooks.
+//!
+//! Interceptors can observe and optionally modify requests before they are sent
+//! and responses after they are received. Use cases include logging, metrics,
+//! rate limiting, and custom header injection.
+
+mod circuit_breaker;
+mod hedging;
+mod metrics;
+mod rate_limit;
+#[cfg(feature = "test-util")]
+mod recording;
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::future::BoxFuture;
+use reqwest::header::HeaderMap;
+
+use crate::auth::SigningDetails;
+
+pub use circuit_breaker::{CircuitBreaker, CircuitBreakerBuilder, CircuitState};
+pub use hedging::{Hedging, HedgingBuilder};
+pub use metrics::{
+    LatencyHistogram, MetricsInterceptor, MetricsSnapshot, OperationMetrics, operation_name,
+};
+pub use rate_limit::{OperationClass, RateLimitInterceptor, RateLimitInterceptorBuilder};
+#[cfg(feature = "test-util")]
+pub use recording::{Interaction, Recorder, Replayer};
+
+/// Context passed to interceptors for each request attempt.
+#[derive(Debug)]
+pub struct InterceptorContext {
+    /// HTTP method (e.g., "GET", "PUT").
+    pub method: String,
+    /// Full request URL.
+    pub url: String,
+    /// Zero-based retry attempt number.
+    pub attempt: u32,
+    /// Canonical request and string-to-sign of this attempt, present only when
+    /// [`ClientBuilder::expose_signing_details`](crate::config::ClientBuilder::expose_signing_details)
+    /// is enabled.
+    pub signing: Option<SigningDetails>,
+}
+
+/// Result of a completed HTTP request, passed to `after_request`.
+#[derive(Debug)]
+pub struct RequestOutcome {
+    /// HTTP status code, if a response was received.
+    pub status: Option<u16>,
+    /// Wall-clock duration of the HTTP round-trip.
+    pub duration: Duration,
+    /// Whether the request succeeded (2xx status).
+    pub success: bool,
+    /// Error message, if the request failed.
+    pub error: Option<String>,
+    /// OSS request ID (`x-oss-request-id`, or the `RequestId` of the error
+    /// body), to quote in support tickets.
+    pub request_id: Option<String>,
+    /// OSS error code (e.g. `NoSuchKey`), if the response was an OSS error.
+    pub error_code: Option<String>,
+    /// Response headers; empty if no response was received.
+    pub headers: HeaderMap,
+    /// Request body bytes sent, if known. `None` for transport errors, where
+    /// the body may have been sent only partially.
+    pub bytes_sent: Option<u64>,
+    /// Response body bytes received. For successful responses the body is
+    /// streamed to the caller after this callback, so this is the
+    /// `Content-Length` (`None` for chunked responses); for error responses it
+    /// is the size of the error body that was read.
+    pub bytes_received: Option<u64>,
+}
+
+/// Trait for intercepting HTTP request/response lifecycle events.
+///
+/// Implement this trait to add cross-cutting concerns such as logging,
+/// metrics collection, rate limiting, or custom header injection.
+///
+/// # Examples
+/// ```
+/// use rs_ali_oss::middleware::{Interceptor, InterceptorContext, RequestOutcome};
+///
+/// struct MetricsInterceptor;
+///
+/// impl Interceptor for MetricsInterceptor {
+///     fn name(&self) -> &str { "metrics" }
+///
+///     fn before_request(&self, ctx: &InterceptorContext) -> Result<(), String> {
+///         println!("[{}] {} {}", ctx.attempt, ctx.method, ctx.url);
+///         Ok(())
+///     }
+///
+///     fn after_request(&self, ctx: &InterceptorContext, outcome: &RequestOutcome) {
+///         println!(
+///             "[{}] {} {} -> {:?} in {:?}",
+///             ctx.attempt, ctx.method, ctx.url, outcome.status, outcome.duration
+///         );
+///     }
+/// }
+/// ```
+pub trait Interceptor: Send + Sync {
+    /// Human-readable name for this interceptor (used in logging).
+    fn name(&self) -> &str;
+
+    /// Called before each attempt, ahead of [`before_request`](Self::before_request),
+    /// to delay it without blocking the async runtime.
+    ///
+    /// The client waits for the longest delay returned by any interceptor.
+    /// The default implementation never delays.
+    fn throttle(&self, _ctx: &InterceptorContext) -> Duration {
+        Duration::ZERO
+    }
+
+    /// Called before each HTTP request attempt (including retries).
+    ///
+    /// Return `Err(reason)` to abort the request with an authentication error.
+    /// Return `Ok(())` to proceed normally.
+    fn before_request(&self, _ctx: &InterceptorContext) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Called after each HTTP request attempt completes (success or failure).
+    fn after_request(&self, _ctx: &InterceptorContext, _outcome: &RequestOutcome) {}
+
+    /// Async variant of [`before_request`](Self::before_request); this is what
+    /// the client calls.
+    ///
+    /// Override it instead of the sync hook when the interceptor needs to
+    /// await (e.g. a distributed rate limiter or a remote policy check). The
+    /// default implementation calls [`before_request`](Self::before_request)
+    /// inline.
+    fn before_request_async<'a>(
+        &'a self,
+        ctx: &'a InterceptorContext,
+    ) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(std::future::ready(self.before_request(ctx)))
+    }
+
+    /// Async variant of [`after_request`](Self::after_request); this is what
+    /// the client calls.
+    ///
+    /// Override it instead of the sync hook to await, e.g., an async log sink.
+    /// The request's result is returned only after it completes. The default
+    /// implementation calls [`after_request`](Self::after_request) inline.
+    fn after_request_async<'a>(
+        &'a self,
+        ctx: &'a InterceptorContext,
+        outcome: &'a RequestOutcome,
+    ) -> BoxFuture<'a, ()> {
+        self.after_request(ctx, outcome);
+        Box::pin(std::future::ready(()))
+    }
+
+    /// Send the signed request, wrapping the rest of the chain.
+    ///
+    /// Interceptors run in registration order; each one passes the request
+    /// on with [`Next::run`], or answers it without calling `next` at all
+    /// (e.g. to replay a recorded response). Errors are treated like
+    /// transport errors and may be retried. The default implementation
+    /// passes the request on unchanged.
+    fn send<'a>(
+        &'a self,
+        request: reqwest::Request,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, crate::error::Result<reqwest::Response>> {
+        next.run(request)
+    }
+}
+
+/// The remainder of the interceptor chain, passed to [`Interceptor::send`].
+pub struct Next<'a> {
+    client: &'a reqwest::Client,
+    rest: &'a [Arc<dyn Interceptor>],
+}
+
+impl<'a> Next<'a> {
+    /// Pass the request to the next interceptor, or send it over the network
+    /// if this is the last one.
+    pub fn run(
+        self,
+        request: reqwest::Request,
+    ) -> BoxFuture<'a, crate::error::Result<reqwest::Response>> {
+        match self.rest.split_first() {
+            Some((interceptor, rest)) => interceptor.send(
+                request,
+                Next {
+                    client: self.client,
+                    rest,
+                },
+            ),
+            None => Box::pin(async move { Ok(self.client.execute(request).await?) }),
+        }
+    }
+}
+
+impl fmt::Debug for Next<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Next")
+            .field("remaining", &self.rest.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Debug for dyn Interceptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Interceptor({})", self.name())
+    }
+}
+
+/// An ordered collection of interceptors applied to every request.
+///
+/// Interceptors are kept sorted by priority, lowest first; interceptors with
+/// equal priority run in registration order.
+#[derive(Clone, Default)]
+pub(crate) struct InterceptorChain {
+    interceptors: Vec<Arc<dyn Interceptor>>,
+    /// Priority of each entry in `interceptors`, at the same index.
+    priorities: Vec<i32>,
+}
+
+impl fmt::Debug for InterceptorChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.interceptors.iter().map(|i| i.name()))
+            .finish()
+    }
+}
+
+impl InterceptorChain {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn push(&mut self, interceptor: Arc<dyn Interceptor>) {
+        self.insert(interceptor, 0);
+    }
+
+    /// Add `interceptor` after every interceptor whose priority is less than
+    /// or equal to `priority`.
+    pub(crate) fn insert(&mut self, interceptor: Arc<dyn Interceptor>, priority: i32) {
+        let index = self.priorities.partition_point(|&p| p <= priority);
+        self.insert_at(index, interceptor, priority);
+    }
+
+    /// Remove every interceptor named `name`, returning how many were removed.
+    pub(crate) fn remove(&mut self, name: &str) -> usize {
+        let before = self.interceptors.len();
+        let mut index = 0;
+        while index < self.interceptors.len() {
+            if self.interceptors[index].name() == name {
+                self.interceptors.remove(index);
+                self.priorities.remove(index);
+            } else {
+                index += 1;
+            }
+        }
+        before - self.interceptors.len()
+    }
+
+    /// Put `interceptor` in the place of the first interceptor named `name`,
+    /// keeping its position and priority, and remove any others of that name.
+    ///
+    /// Returns `false`, leaving the chain unchanged, if none is named `name`.
+    pub(crate) fn replace(&mut self, name: &str, interceptor: Arc<dyn Interceptor>) -> bool {
+        let Some(index) = self.interceptors.iter().position(|i| i.name() == name) else {
+            return false;
+        };
+        let priority = self.priorities[index];
+        self.remove(name);
+        self.insert_at(index, interceptor, priority);
+        true
+    }
+
+    fn insert_at(&mut self, index: usize, interceptor: Arc<dyn Interceptor>, priority: i32) {
+        self.interceptors.insert(index, interceptor);
+        self.priorities.insert(index, priority);
+    }
+
+    pub(crate) fn names(&self) -> Vec<&str> {
+        self.interceptors.iter().map(|i| i.name()).collect()
+    }
+
+    pub(crate) fn throttle(&self, ctx: &InterceptorContext) -> Duration {
+        self.interceptors
+            .iter()
+            .map(|interceptor| interceptor.throttle(ctx))
+            .max()
+            .unwrap_or_default()
+    }
+
+    pub(crate) async fn before_request(&self, ctx: &InterceptorContext) -> Result<(), String> {
+        for interceptor in &self.interceptors {
+            interceptor.before_request_async(ctx).await?;
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn after_request(&self, ctx: &InterceptorContext, outcome: &RequestOutcome) {
+        for interceptor in &self.interceptors {
+            interceptor.after_request_async(ctx, outcome).await;
+        }
+    }
+
+    pub(crate) async fn send(
+        &self,
+        client: &reqwest::Client,
+        request: reqwest::Request,
+    ) -> crate::error::Result<reqwest::Response> {
+        Next {
+            client,
+            rest: &self.interceptors,
+        }
+        .run(request)
+        .await
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.interceptors.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    struct CountingInterceptor {
+        before_count: AtomicU32,
+        after_count: AtomicU32,
+    }
+
+    impl CountingInterceptor {
+        fn new() -> Self {
+            Self {
+                before_count: AtomicU32::new(0),
+                after_count: AtomicU32::new(0),
+            }
+        }
+    }
+
+    impl Interceptor for CountingInterceptor {
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        fn before_request(&self, _ctx: &InterceptorContext) -> Result<(), String> {
+            self.before_count.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn after_request(&self, _ctx: &InterceptorContext, _outcome: &RequestOutcome) {
+            self.after_count.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    struct BlockingInterceptor;
+
+    impl Interceptor for BlockingInterceptor {
+        fn name(&self) -> &str {
+            "blocking"
+        }
+
+        fn before_request(&self, _ctx: &InterceptorContext) -> Result<(), String> {
+            Err("rate limited".to_string())
+        }
+    }
+
+    fn sample_context() -> InterceptorContext {
+        InterceptorContext {
+            method: "GET".to_string(),
+            url: "https://example.com/test".to_string(),
+            attempt: 0,
+            signing: None,
+        }
+    }
+
+    fn sample_outcome() -> RequestOutcome {
+        RequestOutcome {
+            status: Some(200),
+            duration: Duration::from_millis(42),
+            success: true,
+            error: None,
+            request_id: Some("5C06A3B67B8B5A3DA422299D".to_string()),
+            error_code: None,
+            headers: HeaderMap::new(),
+            bytes_sent: Some(0),
+            bytes_received: Some(1024),
+        }
+    }
+
+    #[tokio::test]
+    async fn chain_calls_interceptors_in_order() {
+        let counter = Arc::new(CountingInterceptor::new());
+        let mut chain = InterceptorChain::new();
+        chain.push(counter.clone());
+
+        let ctx = sample_context();
+        chain.before_request(&ctx).await.unwrap();
+        chain.after_request(&ctx, &sample_outcome()).await;
+
+        assert_eq!(counter.before_count.load(Ordering::SeqCst), 1);
+        assert_eq!(counter.after_count.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn chain_propagates_before_error() {
+        let mut chain = InterceptorChain::new();
+        chain.push(Arc::new(BlockingInterceptor));
+
+        let ctx = sample_context();
+        let result = chain.before_request(&ctx).await;
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "rate limited");
+    }
+
+    #[tokio::test]
+    async fn chain_stops_on_first_error() {
+        let counter = Arc::new(CountingInterceptor::new());
+        let mut chain = InterceptorChain::new();
+        chain.push(Arc::new(BlockingInterceptor));
+        chain.push(counter.clone());
+
+        let ctx = sample_context();
+        let _ = chain.before_request(&ctx).await;
+
+        assert_eq!(counter.before_count.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn chain_throttle_takes_longest_delay() {
+        struct Delay(Duration);
+
+        impl Interceptor for Delay {
+            fn name(&self) -> &str {
+                "delay"
+            }
+
+            fn throttle(&self, _ctx: &InterceptorContext) -> Duration {
+                self.0
+            }
+        }
+
+        let mut chain = InterceptorChain::new();
+        assert_eq!(chain.throttle(&sample_context()), Duration::ZERO);
+        chain.push(Arc::new(Delay(Duration::from_millis(5))));
+        chain.push(Arc::new(Delay(Duration::from_millis(20))));
+        chain.push(Arc::new(CountingInterceptor::new()));
+        assert_eq!(chain.throttle(&sample_context()), Duration::from_millis(20));
+    }
+
+    #[tokio::test]
+    async fn empty_chain_succeeds() {
+        let chain = InterceptorChain::new();
+        let ctx = sample_context();
+        assert!(chain.before_request(&ctx).await.is_ok());
+        assert!(chain.is_empty());
+    }
+
+    #[test]
+    fn interceptor_debug_shows_name() {
+        let interceptor: Arc<dyn Interceptor> = Arc::new(CountingInterceptor::new());
+        let debug = format!("{interceptor:?}");
+        assert!(debug.contains("counting"));
+    }
+
+    struct Named(&'static str);
+
+    impl Interceptor for Named {
+        fn name(&self) -> &str {
+            self.0
+        }
+    }
+
+    #[test]
+    fn chain_orders_by_priority_then_registration() {
+        let mut chain = InterceptorChain::new();
+        chain.push(Arc::new(Named("a")));
+        chain.insert(Arc::new(Named("late")), 10);
+        chain.insert(Arc::new(Named("early")), -10);
+        chain.push(Arc::new(Named("b")));
+        assert_eq!(chain.names(), ["early", "a", "b", "late"]);
+    }
+
+    #[test]
+    fn chain_removes_and_replaces_by_name() {
+        let mut chain = InterceptorChain::new();
+        chain.insert(Arc::new(Named("metrics")), -5);
+        chain.push(Arc::new(Named("log")));
+        chain.push(Arc::new(Named("metrics")));
+
+        assert!(chain.replace("metrics", Arc::new(Named("capture"))));
+        assert_eq!(chain.names(), ["capture", "log"]);
+        // The replacement keeps the priority of the interceptor it replaced.
+        chain.insert(Arc::new(Named("first")), -5);
+        assert_eq!(chain.names(), ["capture", "first", "log"]);
+
+        assert!(!chain.replace("missing", Arc::new(Named("x"))));
+        assert_eq!(chain.remove("log"), 1);
+        assert_eq!(chain.remove("log"), 0);
+        assert_eq!(chain.names(), ["capture", "first"]);
+    }
+
+    #[test]
+    fn chain_debug_shows_names() {
+        let mut chain = InterceptorChain::new();
+        chain.push(Arc::new(CountingInterceptor::new()));
+        chain.push(Arc::new(BlockingInterceptor));
+        let debug = format!("{chain:?}");
+        assert!(debug.contains("counting"));
+        assert!(debug.contains("blocking"));
+    }
+}
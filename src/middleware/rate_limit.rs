@@ -0,0 +1,327 @@
+//! Client-side request rate limiting.
+
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::{Interceptor, InterceptorContext};
+use crate::error::{OssError, Result};
+
+/// Broad class of an OSS operation, used for per-class rate limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OperationClass {
+    /// `GET` and `HEAD` requests.
+    Read,
+    /// All other requests (`PUT`, `POST`, `DELETE`, ...).
+    Write,
+}
+
+impl OperationClass {
+    /// Classify a request by its HTTP method.
+    pub fn from_method(method: &str) -> Self {
+        if method.eq_ignore_ascii_case("GET") || method.eq_ignore_ascii_case("HEAD") {
+            Self::Read
+        } else {
+            Self::Write
+        }
+    }
+}
+
+/// Lowest accepted rate: one request per hour. Slower rates would make the
+/// pacing interval too long to add to an [`Instant`].
+const MIN_REQUESTS_PER_SECOND: f64 = 1.0 / 3600.0;
+
+/// Token bucket implemented as a generic cell rate algorithm: each request
+/// reserves the next free slot, so waiters are served in arrival order.
+struct Bucket {
+    rate: f64,
+    interval: Duration,
+    tolerance: Duration,
+    next_free: Mutex<Option<Instant>>,
+}
+
+impl Bucket {
+    fn new(rate: f64, burst: u32) -> Self {
+        let interval = Duration::from_secs_f64(1.0 / rate);
+        Self {
+            rate,
+            interval,
+            tolerance: interval * burst.saturating_sub(1),
+            next_free: Mutex::new(None),
+        }
+    }
+
+    /// Reserve a slot and return how long the caller must wait for it.
+    fn reserve(&self, now: Instant) -> Duration {
+        let mut next_free = self.next_free.lock().unwrap_or_else(|e| e.into_inner());
+        let tat = next_free.map_or(now, |t| t.max(now));
+        let allowed_at = tat.checked_sub(self.tolerance).unwrap_or(now);
+        *next_free = Some(tat + self.interval);
+        allowed_at.saturating_duration_since(now)
+    }
+}
+
+/// Interceptor that caps the request rate of a client with a token bucket.
+///
+/// Every attempt, including retries, takes one token. When the bucket is empty
+/// the request is delayed (without blocking the runtime) until a token frees
+/// up, keeping batch jobs under the OSS QPS limits instead of tripping
+/// throttling errors. Reads and writes can be given their own limits; a class
+/// without an override shares the global bucket.
+///
+/// Clones of the client share the limiter because interceptors are held by `Arc`.
+///
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+/// use rs_ali_oss::middleware::RateLimitInterceptor;
+///
+/// # fn example(client: rs_ali_oss::OssClient) -> rs_ali_oss::Result<()> {
+/// let limiter = RateLimitInterceptor::builder()
+///     .requests_per_second(200.0)
+///     .write_requests_per_second(50.0)
+///     .burst(20)
+///     .build()?;
+/// let client = client.interceptor(Arc::new(limiter));
+/// # Ok(())
+/// # }
+/// ```
+pub struct RateLimitInterceptor {
+    global: Option<Bucket>,
+    read: Option<Bucket>,
+    write: Option<Bucket>,
+}
+
+impl RateLimitInterceptor {
+    /// Create a builder.
+    pub fn builder() -> RateLimitInterceptorBuilder {
+        RateLimitInterceptorBuilder::new()
+    }
+
+    fn bucket(&self, class: OperationClass) -> Option<&Bucket> {
+        let class_bucket = match class {
+            OperationClass::Read => self.read.as_ref(),
+            OperationClass::Write => self.write.as_ref(),
+        };
+        class_bucket.or(self.global.as_ref())
+    }
+}
+
+impl Interceptor for RateLimitInterceptor {
+    fn name(&self) -> &str {
+        "rate_limit"
+    }
+
+    fn throttle(&self, ctx: &InterceptorContext) -> Duration {
+        self.bucket(OperationClass::from_method(&ctx.method))
+            .map_or(Duration::ZERO, |bucket| bucket.reserve(Instant::now()))
+    }
+}
+
+impl fmt::Debug for RateLimitInterceptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rate = |bucket: &Option<Bucket>| bucket.as_ref().map(|b| b.rate);
+        f.debug_struct("RateLimitInterceptor")
+            .field("requests_per_second", &rate(&self.global))
+            .field("read_requests_per_second", &rate(&self.read))
+            .field("write_requests_per_second", &rate(&self.write))
+            .finish()
+    }
+}
+
+/// Builder for [`RateLimitInterceptor`].
+#[derive(Debug, Default)]
+pub struct RateLimitInterceptorBuilder {
+    requests_per_second: Option<f64>,
+    read_requests_per_second: Option<f64>,
+    write_requests_per_second: Option<f64>,
+    burst: Option<u32>,
+}
+
+impl RateLimitInterceptorBuilder {
+    /// Create a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit all requests without a class-specific limit to this rate.
+    pub fn requests_per_second(mut self, rate: f64) -> Self {
+        self.requests_per_second = Some(rate);
+        self
+    }
+
+    /// Limit `GET`/`HEAD` requests to this rate, separately from the global limit.
+    pub fn read_requests_per_second(mut self, rate: f64) -> Self {
+        self.read_requests_per_second = Some(rate);
+        self
+    }
+
+    /// Limit all other requests to this rate, separately from the global limit.
+    pub fn write_requests_per_second(mut self, rate: f64) -> Self {
+        self.write_requests_per_second = Some(rate);
+        self
+    }
+
+    /// Allow up to `burst` requests back to back before pacing kicks in (default: 1).
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.burst = Some(burst);
+        self
+    }
+
+    /// Build the interceptor.
+    ///
+    /// Fails if no rate is set, a rate is not finite or is below one request
+    /// per hour, or `burst` is `0`.
+    pub fn build(self) -> Result<RateLimitInterceptor> {
+        let burst = self.burst.unwrap_or(1);
+        if burst == 0 {
+            return Err(OssError::InvalidParameter {
+                field: "burst".into(),
+                reason: "must be at least 1".into(),
+            });
+        }
+        let bucket = |field: &str, rate: Option<f64>| -> Result<Option<Bucket>> {
+            match rate {
+                Some(rate) if !(rate.is_finite() && rate >= MIN_REQUESTS_PER_SECOND) => {
+                    Err(OssError::InvalidParameter {
+                        field: field.into(),
+                        reason: "must be a finite number of at least one request per hour".into(),
+                    })
+                }
+                Some(rate) => Ok(Some(Bucket::new(rate, burst))),
+                None => Ok(None),
+            }
+        };
+        let limiter = RateLimitInterceptor {
+            global: bucket("requests_per_second", self.requests_per_second)?,
+            read: bucket("read_requests_per_second", self.read_requests_per_second)?,
+            write: bucket("write_requests_per_second", self.write_requests_per_second)?,
+        };
+        if limiter.global.is_none() && limiter.read.is_none() && limiter.write.is_none() {
+            return Err(OssError::MissingField("requests_per_second".into()));
+        }
+        Ok(limiter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(method: &str) -> InterceptorContext {
+        InterceptorContext {
+            method: method.to_string(),
+            url: "https://bucket.oss-cn-hangzhou.aliyuncs.com/key".to_string(),
+            attempt: 0,
+            signing: None,
+        }
+    }
+
+    #[test]
+    fn operation_class_from_method() {
+        assert_eq!(OperationClass::from_method("GET"), OperationClass::Read);
+        assert_eq!(OperationClass::from_method("head"), OperationClass::Read);
+        assert_eq!(OperationClass::from_method("PUT"), OperationClass::Write);
+        assert_eq!(OperationClass::from_method("DELETE"), OperationClass::Write);
+    }
+
+    #[test]
+    fn bucket_paces_requests() {
+        let bucket = Bucket::new(10.0, 1);
+        let now = Instant::now();
+        assert_eq!(bucket.reserve(now), Duration::ZERO);
+        assert_eq!(bucket.reserve(now), Duration::from_millis(100));
+        assert_eq!(bucket.reserve(now), Duration::from_millis(200));
+        // Waiting refills the bucket.
+        let later = now + Duration::from_secs(1);
+        assert_eq!(bucket.reserve(later), Duration::ZERO);
+    }
+
+    #[test]
+    fn bucket_allows_burst() {
+        let bucket = Bucket::new(10.0, 3);
+        let now = Instant::now();
+        for _ in 0..3 {
+            assert_eq!(bucket.reserve(now), Duration::ZERO);
+        }
+        assert_eq!(bucket.reserve(now), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn class_overrides_use_separate_buckets() {
+        let limiter = RateLimitInterceptor::builder()
+            .requests_per_second(1.0)
+            .read_requests_per_second(1000.0)
+            .build()
+            .unwrap();
+        assert_eq!(limiter.throttle(&ctx("PUT")), Duration::ZERO);
+        assert!(limiter.throttle(&ctx("PUT")) > Duration::from_millis(900));
+        // Reads are unaffected by the exhausted global bucket.
+        assert_eq!(limiter.throttle(&ctx("GET")), Duration::ZERO);
+    }
+
+    #[test]
+    fn unlimited_class_is_not_throttled() {
+        let limiter = RateLimitInterceptor::builder()
+            .write_requests_per_second(1.0)
+            .build()
+            .unwrap();
+        for _ in 0..10 {
+            assert_eq!(limiter.throttle(&ctx("GET")), Duration::ZERO);
+        }
+    }
+
+    #[test]
+    fn builder_validates() {
+        assert!(matches!(
+            RateLimitInterceptor::builder().build(),
+            Err(OssError::MissingField(_))
+        ));
+        assert!(matches!(
+            RateLimitInterceptor::builder()
+                .requests_per_second(0.0)
+                .build(),
+            Err(OssError::InvalidParameter { .. })
+        ));
+        assert!(matches!(
+            RateLimitInterceptor::builder()
+                .requests_per_second(f64::NAN)
+                .build(),
+            Err(OssError::InvalidParameter { .. })
+        ));
+        // Tiny and subnormal rates are rejected instead of overflowing the interval.
+        for rate in [1e-20, f64::MIN_POSITIVE / 4.0, f64::INFINITY] {
+            assert!(matches!(
+                RateLimitInterceptor::builder()
+                    .read_requests_per_second(rate)
+                    .build(),
+                Err(OssError::InvalidParameter { .. })
+            ));
+        }
+        assert!(
+            RateLimitInterceptor::builder()
+                .requests_per_second(MIN_REQUESTS_PER_SECOND)
+                .burst(u32::MAX)
+                .build()
+                .is_ok()
+        );
+        assert!(matches!(
+            RateLimitInterceptor::builder()
+                .requests_per_second(5.0)
+                .burst(0)
+                .build(),
+            Err(OssError::InvalidParameter { .. })
+        ));
+    }
+
+    #[test]
+    fn debug_shows_rates() {
+        let limiter = RateLimitInterceptor::builder()
+            .requests_per_second(5.0)
+            .build()
+            .unwrap();
+        let debug = format!("{limiter:?}");
+        assert!(debug.contains("RateLimitInterceptor"));
+        assert!(debug.contains("5.0"));
+    }
+}
@@ -0,0 +1,362 @@
+//! Record/replay of HTTP exchanges for hermetic tests.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::sync::Mutex;
+
+use futures_util::future::BoxFuture;
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+
+use super::{Interceptor, Next};
+use crate::error::{OssError, Result};
+
+/// Placeholder written in place of redacted values.
+const REDACTED: &str = "REDACTED";
+
+/// Request headers that carry credentials or signatures.
+const SECRET_HEADERS: &[&str] = &["authorization", "x-oss-security-token"];
+
+/// Query parameters that carry credentials or signatures.
+const SECRET_PARAMS: &[&str] = &[
+    "OSSAccessKeyId",
+    "Signature",
+    "security-token",
+    "x-oss-credential",
+    "x-oss-signature",
+    "x-oss-security-token",
+];
+
+/// One recorded request and the response it received.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interaction {
+    /// HTTP method, e.g. `GET`.
+    pub method: String,
+    /// Path and query of the request URL, with secret parameters redacted.
+    /// Replay matches on this and the method, so fixtures do not depend on
+    /// the endpoint host.
+    pub path: String,
+    /// Request headers, with credentials redacted.
+    #[serde(default)]
+    pub request_headers: BTreeMap<String, String>,
+    /// Response status code.
+    pub status: u16,
+    /// Response headers.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Response body.
+    #[serde(default, with = "base64_body")]
+    pub body: Vec<u8>,
+}
+
+/// Fixture file layout.
+#[derive(Serialize, Deserialize)]
+struct Fixture {
+    interactions: Vec<Interaction>,
+}
+
+/// An interceptor that records every exchange, for saving as a fixture that
+/// a [`Replayer`] serves back.
+///
+/// Credentials and signatures are redacted. Response bodies are buffered in
+/// memory, so record only test-sized objects. Requires the `test-util`
+/// feature.
+///
+/// # Examples
+/// ```no_run
+/// use std::sync::Arc;
+/// use rs_ali_oss::middleware::Recorder;
+///
+/// # async fn example(client: rs_ali_oss::OssClient) -> rs_ali_oss::Result<()> {
+/// let recorder = Arc::new(Recorder::new());
+/// let client = client.interceptor(recorder.clone());
+/// // ... run the scenario against OSS ...
+/// recorder.save("tests/fixtures/scenario.json")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct Recorder {
+    interactions: Mutex<Vec<Interaction>>,
+}
+
+impl Recorder {
+    /// Create an empty recorder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the exchanges recorded so far.
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.interactions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Serialize the recorded exchanges as a JSON fixture.
+    pub fn to_json(&self) -> Result<String> {
+        let fixture = Fixture {
+            interactions: self.interactions(),
+        };
+        serde_json::to_string_pretty(&fixture).map_err(|e| OssError::JsonParse(e.to_string()))
+    }
+
+    /// Write the recorded exchanges to a fixture file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    async fn record(&self, request: reqwest::Request, next: Next<'_>) -> Result<reqwest::Response> {
+        let method = request.method().to_string();
+        let path = redacted_path(request.url());
+        let request_headers = header_map(request.headers(), SECRET_HEADERS);
+
+        let response = next.run(request).await?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.bytes().await?;
+
+        self.interactions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(Interaction {
+                method,
+                path,
+                request_headers,
+                status: status.as_u16(),
+                headers: header_map(&headers, &[]),
+                body: body.to_vec(),
+            });
+
+        let mut rebuilt = http::Response::builder().status(status);
+        if let Some(rebuilt_headers) = rebuilt.headers_mut() {
+            *rebuilt_headers = headers;
+        }
+        build_response(rebuilt, body.to_vec())
+    }
+}
+
+impl Interceptor for Recorder {
+    fn name(&self) -> &str {
+        "recorder"
+    }
+
+    fn send<'a>(
+        &'a self,
+        request: reqwest::Request,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<reqwest::Response>> {
+        Box::pin(self.record(request, next))
+    }
+}
+
+/// An interceptor that answers requests from recorded exchanges instead of
+/// the network.
+///
+/// Each request is answered by the first unused interaction with the same
+/// method and path, so a scenario that reads an object before and after
+/// overwriting it replays both responses in order. A request with no
+/// matching interaction fails with [`OssError::Unsupported`]. Requires the
+/// `test-util` feature.
+///
+/// # Examples
+/// ```no_run
+/// use std::sync::Arc;
+/// use rs_ali_oss::middleware::Replayer;
+///
+/// # fn example(client: rs_ali_oss::OssClient) -> rs_ali_oss::Result<()> {
+/// let replayer = Replayer::load("tests/fixtures/scenario.json")?;
+/// let client = client.interceptor(Arc::new(replayer));
+/// # Ok(())
+/// # }
+/// ```
+pub struct Replayer {
+    remaining: Mutex<Vec<Interaction>>,
+}
+
+impl Replayer {
+    /// Replay the given exchanges.
+    pub fn new(interactions: Vec<Interaction>) -> Self {
+        Self {
+            remaining: Mutex::new(interactions),
+        }
+    }
+
+    /// Parse a JSON fixture written by [`Recorder::to_json`].
+    pub fn from_json(json: &str) -> Result<Self> {
+        let fixture: Fixture =
+            serde_json::from_str(json).map_err(|e| OssError::JsonParse(e.to_string()))?;
+        Ok(Self::new(fixture.interactions))
+    }
+
+    /// Load a fixture file written by [`Recorder::save`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Number of recorded exchanges not yet replayed.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .len()
+    }
+
+    fn replay(&self, request: &reqwest::Request) -> Result<reqwest::Response> {
+        let method = request.method().as_str();
+        let path = redacted_path(request.url());
+        let interaction = {
+            let mut remaining = self.remaining.lock().unwrap_or_else(|e| e.into_inner());
+            let index = remaining
+                .iter()
+                .position(|i| i.method == method && i.path == path)
+                .ok_or_else(|| {
+                    OssError::Unsupported(format!("no recorded response for {method} {path}"))
+                })?;
+            remaining.remove(index)
+        };
+
+        let mut response = http::Response::builder().status(interaction.status);
+        for (name, value) in &interaction.headers {
+            response = response.header(name, value);
+        }
+        build_response(response, interaction.body)
+    }
+}
+
+impl Interceptor for Replayer {
+    fn name(&self) -> &str {
+        "replayer"
+    }
+
+    fn send<'a>(
+        &'a self,
+        request: reqwest::Request,
+        _next: Next<'a>,
+    ) -> BoxFuture<'a, Result<reqwest::Response>> {
+        Box::pin(std::future::ready(self.replay(&request)))
+    }
+}
+
+impl fmt::Debug for Replayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Replayer")
+            .field("remaining", &self.remaining())
+            .finish()
+    }
+}
+
+fn build_response(builder: http::response::Builder, body: Vec<u8>) -> Result<reqwest::Response> {
+    let response = builder.body(body).map_err(|e| OssError::InvalidParameter {
+        field: "response".into(),
+        reason: e.to_string(),
+    })?;
+    Ok(reqwest::Response::from(response))
+}
+
+fn redacted_path(url: &url::Url) -> String {
+    let mut path = url.path().to_string();
+    if url.query().is_some() {
+        let query: Vec<String> = url
+            .query_pairs()
+            .map(|(name, value)| {
+                let value = if SECRET_PARAMS.contains(&name.as_ref()) {
+                    REDACTED.into()
+                } else {
+                    value
+                };
+                if value.is_empty() {
+                    name.into_owned()
+                } else {
+                    format!("{name}={value}")
+                }
+            })
+            .collect();
+        path.push('?');
+        path.push_str(&query.join("&"));
+    }
+    path
+}
+
+fn header_map(headers: &HeaderMap, secret: &[&str]) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if secret.contains(&name.as_str()) {
+                REDACTED.to_string()
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).into_owned()
+            };
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+mod base64_body {
+    use base64::Engine;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(body: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&base64::engine::general_purpose::STANDARD.encode(body))
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<u8>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secrets_are_redacted_from_paths() {
+        let url = url::Url::parse(
+            "https://b.oss.example.com/k?uploads&OSSAccessKeyId=AK&Signature=sig&prefix=a",
+        )
+        .unwrap();
+        assert_eq!(
+            redacted_path(&url),
+            "/k?uploads&OSSAccessKeyId=REDACTED&Signature=REDACTED&prefix=a"
+        );
+    }
+
+    #[test]
+    fn fixture_round_trips_binary_bodies() {
+        let interaction = Interaction {
+            method: "GET".into(),
+            path: "/k".into(),
+            request_headers: BTreeMap::new(),
+            status: 200,
+            headers: BTreeMap::from([("etag".into(), "\"e\"".into())]),
+            body: vec![0, 159, 146, 150],
+        };
+        let json = serde_json::to_string(&Fixture {
+            interactions: vec![interaction.clone()],
+        })
+        .unwrap();
+        let replayer = Replayer::from_json(&json).unwrap();
+        assert_eq!(*replayer.remaining.lock().unwrap(), [interaction]);
+    }
+
+    #[test]
+    fn unmatched_requests_are_reported() {
+        let replayer = Replayer::new(Vec::new());
+        let request = reqwest::Request::new(
+            reqwest::Method::GET,
+            url::Url::parse("https://b.oss.example.com/missing").unwrap(),
+        );
+        assert!(matches!(
+            replayer.replay(&request),
+            Err(OssError::Unsupported(message)) if message.contains("GET /missing")
+        ));
+    }
+}
@@ -0,0 +1,776 @@
+//! In-memory OSS backend for tests (requires the `test-util` feature).
+//!
+//! [`MockOss`] implements [`OssApi`] over in-process state, so code written
+//! against `Arc<dyn OssApi>` can be exercised without credentials or network.
+//! It models enough of OSS for integration-style tests: buckets, objects with
+//! ETags and metadata, ranged reads, paginated listings with delimiters,
+//! copies, batch deletes and multipart uploads. Failures are reported as
+//! [`OssError::ServerError`] with the codes OSS would return, such as
+//! `NoSuchKey` or `BucketNotEmpty`.
+//!
+//! Operations it does not model return [`OssError::Unsupported`].
+//!
+//! # Examples
+//! ```
+//! # async fn example() -> rs_ali_oss::Result<()> {
+//! use rs_ali_oss::mock::MockOss;
+//! use rs_ali_oss::types::request::{GetObjectRequestBuilder, PutObjectRequestBuilder};
+//! use rs_ali_oss::{BucketName, ObjectKey, OssApi};
+//!
+//! let oss = MockOss::new().with_bucket("my-bucket");
+//! oss.put_object(
+//!     PutObjectRequestBuilder::new()
+//!         .bucket(BucketName::new("my-bucket")?)
+//!         .key(ObjectKey::new("hello.txt")?)
+//!         .body(b"hello".to_vec())
+//!         .build()?,
+//! )
+//! .await?;
+//! let response = oss
+//!     .get_object(
+//!         GetObjectRequestBuilder::new()
+//!             .bucket(BucketName::new("my-bucket")?)
+//!             .key(ObjectKey::new("hello.txt")?)
+//!             .build()?,
+//!     )
+//!     .await?;
+//! assert_eq!(&response.body.bytes().await?[..], b"hello");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use base64::Engine;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use http_body_util::BodyExt;
+use md5::{Digest, Md5};
+
+use crate::api::{OssApi, OssFuture};
+use crate::body::RequestBody;
+use crate::error::{OssError, Result};
+use crate::progress::{ProgressTracker, TransferKind};
+use crate::types::common::{MetadataDirective, ObjectType, StorageClass};
+use crate::types::request::{
+    AbortMultipartUploadRequest, CompleteMultipartUploadRequest, CompletedPart, CopyObjectRequest,
+    CreateBucketRequest, DeleteBucketRequest, DeleteMultipleObjectsRequest, DeleteObjectRequest,
+    GetObjectRequest, HeadObjectRequest, InitiateMultipartUploadRequest, ListBucketsRequest,
+    ListObjectsV2Request, ListPartsRequest, PutObjectRequest, UploadPartRequest,
+};
+use crate::types::response::{
+    AbortMultipartUploadResponse, BucketInfo, BucketsContainer, CommonPrefix,
+    CompleteMultipartUploadResponse, CopyObjectResponse, CreateBucketResponse,
+    DeleteBucketResponse, DeleteMultipleObjectsResponse, DeleteObjectResponse, DeletedObject,
+    GetObjectResponse, HeadObjectResponse, InitiateMultipartUploadResponse, ListBucketsResponse,
+    ListObjectsV2Response, ListPartsResponse, ObjectBody, ObjectInfo, PartInfo, PutObjectResponse,
+    UploadPartResponse,
+};
+
+const DEFAULT_MAX_KEYS: u32 = 100;
+const DEFAULT_MAX_PARTS: u32 = 1000;
+
+/// An in-memory OSS backend implementing [`OssApi`].
+///
+/// Clones share state, so a test can hand one clone to the code under test
+/// and inspect another.
+#[derive(Debug, Clone, Default)]
+pub struct MockOss {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    buckets: BTreeMap<String, Bucket>,
+    uploads: HashMap<String, Upload>,
+    next_upload_id: u64,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    created: DateTime<Utc>,
+    storage_class: StorageClass,
+    objects: BTreeMap<String, StoredObject>,
+}
+
+#[derive(Debug, Clone)]
+struct StoredObject {
+    data: Bytes,
+    etag: String,
+    content_type: Option<String>,
+    storage_class: StorageClass,
+    object_type: ObjectType,
+    metadata: HashMap<String, String>,
+    last_modified: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+struct Upload {
+    bucket: String,
+    key: String,
+    content_type: Option<String>,
+    storage_class: StorageClass,
+    metadata: HashMap<String, String>,
+    parts: BTreeMap<u32, StoredPart>,
+}
+
+#[derive(Debug)]
+struct StoredPart {
+    data: Bytes,
+    etag: String,
+    last_modified: DateTime<Utc>,
+}
+
+impl MockOss {
+    /// Create an empty backend with no buckets.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an empty bucket.
+    pub fn with_bucket(self, name: &str) -> Self {
+        self.lock()
+            .buckets
+            .entry(name.to_string())
+            .or_insert_with(|| Bucket::new(StorageClass::Standard));
+        self
+    }
+
+    /// Returns the stored content of an object, if it exists.
+    pub fn object(&self, bucket: &str, key: &str) -> Option<Bytes> {
+        self.lock()
+            .buckets
+            .get(bucket)?
+            .objects
+            .get(key)
+            .map(|object| object.data.clone())
+    }
+
+    /// Returns the keys of all objects in a bucket, in listing order.
+    pub fn keys(&self, bucket: &str) -> Vec<String> {
+        self.lock()
+            .buckets
+            .get(bucket)
+            .map(|bucket| bucket.objects.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Bucket {
+    fn new(storage_class: StorageClass) -> Self {
+        Self {
+            created: Utc::now(),
+            storage_class,
+            objects: BTreeMap::new(),
+        }
+    }
+}
+
+impl State {
+    fn bucket(&self, name: &str) -> Result<&Bucket> {
+        self.buckets.get(name).ok_or_else(no_such_bucket)
+    }
+
+    fn bucket_mut(&mut self, name: &str) -> Result<&mut Bucket> {
+        self.buckets.get_mut(name).ok_or_else(no_such_bucket)
+    }
+
+    fn object(&self, bucket: &str, key: &str) -> Result<&StoredObject> {
+        self.bucket(bucket)?
+            .objects
+            .get(key)
+            .ok_or_else(|| service_error(404, "NoSuchKey", "The specified key does not exist."))
+    }
+
+    fn upload(&mut self, upload_id: &str, bucket: &str, key: &str) -> Result<&mut Upload> {
+        self.uploads
+            .get_mut(upload_id)
+            .filter(|upload| upload.bucket == bucket && upload.key == key)
+            .ok_or_else(|| {
+                service_error(404, "NoSuchUpload", "The specified upload does not exist.")
+            })
+    }
+}
+
+fn service_error(status: u16, code: &str, message: &str) -> OssError {
+    OssError::ServerError {
+        status,
+        code: code.to_string(),
+        message: message.to_string(),
+        request_id: String::new(),
+        host_id: String::new(),
+        ec: None,
+        raw_body: String::new(),
+    }
+}
+
+fn no_such_bucket() -> OssError {
+    service_error(404, "NoSuchBucket", "The specified bucket does not exist.")
+}
+
+fn md5_hex(data: &[u8]) -> String {
+    hex::encode_upper(Md5::digest(data))
+}
+
+async fn collect(body: reqwest::Body) -> Result<Bytes> {
+    Ok(body.collect().await?.to_bytes())
+}
+
+/// Parse a `bytes=start-end` range against an object of `len` bytes.
+fn parse_range(range: &str, len: u64) -> Result<(u64, u64)> {
+    let invalid = || service_error(416, "InvalidRange", "The requested range is not valid.");
+    let (start, end) = range
+        .strip_prefix("bytes=")
+        .and_then(|spec| spec.split_once('-'))
+        .ok_or_else(invalid)?;
+    let (start, end) = match (start.parse::<u64>().ok(), end.parse::<u64>().ok()) {
+        (Some(start), Some(end)) => (start, end.min(len.saturating_sub(1))),
+        (Some(start), None) if end.is_empty() => (start, len.saturating_sub(1)),
+        (None, Some(suffix)) if start.is_empty() => {
+            (len.saturating_sub(suffix), len.saturating_sub(1))
+        }
+        _ => return Err(invalid()),
+    };
+    if start > end || start >= len {
+        return Err(invalid());
+    }
+    Ok((start, end))
+}
+
+impl OssApi for MockOss {
+    fn create_bucket(&self, request: CreateBucketRequest) -> OssFuture<'_, CreateBucketResponse> {
+        Box::pin(async move {
+            let mut state = self.lock();
+            let name = request.bucket.to_string();
+            if state.buckets.contains_key(&name) {
+                return Err(service_error(
+                    409,
+                    "BucketAlreadyExists",
+                    "The requested bucket name is not available.",
+                ));
+            }
+            let storage_class = request.storage_class.unwrap_or(StorageClass::Standard);
+            state.buckets.insert(name, Bucket::new(storage_class));
+            Ok(CreateBucketResponse { request_id: None })
+        })
+    }
+
+    fn delete_bucket(&self, request: DeleteBucketRequest) -> OssFuture<'_, DeleteBucketResponse> {
+        Box::pin(async move {
+            let mut state = self.lock();
+            let name = request.bucket.to_string();
+            if !state.bucket(&name)?.objects.is_empty() {
+                return Err(service_error(
+                    409,
+                    "BucketNotEmpty",
+                    "The bucket you tried to delete is not empty.",
+                ));
+            }
+            state.buckets.remove(&name);
+            Ok(DeleteBucketResponse { request_id: None })
+        })
+    }
+
+    fn list_buckets(&self, request: ListBucketsRequest) -> OssFuture<'_, ListBucketsResponse> {
+        Box::pin(async move {
+            let state = self.lock();
+            let prefix = request.prefix.unwrap_or_default();
+            let marker = request.marker.unwrap_or_default();
+            let max_keys = request.max_keys.unwrap_or(DEFAULT_MAX_KEYS);
+            let mut matching = state
+                .buckets
+                .iter()
+                .filter(|(name, _)| name.starts_with(&prefix) && name.as_str() > marker.as_str());
+            let buckets: Vec<BucketInfo> = matching
+                .by_ref()
+                .take(max_keys as usize)
+                .map(|(name, bucket)| BucketInfo {
+                    name: name.clone(),
+                    location: String::new(),
+                    creation_date: bucket.created.to_rfc3339(),
+                    storage_class: bucket.storage_class,
+                    extranet_endpoint: String::new(),
+                    intranet_endpoint: String::new(),
+                })
+                .collect();
+            let is_truncated = matching.next().is_some();
+            Ok(ListBucketsResponse {
+                prefix,
+                marker,
+                max_keys,
+                is_truncated,
+                next_marker: is_truncated
+                    .then(|| buckets.last().map(|b| b.name.clone()))
+                    .flatten(),
+                buckets: BucketsContainer { bucket: buckets },
+            })
+        })
+    }
+
+    fn put_object(&self, request: PutObjectRequest) -> OssFuture<'_, PutObjectResponse> {
+        Box::pin(async move {
+            let body = match request.body {
+                RequestBody::Once(body) => body,
+                RequestBody::Replayable(body) => body.body()?,
+            };
+            let data = collect(body).await?;
+            let etag = md5_hex(&data);
+            let crc64 = crate::crc64::checksum(&data);
+            let object = StoredObject {
+                data,
+                etag: etag.clone(),
+                content_type: request.content_type,
+                storage_class: request.storage_class.unwrap_or(StorageClass::Standard),
+                object_type: ObjectType::Normal,
+                metadata: request.metadata,
+                last_modified: Utc::now(),
+            };
+            self.lock()
+                .bucket_mut(request.bucket.as_ref())?
+                .objects
+                .insert(request.key.to_string(), object);
+            Ok(PutObjectResponse {
+                etag,
+                crc64: Some(crc64),
+                version_id: None,
+                server_side_encryption: None,
+                kms_key_id: None,
+                expiration: None,
+                request_id: None,
+            })
+        })
+    }
+
+    fn get_object(&self, request: GetObjectRequest) -> OssFuture<'_, GetObjectResponse> {
+        Box::pin(async move {
+            let object = self
+                .lock()
+                .object(request.bucket.as_ref(), request.key.as_ref())?
+                .clone();
+            let len = object.data.len() as u64;
+            let (content_range, data) = match request.range {
+                Some(range) => {
+                    let (start, end) = parse_range(&range, len)?;
+                    let data = object.data.slice(start as usize..=end as usize);
+                    (Some(format!("bytes {start}-{end}/{len}")), data)
+                }
+                None => (None, object.data),
+            };
+
+            let mut response = http::Response::builder()
+                .status(if content_range.is_some() { 206 } else { 200 })
+                .header("etag", format!("\"{}\"", object.etag))
+                .header("content-length", data.len());
+            if let Some(content_range) = content_range {
+                response = response.header("content-range", content_range);
+            }
+            if let Some(content_type) = &object.content_type {
+                response = response.header("content-type", content_type);
+            }
+            let response = response
+                .body(data.clone())
+                .map_err(|e| OssError::InvalidParameter {
+                    field: "response".into(),
+                    reason: e.to_string(),
+                })?;
+
+            let content_length = Some(data.len() as u64);
+            let mut body = ObjectBody::new(reqwest::Response::from(response));
+            if let Some(listener) = request.progress_listener {
+                let tracker = ProgressTracker::new(
+                    listener,
+                    &request.bucket,
+                    &request.key,
+                    content_length,
+                    TransferKind::Download,
+                );
+                tracker.start();
+                body = body.with_progress(tracker);
+            }
+            Ok(GetObjectResponse {
+                body,
+                content_type: object.content_type,
+                content_length,
+                etag: Some(object.etag),
+                storage_class: Some(object.storage_class),
+                object_type: Some(object.object_type),
+                tagging_count: None,
+                restore: None,
+                expiration: None,
+                metadata: object.metadata,
+                request_id: None,
+            })
+        })
+    }
+
+    fn head_object(&self, request: HeadObjectRequest) -> OssFuture<'_, HeadObjectResponse> {
+        Box::pin(async move {
+            let state = self.lock();
+            let object = state.object(request.bucket.as_ref(), request.key.as_ref())?;
+            Ok(HeadObjectResponse {
+                content_type: object.content_type.clone(),
+                content_length: Some(object.data.len() as u64),
+                cache_control: None,
+                content_disposition: None,
+                content_encoding: None,
+                expires: None,
+                etag: Some(object.etag.clone()),
+                last_modified: Some(object.last_modified),
+                crc64: Some(crate::crc64::checksum(&object.data)),
+                storage_class: Some(object.storage_class),
+                object_type: Some(object.object_type),
+                version_id: None,
+                server_side_encryption: None,
+                kms_key_id: None,
+                restore: None,
+                expiration: None,
+                metadata: object.metadata.clone(),
+                request_id: None,
+            })
+        })
+    }
+
+    fn delete_object(&self, request: DeleteObjectRequest) -> OssFuture<'_, DeleteObjectResponse> {
+        Box::pin(async move {
+            // Like OSS, deleting a missing key succeeds.
+            self.lock()
+                .bucket_mut(request.bucket.as_ref())?
+                .objects
+                .remove(request.key.as_ref());
+            Ok(DeleteObjectResponse::default())
+        })
+    }
+
+    fn delete_multiple_objects(
+        &self,
+        request: DeleteMultipleObjectsRequest,
+    ) -> OssFuture<'_, DeleteMultipleObjectsResponse> {
+        Box::pin(async move {
+            let mut state = self.lock();
+            let bucket = state.bucket_mut(request.bucket.as_ref())?;
+            let mut deleted = Vec::new();
+            for (key, _) in request.keys {
+                bucket.objects.remove(key.as_ref());
+                if !request.quiet {
+                    deleted.push(DeletedObject {
+                        key: key.to_string(),
+                        version_id: None,
+                        delete_marker: false,
+                        delete_marker_version_id: None,
+                    });
+                }
+            }
+            Ok(DeleteMultipleObjectsResponse { deleted })
+        })
+    }
+
+    fn list_objects_v2(
+        &self,
+        request: ListObjectsV2Request,
+    ) -> OssFuture<'_, ListObjectsV2Response> {
+        Box::pin(async move {
+            let state = self.lock();
+            let bucket = state.bucket(request.bucket.as_ref())?;
+            let prefix = request.prefix.unwrap_or_default();
+            let max_keys = request.max_keys.unwrap_or(DEFAULT_MAX_KEYS);
+            // The continuation token is the last entry (key or common prefix)
+            // returned, so entries at or before it are skipped.
+            let after = match (request.continuation_token, request.start_after) {
+                (Some(token), _) => token,
+                (None, Some(start_after)) => start_after,
+                (None, None) => String::new(),
+            };
+
+            let mut contents = Vec::new();
+            let mut common_prefixes: Vec<CommonPrefix> = Vec::new();
+            let mut last_entry: Option<String> = None;
+            let mut is_truncated = false;
+            for (key, object) in bucket.objects.range(prefix.clone()..) {
+                if !key.starts_with(&prefix) {
+                    break;
+                }
+                let common_prefix = request.delimiter.as_deref().and_then(|delimiter| {
+                    key[prefix.len()..]
+                        .find(delimiter)
+                        .map(|i| key[..prefix.len() + i + delimiter.len()].to_string())
+                });
+                let entry = common_prefix.as_deref().unwrap_or(key);
+                if entry <= after.as_str() || last_entry.as_deref() == Some(entry) {
+                    continue;
+                }
+                if contents.len() + common_prefixes.len() == max_keys as usize {
+                    is_truncated = true;
+                    break;
+                }
+                last_entry = Some(entry.to_string());
+                match common_prefix {
+                    Some(prefix) => common_prefixes.push(CommonPrefix { prefix }),
+                    None => contents.push(ObjectInfo {
+                        key: key.clone(),
+                        last_modified: object.last_modified,
+                        etag: format!("\"{}\"", object.etag),
+                        size: object.data.len() as u64,
+                        storage_class: object.storage_class,
+                    }),
+                }
+            }
+
+            Ok(ListObjectsV2Response {
+                name: request.bucket.to_string(),
+                prefix,
+                max_keys,
+                key_count: (contents.len() + common_prefixes.len()) as u32,
+                is_truncated,
+                next_continuation_token: if is_truncated { last_entry } else { None },
+                contents,
+                common_prefixes,
+            })
+        })
+    }
+
+    fn copy_object(&self, request: CopyObjectRequest) -> OssFuture<'_, CopyObjectResponse> {
+        Box::pin(async move {
+            let mut state = self.lock();
+            let mut object = state
+                .object(request.source_bucket.as_ref(), request.source_key.as_ref())?
+                .clone();
+            if request.metadata_directive == Some(MetadataDirective::Replace) {
+                object.content_type = request.content_type;
+                object.metadata = request.metadata;
+            }
+            if let Some(storage_class) = request.storage_class {
+                object.storage_class = storage_class;
+            }
+            object.last_modified = Utc::now();
+            let response = CopyObjectResponse {
+                last_modified: object.last_modified,
+                etag: object.etag.clone(),
+                crc64: Some(crate::crc64::checksum(&object.data)),
+                version_id: None,
+                source_version_id: None,
+                server_side_encryption: None,
+                kms_key_id: None,
+            };
+            state
+                .bucket_mut(request.bucket.as_ref())?
+                .objects
+                .insert(request.key.to_string(), object);
+            Ok(response)
+        })
+    }
+
+    fn initiate_multipart_upload(
+        &self,
+        request: InitiateMultipartUploadRequest,
+    ) -> OssFuture<'_, InitiateMultipartUploadResponse> {
+        Box::pin(async move {
+            let mut state = self.lock();
+            state.bucket(request.bucket.as_ref())?;
+            state.next_upload_id += 1;
+            let upload_id = format!("{:032X}", state.next_upload_id);
+            state.uploads.insert(
+                upload_id.clone(),
+                Upload {
+                    bucket: request.bucket.to_string(),
+                    key: request.key.to_string(),
+                    content_type: request.content_type,
+                    storage_class: request.storage_class.unwrap_or(StorageClass::Standard),
+                    metadata: request.metadata,
+                    parts: BTreeMap::new(),
+                },
+            );
+            Ok(InitiateMultipartUploadResponse {
+                bucket: request.bucket.to_string(),
+                key: request.key.to_string(),
+                upload_id,
+            })
+        })
+    }
+
+    fn upload_part(&self, request: UploadPartRequest) -> OssFuture<'_, UploadPartResponse> {
+        Box::pin(async move {
+            let data = collect(request.body).await?;
+            if let Some(ref content_md5) = request.content_md5 {
+                let digest = base64::engine::general_purpose::STANDARD.encode(Md5::digest(&data));
+                if *content_md5 != digest {
+                    return Err(service_error(
+                        400,
+                        "InvalidDigest",
+                        "The Content-MD5 you specified was invalid.",
+                    ));
+                }
+            }
+            let etag = md5_hex(&data);
+            let crc64 = crate::crc64::checksum(&data);
+            let len = data.len() as u64;
+            self.lock()
+                .upload(
+                    &request.upload_id,
+                    request.bucket.as_ref(),
+                    request.key.as_ref(),
+                )?
+                .parts
+                .insert(
+                    request.part_number,
+                    StoredPart {
+                        data,
+                        etag: etag.clone(),
+                        last_modified: Utc::now(),
+                    },
+                );
+            if let Some(listener) = request.progress_listener {
+                let tracker = ProgressTracker::new(
+                    listener,
+                    &request.bucket,
+                    &request.key,
+                    Some(len),
+                    TransferKind::Upload,
+                );
+                tracker.start();
+                tracker.advance(len);
+            }
+            Ok(UploadPartResponse {
+                etag,
+                crc64: Some(crc64),
+            })
+        })
+    }
+
+    fn complete_multipart_upload(
+        &self,
+        request: CompleteMultipartUploadRequest,
+    ) -> OssFuture<'_, CompleteMultipartUploadResponse> {
+        Box::pin(async move {
+            let mut state = self.lock();
+            let bucket = request.bucket.to_string();
+            let key = request.key.to_string();
+            let upload = state.upload(&request.upload_id, &bucket, &key)?;
+            let parts = if request.complete_all {
+                upload
+                    .parts
+                    .iter()
+                    .map(|(&part_number, stored)| CompletedPart {
+                        part_number,
+                        etag: stored.etag.clone(),
+                    })
+                    .collect()
+            } else {
+                request.parts
+            };
+
+            let mut data = Vec::new();
+            let mut digests = Vec::new();
+            let mut previous = 0;
+            for part in &parts {
+                if part.part_number <= previous {
+                    return Err(service_error(
+                        400,
+                        "InvalidPartOrder",
+                        "The list of parts was not in ascending order.",
+                    ));
+                }
+                previous = part.part_number;
+                let stored = upload
+                    .parts
+                    .get(&part.part_number)
+                    .filter(|stored| stored.etag == part.etag.trim_matches('"'))
+                    .ok_or_else(|| {
+                        service_error(
+                            400,
+                            "InvalidPart",
+                            "One or more of the specified parts could not be found.",
+                        )
+                    })?;
+                data.extend_from_slice(&stored.data);
+                digests.extend_from_slice(&Md5::digest(&stored.data));
+            }
+            let etag = format!("{}-{}", md5_hex(&digests), parts.len());
+            let crc64 = crate::crc64::checksum(&data);
+
+            let object = StoredObject {
+                data: data.into(),
+                etag: etag.clone(),
+                content_type: upload.content_type.clone(),
+                storage_class: upload.storage_class,
+                object_type: ObjectType::Multipart,
+                metadata: upload.metadata.clone(),
+                last_modified: Utc::now(),
+            };
+            state.uploads.remove(&request.upload_id);
+            state
+                .bucket_mut(&bucket)?
+                .objects
+                .insert(key.clone(), object);
+            Ok(CompleteMultipartUploadResponse {
+                location: format!("/{bucket}/{key}"),
+                bucket,
+                key,
+                etag,
+                crc64: Some(crc64),
+                version_id: None,
+                server_side_encryption: None,
+                kms_key_id: None,
+                callback_result: None,
+            })
+        })
+    }
+
+    fn abort_multipart_upload(
+        &self,
+        request: AbortMultipartUploadRequest,
+    ) -> OssFuture<'_, AbortMultipartUploadResponse> {
+        Box::pin(async move {
+            let mut state = self.lock();
+            state.upload(
+                &request.upload_id,
+                request.bucket.as_ref(),
+                request.key.as_ref(),
+            )?;
+            state.uploads.remove(&request.upload_id);
+            Ok(AbortMultipartUploadResponse { request_id: None })
+        })
+    }
+
+    fn list_parts(&self, request: ListPartsRequest) -> OssFuture<'_, ListPartsResponse> {
+        Box::pin(async move {
+            let mut state = self.lock();
+            let upload = state.upload(
+                &request.upload_id,
+                request.bucket.as_ref(),
+                request.key.as_ref(),
+            )?;
+            let max_parts = request.max_parts.unwrap_or(DEFAULT_MAX_PARTS);
+            let marker = request.part_number_marker.unwrap_or(0);
+            let mut remaining = upload.parts.range(marker + 1..);
+            let parts: Vec<PartInfo> = remaining
+                .by_ref()
+                .take(max_parts as usize)
+                .map(|(&part_number, part)| PartInfo {
+                    part_number,
+                    last_modified: part.last_modified,
+                    etag: format!("\"{}\"", part.etag),
+                    size: part.data.len() as u64,
+                })
+                .collect();
+            let is_truncated = remaining.next().is_some();
+            Ok(ListPartsResponse {
+                bucket: upload.bucket.clone(),
+                key: upload.key.clone(),
+                upload_id: request.upload_id,
+                max_parts,
+                is_truncated,
+                next_part_number_marker: if is_truncated {
+                    parts.last().map(|part| part.part_number)
+                } else {
+                    None
+                },
+                parts,
+            })
+        })
+    }
+}
@@ -1,32 +1,40 @@
-//! Bucket operations: CreateBucket, DeleteBucket, ListBuckets, GetBucketInfo, BucketAcl, BucketCors, BucketReferer, BucketPolicy, BucketVersioning, BucketLifecycle, BucketEncryption, BucketLogging.
+//! Bucket operations: CreateBucket, DeleteBucket, ListBuckets, GetBucketInfo, BucketAcl, BucketCors, BucketReferer, BucketPolicy, BucketVersioning, BucketLifecycle, BucketEncryption, BucketLogging, BucketTransferAcceleration, BucketDataRedundancyTransition, Cname.
 
 use reqwest::Method;
 
 use crate::client::{OssClient, header_opt, parse_xml, serialize_xml};
 use crate::error::Result;
 use crate::types::request::{
-    ApplyServerSideEncryptionByDefaultXml, CorsConfigurationXml, CorsRuleXml, CreateBucketRequest,
-    DeleteBucketCorsRequest, DeleteBucketEncryptionRequest, DeleteBucketLifecycleRequest,
-    DeleteBucketLoggingRequest, DeleteBucketPolicyRequest, DeleteBucketRequest,
-    EncryptionConfigurationXml, EncryptionRuleXml, GetBucketAclRequest, GetBucketCorsRequest,
+    ApplyServerSideEncryptionByDefaultXml, CertificateConfigurationXml, CnameConfigurationXml,
+    CnameXml, CorsConfigurationXml, CorsRuleXml, CreateBucketDataRedundancyTransitionRequest,
+    CreateBucketRequest, DeleteBucketCorsRequest, DeleteBucketDataRedundancyTransitionRequest,
+    DeleteBucketEncryptionRequest, DeleteBucketLifecycleRequest, DeleteBucketLoggingRequest,
+    DeleteBucketPolicyRequest, DeleteBucketRequest, EncryptionConfigurationXml, EncryptionRuleXml,
+    GetBucketAclRequest, GetBucketCorsRequest, GetBucketDataRedundancyTransitionRequest,
     GetBucketEncryptionRequest, GetBucketInfoRequest, GetBucketLifecycleRequest,
     GetBucketLocationRequest, GetBucketLoggingRequest, GetBucketPolicyRequest,
-    GetBucketRefererRequest, GetBucketVersioningRequest, LifecycleConfigurationXml,
-    LifecycleExpirationXml, LifecycleRuleXml, LifecycleTransitionXml, ListBucketsRequest,
-    LoggingConfigurationXml, LoggingEnabledXml, PutBucketAclRequest, PutBucketCorsRequest,
-    PutBucketEncryptionRequest, PutBucketLifecycleRequest, PutBucketLoggingRequest,
-    PutBucketPolicyRequest, PutBucketRefererRequest, PutBucketVersioningRequest,
-    RefererBlacklistXml, RefererConfigurationXml, RefererListXml, VersioningConfigurationXml,
+    GetBucketRefererRequest, GetBucketTransferAccelerationRequest, GetBucketVersioningRequest,
+    LifecycleConfigurationXml, LifecycleExpirationXml, LifecycleRuleXml, LifecycleTransitionXml,
+    ListBucketDataRedundancyTransitionRequest, ListBucketsRequest, LoggingConfigurationXml,
+    LoggingEnabledXml, PutBucketAclRequest, PutBucketCorsRequest, PutBucketEncryptionRequest,
+    PutBucketLifecycleRequest, PutBucketLoggingRequest, PutBucketPolicyRequest,
+    PutBucketRefererRequest, PutBucketTransferAccelerationRequest, PutBucketVersioningRequest,
+    PutCnameRequest, RefererBlacklistXml, RefererConfigurationXml, RefererListXml,
+    TransferAccelerationConfigurationXml, VersioningConfigurationXml,
 };
 use crate::types::response::{
-    CreateBucketResponse, DeleteBucketCorsResponse, DeleteBucketEncryptionResponse,
-    DeleteBucketLifecycleResponse, DeleteBucketLoggingResponse, DeleteBucketPolicyResponse,
-    DeleteBucketResponse, GetBucketAclResponse, GetBucketCorsResponse, GetBucketEncryptionResponse,
-    GetBucketInfoResponse, GetBucketLifecycleResponse, GetBucketLocationResponse,
-    GetBucketLoggingResponse, GetBucketPolicyResponse, GetBucketRefererResponse,
-    GetBucketVersioningResponse, ListBucketsResponse, PutBucketAclResponse, PutBucketCorsResponse,
-    PutBucketEncryptionResponse, PutBucketLifecycleResponse, PutBucketLoggingResponse,
-    PutBucketPolicyResponse, PutBucketRefererResponse, PutBucketVersioningResponse,
+    CreateBucketDataRedundancyTransitionResponse, CreateBucketResponse, DataRedundancyTransition,
+    DeleteBucketCorsResponse, DeleteBucketDataRedundancyTransitionResponse,
+    DeleteBucketEncryptionResponse, DeleteBucketLifecycleResponse, DeleteBucketLoggingResponse,
+    DeleteBucketPolicyResponse, DeleteBucketResponse, GetBucketAclResponse, GetBucketCorsResponse,
+    GetBucketDataRedundancyTransitionResponse, GetBucketEncryptionResponse, GetBucketInfoResponse,
+    GetBucketLifecycleResponse, GetBucketLocationResponse, GetBucketLoggingResponse,
+    GetBucketPolicyResponse, GetBucketRefererResponse, GetBucketTransferAccelerationResponse,
+    GetBucketVersioningResponse, ListBucketDataRedundancyTransitionResponse, ListBucketsResponse,
+    PutBucketAclResponse, PutBucketCorsResponse, PutBucketEncryptionResponse,
+    PutBucketLifecycleResponse, PutBucketLoggingResponse, PutBucketPolicyResponse,
+    PutBucketRefererResponse, PutBucketTransferAccelerationResponse, PutBucketVersioningResponse,
+    PutCnameResponse,
 };
 
 impl OssClient {
@@ -947,4 +955,301 @@ impl OssClient {
         let request_id = header_opt(&response, "x-oss-request-id");
         Ok(DeleteBucketLoggingResponse { request_id })
     }
+
+    /// Enable or disable transfer acceleration for a bucket.
+    ///
+    /// Once enabled (which can take up to 30 minutes to take effect), the
+    /// bucket can be accessed through the acceleration endpoints, see
+    /// [`ClientBuilder::use_accelerate_endpoint`](crate::config::ClientBuilder::use_accelerate_endpoint).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rs_ali_oss::*;
+    /// # use rs_ali_oss::types::request::PutBucketTransferAccelerationRequestBuilder;
+    /// # async fn example(client: OssClient) -> Result<()> {
+    /// let request = PutBucketTransferAccelerationRequestBuilder::new()
+    ///     .bucket(BucketName::new("my-bucket")?)
+    ///     .enabled(true)
+    ///     .build()?;
+    /// client.put_bucket_transfer_acceleration(request).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn put_bucket_transfer_acceleration(
+        &self,
+        request: PutBucketTransferAccelerationRequest,
+    ) -> Result<PutBucketTransferAccelerationResponse> {
+        let url = self.build_url(Some(&request.bucket), None, &[("transferAcceleration", "")])?;
+        let resource_path = format!("/{}/", request.bucket);
+
+        let config = TransferAccelerationConfigurationXml {
+            enabled: request.enabled,
+        };
+
+        let xml_body = serialize_xml(&config)?;
+        let http_req = self
+            .http_client()
+            .request(Method::PUT, url)
+            .header("content-type", "application/xml")
+            .body(xml_body)
+            .build()?;
+
+        let response = self.execute(http_req, &resource_path).await?;
+        let request_id = header_opt(&response, "x-oss-request-id");
+        Ok(PutBucketTransferAccelerationResponse { request_id })
+    }
+
+    /// Get the transfer acceleration configuration of a bucket.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rs_ali_oss::*;
+    /// # use rs_ali_oss::types::request::GetBucketTransferAccelerationRequestBuilder;
+    /// # async fn example(client: OssClient) -> Result<()> {
+    /// let request = GetBucketTransferAccelerationRequestBuilder::new()
+    ///     .bucket(BucketName::new("my-bucket")?)
+    ///     .build()?;
+    /// let response = client.get_bucket_transfer_acceleration(request).await?;
+    /// println!("Transfer acceleration enabled: {}", response.enabled);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_bucket_transfer_acceleration(
+        &self,
+        request: GetBucketTransferAccelerationRequest,
+    ) -> Result<GetBucketTransferAccelerationResponse> {
+        let url = self.build_url(Some(&request.bucket), None, &[("transferAcceleration", "")])?;
+        let resource_path = format!("/{}/", request.bucket);
+        let http_req = self.http_client().request(Method::GET, url).build()?;
+        let response = self.execute(http_req, &resource_path).await?;
+        let request_id = header_opt(&response, "x-oss-request-id");
+        let body = response.text().await?;
+        let mut resp: GetBucketTransferAccelerationResponse = parse_xml(&body)?;
+        resp.request_id = request_id;
+        Ok(resp)
+    }
+
+    /// Start converting a bucket to another data redundancy type, e.g. from
+    /// LRS to ZRS.
+    ///
+    /// The conversion runs in the background; poll its progress with
+    /// [`get_bucket_data_redundancy_transition`](Self::get_bucket_data_redundancy_transition)
+    /// using the returned task ID.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rs_ali_oss::*;
+    /// # use rs_ali_oss::types::request::CreateBucketDataRedundancyTransitionRequestBuilder;
+    /// # async fn example(client: OssClient) -> Result<()> {
+    /// let request = CreateBucketDataRedundancyTransitionRequestBuilder::new()
+    ///     .bucket(BucketName::new("my-bucket")?)
+    ///     .target_redundancy_type(DataRedundancyType::ZRS)
+    ///     .build()?;
+    /// let response = client.create_bucket_data_redundancy_transition(request).await?;
+    /// println!("Task ID: {}", response.task_id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_bucket_data_redundancy_transition(
+        &self,
+        request: CreateBucketDataRedundancyTransitionRequest,
+    ) -> Result<CreateBucketDataRedundancyTransitionResponse> {
+        let target = request.target_redundancy_type.to_string();
+        let url = self.build_url(
+            Some(&request.bucket),
+            None,
+            &[
+                ("redundancyTransition", ""),
+                ("x-oss-target-redundancy-type", &target),
+            ],
+        )?;
+        let resource_path = format!("/{}/", request.bucket);
+        let http_req = self.http_client().request(Method::POST, url).build()?;
+        let response = self.execute(http_req, &resource_path).await?;
+        let request_id = header_opt(&response, "x-oss-request-id");
+        let body = response.text().await?;
+        let mut resp: CreateBucketDataRedundancyTransitionResponse = parse_xml(&body)?;
+        resp.request_id = request_id;
+        Ok(resp)
+    }
+
+    /// Get the state and progress of a data redundancy transition task.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rs_ali_oss::*;
+    /// # use rs_ali_oss::types::request::GetBucketDataRedundancyTransitionRequestBuilder;
+    /// # async fn example(client: OssClient) -> Result<()> {
+    /// let request = GetBucketDataRedundancyTransitionRequestBuilder::new()
+    ///     .bucket(BucketName::new("my-bucket")?)
+    ///     .task_id("task-id")
+    ///     .build()?;
+    /// let response = client.get_bucket_data_redundancy_transition(request).await?;
+    /// println!(
+    ///     "{}: {}%",
+    ///     response.transition.status,
+    ///     response.transition.process_percentage.unwrap_or(0)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_bucket_data_redundancy_transition(
+        &self,
+        request: GetBucketDataRedundancyTransitionRequest,
+    ) -> Result<GetBucketDataRedundancyTransitionResponse> {
+        let url = self.build_url(
+            Some(&request.bucket),
+            None,
+            &[
+                ("redundancyTransition", ""),
+                ("x-oss-redundancy-transition-taskid", &request.task_id),
+            ],
+        )?;
+        let resource_path = format!("/{}/", request.bucket);
+        let http_req = self.http_client().request(Method::GET, url).build()?;
+        let response = self.execute(http_req, &resource_path).await?;
+        let request_id = header_opt(&response, "x-oss-request-id");
+        let body = response.text().await?;
+        let transition: DataRedundancyTransition = parse_xml(&body)?;
+        Ok(GetBucketDataRedundancyTransitionResponse {
+            transition,
+            request_id,
+        })
+    }
+
+    /// List the data redundancy transition tasks of a bucket.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rs_ali_oss::*;
+    /// # use rs_ali_oss::types::request::ListBucketDataRedundancyTransitionRequestBuilder;
+    /// # async fn example(client: OssClient) -> Result<()> {
+    /// let request = ListBucketDataRedundancyTransitionRequestBuilder::new()
+    ///     .bucket(BucketName::new("my-bucket")?)
+    ///     .build()?;
+    /// let response = client.list_bucket_data_redundancy_transition(request).await?;
+    /// for transition in &response.transitions {
+    ///     println!("{}: {}", transition.task_id, transition.status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_bucket_data_redundancy_transition(
+        &self,
+        request: ListBucketDataRedundancyTransitionRequest,
+    ) -> Result<ListBucketDataRedundancyTransitionResponse> {
+        let url = self.build_url(Some(&request.bucket), None, &[("redundancyTransition", "")])?;
+        let resource_path = format!("/{}/", request.bucket);
+        let http_req = self.http_client().request(Method::GET, url).build()?;
+        let response = self.execute(http_req, &resource_path).await?;
+        let request_id = header_opt(&response, "x-oss-request-id");
+        let body = response.text().await?;
+        let mut resp: ListBucketDataRedundancyTransitionResponse = parse_xml(&body)?;
+        resp.request_id = request_id;
+        Ok(resp)
+    }
+
+    /// Delete a data redundancy transition task.
+    ///
+    /// Only tasks that are still queueing can be deleted.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rs_ali_oss::*;
+    /// # use rs_ali_oss::types::request::DeleteBucketDataRedundancyTransitionRequestBuilder;
+    /// # async fn example(client: OssClient) -> Result<()> {
+    /// let request = DeleteBucketDataRedundancyTransitionRequestBuilder::new()
+    ///     .bucket(BucketName::new("my-bucket")?)
+    ///     .task_id("task-id")
+    ///     .build()?;
+    /// client.delete_bucket_data_redundancy_transition(request).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_bucket_data_redundancy_transition(
+        &self,
+        request: DeleteBucketDataRedundancyTransitionRequest,
+    ) -> Result<DeleteBucketDataRedundancyTransitionResponse> {
+        let url = self.build_url(
+            Some(&request.bucket),
+            None,
+            &[
+                ("redundancyTransition", ""),
+                ("x-oss-redundancy-transition-taskid", &request.task_id),
+            ],
+        )?;
+        let resource_path = format!("/{}/", request.bucket);
+        let http_req = self.http_client().request(Method::DELETE, url).build()?;
+        let response = self.execute(http_req, &resource_path).await?;
+        let request_id = header_opt(&response, "x-oss-request-id");
+        Ok(DeleteBucketDataRedundancyTransitionResponse { request_id })
+    }
+
+    /// Map a custom domain to a bucket, optionally uploading, binding or
+    /// unbinding its HTTPS certificate.
+    ///
+    /// The same call updates the certificate of an already mapped domain.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rs_ali_oss::*;
+    /// # use rs_ali_oss::types::request::{CnameCertificate, PutCnameRequestBuilder};
+    /// # async fn example(client: OssClient) -> Result<()> {
+    /// let request = PutCnameRequestBuilder::new()
+    ///     .bucket(BucketName::new("my-bucket")?)
+    ///     .domain("static.example.com")
+    ///     .certificate(CnameCertificate {
+    ///         certificate: Some(std::fs::read_to_string("cert.pem")?),
+    ///         private_key: Some(std::fs::read_to_string("key.pem")?),
+    ///         force: true,
+    ///         ..Default::default()
+    ///     })
+    ///     .build()?;
+    /// client.put_cname(request).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn put_cname(&self, request: PutCnameRequest) -> Result<PutCnameResponse> {
+        let url = self.build_url(
+            Some(&request.bucket),
+            None,
+            &[("cname", ""), ("comp", "add")],
+        )?;
+        let resource_path = format!("/{}/", request.bucket);
+
+        let config = CnameConfigurationXml {
+            cname: CnameXml {
+                domain: request.domain,
+                certificate_configuration: request.certificate.map(|cert| {
+                    CertificateConfigurationXml {
+                        cert_id: cert.cert_id,
+                        certificate: cert.certificate,
+                        private_key: cert.private_key,
+                        previous_cert_id: cert.previous_cert_id,
+                        force: cert.force,
+                        delete_certificate: cert.delete_certificate,
+                    }
+                }),
+            },
+        };
+
+        let xml_body = serialize_xml(&config)?;
+        let http_req = self
+            .http_client()
+            .request(Method::POST, url)
+            .header("content-type", "application/xml")
+            .body(xml_body)
+            .build()?;
+
+        let response = self.execute(http_req, &resource_path).await?;
+        let request_id = header_opt(&response, "x-oss-request-id");
+        Ok(PutCnameResponse { request_id })
+    }
 }
@@ -0,0 +1,879 @@
+//! Helpers that apply an operation to every object under a prefix.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use tokio::task::JoinSet;
+
+use crate::client::OssClient;
+use crate::error::{ErrorCode, OssError, Result};
+use crate::types::common::{BucketName, ObjectAcl, ObjectKey, StorageClass};
+use crate::types::request::{
+    AbortMultipartUploadRequestBuilder, CopyObjectRequestBuilder, DeleteBucketRequestBuilder,
+    DeleteMultipleObjectsRequestBuilder, GetBucketVersioningRequestBuilder,
+    HeadObjectRequestBuilder, ListMultipartUploadsRequestBuilder, ListObjectVersionsRequestBuilder,
+    ListObjectsV2RequestBuilder, RestoreObjectRequestBuilder,
+};
+
+/// Maximum number of keys in a single DeleteMultipleObjects request.
+const DELETE_BATCH_SIZE: usize = 1000;
+
+const DEFAULT_COPY_CONCURRENCY: usize = 8;
+
+const DEFAULT_RESTORE_CONCURRENCY: usize = 8;
+
+/// What [`OssClient::delete_prefix`] deletes in a versioned bucket.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VersionedDeletion {
+    /// Delete the current version of each object by key. In a versioned
+    /// bucket this only adds delete markers, leaving earlier versions
+    /// recoverable; in an unversioned bucket the objects are removed.
+    #[default]
+    DeleteMarkers,
+    /// Permanently delete every version and delete marker under the prefix.
+    AllVersions,
+}
+
+/// An object that could not be deleted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeleteFailure {
+    /// The object key.
+    pub key: String,
+    /// The version that was targeted, in [`VersionedDeletion::AllVersions`] mode.
+    pub version_id: Option<String>,
+    /// Why the deletion failed.
+    pub error: String,
+}
+
+/// Result of [`OssClient::delete_prefix`].
+#[derive(Debug, Clone, Default)]
+pub struct DeletePrefixResponse {
+    /// Number of objects, versions or delete markers deleted.
+    pub deleted: u64,
+    /// Entries that could not be deleted.
+    pub failures: Vec<DeleteFailure>,
+}
+
+impl DeletePrefixResponse {
+    /// Returns `true` if every listed entry was deleted.
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+impl OssClient {
+    /// Delete every object under `prefix`.
+    ///
+    /// The listing is paged and keys are deleted in DeleteMultipleObjects
+    /// batches of up to 1000. A failed batch, or a key OSS does not report
+    /// as deleted, is recorded in [`DeletePrefixResponse::failures`] and the
+    /// deletion carries on; only a listing error aborts it. An empty prefix
+    /// deletes the whole bucket's contents.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use rs_ali_oss::*;
+    /// # use rs_ali_oss::ops::bulk::VersionedDeletion;
+    /// # async fn example(client: OssClient) -> Result<()> {
+    /// let response = client
+    ///     .delete_prefix(BucketName::new("my-bucket")?, "tmp/")
+    ///     .versions(VersionedDeletion::AllVersions)
+    ///     .send()
+    ///     .await?;
+    /// println!("deleted {}, failed {}", response.deleted, response.failures.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn delete_prefix(
+        &self,
+        bucket: BucketName,
+        prefix: impl Into<String>,
+    ) -> DeletePrefixBuilder {
+        DeletePrefixBuilder {
+            client: self.clone(),
+            bucket,
+            prefix: prefix.into(),
+            versions: VersionedDeletion::default(),
+        }
+    }
+}
+
+/// A multipart upload that could not be aborted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbortFailure {
+    /// The object key of the upload.
+    pub key: String,
+    /// The upload ID.
+    pub upload_id: String,
+    /// Why the abort failed.
+    pub error: String,
+}
+
+/// Result of [`OssClient::delete_bucket_force`].
+#[derive(Debug, Clone, Default)]
+pub struct DeleteBucketForceResponse {
+    /// Number of multipart uploads aborted.
+    pub aborted_uploads: u64,
+    /// Uploads that were skipped because their key is not a valid object key.
+    pub abort_failures: Vec<AbortFailure>,
+    /// Number of objects, versions or delete markers deleted.
+    pub deleted: u64,
+    /// OSS request ID of the DeleteBucket call.
+    pub request_id: Option<String>,
+}
+
+impl OssClient {
+    /// Empty a bucket and delete it.
+    ///
+    /// Aborts every in-progress multipart upload, deletes every object, and
+    /// then deletes the bucket. If versioning was ever enabled, every
+    /// version and delete marker is deleted permanently. Meant for tearing
+    /// down test environments: nothing in the bucket can be recovered.
+    ///
+    /// An upload whose key is not a valid [`ObjectKey`] is skipped and
+    /// recorded in [`DeleteBucketForceResponse::abort_failures`]. Objects that
+    /// cannot be deleted make the final DeleteBucket call fail with
+    /// [`ErrorCode::BucketNotEmpty`](crate::error::ErrorCode::BucketNotEmpty).
+    pub async fn delete_bucket_force(
+        &self,
+        bucket: BucketName,
+    ) -> Result<DeleteBucketForceResponse> {
+        let (aborted_uploads, abort_failures) = self.abort_all_multipart_uploads(&bucket).await?;
+
+        let versioning = self
+            .get_bucket_versioning(
+                GetBucketVersioningRequestBuilder::new()
+                    .bucket(bucket.clone())
+                    .build()?,
+            )
+            .await?;
+        // A suspended bucket still holds the versions made while enabled.
+        let versions = match versioning.status {
+            Some(_) => VersionedDeletion::AllVersions,
+            None => VersionedDeletion::DeleteMarkers,
+        };
+        let deleted = self
+            .delete_prefix(bucket.clone(), "")
+            .versions(versions)
+            .send()
+            .await?
+            .deleted;
+
+        let response = self
+            .delete_bucket(DeleteBucketRequestBuilder::new().bucket(bucket).build()?)
+            .await?;
+        Ok(DeleteBucketForceResponse {
+            aborted_uploads,
+            abort_failures,
+            deleted,
+            request_id: response.request_id,
+        })
+    }
+
+    async fn abort_all_multipart_uploads(
+        &self,
+        bucket: &BucketName,
+    ) -> Result<(u64, Vec<AbortFailure>)> {
+        let mut aborted = 0;
+        let mut failures = Vec::new();
+        let mut markers: Option<(String, String)> = None;
+        loop {
+            let mut builder = ListMultipartUploadsRequestBuilder::new().bucket(bucket.clone());
+            if let Some((key_marker, upload_id_marker)) = markers.take() {
+                builder = builder
+                    .key_marker(key_marker)
+                    .upload_id_marker(upload_id_marker);
+            }
+            let page = self.list_multipart_uploads(builder.build()?).await?;
+            for upload in page.uploads {
+                let key = match ObjectKey::new(upload.key.clone()) {
+                    Ok(key) => key,
+                    Err(e) => {
+                        failures.push(AbortFailure {
+                            key: upload.key,
+                            upload_id: upload.upload_id,
+                            error: e.to_string(),
+                        });
+                        continue;
+                    }
+                };
+                let request = AbortMultipartUploadRequestBuilder::new()
+                    .bucket(bucket.clone())
+                    .key(key)
+                    .upload_id(upload.upload_id)
+                    .build()?;
+                match self.abort_multipart_upload(request).await {
+                    Ok(_) => aborted += 1,
+                    // Completed or aborted since it was listed.
+                    Err(e) if e.is_not_found() => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            match (page.next_key_marker, page.next_upload_id_marker) {
+                (Some(key_marker), Some(upload_id_marker)) if page.is_truncated => {
+                    markers = Some((key_marker, upload_id_marker));
+                }
+                _ => return Ok((aborted, failures)),
+            }
+        }
+    }
+}
+
+/// Builder for a [`OssClient::delete_prefix`] call.
+pub struct DeletePrefixBuilder {
+    client: OssClient,
+    bucket: BucketName,
+    prefix: String,
+    versions: VersionedDeletion,
+}
+
+impl DeletePrefixBuilder {
+    /// Choose what to delete in a versioned bucket (default:
+    /// [`VersionedDeletion::DeleteMarkers`]).
+    pub fn versions(mut self, versions: VersionedDeletion) -> Self {
+        self.versions = versions;
+        self
+    }
+
+    /// Run the deletion.
+    pub async fn send(self) -> Result<DeletePrefixResponse> {
+        let mut batch = DeleteBatch::new(&self.client, &self.bucket);
+        match self.versions {
+            VersionedDeletion::DeleteMarkers => self.delete_current(&mut batch).await?,
+            VersionedDeletion::AllVersions => self.delete_all_versions(&mut batch).await?,
+        }
+        batch.flush().await;
+        Ok(batch.response)
+    }
+
+    async fn delete_current(&self, batch: &mut DeleteBatch<'_>) -> Result<()> {
+        let mut continuation_token = None;
+        loop {
+            let mut builder = ListObjectsV2RequestBuilder::new()
+                .bucket(self.bucket.clone())
+                .prefix(self.prefix.clone());
+            if let Some(token) = continuation_token.take() {
+                builder = builder.continuation_token(token);
+            }
+            let page = self.client.list_objects_v2(builder.build()?).await?;
+            for object in page.contents {
+                batch.push(object.key, None).await;
+            }
+            match page.next_continuation_token {
+                Some(token) if page.is_truncated => continuation_token = Some(token),
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    async fn delete_all_versions(&self, batch: &mut DeleteBatch<'_>) -> Result<()> {
+        let mut markers: Option<(String, String)> = None;
+        loop {
+            let mut builder = ListObjectVersionsRequestBuilder::new()
+                .bucket(self.bucket.clone())
+                .prefix(self.prefix.clone());
+            if let Some((key_marker, version_id_marker)) = markers.take() {
+                builder = builder
+                    .key_marker(key_marker)
+                    .version_id_marker(version_id_marker);
+            }
+            let page = self.client.list_object_versions(builder.build()?).await?;
+            let mut entries: Vec<(String, String)> = page
+                .versions
+                .into_iter()
+                .map(|v| (v.key, v.version_id))
+                .chain(
+                    page.delete_markers
+                        .into_iter()
+                        .map(|m| (m.key, m.version_id)),
+                )
+                .collect();
+            entries.sort();
+            for (key, version_id) in entries {
+                batch.push(key, Some(version_id)).await;
+            }
+            match (page.next_key_marker, page.next_version_id_marker) {
+                (Some(key_marker), Some(version_id_marker)) if page.is_truncated => {
+                    markers = Some((key_marker, version_id_marker));
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+}
+
+impl fmt::Debug for DeletePrefixBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeletePrefixBuilder")
+            .field("bucket", &self.bucket)
+            .field("prefix", &self.prefix)
+            .field("versions", &self.versions)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Accumulates entries and deletes them a full batch at a time.
+struct DeleteBatch<'a> {
+    client: &'a OssClient,
+    bucket: &'a BucketName,
+    pending: Vec<(ObjectKey, Option<String>)>,
+    response: DeletePrefixResponse,
+}
+
+impl<'a> DeleteBatch<'a> {
+    fn new(client: &'a OssClient, bucket: &'a BucketName) -> Self {
+        Self {
+            client,
+            bucket,
+            pending: Vec::new(),
+            response: DeletePrefixResponse::default(),
+        }
+    }
+
+    async fn push(&mut self, key: String, version_id: Option<String>) {
+        match ObjectKey::new(key.clone()) {
+            Ok(object_key) => self.pending.push((object_key, version_id)),
+            Err(e) => self.response.failures.push(DeleteFailure {
+                key,
+                version_id,
+                error: e.to_string(),
+            }),
+        }
+        if self.pending.len() == DELETE_BATCH_SIZE {
+            self.flush().await;
+        }
+    }
+
+    async fn flush(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let entries = std::mem::take(&mut self.pending);
+        let mut builder = DeleteMultipleObjectsRequestBuilder::new()
+            .bucket(self.bucket.clone())
+            .quiet(false);
+        for (key, version_id) in &entries {
+            builder = match version_id {
+                Some(version_id) => builder.version(key.clone(), version_id.clone()),
+                None => builder.key(key.clone()),
+            };
+        }
+        let result = match builder.build() {
+            Ok(request) => self.client.delete_multiple_objects(request).await,
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(response) => {
+                // OSS only echoes a version ID for entries that named one.
+                let deleted: HashSet<(&str, Option<&str>)> = response
+                    .deleted
+                    .iter()
+                    .map(|d| (d.key.as_str(), d.version_id.as_deref()))
+                    .collect();
+                for (key, version_id) in entries {
+                    if deleted.contains(&(key.as_ref(), version_id.as_deref()))
+                        || deleted.contains(&(key.as_ref(), None))
+                    {
+                        self.response.deleted += 1;
+                    } else {
+                        self.response.failures.push(DeleteFailure {
+                            key: key.to_string(),
+                            version_id,
+                            error: "not reported as deleted".into(),
+                        });
+                    }
+                }
+            }
+            Err(e) => {
+                let error = e.to_string();
+                self.response
+                    .failures
+                    .extend(entries.into_iter().map(|(key, version_id)| DeleteFailure {
+                        key: key.to_string(),
+                        version_id,
+                        error: error.clone(),
+                    }));
+            }
+        }
+    }
+}
+
+/// A copy made, or planned in dry-run mode, by [`OssClient::copy_prefix`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixCopy {
+    /// Key of the source object.
+    pub source_key: String,
+    /// Key of the copy in the destination bucket.
+    pub destination_key: String,
+    /// Size of the object in bytes.
+    pub size: u64,
+}
+
+/// An object that could not be copied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyFailure {
+    /// Key of the source object.
+    pub source_key: String,
+    /// Why the copy failed.
+    pub error: String,
+}
+
+/// Result of [`OssClient::copy_prefix`].
+#[derive(Debug, Clone, Default)]
+pub struct CopyPrefixResponse {
+    /// Number of objects copied, or that would be copied in dry-run mode.
+    pub copied: u64,
+    /// Total size of those objects in bytes.
+    pub bytes: u64,
+    /// The copies that would be made. Only filled in dry-run mode.
+    pub planned: Vec<PrefixCopy>,
+    /// Objects that could not be copied.
+    pub failures: Vec<CopyFailure>,
+}
+
+impl CopyPrefixResponse {
+    /// Returns `true` if every listed object was copied.
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    fn record(&mut self, result: std::result::Result<PrefixCopy, CopyFailure>) {
+        match result {
+            Ok(copy) => {
+                self.copied += 1;
+                self.bytes += copy.size;
+            }
+            Err(failure) => self.failures.push(failure),
+        }
+    }
+}
+
+impl OssClient {
+    /// Server-side copy every object under `source_prefix` in `source_bucket`
+    /// to `destination_prefix` in `destination_bucket`.
+    ///
+    /// Each key keeps its path relative to the prefix, so copying `a/` to
+    /// `b/` turns `a/x/y.txt` into `b/x/y.txt`. A failed copy is recorded in
+    /// [`CopyPrefixResponse::failures`] and the rest carry on; only a
+    /// listing error aborts the run.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use rs_ali_oss::*;
+    /// # async fn example(client: OssClient) -> Result<()> {
+    /// let plan = client
+    ///     .copy_prefix(
+    ///         BucketName::new("my-bucket")?,
+    ///         "2023/",
+    ///         BucketName::new("my-archive")?,
+    ///         "logs/2023/",
+    ///     )
+    ///     .storage_class(StorageClass::Archive)
+    ///     .dry_run(true)
+    ///     .send()
+    ///     .await?;
+    /// for copy in &plan.planned {
+    ///     println!("{} -> {}", copy.source_key, copy.destination_key);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn copy_prefix(
+        &self,
+        source_bucket: BucketName,
+        source_prefix: impl Into<String>,
+        destination_bucket: BucketName,
+        destination_prefix: impl Into<String>,
+    ) -> CopyPrefixBuilder {
+        CopyPrefixBuilder {
+            client: self.clone(),
+            source_bucket,
+            source_prefix: source_prefix.into(),
+            destination_bucket,
+            destination_prefix: destination_prefix.into(),
+            concurrency: DEFAULT_COPY_CONCURRENCY,
+            storage_class: None,
+            acl: None,
+            dry_run: false,
+        }
+    }
+}
+
+/// Builder for a [`OssClient::copy_prefix`] call.
+pub struct CopyPrefixBuilder {
+    client: OssClient,
+    source_bucket: BucketName,
+    source_prefix: String,
+    destination_bucket: BucketName,
+    destination_prefix: String,
+    concurrency: usize,
+    storage_class: Option<StorageClass>,
+    acl: Option<ObjectAcl>,
+    dry_run: bool,
+}
+
+impl CopyPrefixBuilder {
+    /// Maximum number of copies in flight (default: 8).
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Give the copies this storage class instead of the source's.
+    pub fn storage_class(mut self, storage_class: StorageClass) -> Self {
+        self.storage_class = Some(storage_class);
+        self
+    }
+
+    /// Give the copies this ACL.
+    pub fn acl(mut self, acl: ObjectAcl) -> Self {
+        self.acl = Some(acl);
+        self
+    }
+
+    /// List the copies that would be made without making them.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Run the copy.
+    pub async fn send(self) -> Result<CopyPrefixResponse> {
+        if self.concurrency == 0 {
+            return Err(OssError::InvalidParameter {
+                field: "concurrency".into(),
+                reason: "must be at least 1".into(),
+            });
+        }
+        // A destination inside the source would be listed, and copied
+        // again, as the run progresses.
+        if self.source_bucket == self.destination_bucket
+            && self.destination_prefix != self.source_prefix
+            && self.destination_prefix.starts_with(&self.source_prefix)
+        {
+            return Err(OssError::InvalidParameter {
+                field: "destination_prefix".into(),
+                reason: "must not be inside the source prefix".into(),
+            });
+        }
+
+        let mut response = CopyPrefixResponse::default();
+        let mut join_set = JoinSet::new();
+        let mut continuation_token = None;
+        loop {
+            let mut builder = ListObjectsV2RequestBuilder::new()
+                .bucket(self.source_bucket.clone())
+                .prefix(self.source_prefix.clone());
+            if let Some(token) = continuation_token.take() {
+                builder = builder.continuation_token(token);
+            }
+            let page = self.client.list_objects_v2(builder.build()?).await?;
+
+            for object in page.contents {
+                let copy = PrefixCopy {
+                    destination_key: format!(
+                        "{}{}",
+                        self.destination_prefix,
+                        &object.key[self.source_prefix.len()..]
+                    ),
+                    source_key: object.key,
+                    size: object.size,
+                };
+                if self.dry_run {
+                    response.record(Ok(copy.clone()));
+                    response.planned.push(copy);
+                    continue;
+                }
+                if join_set.len() == self.concurrency
+                    && let Some(result) = join_set.join_next().await
+                {
+                    response.record(result.map_err(join_failure)?);
+                }
+                join_set.spawn(self.copy_one(copy));
+            }
+
+            match page.next_continuation_token {
+                Some(token) if page.is_truncated => continuation_token = Some(token),
+                _ => break,
+            }
+        }
+        while let Some(result) = join_set.join_next().await {
+            response.record(result.map_err(join_failure)?);
+        }
+        Ok(response)
+    }
+
+    fn copy_one(
+        &self,
+        copy: PrefixCopy,
+    ) -> impl Future<Output = std::result::Result<PrefixCopy, CopyFailure>> + Send + 'static {
+        let client = self.client.clone();
+        let mut builder = CopyObjectRequestBuilder::new()
+            .bucket(self.destination_bucket.clone())
+            .source_bucket(self.source_bucket.clone());
+        if let Some(storage_class) = self.storage_class {
+            builder = builder.storage_class(storage_class);
+        }
+        if let Some(acl) = &self.acl {
+            builder = builder.acl(acl.clone());
+        }
+        async move {
+            let result = async {
+                let request = builder
+                    .key(ObjectKey::new(copy.destination_key.clone())?)
+                    .source_key(ObjectKey::new(copy.source_key.clone())?)
+                    .build()?;
+                client.copy_object_sized(request, copy.size).await
+            }
+            .await;
+            match result {
+                Ok(_) => Ok(copy),
+                Err(e) => Err(CopyFailure {
+                    source_key: copy.source_key,
+                    error: e.to_string(),
+                }),
+            }
+        }
+    }
+}
+
+impl fmt::Debug for CopyPrefixBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CopyPrefixBuilder")
+            .field("source_bucket", &self.source_bucket)
+            .field("source_prefix", &self.source_prefix)
+            .field("destination_bucket", &self.destination_bucket)
+            .field("destination_prefix", &self.destination_prefix)
+            .field("concurrency", &self.concurrency)
+            .field("storage_class", &self.storage_class)
+            .field("acl", &self.acl)
+            .field("dry_run", &self.dry_run)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An archived object whose restore could not be requested.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestoreFailure {
+    /// Key of the object.
+    pub key: String,
+    /// Why the restore failed.
+    pub error: String,
+}
+
+/// Result of [`OssClient::restore_prefix`].
+#[derive(Debug, Clone, Default)]
+pub struct RestorePrefixResponse {
+    /// Number of objects a restore was requested for.
+    pub requested: u64,
+    /// Number of objects skipped because they were already restored.
+    pub already_restored: u64,
+    /// Number of objects skipped because a restore was already in progress.
+    pub in_progress: u64,
+    /// Number of listed objects skipped because they are not archived.
+    pub not_archived: u64,
+    /// Objects whose restore could not be requested.
+    pub failures: Vec<RestoreFailure>,
+}
+
+impl RestorePrefixResponse {
+    /// Returns `true` if every archived object is restored or being restored.
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    fn record(&mut self, result: std::result::Result<RestoreOutcome, RestoreFailure>) {
+        match result {
+            Ok(RestoreOutcome::Requested) => self.requested += 1,
+            Ok(RestoreOutcome::AlreadyRestored) => self.already_restored += 1,
+            Ok(RestoreOutcome::InProgress) => self.in_progress += 1,
+            Err(failure) => self.failures.push(failure),
+        }
+    }
+}
+
+/// What happened to a single archived object.
+enum RestoreOutcome {
+    Requested,
+    AlreadyRestored,
+    InProgress,
+}
+
+impl OssClient {
+    /// Request a restore of every Archive, ColdArchive and DeepColdArchive
+    /// object under `prefix`.
+    ///
+    /// Each archived object is checked with HeadObject first, and objects
+    /// that are already restored or being restored are skipped. A failed
+    /// restore is recorded in [`RestorePrefixResponse::failures`] and the
+    /// rest carry on; only a listing error aborts the run.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use rs_ali_oss::*;
+    /// # async fn example(client: OssClient) -> Result<()> {
+    /// let report = client
+    ///     .restore_prefix(BucketName::new("my-bucket")?, "events/dt=2024-06-01/")
+    ///     .days(3)
+    ///     .send()
+    ///     .await?;
+    /// println!(
+    ///     "{} requested, {} in progress, {} already restored",
+    ///     report.requested, report.in_progress, report.already_restored
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn restore_prefix(
+        &self,
+        bucket: BucketName,
+        prefix: impl Into<String>,
+    ) -> RestorePrefixBuilder {
+        RestorePrefixBuilder {
+            client: self.clone(),
+            bucket,
+            prefix: prefix.into(),
+            days: 1,
+            concurrency: DEFAULT_RESTORE_CONCURRENCY,
+        }
+    }
+}
+
+/// Builder for a [`OssClient::restore_prefix`] call.
+pub struct RestorePrefixBuilder {
+    client: OssClient,
+    bucket: BucketName,
+    prefix: String,
+    days: u32,
+    concurrency: usize,
+}
+
+impl RestorePrefixBuilder {
+    /// Number of days to keep the restored copies available (default: 1).
+    pub fn days(mut self, days: u32) -> Self {
+        self.days = days;
+        self
+    }
+
+    /// Maximum number of objects checked or restored at once (default: 8).
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Run the restore.
+    pub async fn send(self) -> Result<RestorePrefixResponse> {
+        if self.concurrency == 0 {
+            return Err(OssError::InvalidParameter {
+                field: "concurrency".into(),
+                reason: "must be at least 1".into(),
+            });
+        }
+        if self.days == 0 {
+            return Err(OssError::InvalidParameter {
+                field: "days".into(),
+                reason: "must be at least 1".into(),
+            });
+        }
+
+        let mut response = RestorePrefixResponse::default();
+        let mut join_set = JoinSet::new();
+        let mut continuation_token = None;
+        loop {
+            let mut builder = ListObjectsV2RequestBuilder::new()
+                .bucket(self.bucket.clone())
+                .prefix(self.prefix.clone());
+            if let Some(token) = continuation_token.take() {
+                builder = builder.continuation_token(token);
+            }
+            let page = self.client.list_objects_v2(builder.build()?).await?;
+
+            for object in page.contents {
+                if !matches!(
+                    object.storage_class,
+                    StorageClass::Archive
+                        | StorageClass::ColdArchive
+                        | StorageClass::DeepColdArchive
+                ) {
+                    response.not_archived += 1;
+                    continue;
+                }
+                if join_set.len() == self.concurrency
+                    && let Some(result) = join_set.join_next().await
+                {
+                    response.record(result.map_err(join_failure)?);
+                }
+                join_set.spawn(self.restore_one(object.key));
+            }
+
+            match page.next_continuation_token {
+                Some(token) if page.is_truncated => continuation_token = Some(token),
+                _ => break,
+            }
+        }
+        while let Some(result) = join_set.join_next().await {
+            response.record(result.map_err(join_failure)?);
+        }
+        Ok(response)
+    }
+
+    fn restore_one(
+        &self,
+        key: String,
+    ) -> impl Future<Output = std::result::Result<RestoreOutcome, RestoreFailure>> + Send + 'static
+    {
+        let client = self.client.clone();
+        let bucket = self.bucket.clone();
+        let days = self.days;
+        async move {
+            let result = async {
+                let object_key = ObjectKey::new(key.clone())?;
+                let head = HeadObjectRequestBuilder::new()
+                    .bucket(bucket.clone())
+                    .key(object_key.clone())
+                    .build()?;
+                if let Some(restore) = client.head_object(head).await?.restore {
+                    return Ok(if restore.ongoing {
+                        RestoreOutcome::InProgress
+                    } else {
+                        RestoreOutcome::AlreadyRestored
+                    });
+                }
+                let request = RestoreObjectRequestBuilder::new()
+                    .bucket(bucket)
+                    .key(object_key)
+                    .days(days)
+                    .build()?;
+                match client.restore_object(request).await {
+                    Ok(_) => Ok(RestoreOutcome::Requested),
+                    // Another client got there between the check and the request.
+                    Err(e) if e.error_code() == Some(ErrorCode::RestoreAlreadyInProgress) => {
+                        Ok(RestoreOutcome::InProgress)
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            .await;
+            result.map_err(|e: OssError| RestoreFailure {
+                key,
+                error: e.to_string(),
+            })
+        }
+    }
+}
+
+impl fmt::Debug for RestorePrefixBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RestorePrefixBuilder")
+            .field("bucket", &self.bucket)
+            .field("prefix", &self.prefix)
+            .field("days", &self.days)
+            .field("concurrency", &self.concurrency)
+            .finish_non_exhaustive()
+    }
+}
+
+fn join_failure(e: tokio::task::JoinError) -> OssError {
+    OssError::Io(std::io::Error::other(format!("prefix task panicked: {e}")))
+}
@@ -0,0 +1,536 @@
+//! Integrity manifests of the objects under a prefix.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinSet;
+
+use crate::client::OssClient;
+use crate::crc64::Crc64;
+use crate::error::{OssError, Result};
+use crate::types::common::{BucketName, ObjectKey};
+use crate::types::request::{HeadObjectRequestBuilder, ListObjectsV2RequestBuilder};
+
+const DEFAULT_HEAD_CONCURRENCY: usize = 8;
+
+const CSV_HEADER: [&str; 5] = ["key", "size", "etag", "crc64", "last_modified"];
+
+/// One object in a [`Manifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Full object key.
+    pub key: String,
+    /// Size in bytes.
+    pub size: u64,
+    /// ETag, without surrounding quotes.
+    pub etag: String,
+    /// CRC64 as computed by OSS, when recorded.
+    pub crc64: Option<u64>,
+    /// Last modified timestamp.
+    pub last_modified: DateTime<Utc>,
+}
+
+/// A record of every object under a prefix, for auditing a bucket or
+/// validating a migration.
+///
+/// Build one with [`OssClient::manifest`], save it with [`to_csv`] or
+/// [`to_json`], and later check a local copy with [`verify_dir`].
+///
+/// [`to_csv`]: Manifest::to_csv
+/// [`to_json`]: Manifest::to_json
+/// [`verify_dir`]: Manifest::verify_dir
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// The listed prefix. [`Manifest::verify_dir`] strips it from each key
+    /// to find the local file.
+    pub prefix: String,
+    /// The objects, in key order.
+    pub entries: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    /// Total size of all entries in bytes.
+    pub fn total_size(&self) -> u64 {
+        self.entries.iter().map(|e| e.size).sum()
+    }
+
+    /// Serialize as JSON.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| OssError::JsonParse(e.to_string()))
+    }
+
+    /// Parse a manifest written by [`Manifest::to_json`].
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| OssError::JsonParse(e.to_string()))
+    }
+
+    /// Serialize the entries as CSV with a
+    /// `key,size,etag,crc64,last_modified` header. A missing CRC64 is an
+    /// empty field.
+    ///
+    /// CSV carries no prefix; set [`Manifest::prefix`] after
+    /// [`Manifest::from_csv`] if the manifest will be verified.
+    pub fn to_csv(&self) -> String {
+        let mut csv = CSV_HEADER.join(",");
+        csv.push('\n');
+        for entry in &self.entries {
+            let fields = [
+                csv_field(&entry.key),
+                entry.size.to_string(),
+                csv_field(&entry.etag),
+                entry.crc64.map(|c| c.to_string()).unwrap_or_default(),
+                entry
+                    .last_modified
+                    .to_rfc3339_opts(SecondsFormat::Millis, true),
+            ];
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Parse a manifest written by [`Manifest::to_csv`]. The prefix is left
+    /// empty.
+    pub fn from_csv(csv: &str) -> Result<Self> {
+        let mut rows = parse_csv(csv)?.into_iter().enumerate();
+        match rows.next() {
+            Some((_, header)) if header == CSV_HEADER => {}
+            _ => {
+                return Err(csv_error(
+                    1,
+                    "expected a key,size,etag,crc64,last_modified header",
+                ));
+            }
+        }
+
+        let mut entries = Vec::new();
+        for (index, row) in rows {
+            let line = index + 1;
+            let [key, size, etag, crc64, last_modified] =
+                <[String; 5]>::try_from(row).map_err(|_| csv_error(line, "expected 5 fields"))?;
+            entries.push(ManifestEntry {
+                key,
+                size: size.parse().map_err(|_| csv_error(line, "invalid size"))?,
+                etag,
+                crc64: match crc64.as_str() {
+                    "" => None,
+                    crc64 => Some(
+                        crc64
+                            .parse()
+                            .map_err(|_| csv_error(line, "invalid crc64"))?,
+                    ),
+                },
+                last_modified: DateTime::parse_from_rfc3339(&last_modified)
+                    .map_err(|_| csv_error(line, "invalid last_modified"))?
+                    .with_timezone(&Utc),
+            });
+        }
+        Ok(Self {
+            prefix: String::new(),
+            entries,
+        })
+    }
+
+    /// Compare a local directory holding a copy of the prefix against this
+    /// manifest.
+    ///
+    /// Each entry's key, minus [`Manifest::prefix`], is a path under `dir`.
+    /// Sizes are always compared, and file contents are checksummed when
+    /// the entry has a CRC64. ETags are not checked, since those of
+    /// multipart uploads are not content hashes. Local files with no entry
+    /// are reported too. Keys ending in `/` are directory placeholders and
+    /// are skipped.
+    pub fn verify_dir(&self, dir: impl AsRef<Path>) -> Result<VerifyReport> {
+        let dir = dir.as_ref();
+        let mut report = VerifyReport::default();
+        let mut expected = HashSet::new();
+
+        for entry in self.entries.iter().filter(|e| !e.key.ends_with('/')) {
+            let relative = entry
+                .key
+                .strip_prefix(self.prefix.as_str())
+                .unwrap_or(&entry.key);
+            expected.insert(relative);
+            let path: PathBuf = dir.join(relative.split('/').collect::<PathBuf>());
+
+            let actual_size = match std::fs::metadata(&path) {
+                Ok(metadata) if metadata.is_file() => metadata.len(),
+                Ok(_) => {
+                    report.push(&entry.key, VerifyIssueKind::Missing);
+                    continue;
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    report.push(&entry.key, VerifyIssueKind::Missing);
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
+            if actual_size != entry.size {
+                report.push(
+                    &entry.key,
+                    VerifyIssueKind::SizeMismatch {
+                        expected: entry.size,
+                        actual: actual_size,
+                    },
+                );
+                continue;
+            }
+            if let Some(expected_crc) = entry.crc64 {
+                let mut crc = Crc64::new();
+                std::io::copy(&mut File::open(&path)?, &mut crc)?;
+                let actual_crc = crc.finalize();
+                if actual_crc != expected_crc {
+                    report.push(
+                        &entry.key,
+                        VerifyIssueKind::Crc64Mismatch {
+                            expected: expected_crc,
+                            actual: actual_crc,
+                        },
+                    );
+                    continue;
+                }
+            }
+            report.matched += 1;
+        }
+
+        let mut local = Vec::new();
+        list_files(dir, "", &mut local)?;
+        for relative in local {
+            if !expected.contains(relative.as_str()) {
+                report.push(
+                    &format!("{}{relative}", self.prefix),
+                    VerifyIssueKind::Unexpected,
+                );
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// What [`Manifest::verify_dir`] found wrong with one key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerifyIssueKind {
+    /// The manifest lists the key but the local file does not exist.
+    Missing,
+    /// The local file has a different size.
+    SizeMismatch {
+        /// Size recorded in the manifest.
+        expected: u64,
+        /// Size of the local file.
+        actual: u64,
+    },
+    /// The local file has a different CRC64.
+    Crc64Mismatch {
+        /// CRC64 recorded in the manifest.
+        expected: u64,
+        /// CRC64 of the local file.
+        actual: u64,
+    },
+    /// A local file that the manifest does not list.
+    Unexpected,
+}
+
+/// A key that failed verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyIssue {
+    /// The object key, or the key a local file would have.
+    pub key: String,
+    /// What is wrong.
+    pub kind: VerifyIssueKind,
+}
+
+/// Result of [`Manifest::verify_dir`].
+#[derive(Debug, Clone, Default)]
+pub struct VerifyReport {
+    /// Number of entries whose local file matched.
+    pub matched: u64,
+    /// Entries and local files that did not match.
+    pub issues: Vec<VerifyIssue>,
+}
+
+impl VerifyReport {
+    /// Returns `true` if the directory matched the manifest exactly.
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    fn push(&mut self, key: &str, kind: VerifyIssueKind) {
+        self.issues.push(VerifyIssue {
+            key: key.to_string(),
+            kind,
+        });
+    }
+}
+
+impl OssClient {
+    /// Build a [`Manifest`] of every object under `prefix`.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use rs_ali_oss::*;
+    /// # async fn example(client: OssClient) -> Result<()> {
+    /// let manifest = client
+    ///     .manifest(BucketName::new("my-bucket")?, "exports/2024/")
+    ///     .send()
+    ///     .await?;
+    /// std::fs::write("manifest.csv", manifest.to_csv())?;
+    ///
+    /// let report = manifest.verify_dir("/mnt/restore/2024")?;
+    /// assert!(report.is_clean(), "{:?}", report.issues);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn manifest(&self, bucket: BucketName, prefix: impl Into<String>) -> ManifestBuilder {
+        ManifestBuilder {
+            client: self.clone(),
+            bucket,
+            prefix: prefix.into(),
+            crc64: true,
+            concurrency: DEFAULT_HEAD_CONCURRENCY,
+        }
+    }
+}
+
+/// Builder for a [`OssClient::manifest`] call.
+pub struct ManifestBuilder {
+    client: OssClient,
+    bucket: BucketName,
+    prefix: String,
+    crc64: bool,
+    concurrency: usize,
+}
+
+impl ManifestBuilder {
+    /// Record each object's CRC64 (default: true). Listings do not include
+    /// it, so this costs one HeadObject request per object.
+    pub fn crc64(mut self, crc64: bool) -> Self {
+        self.crc64 = crc64;
+        self
+    }
+
+    /// Maximum number of HeadObject requests in flight (default: 8).
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// List the prefix and build the manifest.
+    pub async fn send(self) -> Result<Manifest> {
+        if self.concurrency == 0 {
+            return Err(OssError::InvalidParameter {
+                field: "concurrency".into(),
+                reason: "must be at least 1".into(),
+            });
+        }
+
+        let mut entries = Vec::new();
+        let mut join_set = JoinSet::new();
+        let mut continuation_token = None;
+        loop {
+            let mut builder = ListObjectsV2RequestBuilder::new()
+                .bucket(self.bucket.clone())
+                .prefix(self.prefix.clone());
+            if let Some(token) = continuation_token.take() {
+                builder = builder.continuation_token(token);
+            }
+            let page = self.client.list_objects_v2(builder.build()?).await?;
+
+            for object in page.contents {
+                let entry = ManifestEntry {
+                    key: object.key,
+                    size: object.size,
+                    etag: object.etag.trim_matches('"').to_string(),
+                    crc64: None,
+                    last_modified: object.last_modified,
+                };
+                if !self.crc64 {
+                    entries.push(entry);
+                    continue;
+                }
+                if join_set.len() == self.concurrency
+                    && let Some(result) = join_set.join_next().await
+                {
+                    entries.push(result.map_err(join_failure)??);
+                }
+                join_set.spawn(self.head_crc64(entry));
+            }
+
+            match page.next_continuation_token {
+                Some(token) if page.is_truncated => continuation_token = Some(token),
+                _ => break,
+            }
+        }
+        while let Some(result) = join_set.join_next().await {
+            entries.push(result.map_err(join_failure)??);
+        }
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+
+        Ok(Manifest {
+            prefix: self.prefix,
+            entries,
+        })
+    }
+
+    fn head_crc64(
+        &self,
+        mut entry: ManifestEntry,
+    ) -> impl Future<Output = Result<ManifestEntry>> + Send + 'static {
+        let client = self.client.clone();
+        let bucket = self.bucket.clone();
+        async move {
+            let request = HeadObjectRequestBuilder::new()
+                .bucket(bucket)
+                .key(ObjectKey::new(entry.key.clone())?)
+                .build()?;
+            entry.crc64 = client.head_object(request).await?.crc64;
+            Ok(entry)
+        }
+    }
+}
+
+impl fmt::Debug for ManifestBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ManifestBuilder")
+            .field("bucket", &self.bucket)
+            .field("prefix", &self.prefix)
+            .field("crc64", &self.crc64)
+            .field("concurrency", &self.concurrency)
+            .finish_non_exhaustive()
+    }
+}
+
+fn join_failure(e: tokio::task::JoinError) -> OssError {
+    OssError::Io(std::io::Error::other(format!(
+        "HeadObject task panicked: {e}"
+    )))
+}
+
+/// Collect the `/`-separated paths of all files under `dir`.
+fn list_files(dir: &Path, relative: &str, files: &mut Vec<String>) -> Result<()> {
+    let read_dir = match std::fs::read_dir(dir) {
+        Ok(read_dir) => read_dir,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && relative.is_empty() => {
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+    for entry in read_dir {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let path = format!("{relative}{name}");
+        if entry.file_type()?.is_dir() {
+            list_files(&entry.path(), &format!("{path}/"), files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn csv_error(line: usize, reason: &str) -> OssError {
+    OssError::InvalidParameter {
+        field: "csv".into(),
+        reason: format!("line {line}: {reason}"),
+    }
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Split CSV text into rows of fields, honouring quoted fields.
+fn parse_csv(csv: &str) -> Result<Vec<Vec<String>>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = csv.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.is_empty() => in_quotes = true,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\r', false) if chars.peek() == Some(&'\n') => {}
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            (c, _) => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(csv_error(rows.len() + 1, "unterminated quoted field"));
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, crc64: Option<u64>) -> ManifestEntry {
+        ManifestEntry {
+            key: key.to_string(),
+            size: 3,
+            etag: "ABC".to_string(),
+            crc64,
+            last_modified: DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        }
+    }
+
+    #[test]
+    fn csv_round_trips_awkward_keys() {
+        let manifest = Manifest {
+            prefix: String::new(),
+            entries: vec![
+                entry("plain.txt", Some(42)),
+                entry("with, comma \"and quotes\"", None),
+                entry("line\nbreak", Some(u64::MAX)),
+            ],
+        };
+        let csv = manifest.to_csv();
+        assert!(csv.starts_with("key,size,etag,crc64,last_modified\nplain.txt,3,ABC,42,"));
+        assert_eq!(Manifest::from_csv(&csv).unwrap(), manifest);
+    }
+
+    #[test]
+    fn csv_errors_name_the_line() {
+        let csv = "key,size,etag,crc64,last_modified\na,3,E,,2025-01-01T00:00:00Z\nb,x,E,,\n";
+        assert!(matches!(
+            Manifest::from_csv(csv),
+            Err(OssError::InvalidParameter { reason, .. }) if reason == "line 3: invalid size"
+        ));
+        assert!(Manifest::from_csv("a,b\n").is_err());
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let manifest = Manifest {
+            prefix: "p/".into(),
+            entries: vec![entry("p/a", Some(1))],
+        };
+        assert_eq!(
+            Manifest::from_json(&manifest.to_json().unwrap()).unwrap(),
+            manifest
+        );
+    }
+}
@@ -1,8 +1,11 @@
 //! Operation implementations for OSS.
 
 pub mod bucket;
+pub mod bulk;
+pub mod manifest;
 pub mod multipart;
 pub mod object;
 pub mod paginator;
 pub mod presign;
 pub mod transfer;
+pub mod walk;
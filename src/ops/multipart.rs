@@ -1,17 +1,24 @@
 //! Multipart upload operations: Initiate, UploadPart, Complete, Abort, ListParts.
 
-use reqwest::Method;
+use futures_util::TryStreamExt;
+use http_body_util::BodyDataStream;
+use percent_encoding::utf8_percent_encode;
+use reqwest::{Method, StatusCode};
 
-use crate::client::{OssClient, header_etag, header_opt, parse_xml, serialize_xml};
-use crate::error::Result;
+use crate::client::{OssClient, header_crc64, header_etag, header_opt, parse_xml, serialize_xml};
+use crate::encoding::{URI_ENCODE_SET, encode_tagging};
+use crate::error::{OssError, Result};
+use crate::progress::{ProgressTracker, TransferKind};
+use crate::types::common::{BucketName, ObjectKey};
 use crate::types::request::{
     AbortMultipartUploadRequest, CompleteMultipartUploadRequest, CompleteMultipartUploadXml,
     InitiateMultipartUploadRequest, ListMultipartUploadsRequest, ListPartsRequest,
-    UploadPartRequest,
+    ListPartsRequestBuilder, UploadPartCopyRequest, UploadPartRequest,
 };
 use crate::types::response::{
     AbortMultipartUploadResponse, CompleteMultipartUploadResponse, InitiateMultipartUploadResponse,
-    ListMultipartUploadsResponse, ListPartsResponse, UploadPartResponse,
+    ListMultipartUploadsResponse, ListPartsResponse, PartInfo, UploadPartCopyResponse,
+    UploadPartResponse,
 };
 
 impl OssClient {
@@ -20,20 +27,52 @@ impl OssClient {
         &self,
         request: InitiateMultipartUploadRequest,
     ) -> Result<InitiateMultipartUploadResponse> {
-        let url = self.build_url(
-            Some(&request.bucket),
-            Some(&request.key),
-            &[("uploads", "")],
-        )?;
+        let mut query = vec![("uploads", "")];
+        if request.sequential {
+            query.push(("sequential", ""));
+        }
+        let url = self.build_url(Some(&request.bucket), Some(&request.key), &query)?;
         let resource_path = format!("/{}/{}", request.bucket, request.key);
         let mut http_req = self.http_client().request(Method::POST, url);
 
-        if let Some(ref ct) = request.content_type {
-            http_req = http_req.header("content-type", ct.as_str());
+        if let Some(ct) = self.upload_content_type(request.content_type.as_deref(), &request.key) {
+            http_req = http_req.header("content-type", ct);
+        }
+        if let Some(ref cache_control) = request.cache_control {
+            http_req = http_req.header("cache-control", cache_control.as_str());
+        }
+        if let Some(ref disposition) = request.content_disposition {
+            http_req = http_req.header("content-disposition", disposition.as_str());
+        }
+        if let Some(ref encoding) = request.content_encoding {
+            http_req = http_req.header("content-encoding", encoding.as_str());
+        }
+        if let Some(ref expires) = request.expires {
+            http_req = http_req.header("expires", expires.as_str());
         }
         if let Some(sc) = request.storage_class {
             http_req = http_req.header("x-oss-storage-class", sc.to_string());
         }
+        if let Some(acl) = request.acl {
+            http_req = http_req.header("x-oss-object-acl", acl.to_string());
+        }
+        if let Some(sse) = request.server_side_encryption {
+            http_req = http_req.header("x-oss-server-side-encryption", sse.to_string());
+        }
+        if let Some(ref kms_key_id) = request.kms_key_id {
+            http_req = http_req.header("x-oss-server-side-encryption-key-id", kms_key_id.as_str());
+        }
+        if request.forbid_overwrite {
+            http_req = http_req.header("x-oss-forbid-overwrite", "true");
+        }
+        let metadata_encoding = self.config().metadata_encoding();
+        for (k, v) in &request.metadata {
+            let value = metadata_encoding.encode(k, v)?;
+            http_req = http_req.header(format!("x-oss-meta-{k}"), value);
+        }
+        if !request.tags.is_empty() {
+            http_req = http_req.header("x-oss-tagging", encode_tagging(&request.tags));
+        }
 
         let http_req = http_req.build()?;
         let response = self.execute(http_req, &resource_path).await?;
@@ -48,10 +87,10 @@ impl OssClient {
     ///
     /// # Payload Signing
     ///
-    /// When the body is in-memory bytes the SDK computes a SHA-256 payload
-    /// hash for the V4 signature. For non-buffered streaming bodies the SDK
-    /// uses `UNSIGNED-PAYLOAD` — the request is authenticated but the payload
-    /// is not integrity-checked by the signature.
+    /// As with [`put_object`](Self::put_object), the payload is only covered
+    /// by the V4 signature when
+    /// [`ClientBuilder::payload_signing`](crate::config::ClientBuilder::payload_signing)
+    /// asks for it; otherwise `UNSIGNED-PAYLOAD` is sent.
     pub async fn upload_part(&self, request: UploadPartRequest) -> Result<UploadPartResponse> {
         let part_num = request.part_number.to_string();
         let query = [
@@ -60,16 +99,93 @@ impl OssClient {
         ];
         let url = self.build_url(Some(&request.bucket), Some(&request.key), &query)?;
         let resource_path = format!("/{}/{}", request.bucket, request.key);
-        let http_req = self
+        let mut http_req = self.http_client().request(Method::PUT, url);
+        if let Some(ref content_md5) = request.content_md5 {
+            http_req = http_req.header("content-md5", content_md5.as_str());
+        }
+        if let Some(length) = request.content_length {
+            http_req = http_req.header("content-length", length);
+        }
+
+        let mut acknowledge = None;
+        let body = match request.progress_listener {
+            Some(listener) => match request.body.as_bytes().map(|b| b.len() as u64) {
+                Some(len) => {
+                    let tracker = ProgressTracker::new(
+                        listener,
+                        &request.bucket,
+                        &request.key,
+                        Some(len),
+                        TransferKind::Upload,
+                    );
+                    tracker.start();
+                    acknowledge = Some((tracker, len));
+                    request.body
+                }
+                None => {
+                    let tracker = ProgressTracker::new(
+                        listener,
+                        &request.bucket,
+                        &request.key,
+                        request.content_length,
+                        TransferKind::Upload,
+                    );
+                    tracker.start();
+                    let stream = BodyDataStream::new(request.body)
+                        .inspect_ok(move |chunk| tracker.advance(chunk.len() as u64));
+                    reqwest::Body::wrap_stream(stream)
+                }
+            },
+            None => request.body,
+        };
+
+        let http_req = http_req.body(body).build()?;
+        let response = self.execute(http_req, &resource_path).await?;
+        if let Some((tracker, len)) = acknowledge {
+            tracker.advance(len);
+        }
+
+        let etag = header_etag(&response);
+        let crc64 = header_crc64(&response);
+
+        Ok(UploadPartResponse { etag, crc64 })
+    }
+
+    /// Copy a byte range of an existing object into a part of a multipart
+    /// upload, without downloading it.
+    pub async fn upload_part_copy(
+        &self,
+        request: UploadPartCopyRequest,
+    ) -> Result<UploadPartCopyResponse> {
+        let part_num = request.part_number.to_string();
+        let query = [
+            ("partNumber", part_num.as_str()),
+            ("uploadId", request.upload_id.as_str()),
+        ];
+        let url = self.build_url(Some(&request.bucket), Some(&request.key), &query)?;
+        let resource_path = format!("/{}/{}", request.bucket, request.key);
+
+        let encoded_key =
+            utf8_percent_encode(request.source_key.as_ref(), URI_ENCODE_SET).to_string();
+        let copy_source = format!("/{}/{}", request.source_bucket, encoded_key);
+        let mut http_req = self
             .http_client()
             .request(Method::PUT, url)
-            .body(request.body)
-            .build()?;
+            .header("x-oss-copy-source", &copy_source);
+        if let Some((start, end)) = request.source_range {
+            http_req = http_req.header("x-oss-copy-source-range", format!("bytes={start}-{end}"));
+        }
+        if let Some(ref etag) = request.source_if_match {
+            http_req = http_req.header("x-oss-copy-source-if-match", etag.as_str());
+        }
+
+        let http_req = http_req.build()?;
         let response = self.execute(http_req, &resource_path).await?;
 
-        let etag = header_etag(&response);
+        let body = response.text().await?;
+        let copy_resp: UploadPartCopyResponse = parse_xml(&body)?;
 
-        Ok(UploadPartResponse { etag })
+        Ok(copy_resp)
     }
 
     /// Complete a multipart upload by assembling previously uploaded parts.
@@ -81,21 +197,62 @@ impl OssClient {
         let url = self.build_url(Some(&request.bucket), Some(&request.key), &query)?;
         let resource_path = format!("/{}/{}", request.bucket, request.key);
 
-        let xml_body = CompleteMultipartUploadXml {
-            parts: request.parts,
-        };
-        let body_str = serialize_xml(&xml_body)?;
-
-        let http_req = self
-            .http_client()
-            .request(Method::POST, url)
-            .header("content-type", "application/xml")
-            .body(body_str)
-            .build()?;
+        let mut http_req = self.http_client().request(Method::POST, url);
+        if let Some(ref callback) = request.callback {
+            http_req = http_req.header("x-oss-callback", callback.header()?);
+            if let Some(vars) = callback.var_header()? {
+                http_req = http_req.header("x-oss-callback-var", vars);
+            }
+        }
+        if request.complete_all {
+            http_req = http_req.header("x-oss-complete-all", "yes");
+        } else {
+            let xml_body = CompleteMultipartUploadXml {
+                parts: request.parts,
+            };
+            http_req = http_req
+                .header("content-type", "application/xml")
+                .body(serialize_xml(&xml_body)?);
+        }
+        let http_req = http_req.build()?;
         let response = self.execute(http_req, &resource_path).await?;
+        let crc64 = header_crc64(&response);
+        let version_id = header_opt(&response, "x-oss-version-id");
+        let server_side_encryption =
+            header_opt(&response, "x-oss-server-side-encryption").and_then(|s| s.parse().ok());
+        let kms_key_id = header_opt(&response, "x-oss-server-side-encryption-key-id");
 
-        let body = response.text().await?;
-        let complete_resp: CompleteMultipartUploadResponse = parse_xml(&body)?;
+        let mut complete_resp = if request.callback.is_some() {
+            // The callback result replaces the XML body. A 203 means the
+            // upload completed but the callback failed; the body is then an
+            // OSS error document.
+            let status = response.status();
+            let etag = header_opt(&response, "etag").unwrap_or_default();
+            let mut location = response.url().clone();
+            location.set_query(None);
+            let body = response.text().await?;
+            if status == StatusCode::NON_AUTHORITATIVE_INFORMATION {
+                return Err(OssError::from_response_body(status, &body));
+            }
+            CompleteMultipartUploadResponse {
+                location: location.to_string(),
+                bucket: request.bucket.to_string(),
+                key: request.key.to_string(),
+                etag,
+                crc64: None,
+                version_id: None,
+                server_side_encryption: None,
+                kms_key_id: None,
+                callback_result: Some(body),
+            }
+        } else {
+            let body = response.text().await?;
+            parse_xml::<CompleteMultipartUploadResponse>(&body)?
+        };
+        complete_resp.crc64 = crc64;
+        complete_resp.version_id = version_id;
+        complete_resp.server_side_encryption = server_side_encryption;
+        complete_resp.kms_key_id = kms_key_id;
 
         Ok(complete_resp)
     }
@@ -138,6 +295,40 @@ impl OssClient {
         Ok(list_resp)
     }
 
+    /// List every part uploaded so far, following ListParts pagination.
+    ///
+    /// Parts are returned sorted by part number, ready for working out which
+    /// parts a resumed upload still needs to send.
+    pub async fn list_all_parts(
+        &self,
+        bucket: BucketName,
+        key: ObjectKey,
+        upload_id: impl Into<String>,
+    ) -> Result<Vec<PartInfo>> {
+        let upload_id = upload_id.into();
+        let mut parts = Vec::new();
+        let mut marker = None;
+        loop {
+            let mut builder = ListPartsRequestBuilder::new()
+                .bucket(bucket.clone())
+                .key(key.clone())
+                .upload_id(&upload_id);
+            if let Some(marker) = marker {
+                builder = builder.part_number_marker(marker);
+            }
+            let response = self.list_parts(builder.build()?).await?;
+            parts.extend(response.parts);
+            match response.next_part_number_marker {
+                Some(next) if response.is_truncated && marker != Some(next) => {
+                    marker = Some(next);
+                }
+                _ => break,
+            }
+        }
+        parts.sort_by_key(|p| p.part_number);
+        Ok(parts)
+    }
+
     /// List in-progress multipart uploads for a bucket.
     pub async fn list_multipart_uploads(
         &self,
@@ -1,42 +1,169 @@
 //! Object operations: PutObject, GetObject, DeleteObject, HeadObject, ListObjectsV2, CopyObject.
 
 use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::{Mutex, PoisonError};
+use std::task::{Context, Poll};
 
 use base64::Engine;
+use chrono::Utc;
+use futures_util::Stream;
+use futures_util::future::BoxFuture;
 use md5::{Digest, Md5};
 use percent_encoding::utf8_percent_encode;
-use reqwest::Method;
+use reqwest::{Method, StatusCode};
+use serde::Serialize;
 
+use crate::auth::v1::http_date;
+use crate::body::RequestBody;
 use crate::client::{
-    OssClient, header_etag, header_etag_opt, header_opt, parse_xml, serialize_xml,
+    OssClient, header_crc64, header_etag, header_etag_opt, header_opt, parse_http_date, parse_xml,
+    serialize_xml,
 };
-use crate::encoding::URI_ENCODE_SET;
-use crate::error::Result;
+use crate::config::OperationOptions;
+#[cfg(feature = "decompression")]
+use crate::decode::{ContentCoding, decode};
+use crate::encoding::{URI_ENCODE_SET, encode_tagging};
+use crate::error::{OssError, Result};
+use crate::progress::{ProgressTracker, TransferKind};
+use crate::types::common::{BucketName, MetadataDirective, ObjectKey, ServerSideEncryption};
 use crate::types::request::{
-    AppendObjectRequest, CopyObjectRequest, DeleteMultipleObjectsRequest, DeleteMultipleObjectsXml,
+    AbortMultipartUploadRequestBuilder, AppendObjectRequest, CompleteMultipartUploadRequestBuilder,
+    CompletedPart, CopyObjectRequest, DeleteMultipleObjectsRequest, DeleteMultipleObjectsXml,
     DeleteObjectRequest, DeleteObjectTaggingRequest, DeleteObjectXmlEntry, GetObjectAclRequest,
-    GetObjectRequest, GetObjectTaggingRequest, HeadObjectRequest, ListObjectsV2Request,
-    PutObjectAclRequest, PutObjectRequest, PutObjectTaggingRequest, RestoreObjectRequest,
+    GetObjectRequest, GetObjectTaggingRequest, GetObjectTaggingRequestBuilder, HeadObjectRequest,
+    HeadObjectRequestBuilder, InitiateMultipartUploadRequestBuilder, ListObjectVersionsRequest,
+    ListObjectsV2Request, PutObjectAclRequest, PutObjectRequest, PutObjectRequestBuilder,
+    PutObjectTaggingRequest, RestoreObjectRequest, UploadPartCopyRequestBuilder,
 };
 use crate::types::response::{
-    AppendObjectResponse, CopyObjectResponse, DeleteMultipleObjectsResponse, DeleteObjectResponse,
-    DeleteObjectTaggingResponse, GetObjectAclResponse, GetObjectResponse, GetObjectTaggingResponse,
-    HeadObjectResponse, ListObjectsV2Response, ObjectBody, PutObjectAclResponse, PutObjectResponse,
-    PutObjectTaggingResponse, RestoreObjectResponse, Tag, TagSet, TaggingXml,
+    AppendObjectResponse, ChunkStream, CopyObjectResponse, DeleteMultipleObjectsResponse,
+    DeleteObjectResponse, DeleteObjectTaggingResponse, GetObjectAclResponse, GetObjectResponse,
+    GetObjectResult, GetObjectTaggingResponse, HeadObjectResponse, ListObjectVersionsResponse,
+    ListObjectsV2Response, ObjectBody, ObjectExpiration, PutObjectAclResponse, PutObjectResponse,
+    PutObjectTaggingResponse, RestoreObjectResponse, RestoreStatus, Tag, TagSet, TaggingXml,
 };
 
+/// An ETag as sent in conditional headers, which expect it quoted.
+fn quote_etag(etag: &str) -> String {
+    if etag.starts_with('"') {
+        etag.to_string()
+    } else {
+        format!("\"{etag}\"")
+    }
+}
+
+/// What a [`ResumingStream`] needs to request the rest of a download.
+struct ResumeDownload {
+    client: OssClient,
+    bucket: BucketName,
+    key: ObjectKey,
+    etag: String,
+    /// Offset within the object of the next byte to receive.
+    offset: u64,
+    /// Offset of the last byte of the download, if known.
+    end: Option<u64>,
+    resumes_left: u32,
+}
+
+impl ResumeDownload {
+    fn can_resume(&self) -> bool {
+        self.resumes_left > 0 && self.end.is_none_or(|end| self.offset <= end)
+    }
+
+    fn resume(&mut self) -> BoxFuture<'static, Result<ChunkStream>> {
+        self.resumes_left -= 1;
+        let range = match self.end {
+            Some(end) => format!("bytes={}-{end}", self.offset),
+            None => format!("bytes={}-", self.offset),
+        };
+        let client = self.client.clone();
+        let bucket = self.bucket.clone();
+        let key = self.key.clone();
+        let etag = self.etag.clone();
+        Box::pin(async move { client.get_object_rest(&bucket, &key, &etag, range).await })
+    }
+}
+
+enum ResumeState {
+    Streaming(ChunkStream),
+    /// The `Mutex` only makes the future `Sync`; it is never contended.
+    Resuming {
+        request: Mutex<BoxFuture<'static, Result<ChunkStream>>>,
+        error: reqwest::Error,
+    },
+    Done,
+}
+
+/// A download body that continues from the last received byte when the
+/// connection breaks.
+struct ResumingStream {
+    resume: ResumeDownload,
+    state: ResumeState,
+}
+
+impl Stream for ResumingStream {
+    type Item = std::result::Result<bytes::Bytes, reqwest::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            match std::mem::replace(&mut this.state, ResumeState::Done) {
+                ResumeState::Streaming(mut stream) => match stream.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(Err(error))) if this.resume.can_resume() => {
+                        tracing::debug!(
+                            offset = this.resume.offset,
+                            error = %error,
+                            "download interrupted, resuming"
+                        );
+                        this.state = ResumeState::Resuming {
+                            request: Mutex::new(this.resume.resume()),
+                            error,
+                        };
+                    }
+                    poll => {
+                        if let Poll::Ready(Some(Ok(chunk))) = &poll {
+                            this.resume.offset += chunk.len() as u64;
+                        }
+                        this.state = ResumeState::Streaming(stream);
+                        return poll;
+                    }
+                },
+                ResumeState::Resuming { mut request, error } => {
+                    let future = request.get_mut().unwrap_or_else(PoisonError::into_inner);
+                    match future.as_mut().poll(cx) {
+                        Poll::Ready(Ok(stream)) => this.state = ResumeState::Streaming(stream),
+                        // Surface the read error rather than why resuming failed.
+                        Poll::Ready(Err(_)) => return Poll::Ready(Some(Err(error))),
+                        Poll::Pending => {
+                            this.state = ResumeState::Resuming { request, error };
+                            return Poll::Pending;
+                        }
+                    }
+                }
+                ResumeState::Done => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+/// Smallest part used when falling back to a multipart copy.
+const MIN_COPY_PART_SIZE: u64 = 128 * 1024 * 1024;
+/// Maximum number of parts in a multipart upload.
+const MAX_PARTS: u64 = 10_000;
+
 impl OssClient {
     /// Upload an object to OSS.
     ///
     /// # Payload Signing
     ///
-    /// When the body is backed by in-memory bytes (e.g., `Vec<u8>`, `Bytes`),
-    /// the SDK computes a SHA-256 hash of the payload and includes it in the
-    /// V4 signature. When the body is a non-buffered stream, the SDK uses
-    /// `UNSIGNED-PAYLOAD` — the request is still authenticated via the
-    /// Authorization header, but the payload itself is not integrity-checked
-    /// by the signature. OSS may still validate Content-MD5 or CRC64 if
-    /// those headers are present.
+    /// By default V4 signatures send `UNSIGNED-PAYLOAD` — the request is
+    /// authenticated via the Authorization header, but the payload itself is
+    /// not integrity-checked by the signature. Set
+    /// [`ClientBuilder::payload_signing`](crate::config::ClientBuilder::payload_signing)
+    /// to hash in-memory bodies (e.g., `Vec<u8>`, `Bytes`) into the
+    /// signature. OSS may still validate Content-MD5 or CRC64 if those
+    /// headers are present.
     ///
     /// # Examples
     /// ```no_run
@@ -59,8 +186,8 @@ impl OssClient {
         let resource_path = format!("/{}/{}", request.bucket, request.key);
         let mut http_req = self.http_client().request(Method::PUT, url);
 
-        if let Some(ref ct) = request.content_type {
-            http_req = http_req.header("content-type", ct.as_str());
+        if let Some(ct) = self.upload_content_type(request.content_type.as_deref(), &request.key) {
+            http_req = http_req.header("content-type", ct);
         }
         if let Some(sc) = request.storage_class {
             http_req = http_req.header("x-oss-storage-class", sc.to_string());
@@ -68,23 +195,169 @@ impl OssClient {
         if let Some(acl) = request.acl {
             http_req = http_req.header("x-oss-object-acl", acl.to_string());
         }
+        if let Some(sse) = request.server_side_encryption {
+            http_req = http_req.header("x-oss-server-side-encryption", sse.to_string());
+        }
+        if let Some(ref kms_key_id) = request.kms_key_id {
+            http_req = http_req.header("x-oss-server-side-encryption-key-id", kms_key_id.as_str());
+        }
+        let metadata_encoding = self.config().metadata_encoding();
         for (k, v) in &request.metadata {
-            http_req = http_req.header(format!("x-oss-meta-{k}"), v.as_str());
+            let value = metadata_encoding.encode(k, v)?;
+            http_req = http_req.header(format!("x-oss-meta-{k}"), value);
+        }
+        if !request.tags.is_empty() {
+            http_req = http_req.header("x-oss-tagging", encode_tagging(&request.tags));
         }
 
-        let http_req = http_req.body(request.body).build()?;
-        let response = self.execute(http_req, &resource_path).await?;
+        if let Some(length) = request.content_length {
+            http_req = http_req.header("content-length", length);
+        }
+
+        let response = match request.body {
+            RequestBody::Once(body) => {
+                let http_req = http_req.body(body).build()?;
+                self.execute(http_req, &resource_path).await?
+            }
+            RequestBody::Replayable(body) => {
+                let http_req = http_req.build()?;
+                self.execute_with_body(http_req, &resource_path, Some(body))
+                    .await?
+            }
+        };
 
         let etag = header_etag(&response);
+        let crc64 = header_crc64(&response);
+        let version_id = header_opt(&response, "x-oss-version-id");
+        let server_side_encryption =
+            header_opt(&response, "x-oss-server-side-encryption").and_then(|s| s.parse().ok());
+        let kms_key_id = header_opt(&response, "x-oss-server-side-encryption-key-id");
+        let expiration =
+            header_opt(&response, "x-oss-expiration").and_then(|s| ObjectExpiration::parse(&s));
         let request_id = header_opt(&response, "x-oss-request-id");
 
-        Ok(PutObjectResponse { etag, request_id })
+        Ok(PutObjectResponse {
+            etag,
+            crc64,
+            version_id,
+            server_side_encryption,
+            kms_key_id,
+            expiration,
+            request_id,
+        })
+    }
+
+    /// Upload an object with per-operation overrides, e.g. a longer timeout
+    /// or a bandwidth limit for a large body.
+    ///
+    /// Shorthand for `client.with_options(options).put_object(request)`.
+    pub async fn put_object_with_options(
+        &self,
+        request: PutObjectRequest,
+        options: OperationOptions,
+    ) -> Result<PutObjectResponse> {
+        self.with_options(options).put_object(request).await
+    }
+
+    /// Upload `value` serialized as JSON, with content type
+    /// `application/json`.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use rs_ali_oss::*;
+    /// # async fn example(client: OssClient) -> Result<()> {
+    /// let settings = serde_json::json!({ "theme": "dark" });
+    /// client
+    ///     .put_object_json(
+    ///         BucketName::new("my-bucket")?,
+    ///         ObjectKey::new("settings.json")?,
+    ///         &settings,
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn put_object_json<T: Serialize + ?Sized>(
+        &self,
+        bucket: BucketName,
+        key: ObjectKey,
+        value: &T,
+    ) -> Result<PutObjectResponse> {
+        let body = serde_json::to_vec(value).map_err(|e| OssError::JsonParse(e.to_string()))?;
+        let request = PutObjectRequestBuilder::new()
+            .bucket(bucket)
+            .key(key)
+            .body_bytes(body.into())
+            .content_type("application/json")
+            .build()?;
+        self.put_object(request).await
+    }
+
+    /// Upload `text`, with content type `text/plain; charset=utf-8`.
+    pub async fn put_object_text(
+        &self,
+        bucket: BucketName,
+        key: ObjectKey,
+        text: impl Into<String>,
+    ) -> Result<PutObjectResponse> {
+        let request = PutObjectRequestBuilder::new()
+            .bucket(bucket)
+            .key(key)
+            .body_bytes(text.into().into())
+            .content_type("text/plain; charset=utf-8")
+            .build()?;
+        self.put_object(request).await
     }
 
     /// Download an object from OSS.
     ///
     /// Returns a streaming response — the body is NOT buffered in memory.
+    /// An unchanged object requested with
+    /// [`if_none_match`](crate::types::request::GetObjectRequestBuilder::if_none_match)
+    /// or `if_modified_since` is reported as a service error with status
+    /// 304; see [`get_object_conditional`](Self::get_object_conditional).
     pub async fn get_object(&self, request: GetObjectRequest) -> Result<GetObjectResponse> {
+        match self.get_object_conditional(request).await? {
+            GetObjectResult::Modified(response) => Ok(*response),
+            GetObjectResult::NotModified { request_id, .. } => Err(OssError::ServerError {
+                status: StatusCode::NOT_MODIFIED.as_u16(),
+                code: String::new(),
+                message: String::new(),
+                request_id: request_id.unwrap_or_default(),
+                host_id: String::new(),
+                ec: None,
+                raw_body: String::new(),
+            }),
+        }
+    }
+
+    /// Download an object unless it matches the request's `If-None-Match`
+    /// or `If-Modified-Since` condition.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use rs_ali_oss::*;
+    /// # use rs_ali_oss::types::request::GetObjectRequestBuilder;
+    /// # use rs_ali_oss::types::response::GetObjectResult;
+    /// # async fn example(client: OssClient, cached_etag: String) -> Result<()> {
+    /// let request = GetObjectRequestBuilder::new()
+    ///     .bucket(BucketName::new("my-bucket")?)
+    ///     .key(ObjectKey::new("index.html")?)
+    ///     .if_none_match(cached_etag)
+    ///     .build()?;
+    /// match client.get_object_conditional(request).await? {
+    ///     GetObjectResult::Modified(response) => {
+    ///         let fresh = response.body.bytes().await?;
+    ///     }
+    ///     GetObjectResult::NotModified { .. } => { /* serve the cached copy */ }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_object_conditional(
+        &self,
+        request: GetObjectRequest,
+    ) -> Result<GetObjectResult> {
         let url = self.build_url(Some(&request.bucket), Some(&request.key), &[])?;
         let resource_path = format!("/{}/{}", request.bucket, request.key);
         let mut http_req = self.http_client().request(Method::GET, url);
@@ -92,9 +365,27 @@ impl OssClient {
         if let Some(ref range) = request.range {
             http_req = http_req.header("range", range.as_str());
         }
+        if let Some(ref etag) = request.if_match {
+            http_req = http_req.header("if-match", quote_etag(etag));
+        }
+        if let Some(ref etag) = request.if_none_match {
+            http_req = http_req.header("if-none-match", quote_etag(etag));
+        }
+        if let Some(time) = request.if_modified_since {
+            http_req = http_req.header("if-modified-since", http_date(time));
+        }
+        if let Some(time) = request.if_unmodified_since {
+            http_req = http_req.header("if-unmodified-since", http_date(time));
+        }
 
         let http_req = http_req.build()?;
         let response = self.execute(http_req, &resource_path).await?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(GetObjectResult::NotModified {
+                etag: header_etag_opt(&response),
+                request_id: header_opt(&response, "x-oss-request-id"),
+            });
+        }
 
         let content_type = header_opt(&response, "content-type");
         let content_length = response
@@ -103,15 +394,109 @@ impl OssClient {
             .and_then(|v| v.to_str().ok())
             .and_then(|s| s.parse().ok());
         let etag = header_etag_opt(&response);
+        let storage_class =
+            header_opt(&response, "x-oss-storage-class").and_then(|s| s.parse().ok());
+        let object_type = header_opt(&response, "x-oss-object-type").and_then(|s| s.parse().ok());
+        let tagging_count =
+            header_opt(&response, "x-oss-tagging-count").and_then(|s| s.parse().ok());
+        let restore = header_opt(&response, "x-oss-restore").and_then(|s| RestoreStatus::parse(&s));
+        let expiration =
+            header_opt(&response, "x-oss-expiration").and_then(|s| ObjectExpiration::parse(&s));
         let request_id = header_opt(&response, "x-oss-request-id");
+        let metadata = self.response_metadata(&response);
+        let range_end = header_opt(&response, "content-range")
+            .and_then(|v| v.split_once('-')?.1.split_once('/')?.0.parse().ok());
+        #[cfg(feature = "decompression")]
+        let decoding = request
+            .decode_content
+            .then(|| header_opt(&response, "content-encoding"))
+            .flatten()
+            .and_then(|value| ContentCoding::parse(&value))
+            .map(|coding| {
+                let whole_object = response.status() == StatusCode::OK;
+                (coding, header_crc64(&response).filter(|_| whole_object))
+            });
 
-        Ok(GetObjectResponse {
-            body: ObjectBody::new(response),
+        let mut body = ObjectBody::new(response);
+        if let Some(listener) = request.progress_listener {
+            let tracker = ProgressTracker::new(
+                listener,
+                &request.bucket,
+                &request.key,
+                content_length,
+                TransferKind::Download,
+            );
+            tracker.start();
+            body = body.with_progress(tracker);
+        }
+        if request.max_resumes > 0
+            && let Some(ref etag) = etag
+        {
+            let offset = body.range_start();
+            let resume = ResumeDownload {
+                client: self.clone(),
+                bucket: request.bucket,
+                key: request.key,
+                etag: etag.clone(),
+                offset,
+                end: range_end
+                    .or_else(|| content_length.and_then(|len: u64| (offset + len).checked_sub(1))),
+                resumes_left: request.max_resumes,
+            };
+            body = body.map_stream(|stream| {
+                Box::pin(ResumingStream {
+                    resume,
+                    state: ResumeState::Streaming(stream),
+                })
+            });
+        }
+        #[cfg(feature = "decompression")]
+        if let Some((coding, crc64)) = decoding {
+            body = decode(body, coding, crc64);
+        }
+
+        Ok(GetObjectResult::Modified(Box::new(GetObjectResponse {
+            body,
             content_type,
             content_length,
             etag,
+            storage_class,
+            object_type,
+            tagging_count,
+            restore,
+            expiration,
+            metadata,
             request_id,
-        })
+        })))
+    }
+
+    /// Download an object with per-operation overrides.
+    ///
+    /// Shorthand for `client.with_options(options).get_object(request)`. The
+    /// timeout covers the request until response headers arrive; reading the
+    /// body is bounded by the client's read timeout.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use rs_ali_oss::*;
+    /// # use rs_ali_oss::config::OperationOptions;
+    /// # use rs_ali_oss::types::request::GetObjectRequest;
+    /// # async fn example(client: OssClient, request: GetObjectRequest) -> Result<()> {
+    /// let options = OperationOptions::builder()
+    ///     .timeout(Duration::from_secs(600))
+    ///     .traffic_limit(8 * 1024 * 1024 * 8)
+    ///     .build()?;
+    /// let response = client.get_object_with_options(request, options).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_object_with_options(
+        &self,
+        request: GetObjectRequest,
+        options: OperationOptions,
+    ) -> Result<GetObjectResponse> {
+        self.with_options(options).get_object(request).await
     }
 
     /// Delete an object from OSS.
@@ -119,14 +504,26 @@ impl OssClient {
         &self,
         request: DeleteObjectRequest,
     ) -> Result<DeleteObjectResponse> {
-        let url = self.build_url(Some(&request.bucket), Some(&request.key), &[])?;
+        let query: Vec<(&str, &str)> = request
+            .version_id
+            .as_deref()
+            .map(|v| ("versionId", v))
+            .into_iter()
+            .collect();
+        let url = self.build_url(Some(&request.bucket), Some(&request.key), &query)?;
         let resource_path = format!("/{}/{}", request.bucket, request.key);
         let http_req = self.http_client().request(Method::DELETE, url).build()?;
         let response = self.execute(http_req, &resource_path).await?;
 
+        let delete_marker = header_opt(&response, "x-oss-delete-marker").as_deref() == Some("true");
+        let version_id = header_opt(&response, "x-oss-version-id");
         let request_id = header_opt(&response, "x-oss-request-id");
 
-        Ok(DeleteObjectResponse { request_id })
+        Ok(DeleteObjectResponse {
+            delete_marker,
+            version_id,
+            request_id,
+        })
     }
 
     /// Retrieve object metadata without downloading the body.
@@ -142,38 +539,45 @@ impl OssClient {
             .get("content-length")
             .and_then(|v| v.to_str().ok())
             .and_then(|s| s.parse().ok());
+        let cache_control = header_opt(&response, "cache-control");
+        let content_disposition = header_opt(&response, "content-disposition");
+        let content_encoding = header_opt(&response, "content-encoding");
+        let expires = header_opt(&response, "expires");
         let etag = header_etag_opt(&response);
-        let last_modified = header_opt(&response, "last-modified").and_then(|s| {
-            chrono::DateTime::parse_from_rfc2822(&s)
-                .map(|dt| dt.with_timezone(&chrono::Utc))
-                .ok()
-                .or_else(|| {
-                    s.find(", ").and_then(|pos| {
-                        chrono::NaiveDateTime::parse_from_str(
-                            &s[pos + 2..],
-                            "%d %b %Y %H:%M:%S GMT",
-                        )
-                        .ok()
-                        .map(|dt| dt.and_utc())
-                    })
-                })
-        });
+        let last_modified =
+            header_opt(&response, "last-modified").and_then(|s| parse_http_date(&s));
+        let crc64 = header_crc64(&response);
+        let storage_class =
+            header_opt(&response, "x-oss-storage-class").and_then(|s| s.parse().ok());
+        let object_type = header_opt(&response, "x-oss-object-type").and_then(|s| s.parse().ok());
+        let version_id = header_opt(&response, "x-oss-version-id");
+        let server_side_encryption =
+            header_opt(&response, "x-oss-server-side-encryption").and_then(|s| s.parse().ok());
+        let kms_key_id = header_opt(&response, "x-oss-server-side-encryption-key-id");
+        let restore = header_opt(&response, "x-oss-restore").and_then(|s| RestoreStatus::parse(&s));
+        let expiration =
+            header_opt(&response, "x-oss-expiration").and_then(|s| ObjectExpiration::parse(&s));
         let request_id = header_opt(&response, "x-oss-request-id");
 
-        let mut metadata = HashMap::new();
-        for (name, value) in response.headers() {
-            if let Some(meta_key) = name.as_str().strip_prefix("x-oss-meta-")
-                && let Ok(v) = value.to_str()
-            {
-                metadata.insert(meta_key.to_string(), v.to_string());
-            }
-        }
+        let metadata = self.response_metadata(&response);
 
         Ok(HeadObjectResponse {
             content_type,
             content_length,
+            cache_control,
+            content_disposition,
+            content_encoding,
+            expires,
             etag,
             last_modified,
+            crc64,
+            storage_class,
+            object_type,
+            version_id,
+            server_side_encryption,
+            kms_key_id,
+            restore,
+            expiration,
             metadata,
             request_id,
         })
@@ -216,11 +620,92 @@ impl OssClient {
         Ok(list_resp)
     }
 
+    /// List object versions and delete markers in a versioned bucket.
+    pub async fn list_object_versions(
+        &self,
+        request: ListObjectVersionsRequest,
+    ) -> Result<ListObjectVersionsResponse> {
+        let mut query: Vec<(&str, String)> = vec![("versions", String::new())];
+        if let Some(ref prefix) = request.prefix {
+            query.push(("prefix", prefix.clone()));
+        }
+        if let Some(ref delimiter) = request.delimiter {
+            query.push(("delimiter", delimiter.clone()));
+        }
+        if let Some(max_keys) = request.max_keys {
+            query.push(("max-keys", max_keys.to_string()));
+        }
+        if let Some(ref key_marker) = request.key_marker {
+            query.push(("key-marker", key_marker.clone()));
+        }
+        if let Some(ref version_id_marker) = request.version_id_marker {
+            query.push(("version-id-marker", version_id_marker.clone()));
+        }
+
+        let query_refs: Vec<(&str, &str)> = query.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        let url = self.build_url(Some(&request.bucket), None, &query_refs)?;
+        let resource_path = format!("/{}/", request.bucket);
+        let http_req = self.http_client().request(Method::GET, url).build()?;
+        let response = self.execute(http_req, &resource_path).await?;
+
+        let body = response.text().await?;
+        parse_xml(&body)
+    }
+
     /// Copy an object within OSS.
     ///
     /// The source is specified via `x-oss-copy-source` header with the format
     /// `/{source_bucket}/{source_key}` (percent-encoded).
+    ///
+    /// `CopyObject` only accepts sources up to 1 GiB. When enabled with
+    /// [`ClientBuilder::multipart_copy`](crate::config::ClientBuilder::multipart_copy),
+    /// the source is inspected with `HeadObject` first and copied part by
+    /// part with `UploadPartCopy` when it exceeds the configured threshold.
+    /// If `HeadObject` fails, the object is copied with a single `CopyObject`.
+    ///
+    /// Under the default `COPY` metadata directive, a multipart copy carries
+    /// over the source's content type, `Cache-Control`, `Content-Disposition`,
+    /// `Content-Encoding`, `Expires`, user metadata, server-side encryption
+    /// and tags (read with `GetObjectTagging`), as `CopyObject` does. Under
+    /// `REPLACE`, only the request's content type and metadata are applied.
     pub async fn copy_object(&self, request: CopyObjectRequest) -> Result<CopyObjectResponse> {
+        let Some(threshold) = self.config().multipart_copy_threshold() else {
+            return self.single_copy_object(request).await;
+        };
+        let head_req = HeadObjectRequestBuilder::new()
+            .bucket(request.source_bucket.clone())
+            .key(request.source_key.clone())
+            .build()?;
+        match self.head_object(head_req).await {
+            Ok(source) if source.content_length.is_some_and(|size| size > threshold) => {
+                let size = source.content_length.unwrap_or_default();
+                self.multipart_copy_object(request, size, Some(source))
+                    .await
+            }
+            Ok(_) => self.single_copy_object(request).await,
+            Err(e) => {
+                tracing::debug!(error = %e, "source lookup failed, copying with CopyObject");
+                self.single_copy_object(request).await
+            }
+        }
+    }
+
+    /// Copy an object whose size is already known, skipping the `HeadObject`
+    /// lookup used to pick between `CopyObject` and a multipart copy.
+    pub(crate) async fn copy_object_sized(
+        &self,
+        request: CopyObjectRequest,
+        size: u64,
+    ) -> Result<CopyObjectResponse> {
+        match self.config().multipart_copy_threshold() {
+            Some(threshold) if size > threshold => {
+                self.multipart_copy_object(request, size, None).await
+            }
+            _ => self.single_copy_object(request).await,
+        }
+    }
+
+    async fn single_copy_object(&self, request: CopyObjectRequest) -> Result<CopyObjectResponse> {
         let url = self.build_url(Some(&request.bucket), Some(&request.key), &[])?;
         let resource_path = format!("/{}/{}", request.bucket, request.key);
         let mut http_req = self.http_client().request(Method::PUT, url);
@@ -242,19 +727,220 @@ impl OssClient {
         if let Some(acl) = request.acl {
             http_req = http_req.header("x-oss-object-acl", acl.to_string());
         }
+        let metadata_encoding = self.config().metadata_encoding();
         for (k, v) in &request.metadata {
-            http_req = http_req.header(format!("x-oss-meta-{k}"), v.as_str());
+            let value = metadata_encoding.encode(k, v)?;
+            http_req = http_req.header(format!("x-oss-meta-{k}"), value);
         }
 
         let http_req = http_req.build()?;
         let response = self.execute(http_req, &resource_path).await?;
+        let crc64 = header_crc64(&response);
+        let version_id = header_opt(&response, "x-oss-version-id");
+        let source_version_id = header_opt(&response, "x-oss-copy-source-version-id");
+        let server_side_encryption =
+            header_opt(&response, "x-oss-server-side-encryption").and_then(|s| s.parse().ok());
+        let kms_key_id = header_opt(&response, "x-oss-server-side-encryption-key-id");
 
         let body = response.text().await?;
-        let copy_resp: CopyObjectResponse = parse_xml(&body)?;
+        let mut copy_resp: CopyObjectResponse = parse_xml(&body)?;
+        copy_resp.crc64 = crc64;
+        copy_resp.version_id = version_id;
+        copy_resp.source_version_id = source_version_id;
+        copy_resp.server_side_encryption = server_side_encryption;
+        copy_resp.kms_key_id = kms_key_id;
 
         Ok(copy_resp)
     }
 
+    /// Fetch `bytes={offset}-{end}` of the object, failing unless OSS
+    /// answers with that range of the expected version.
+    async fn get_object_rest(
+        &self,
+        bucket: &BucketName,
+        key: &ObjectKey,
+        etag: &str,
+        range: String,
+    ) -> Result<ChunkStream> {
+        let url = self.build_url(Some(bucket), Some(key), &[])?;
+        let resource_path = format!("/{bucket}/{key}");
+        let http_req = self
+            .http_client()
+            .request(Method::GET, url)
+            .header("range", range)
+            .header("if-match", quote_etag(etag))
+            .build()?;
+        let response = self.execute(http_req, &resource_path).await?;
+        if response.status() != StatusCode::PARTIAL_CONTENT
+            || header_etag_opt(&response).as_deref() != Some(etag)
+        {
+            return Err(OssError::InvalidParameter {
+                field: "range".into(),
+                reason: "OSS did not return the requested range of the object".into(),
+            });
+        }
+        Ok(Box::pin(response.bytes_stream()))
+    }
+
+    /// Copy `size` bytes part by part. `source` is the source's `HeadObject`
+    /// response if the caller already has it; it is looked up here only when
+    /// the source's attributes have to be carried over.
+    async fn multipart_copy_object(
+        &self,
+        request: CopyObjectRequest,
+        size: u64,
+        source: Option<HeadObjectResponse>,
+    ) -> Result<CopyObjectResponse> {
+        let part_size = MIN_COPY_PART_SIZE.max(size.div_ceil(MAX_PARTS));
+        let replace = request.metadata_directive == Some(MetadataDirective::Replace);
+        let source = match source {
+            Some(source) => Some(source),
+            None if replace => None,
+            None => {
+                let head_req = HeadObjectRequestBuilder::new()
+                    .bucket(request.source_bucket.clone())
+                    .key(request.source_key.clone())
+                    .build()?;
+                Some(self.head_object(head_req).await?)
+            }
+        };
+
+        let mut init_builder = InitiateMultipartUploadRequestBuilder::new()
+            .bucket(request.bucket.clone())
+            .key(request.key.clone());
+        if replace {
+            if let Some(ct) = request.content_type {
+                init_builder = init_builder.content_type(ct);
+            }
+            for (k, v) in request.metadata {
+                init_builder = init_builder.metadata(k, v);
+            }
+        } else if let Some(ref source) = source {
+            init_builder = self
+                .copy_source_attributes(init_builder, &request, source)
+                .await?;
+        }
+        if let Some(sc) = request.storage_class {
+            init_builder = init_builder.storage_class(sc);
+        }
+        if let Some(acl) = request.acl {
+            init_builder = init_builder.acl(acl);
+        }
+        let upload_id = self
+            .initiate_multipart_upload(init_builder.build()?)
+            .await?
+            .upload_id;
+
+        let mut parts = Vec::new();
+        let mut last_modified = None;
+        let mut offset = 0;
+        let mut part_number = 1;
+        let copied: Result<()> = async {
+            while offset < size {
+                let end = (offset + part_size).min(size) - 1;
+                let mut part_builder = UploadPartCopyRequestBuilder::new()
+                    .bucket(request.bucket.clone())
+                    .key(request.key.clone())
+                    .upload_id(&upload_id)
+                    .part_number(part_number)
+                    .source_bucket(request.source_bucket.clone())
+                    .source_key(request.source_key.clone())
+                    .source_range(offset, end);
+                if let Some(etag) = source.as_ref().and_then(|s| s.etag.as_ref()) {
+                    part_builder = part_builder.source_if_match(etag);
+                }
+                let part = self.upload_part_copy(part_builder.build()?).await?;
+                last_modified = Some(part.last_modified);
+                parts.push(CompletedPart {
+                    part_number,
+                    etag: part.etag,
+                });
+                offset = end + 1;
+                part_number += 1;
+            }
+            Ok(())
+        }
+        .await;
+
+        let completed = match copied {
+            Ok(()) => {
+                let complete_req = CompleteMultipartUploadRequestBuilder::new()
+                    .bucket(request.bucket.clone())
+                    .key(request.key.clone())
+                    .upload_id(&upload_id)
+                    .parts(parts)
+                    .build()?;
+                self.complete_multipart_upload(complete_req).await
+            }
+            Err(e) => Err(e),
+        };
+        match completed {
+            Ok(complete) => Ok(CopyObjectResponse {
+                last_modified: last_modified.unwrap_or_else(Utc::now),
+                etag: complete.etag,
+                crc64: complete.crc64,
+                version_id: complete.version_id,
+                source_version_id: source.and_then(|s| s.version_id),
+                server_side_encryption: complete.server_side_encryption,
+                kms_key_id: complete.kms_key_id,
+            }),
+            Err(e) => {
+                let abort_req = AbortMultipartUploadRequestBuilder::new()
+                    .bucket(request.bucket)
+                    .key(request.key)
+                    .upload_id(&upload_id)
+                    .build()?;
+                // Best-effort abort — ignore errors
+                let _ = self.abort_multipart_upload(abort_req).await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Apply the attributes `CopyObject` carries over under the `COPY`
+    /// directive to the upload that replaces it.
+    async fn copy_source_attributes(
+        &self,
+        mut builder: InitiateMultipartUploadRequestBuilder,
+        request: &CopyObjectRequest,
+        source: &HeadObjectResponse,
+    ) -> Result<InitiateMultipartUploadRequestBuilder> {
+        if let Some(ref ct) = source.content_type {
+            builder = builder.content_type(ct);
+        }
+        if let Some(ref cache_control) = source.cache_control {
+            builder = builder.cache_control(cache_control);
+        }
+        if let Some(ref disposition) = source.content_disposition {
+            builder = builder.content_disposition(disposition);
+        }
+        if let Some(ref encoding) = source.content_encoding {
+            builder = builder.content_encoding(encoding);
+        }
+        if let Some(ref expires) = source.expires {
+            builder = builder.expires(expires);
+        }
+        for (k, v) in &source.metadata {
+            builder = builder.metadata(k, v);
+        }
+        if let Some(sse) = source.server_side_encryption {
+            builder = builder.server_side_encryption(sse);
+            if let Some(ref kms_key_id) = source.kms_key_id
+                && sse == ServerSideEncryption::KMS
+            {
+                builder = builder.kms_key_id(kms_key_id);
+            }
+        }
+        let tagging_req = GetObjectTaggingRequestBuilder::new()
+            .bucket(request.source_bucket.clone())
+            .key(request.source_key.clone())
+            .build()?;
+        for tag in self.get_object_tagging(tagging_req).await?.tag_set.tags {
+            builder = builder.tag(tag.key, tag.value);
+        }
+        Ok(builder)
+    }
+
     /// Delete multiple objects from OSS in a single request.
     ///
     /// Supports deleting up to 1000 objects per request. Uses quiet mode by default,
@@ -271,8 +957,9 @@ impl OssClient {
             objects: request
                 .keys
                 .iter()
-                .map(|k| DeleteObjectXmlEntry {
+                .map(|(k, version_id)| DeleteObjectXmlEntry {
                     key: k.as_ref().to_string(),
+                    version_id: version_id.clone(),
                 })
                 .collect(),
         };
@@ -339,13 +1026,13 @@ impl OssClient {
     ///
     /// # Payload Signing
     ///
-    /// When the body is backed by in-memory bytes (e.g., `Vec<u8>`, `Bytes`),
-    /// the SDK computes a SHA-256 hash of the payload and includes it in the
-    /// V4 signature. When the body is a non-buffered stream, the SDK uses
-    /// `UNSIGNED-PAYLOAD` — the request is still authenticated via the
-    /// Authorization header, but the payload itself is not integrity-checked
-    /// by the signature. OSS may still validate Content-MD5 or CRC64 if
-    /// those headers are present.
+    /// By default V4 signatures send `UNSIGNED-PAYLOAD` — the request is
+    /// authenticated via the Authorization header, but the payload itself is
+    /// not integrity-checked by the signature. Set
+    /// [`ClientBuilder::payload_signing`](crate::config::ClientBuilder::payload_signing)
+    /// to hash in-memory bodies (e.g., `Vec<u8>`, `Bytes`) into the
+    /// signature. OSS may still validate Content-MD5 or CRC64 if those
+    /// headers are present.
     pub async fn append_object(
         &self,
         request: AppendObjectRequest,
@@ -358,8 +1045,25 @@ impl OssClient {
         )?;
         let resource_path = format!("/{}/{}", request.bucket, request.key);
         let mut http_req = self.http_client().request(Method::POST, url);
-        if let Some(ref ct) = request.content_type {
-            http_req = http_req.header("content-type", ct.as_str());
+        if let Some(ct) = self.upload_content_type(request.content_type.as_deref(), &request.key) {
+            http_req = http_req.header("content-type", ct);
+        }
+        if let Some(ref cache_control) = request.cache_control {
+            http_req = http_req.header("cache-control", cache_control.as_str());
+        }
+        if let Some(sc) = request.storage_class {
+            http_req = http_req.header("x-oss-storage-class", sc.to_string());
+        }
+        if let Some(acl) = request.acl {
+            http_req = http_req.header("x-oss-object-acl", acl.to_string());
+        }
+        if let Some(sse) = request.server_side_encryption {
+            http_req = http_req.header("x-oss-server-side-encryption", sse.to_string());
+        }
+        let metadata_encoding = self.config().metadata_encoding();
+        for (k, v) in &request.metadata {
+            let value = metadata_encoding.encode(k, v)?;
+            http_req = http_req.header(format!("x-oss-meta-{k}"), value);
         }
         let http_req = http_req.body(request.body).build()?;
         let response = self.execute(http_req, &resource_path).await?;
@@ -367,7 +1071,7 @@ impl OssClient {
         let next_append_position = header_opt(&response, "x-oss-next-append-position")
             .and_then(|s| s.parse::<u64>().ok())
             .unwrap_or(0);
-        let crc64 = header_opt(&response, "x-oss-hash-crc64ecma");
+        let crc64 = header_crc64(&response);
         let request_id = header_opt(&response, "x-oss-request-id");
 
         Ok(AppendObjectResponse {
@@ -474,4 +1178,19 @@ impl OssClient {
         let request_id = header_opt(&response, "x-oss-request-id");
         Ok(DeleteObjectTaggingResponse { request_id })
     }
+
+    /// Collect the `x-oss-meta-*` headers of a response, decoded with the
+    /// configured metadata encoding.
+    fn response_metadata(&self, response: &reqwest::Response) -> HashMap<String, String> {
+        let encoding = self.config().metadata_encoding();
+        response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| {
+                let meta_key = name.as_str().strip_prefix("x-oss-meta-")?;
+                let value = std::str::from_utf8(value.as_bytes()).ok()?;
+                Some((meta_key.to_string(), encoding.decode(value)))
+            })
+            .collect()
+    }
 }
@@ -1,10 +1,91 @@
 //! Auto-paginators for listing operations.
 
+use serde::{Deserialize, Serialize};
+
 use crate::client::OssClient;
 use crate::error::Result;
 use crate::types::common::BucketName;
 use crate::types::response::{BucketInfo, ListBucketsResponse, ListObjectsV2Response, ObjectInfo};
 
+/// Page and item budgets shared by the paginators, with what has been
+/// consumed so far.
+#[derive(Debug, Clone, Copy, Default)]
+struct Limits {
+    max_pages: Option<usize>,
+    max_items: Option<usize>,
+    pages: usize,
+    items: usize,
+}
+
+impl Limits {
+    /// Whether a budget has been used up.
+    fn reached(&self) -> bool {
+        self.max_pages.is_some_and(|max| self.pages >= max)
+            || self.max_items.is_some_and(|max| self.items >= max)
+    }
+
+    /// Page size to request: `max_keys`, shrunk to the items left, if any.
+    fn page_size(&self, max_keys: Option<u32>) -> Option<u32> {
+        let Some(max_items) = self.max_items else {
+            return max_keys;
+        };
+        let left = u32::try_from(max_items - self.items).unwrap_or(u32::MAX);
+        Some(max_keys.map_or(left.min(1000), |keys| keys.min(left)))
+    }
+
+    /// Count a fetched page, truncating `items` to what the budget allows.
+    fn record<T>(&mut self, items: &mut Vec<T>) {
+        self.pages += 1;
+        if let Some(max) = self.max_items {
+            items.truncate(max - self.items);
+        }
+        self.items += items.len();
+    }
+}
+
+/// How far a paginator has got, saved with
+/// [`ListObjectsV2Paginator::resume_state`] or
+/// [`ListBucketsPaginator::resume_state`] so a listing can be checkpointed
+/// and picked up again, e.g. after a process restart.
+///
+/// Resume with the same bucket, prefix and other options the state was
+/// saved from; the state only records the position in the listing.
+///
+/// # Examples
+/// ```no_run
+/// # use rs_ali_oss::*;
+/// # async fn example(client: OssClient, checkpoint: Option<String>) -> Result<()> {
+/// let mut builder = client
+///     .list_objects_v2_paginator(BucketName::new("my-bucket")?)
+///     .prefix("events/");
+/// if let Some(json) = checkpoint {
+///     let state: ResumeState =
+///         serde_json::from_str(&json).map_err(|e| OssError::JsonParse(e.to_string()))?;
+///     builder = builder.resume_from(state);
+/// }
+/// let mut paginator = builder.build();
+/// while let Some(page) = paginator.next_page().await? {
+///     // ... process page.contents ...
+///     let json = serde_json::to_string(&paginator.resume_state())
+///         .map_err(|e| OssError::JsonParse(e.to_string()))?;
+///     // ... persist json ...
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResumeState {
+    /// Continuation token (ListObjectsV2) or marker (ListBuckets) of the
+    /// next page; `None` before the first page.
+    pub token: Option<String>,
+    /// Whether the listing has reached its last page.
+    pub done: bool,
+    /// Pages fetched so far, counted against `max_pages`.
+    pub pages_fetched: usize,
+    /// Items returned so far, counted against `max_items`.
+    pub items_fetched: usize,
+}
+
 /// A paginator that yields [`ObjectInfo`] items across all pages of a ListObjectsV2 call.
 pub struct ListObjectsV2Paginator {
     client: OssClient,
@@ -14,80 +95,26 @@ pub struct ListObjectsV2Paginator {
     max_keys: Option<u32>,
     start_after: Option<String>,
     continuation_token: Option<String>,
-    buffer: std::collections::VecDeque<ObjectInfo>,
+    limits: Limits,
     done: bool,
 }
 
 impl ListObjectsV2Paginator {
-    pub(crate) fn new(
-        client: OssClient,
-        bucket: BucketName,
-        prefix: Option<String>,
-        delimiter: Option<String>,
-        max_keys: Option<u32>,
-        start_after: Option<String>,
-    ) -> Self {
-        Self {
-            client,
-            bucket,
-            prefix,
-            delimiter,
-            max_keys,
-            start_after,
-            continuation_token: None,
-            buffer: std::collections::VecDeque::new(),
-            done: false,
-        }
-    }
-
-    async fn fetch_next_page(&mut self) -> Result<()> {
-        use crate::types::request::ListObjectsV2RequestBuilder;
-
-        let mut builder = ListObjectsV2RequestBuilder::new().bucket(self.bucket.clone());
-
-        if let Some(ref prefix) = self.prefix {
-            builder = builder.prefix(prefix.clone());
-        }
-        if let Some(ref delimiter) = self.delimiter {
-            builder = builder.delimiter(delimiter.clone());
-        }
-        if let Some(max_keys) = self.max_keys {
-            builder = builder.max_keys(max_keys);
-        }
-        if let Some(ref start_after) = self.start_after {
-            builder = builder.start_after(start_after.clone());
-        }
-        if let Some(ref token) = self.continuation_token {
-            builder = builder.continuation_token(token.clone());
-        }
-
-        let request = builder.build()?;
-        let response = self.client.list_objects_v2(request).await?;
-
-        self.buffer.extend(response.contents);
-
-        if response.is_truncated {
-            self.continuation_token = response.next_continuation_token;
-        } else {
-            self.done = true;
-        }
-
-        Ok(())
-    }
-
     /// Collect all objects across all pages into a single Vec.
     pub async fn collect_all(mut self) -> Result<Vec<ObjectInfo>> {
         let mut all = Vec::new();
-        while !self.done {
-            self.fetch_next_page().await?;
-            all.extend(self.buffer.drain(..));
+        while let Some(page) = self.next_page().await? {
+            all.extend(page.contents);
         }
         Ok(all)
     }
 
     /// Get the raw next page response (useful when you need metadata like common_prefixes).
+    ///
+    /// With [`max_items`](ListObjectsV2PaginatorBuilder::max_items) set, the
+    /// last page's `contents` are cut to the limit.
     pub async fn next_page(&mut self) -> Result<Option<ListObjectsV2Response>> {
-        if self.done {
+        if self.done || self.limits.reached() {
             return Ok(None);
         }
 
@@ -100,7 +127,7 @@ impl ListObjectsV2Paginator {
         if let Some(ref delimiter) = self.delimiter {
             builder = builder.delimiter(delimiter.clone());
         }
-        if let Some(max_keys) = self.max_keys {
+        if let Some(max_keys) = self.limits.page_size(self.max_keys) {
             builder = builder.max_keys(max_keys);
         }
         if let Some(ref start_after) = self.start_after {
@@ -111,7 +138,8 @@ impl ListObjectsV2Paginator {
         }
 
         let request = builder.build()?;
-        let response = self.client.list_objects_v2(request).await?;
+        let mut response = self.client.list_objects_v2(request).await?;
+        self.limits.record(&mut response.contents);
 
         if response.is_truncated {
             self.continuation_token = response.next_continuation_token.clone();
@@ -121,6 +149,27 @@ impl ListObjectsV2Paginator {
 
         Ok(Some(response))
     }
+
+    /// Number of pages fetched so far.
+    pub fn pages_fetched(&self) -> usize {
+        self.limits.pages
+    }
+
+    /// Number of objects returned so far.
+    pub fn items_fetched(&self) -> usize {
+        self.limits.items
+    }
+
+    /// The paginator's position, to resume it later with
+    /// [`ListObjectsV2PaginatorBuilder::resume_from`].
+    pub fn resume_state(&self) -> ResumeState {
+        ResumeState {
+            token: self.continuation_token.clone(),
+            done: self.done,
+            pages_fetched: self.limits.pages,
+            items_fetched: self.limits.items,
+        }
+    }
 }
 
 /// A paginator that yields [`BucketInfo`] items across all pages of a ListBuckets call.
@@ -129,63 +178,26 @@ pub struct ListBucketsPaginator {
     prefix: Option<String>,
     max_keys: Option<u32>,
     marker: Option<String>,
-    buffer: std::collections::VecDeque<BucketInfo>,
+    limits: Limits,
     done: bool,
 }
 
 impl ListBucketsPaginator {
-    pub(crate) fn new(client: OssClient, prefix: Option<String>, max_keys: Option<u32>) -> Self {
-        Self {
-            client,
-            prefix,
-            max_keys,
-            marker: None,
-            buffer: std::collections::VecDeque::new(),
-            done: false,
-        }
-    }
-
-    async fn fetch_next_page(&mut self) -> Result<()> {
-        use crate::types::request::ListBucketsRequestBuilder;
-
-        let mut builder = ListBucketsRequestBuilder::new();
-        if let Some(ref prefix) = self.prefix {
-            builder = builder.prefix(prefix.clone());
-        }
-        if let Some(max_keys) = self.max_keys {
-            builder = builder.max_keys(max_keys);
-        }
-        if let Some(ref marker) = self.marker {
-            builder = builder.marker(marker.clone());
-        }
-
-        let request = builder.build()?;
-        let response = self.client.list_buckets(request).await?;
-
-        self.buffer.extend(response.buckets.bucket);
-
-        if response.is_truncated {
-            self.marker = response.next_marker;
-        } else {
-            self.done = true;
-        }
-
-        Ok(())
-    }
-
     /// Collect all buckets across all pages into a single Vec.
     pub async fn collect_all(mut self) -> Result<Vec<BucketInfo>> {
         let mut all = Vec::new();
-        while !self.done {
-            self.fetch_next_page().await?;
-            all.extend(self.buffer.drain(..));
+        while let Some(page) = self.next_page().await? {
+            all.extend(page.buckets.bucket);
         }
         Ok(all)
     }
 
     /// Get the raw next page response.
+    ///
+    /// With [`max_items`](ListBucketsPaginatorBuilder::max_items) set, the
+    /// last page's buckets are cut to the limit.
     pub async fn next_page(&mut self) -> Result<Option<ListBucketsResponse>> {
-        if self.done {
+        if self.done || self.limits.reached() {
             return Ok(None);
         }
 
@@ -195,7 +207,7 @@ impl ListBucketsPaginator {
         if let Some(ref prefix) = self.prefix {
             builder = builder.prefix(prefix.clone());
         }
-        if let Some(max_keys) = self.max_keys {
+        if let Some(max_keys) = self.limits.page_size(self.max_keys) {
             builder = builder.max_keys(max_keys);
         }
         if let Some(ref marker) = self.marker {
@@ -203,7 +215,8 @@ impl ListBucketsPaginator {
         }
 
         let request = builder.build()?;
-        let response = self.client.list_buckets(request).await?;
+        let mut response = self.client.list_buckets(request).await?;
+        self.limits.record(&mut response.buckets.bucket);
 
         if response.is_truncated {
             self.marker = response.next_marker.clone();
@@ -213,6 +226,27 @@ impl ListBucketsPaginator {
 
         Ok(Some(response))
     }
+
+    /// Number of pages fetched so far.
+    pub fn pages_fetched(&self) -> usize {
+        self.limits.pages
+    }
+
+    /// Number of buckets returned so far.
+    pub fn items_fetched(&self) -> usize {
+        self.limits.items
+    }
+
+    /// The paginator's position, to resume it later with
+    /// [`ListBucketsPaginatorBuilder::resume_from`].
+    pub fn resume_state(&self) -> ResumeState {
+        ResumeState {
+            token: self.marker.clone(),
+            done: self.done,
+            pages_fetched: self.limits.pages,
+            items_fetched: self.limits.items,
+        }
+    }
 }
 
 impl OssClient {
@@ -225,6 +259,8 @@ impl OssClient {
             delimiter: None,
             max_keys: None,
             start_after: None,
+            limits: Limits::default(),
+            resume: ResumeState::default(),
         }
     }
 
@@ -234,6 +270,8 @@ impl OssClient {
             client: self.clone(),
             prefix: None,
             max_keys: None,
+            limits: Limits::default(),
+            resume: ResumeState::default(),
         }
     }
 }
@@ -246,6 +284,8 @@ pub struct ListObjectsV2PaginatorBuilder {
     delimiter: Option<String>,
     max_keys: Option<u32>,
     start_after: Option<String>,
+    limits: Limits,
+    resume: ResumeState,
 }
 
 impl ListObjectsV2PaginatorBuilder {
@@ -273,16 +313,42 @@ impl ListObjectsV2PaginatorBuilder {
         self
     }
 
+    /// Stop after fetching this many pages.
+    pub fn max_pages(mut self, max_pages: usize) -> Self {
+        self.limits.max_pages = Some(max_pages);
+        self
+    }
+
+    /// Stop after returning this many objects.
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.limits.max_items = Some(max_items);
+        self
+    }
+
+    /// Continue from a position saved with
+    /// [`ListObjectsV2Paginator::resume_state`].
+    pub fn resume_from(mut self, state: ResumeState) -> Self {
+        self.resume = state;
+        self
+    }
+
     /// Build the paginator.
     pub fn build(self) -> ListObjectsV2Paginator {
-        ListObjectsV2Paginator::new(
-            self.client,
-            self.bucket,
-            self.prefix,
-            self.delimiter,
-            self.max_keys,
-            self.start_after,
-        )
+        ListObjectsV2Paginator {
+            client: self.client,
+            bucket: self.bucket,
+            prefix: self.prefix,
+            delimiter: self.delimiter,
+            max_keys: self.max_keys,
+            start_after: self.start_after,
+            continuation_token: self.resume.token,
+            limits: Limits {
+                pages: self.resume.pages_fetched,
+                items: self.resume.items_fetched,
+                ..self.limits
+            },
+            done: self.resume.done,
+        }
     }
 }
 
@@ -291,6 +357,8 @@ pub struct ListBucketsPaginatorBuilder {
     client: OssClient,
     prefix: Option<String>,
     max_keys: Option<u32>,
+    limits: Limits,
+    resume: ResumeState,
 }
 
 impl ListBucketsPaginatorBuilder {
@@ -306,8 +374,38 @@ impl ListBucketsPaginatorBuilder {
         self
     }
 
+    /// Stop after fetching this many pages.
+    pub fn max_pages(mut self, max_pages: usize) -> Self {
+        self.limits.max_pages = Some(max_pages);
+        self
+    }
+
+    /// Stop after returning this many buckets.
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.limits.max_items = Some(max_items);
+        self
+    }
+
+    /// Continue from a position saved with
+    /// [`ListBucketsPaginator::resume_state`].
+    pub fn resume_from(mut self, state: ResumeState) -> Self {
+        self.resume = state;
+        self
+    }
+
     /// Build the paginator.
     pub fn build(self) -> ListBucketsPaginator {
-        ListBucketsPaginator::new(self.client, self.prefix, self.max_keys)
+        ListBucketsPaginator {
+            client: self.client,
+            prefix: self.prefix,
+            max_keys: self.max_keys,
+            marker: self.resume.token,
+            limits: Limits {
+                pages: self.resume.pages_fetched,
+                items: self.resume.items_fetched,
+                ..self.limits
+            },
+            done: self.resume.done,
+        }
     }
 }
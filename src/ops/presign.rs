@@ -1,17 +1,22 @@
 //! Presigned URL generation for OSS objects.
 //!
 //! Generates time-limited URLs that allow unauthenticated access to private objects.
-//! Supports both GET (download) and PUT (upload) presigned URLs using V4 query-string signing.
+//! Supports both GET (download) and PUT (upload) presigned URLs using V4 query-string
+//! signing, or legacy V1 signing when the client is configured for it.
+//! [`OssClient::object_url`] builds the unsigned URL of a public object.
 
 use chrono::Utc;
 use percent_encoding::percent_encode;
 
+use crate::auth::SignatureVersion;
+use crate::auth::v1;
 use crate::auth::v4::{
     build_string_to_sign, calculate_signature, canonical_uri, derive_signing_key,
 };
 use crate::client::OssClient;
 use crate::encoding::QUERY_ENCODE_SET;
-use crate::error::Result;
+use crate::error::{OssError, Result};
+use crate::types::common::{BucketName, ObjectKey};
 use crate::types::request::PresignedUrlRequest;
 
 impl OssClient {
@@ -62,7 +67,48 @@ impl OssClient {
 
     /// V4 query-string presign: canonical URI always includes `/{bucket}/{key}`,
     /// canonical headers and additional headers are both empty.
+    /// Build the unsigned URL of an object, for objects readable without
+    /// credentials (public-read object or bucket ACL).
+    ///
+    /// Follows the client's addressing: virtual-hosted by default, the bucket
+    /// as the first path segment with
+    /// [`use_path_style`](crate::config::ClientBuilder::use_path_style), and
+    /// the endpoint as-is with [`cname`](crate::config::ClientBuilder::cname).
+    /// Key segments are percent-encoded, so keys containing `?`, `#`, `%` or
+    /// spaces produce working URLs.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use rs_ali_oss::*;
+    /// # fn example(client: OssClient) -> Result<()> {
+    /// let url = client.object_url(
+    ///     &BucketName::new("my-bucket")?,
+    ///     &ObjectKey::new("images/logo.png")?,
+    /// )?;
+    /// assert_eq!(url, "https://my-bucket.oss-cn-hangzhou.aliyuncs.com/images/logo.png");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn object_url(&self, bucket: &BucketName, key: &ObjectKey) -> Result<String> {
+        let mut url = url::Url::parse(&self.endpoint(Some(bucket)))
+            .map_err(|e| OssError::InvalidUrl(e.to_string()))?;
+        {
+            let mut segments = url
+                .path_segments_mut()
+                .map_err(|_| OssError::InvalidUrl("endpoint cannot be a base URL".into()))?;
+            segments.pop_if_empty();
+            if self.config().use_path_style() && !self.config().cname() {
+                segments.push(bucket.as_ref());
+            }
+            segments.extend(key.as_ref().split('/'));
+        }
+        Ok(url.into())
+    }
+
     fn generate_presigned_url(&self, method: &str, request: PresignedUrlRequest) -> Result<String> {
+        if self.config().signature_version() == SignatureVersion::V1 {
+            return self.generate_presigned_url_v1(method, request);
+        }
         let now = request.datetime.unwrap_or_else(Utc::now);
         let datetime_str = now.format("%Y%m%dT%H%M%SZ").to_string();
         let date_str = now.format("%Y%m%d").to_string();
@@ -73,10 +119,11 @@ impl OssClient {
         let host = base_url.host_str().unwrap_or_default().to_string();
 
         let signing_uri = canonical_uri(&format!("/{}/{}", request.bucket, request.key));
+        let credentials = self.config().credentials()?;
 
         let credential = format!(
             "{}/{}/{}/oss/aliyun_v4_request",
-            self.config().credentials().access_key_id(),
+            credentials.access_key_id(),
             date_str,
             region_str,
         );
@@ -91,7 +138,7 @@ impl OssClient {
             ),
         ];
 
-        if let Some(token) = self.config().credentials().security_token() {
+        if let Some(token) = credentials.security_token() {
             query_params.push(("x-oss-security-token".to_string(), token.to_string()));
         }
 
@@ -116,11 +163,8 @@ impl OssClient {
 
         let string_to_sign =
             build_string_to_sign(&datetime_str, &date_str, region_str, &canonical_request);
-        let signing_key = derive_signing_key(
-            self.config().credentials().access_key_secret(),
-            &date_str,
-            region_str,
-        )?;
+        let signing_key =
+            derive_signing_key(credentials.access_key_secret(), &date_str, region_str)?;
         let signature = calculate_signature(&signing_key, &string_to_sign)?;
 
         let url_path = if self.config().use_path_style() {
@@ -136,6 +180,48 @@ impl OssClient {
 
         Ok(final_url)
     }
+
+    /// V1 query-string presign: signs `Expires` in place of the `Date` header,
+    /// and the content type when one is set on the request.
+    fn generate_presigned_url_v1(
+        &self,
+        method: &str,
+        request: PresignedUrlRequest,
+    ) -> Result<String> {
+        let now = request.datetime.unwrap_or_else(Utc::now);
+        let expires = (now.timestamp() + request.expires.as_secs() as i64).to_string();
+        let credentials = self.config().credentials()?;
+
+        let resource_path = format!("/{}/{}", request.bucket, request.key);
+        let token_param = credentials
+            .security_token()
+            .map(|token| ("security-token", token));
+        let string_to_sign = v1::build_string_to_sign(
+            method,
+            "",
+            request.content_type.as_deref().unwrap_or(""),
+            &expires,
+            "",
+            &v1::canonicalized_resource(&resource_path, token_param),
+        );
+        let signature = v1::calculate_signature(credentials.access_key_secret(), &string_to_sign)?;
+
+        let mut query_params = vec![
+            ("OSSAccessKeyId", credentials.access_key_id()),
+            ("Expires", expires.as_str()),
+            ("Signature", signature.as_str()),
+        ];
+        query_params.extend(token_param);
+        let query = query_params
+            .iter()
+            .map(|(k, v)| format!("{k}={}", percent_encode(v.as_bytes(), QUERY_ENCODE_SET)))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let mut url = self.build_url(Some(&request.bucket), Some(&request.key), &[])?;
+        url.set_query(Some(&query));
+        Ok(url.to_string())
+    }
 }
 
 #[cfg(test)]
@@ -154,6 +240,58 @@ mod tests {
         .unwrap()
     }
 
+    fn object_url(builder: ClientBuilder, key: &str) -> String {
+        let client = crate::client::OssClient::from_builder(
+            builder
+                .access_key_id("test-key-id")
+                .access_key_secret("test-key-secret")
+                .region("cn-hangzhou"),
+        )
+        .unwrap();
+        client
+            .object_url(
+                &BucketName::new("my-bucket").unwrap(),
+                &ObjectKey::new(key).unwrap(),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn object_url_follows_addressing_style() {
+        assert_eq!(
+            object_url(ClientBuilder::new(), "docs/a.pdf"),
+            "https://my-bucket.oss-cn-hangzhou.aliyuncs.com/docs/a.pdf"
+        );
+        assert_eq!(
+            object_url(ClientBuilder::new().use_path_style(true), "docs/a.pdf"),
+            "https://oss-cn-hangzhou.aliyuncs.com/my-bucket/docs/a.pdf"
+        );
+        assert_eq!(
+            object_url(
+                ClientBuilder::new().endpoint("https://oss.example.com/"),
+                "a.pdf"
+            ),
+            "https://my-bucket.oss.example.com/a.pdf"
+        );
+        assert_eq!(
+            object_url(
+                ClientBuilder::new()
+                    .endpoint("https://static.example.com")
+                    .cname(true),
+                "a.pdf"
+            ),
+            "https://static.example.com/a.pdf"
+        );
+    }
+
+    #[test]
+    fn object_url_encodes_key_segments() {
+        assert_eq!(
+            object_url(ClientBuilder::new(), "dir/100% done?#1 .txt"),
+            "https://my-bucket.oss-cn-hangzhou.aliyuncs.com/dir/100%25%20done%3F%231%20.txt"
+        );
+    }
+
     #[test]
     fn presign_get_object_produces_valid_url() {
         let client = test_client();
@@ -260,4 +398,39 @@ mod tests {
         assert!(sig1.chars().all(|c| c.is_ascii_hexdigit()));
         assert!(sig2.chars().all(|c| c.is_ascii_hexdigit()));
     }
+
+    #[test]
+    fn presign_v1_signs_expires_and_token() {
+        let client = crate::client::OssClient::from_builder(
+            ClientBuilder::new()
+                .access_key_id("test-key-id")
+                .access_key_secret("test-key-secret")
+                .security_token("sts-token")
+                .region("cn-hangzhou")
+                .signature_version(crate::auth::SignatureVersion::V1),
+        )
+        .unwrap();
+        let datetime = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let request = PresignedUrlRequestBuilder::new()
+            .bucket(BucketName::new("my-bucket").unwrap())
+            .key(ObjectKey::new("docs/report.pdf").unwrap())
+            .datetime(datetime)
+            .build()
+            .unwrap();
+        let url = client.presign_get_object(request).unwrap();
+
+        let expected_signature = crate::auth::v1::calculate_signature(
+            "test-key-secret",
+            "GET\n\n\n1700003600\n/my-bucket/docs/report.pdf?security-token=sts-token",
+        )
+        .unwrap();
+        let parsed = url::Url::parse(&url).unwrap();
+        let query: std::collections::HashMap<_, _> = parsed.query_pairs().into_owned().collect();
+        assert!(url.starts_with("https://my-bucket.oss-cn-hangzhou.aliyuncs.com/docs/report.pdf?"));
+        assert_eq!(query["OSSAccessKeyId"], "test-key-id");
+        assert_eq!(query["Expires"], "1700003600");
+        assert_eq!(query["security-token"], "sts-token");
+        assert_eq!(query["Signature"], expected_signature);
+        assert!(!url.contains("x-oss-signature"));
+    }
 }
@@ -1,30 +1,39 @@
-//! Transfer Manager for automatic multipart uploads of large files.
+//! Transfer Manager for automatic multipart uploads and ranged downloads of
+//! large files.
 
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use tokio::sync::Semaphore;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, watch};
 use tokio::task::JoinSet;
 
 use crate::client::OssClient;
 use crate::error::{OssError, Result};
-use crate::progress::{NoopProgressListener, ProgressListener, TransferKind, TransferProgress};
-use crate::types::common::{BucketName, ObjectKey, StorageClass};
+use crate::progress::{
+    NoopProgressListener, ProgressListener, ProgressThrottle, ProgressTracker,
+    ThrottledProgressListener, TransferKind,
+};
+use crate::types::common::{BucketName, ObjectAcl, ObjectKey, ServerSideEncryption, StorageClass};
 use crate::types::request::{
     AbortMultipartUploadRequestBuilder, CompleteMultipartUploadRequestBuilder, CompletedPart,
-    InitiateMultipartUploadRequestBuilder, PutObjectRequestBuilder, UploadPartRequestBuilder,
+    GetObjectRequestBuilder, HeadObjectRequestBuilder, InitiateMultipartUploadRequestBuilder,
+    MIN_PART_SIZE, PutObjectRequestBuilder, UploadPartRequestBuilder, validate_metadata_key,
+    validate_tags,
 };
 
 const DEFAULT_PART_SIZE: u64 = 8 * 1024 * 1024;
-const MIN_PART_SIZE: u64 = 100 * 1024;
 const DEFAULT_MULTIPART_THRESHOLD: u64 = 8 * 1024 * 1024;
 const DEFAULT_CONCURRENCY: usize = 8;
 
-/// Automatic multipart upload manager.
+/// Automatic multipart upload and ranged download manager.
 ///
 /// Splits large uploads into parts, tracks progress, and computes CRC64
 /// checksums when enabled. Falls back to simple `PutObject` for data
-/// below the multipart threshold.
+/// below the multipart threshold. Large downloads are fetched as parallel
+/// ranged `GetObject` requests of the part size.
 ///
 /// # Examples
 /// ```no_run
@@ -50,8 +59,14 @@ pub struct TransferManager {
     part_size: u64,
     multipart_threshold: u64,
     concurrency: usize,
+    max_total_parts_in_flight: Option<usize>,
+    total_parts: Option<Arc<Semaphore>>,
     progress_listener: Arc<dyn ProgressListener>,
+    progress_throttle: ProgressThrottle,
+    transfer_listener: Option<Arc<dyn TransferListener>>,
+    part_retries: u32,
     enable_crc64: bool,
+    paused: watch::Sender<bool>,
 }
 
 /// Builder for [`TransferManager`].
@@ -60,7 +75,11 @@ pub struct TransferManagerBuilder {
     part_size: u64,
     multipart_threshold: u64,
     concurrency: usize,
+    max_total_parts_in_flight: Option<usize>,
     progress_listener: Option<Arc<dyn ProgressListener>>,
+    progress_throttle: ProgressThrottle,
+    transfer_listener: Option<Arc<dyn TransferListener>>,
+    part_retries: u32,
     enable_crc64: bool,
 }
 
@@ -72,7 +91,11 @@ impl TransferManagerBuilder {
             part_size: DEFAULT_PART_SIZE,
             multipart_threshold: DEFAULT_MULTIPART_THRESHOLD,
             concurrency: DEFAULT_CONCURRENCY,
+            max_total_parts_in_flight: None,
             progress_listener: None,
+            progress_throttle: ProgressThrottle::default(),
+            transfer_listener: None,
+            part_retries: 0,
             enable_crc64: false,
         }
     }
@@ -83,7 +106,8 @@ impl TransferManagerBuilder {
         self
     }
 
-    /// Set the size threshold above which multipart upload is used.
+    /// Set the size threshold above which multipart upload and ranged
+    /// download are used.
     pub fn multipart_threshold(mut self, threshold: u64) -> Self {
         self.multipart_threshold = threshold;
         self
@@ -95,18 +119,72 @@ impl TransferManagerBuilder {
         self
     }
 
+    /// Limit how often the progress listener is called (default: on every
+    /// part or body chunk).
+    ///
+    /// Useful on fast links, where per-chunk callbacks can flood a UI event
+    /// loop.
+    pub fn progress_throttle(mut self, throttle: ProgressThrottle) -> Self {
+        self.progress_throttle = throttle;
+        self
+    }
+
+    /// Attach a listener for transfer lifecycle events: started, part
+    /// completed or retried, paused, resumed, failed and completed.
+    pub fn transfer_listener(mut self, listener: Arc<dyn TransferListener>) -> Self {
+        self.transfer_listener = Some(listener);
+        self
+    }
+
+    /// Retry a failed part upload or range download up to `retries` times
+    /// (default: 0).
+    ///
+    /// Only transient errors and checksum mismatches are retried; each retry
+    /// is reported as [`TransferEvent::PartRetried`]. This is on top of the
+    /// client's own per-request retries.
+    pub fn part_retries(mut self, retries: u32) -> Self {
+        self.part_retries = retries;
+        self
+    }
+
     /// Enable CRC64 checksum computation and combination across parts.
+    ///
+    /// Each part's CRC64 is checked against the one OSS returns for it, and
+    /// the combined CRC64 against the one returned for the completed object;
+    /// a difference fails the upload with [`OssError::ChecksumMismatch`].
     pub fn enable_crc64(mut self, enable: bool) -> Self {
         self.enable_crc64 = enable;
         self
     }
 
-    /// Set the maximum number of concurrent part uploads (default: 8).
+    /// Set the maximum number of concurrent part uploads or range downloads
+    /// of each transfer (default: 8).
+    ///
+    /// Same as
+    /// [`max_parts_in_flight_per_transfer`](Self::max_parts_in_flight_per_transfer).
     pub fn concurrency(mut self, concurrency: usize) -> Self {
         self.concurrency = concurrency;
         self
     }
 
+    /// Set the maximum number of part uploads or range downloads one
+    /// transfer runs at a time (default: 8).
+    pub fn max_parts_in_flight_per_transfer(self, max: usize) -> Self {
+        self.concurrency(max)
+    }
+
+    /// Set the maximum number of part uploads or range downloads in flight
+    /// across all transfers of the manager (default: unlimited).
+    ///
+    /// Simple uploads and downloads count as one part. Slots are handed out
+    /// first come, first served, so with a per-transfer limit below this
+    /// one, a huge transfer leaves room for the small ones sharing the
+    /// manager.
+    pub fn max_total_parts_in_flight(mut self, max: usize) -> Self {
+        self.max_total_parts_in_flight = Some(max.max(1));
+        self
+    }
+
     /// Build the transfer manager.
     pub fn build(self) -> TransferManager {
         let part_size = if self.part_size < MIN_PART_SIZE {
@@ -120,10 +198,18 @@ impl TransferManagerBuilder {
             part_size,
             multipart_threshold: self.multipart_threshold,
             concurrency,
+            max_total_parts_in_flight: self.max_total_parts_in_flight,
+            total_parts: self
+                .max_total_parts_in_flight
+                .map(|max| Arc::new(Semaphore::new(max))),
             progress_listener: self
                 .progress_listener
                 .unwrap_or_else(|| Arc::new(NoopProgressListener)),
+            progress_throttle: self.progress_throttle,
+            transfer_listener: self.transfer_listener,
+            part_retries: self.part_retries,
             enable_crc64: self.enable_crc64,
+            paused: watch::Sender::new(false),
         }
     }
 }
@@ -136,9 +222,17 @@ pub struct TransferUploadRequest {
     pub(crate) data: Vec<u8>,
     pub(crate) content_type: Option<String>,
     pub(crate) storage_class: Option<StorageClass>,
+    pub(crate) acl: Option<ObjectAcl>,
+    pub(crate) server_side_encryption: Option<ServerSideEncryption>,
+    pub(crate) kms_key_id: Option<String>,
+    pub(crate) metadata: HashMap<String, String>,
+    pub(crate) tags: Vec<(String, String)>,
 }
 
 /// Builder for [`TransferUploadRequest`].
+///
+/// The object attributes are sent with the PutObject or
+/// InitiateMultipartUpload request, whichever the upload uses.
 #[derive(Debug, Default)]
 pub struct TransferUploadRequestBuilder {
     bucket: Option<BucketName>,
@@ -146,6 +240,11 @@ pub struct TransferUploadRequestBuilder {
     data: Option<Vec<u8>>,
     content_type: Option<String>,
     storage_class: Option<StorageClass>,
+    acl: Option<ObjectAcl>,
+    server_side_encryption: Option<ServerSideEncryption>,
+    kms_key_id: Option<String>,
+    metadata: HashMap<String, String>,
+    tags: Vec<(String, String)>,
 }
 
 impl TransferUploadRequestBuilder {
@@ -184,8 +283,50 @@ impl TransferUploadRequestBuilder {
         self
     }
 
+    /// Set the object ACL.
+    pub fn acl(mut self, acl: ObjectAcl) -> Self {
+        self.acl = Some(acl);
+        self
+    }
+
+    /// Encrypt the object at rest (`x-oss-server-side-encryption`).
+    pub fn server_side_encryption(mut self, encryption: ServerSideEncryption) -> Self {
+        self.server_side_encryption = Some(encryption);
+        self
+    }
+
+    /// Set the KMS key used with [`ServerSideEncryption::KMS`].
+    pub fn kms_key_id(mut self, kms_key_id: impl Into<String>) -> Self {
+        self.kms_key_id = Some(kms_key_id.into());
+        self
+    }
+
+    /// Add a custom metadata entry (x-oss-meta-*).
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Add a tag (sent as `x-oss-tagging`).
+    pub fn tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.tags.push((key.into(), value.into()));
+        self
+    }
+
     /// Build the request.
     pub fn build(self) -> Result<TransferUploadRequest> {
+        for key in self.metadata.keys() {
+            validate_metadata_key(key)?;
+        }
+        validate_tags(&self.tags)?;
+        if self.kms_key_id.is_some()
+            && self.server_side_encryption != Some(ServerSideEncryption::KMS)
+        {
+            return Err(OssError::InvalidParameter {
+                field: "kms_key_id".into(),
+                reason: "a KMS key requires KMS server-side encryption".into(),
+            });
+        }
         Ok(TransferUploadRequest {
             bucket: self
                 .bucket
@@ -198,6 +339,11 @@ impl TransferUploadRequestBuilder {
                 .ok_or_else(|| OssError::MissingField("data".into()))?,
             content_type: self.content_type,
             storage_class: self.storage_class,
+            acl: self.acl,
+            server_side_encryption: self.server_side_encryption,
+            kms_key_id: self.kms_key_id,
+            metadata: self.metadata,
+            tags: self.tags,
         })
     }
 }
@@ -213,7 +359,246 @@ pub struct TransferUploadResponse {
     pub multipart: bool,
 }
 
+/// Request for a managed download.
+#[derive(Debug)]
+pub struct TransferDownloadRequest {
+    pub(crate) bucket: BucketName,
+    pub(crate) key: ObjectKey,
+}
+
+/// Builder for [`TransferDownloadRequest`].
+#[derive(Debug, Default)]
+pub struct TransferDownloadRequestBuilder {
+    bucket: Option<BucketName>,
+    key: Option<ObjectKey>,
+}
+
+impl TransferDownloadRequestBuilder {
+    /// Create a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the source bucket.
+    pub fn bucket(mut self, bucket: BucketName) -> Self {
+        self.bucket = Some(bucket);
+        self
+    }
+
+    /// Set the object key.
+    pub fn key(mut self, key: ObjectKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Build the request.
+    pub fn build(self) -> Result<TransferDownloadRequest> {
+        Ok(TransferDownloadRequest {
+            bucket: self
+                .bucket
+                .ok_or_else(|| OssError::MissingField("bucket".into()))?,
+            key: self
+                .key
+                .ok_or_else(|| OssError::MissingField("key".into()))?,
+        })
+    }
+}
+
+/// Response from a managed download.
+#[derive(Debug)]
+pub struct TransferDownloadResponse {
+    /// The object's content.
+    pub data: Vec<u8>,
+    /// ETag of the downloaded object.
+    pub etag: Option<String>,
+    /// Whether the object was fetched as parallel ranged requests.
+    pub ranged: bool,
+}
+
+/// Response from [`TransferManager::download_to_writer`].
+#[derive(Debug)]
+pub struct TransferWriteResponse {
+    /// Number of bytes written to the sink.
+    pub bytes_written: u64,
+    /// ETag of the downloaded object.
+    pub etag: Option<String>,
+    /// Whether the object was fetched as parallel ranged requests.
+    pub ranged: bool,
+}
+
+/// The object a [`TransferEvent`] belongs to.
+#[derive(Debug, Clone)]
+pub struct TransferTarget {
+    /// Bucket of the object.
+    pub bucket: BucketName,
+    /// Key of the object.
+    pub key: ObjectKey,
+    /// Transfer direction.
+    pub kind: TransferKind,
+}
+
+/// A state change of a managed transfer.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum TransferEvent<'a> {
+    /// The transfer started. Downloads report this once the object size is
+    /// known, so a download whose `HeadObject` fails only reports
+    /// [`Failed`](Self::Failed).
+    Started {
+        /// Size of the object in bytes.
+        total_bytes: u64,
+    },
+    /// A part (uploads) or range (downloads) finished.
+    PartCompleted {
+        /// 1-based number of the part or range.
+        part_number: u32,
+        /// Size of the part or range in bytes.
+        bytes: u64,
+    },
+    /// A part or range failed and is being tried again.
+    PartRetried {
+        /// 1-based number of the part or range.
+        part_number: u32,
+        /// Retry number, starting at 1.
+        attempt: u32,
+        /// The error of the failed attempt.
+        error: &'a OssError,
+    },
+    /// The manager was paused; the transfer waits before starting its next
+    /// part or range.
+    Paused,
+    /// The manager was resumed and the transfer continues.
+    Resumed,
+    /// The transfer failed.
+    Failed {
+        /// The error the transfer failed with.
+        error: &'a OssError,
+    },
+    /// An upload finished.
+    UploadCompleted {
+        /// The upload's response.
+        response: &'a TransferUploadResponse,
+    },
+    /// A download finished.
+    DownloadCompleted {
+        /// The download's response.
+        response: &'a TransferWriteResponse,
+    },
+}
+
+/// Receives lifecycle events of the transfers run by a [`TransferManager`].
+///
+/// Unlike [`ProgressListener`], which reports byte counts, this reports
+/// state changes, for orchestration layers that schedule, retry or
+/// display transfers.
+pub trait TransferListener: Send + Sync {
+    /// Called on every state change of a transfer.
+    fn on_event(&self, target: &TransferTarget, event: &TransferEvent<'_>);
+}
+
+impl<F> TransferListener for F
+where
+    F: Fn(&TransferTarget, &TransferEvent<'_>) + Send + Sync,
+{
+    fn on_event(&self, target: &TransferTarget, event: &TransferEvent<'_>) {
+        self(target, event);
+    }
+}
+
+/// State of one running transfer, shared with its part tasks.
+struct Transfer {
+    target: TransferTarget,
+    listener: Option<Arc<dyn TransferListener>>,
+    paused: watch::Receiver<bool>,
+    pause_reported: AtomicBool,
+    part_retries: u32,
+    total_parts: Option<Arc<Semaphore>>,
+}
+
+impl Transfer {
+    /// Take a slot of the manager-wide part limit, if one is set.
+    async fn acquire_slot(&self) -> Result<Option<OwnedSemaphorePermit>> {
+        let Some(total_parts) = &self.total_parts else {
+            return Ok(None);
+        };
+        let permit = Arc::clone(total_parts)
+            .acquire_owned()
+            .await
+            .map_err(|_| OssError::Auth("semaphore closed".to_string()))?;
+        Ok(Some(permit))
+    }
+
+    fn emit(&self, event: TransferEvent<'_>) {
+        if let Some(listener) = &self.listener {
+            listener.on_event(&self.target, &event);
+        }
+    }
+
+    /// Wait while the manager is paused, reporting the pause once per
+    /// transfer.
+    async fn wait_if_paused(&self) {
+        let mut paused = self.paused.clone();
+        if !*paused.borrow_and_update() {
+            return;
+        }
+        if !self.pause_reported.swap(true, Ordering::AcqRel) {
+            self.emit(TransferEvent::Paused);
+        }
+        // The sender lives as long as the manager, which outlives its
+        // transfers.
+        let _ = paused.wait_for(|paused| !paused).await;
+        if self.pause_reported.swap(false, Ordering::AcqRel) {
+            self.emit(TransferEvent::Resumed);
+        }
+    }
+
+    /// Run one part or range, retrying transient failures.
+    async fn run_part<T, F, Fut>(&self, part_number: u32, mut attempt: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut retries = 0;
+        loop {
+            self.wait_if_paused().await;
+            match attempt().await {
+                Err(e)
+                    if retries < self.part_retries
+                        && (e.is_retryable() || matches!(e, OssError::ChecksumMismatch { .. })) =>
+                {
+                    retries += 1;
+                    self.emit(TransferEvent::PartRetried {
+                        part_number,
+                        attempt: retries,
+                        error: &e,
+                    });
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
 impl TransferManager {
+    /// Pause all transfers of this manager.
+    ///
+    /// Parts and ranges already in flight finish; no new ones start until
+    /// [`resume`](Self::resume) is called. Each affected transfer reports
+    /// [`TransferEvent::Paused`].
+    pub fn pause(&self) {
+        self.paused.send_replace(true);
+    }
+
+    /// Resume transfers paused with [`pause`](Self::pause).
+    pub fn resume(&self) {
+        self.paused.send_replace(false);
+    }
+
+    /// Whether the manager is paused.
+    pub fn is_paused(&self) -> bool {
+        *self.paused.borrow()
+    }
+
     /// Upload data, automatically choosing simple or multipart upload.
     ///
     /// If the data length exceeds the multipart threshold, the upload is split
@@ -222,17 +607,37 @@ impl TransferManager {
     /// the multipart upload is aborted.
     pub async fn upload(&self, request: TransferUploadRequest) -> Result<TransferUploadResponse> {
         let total_size = request.data.len() as u64;
+        let transfer = self.transfer(&request.bucket, &request.key, TransferKind::Upload);
+        transfer.emit(TransferEvent::Started {
+            total_bytes: total_size,
+        });
 
-        if total_size <= self.multipart_threshold {
-            return self.simple_upload(request, total_size).await;
+        let progress = self.tracker(&transfer, Some(total_size));
+        let cancelled = progress.cancel_guard();
+
+        let result = if total_size <= self.multipart_threshold {
+            self.simple_upload(request, total_size, &transfer, &progress)
+                .await
+        } else {
+            self.multipart_upload(request, &transfer, &progress).await
+        };
+        cancelled.disarm();
+        match &result {
+            Ok(response) => transfer.emit(TransferEvent::UploadCompleted { response }),
+            Err(error) => {
+                progress.fail(error);
+                transfer.emit(TransferEvent::Failed { error });
+            }
         }
-        self.multipart_upload(request, total_size).await
+        result
     }
 
     async fn simple_upload(
         &self,
         request: TransferUploadRequest,
         total_size: u64,
+        transfer: &Transfer,
+        progress: &ProgressTracker,
     ) -> Result<TransferUploadResponse> {
         let crc = if self.enable_crc64 {
             Some(crate::crc64::checksum(&request.data))
@@ -251,20 +656,30 @@ impl TransferManager {
         if let Some(sc) = request.storage_class {
             builder = builder.storage_class(sc);
         }
+        if let Some(acl) = request.acl {
+            builder = builder.acl(acl);
+        }
+        if let Some(sse) = request.server_side_encryption {
+            builder = builder.server_side_encryption(sse);
+        }
+        if let Some(kms_key_id) = request.kms_key_id {
+            builder = builder.kms_key_id(kms_key_id);
+        }
+        for (k, v) in request.metadata {
+            builder = builder.metadata(k, v);
+        }
+        for (k, v) in request.tags {
+            builder = builder.tag(k, v);
+        }
 
-        self.progress_listener.on_progress(&TransferProgress {
-            bytes_transferred: 0,
-            total_bytes: Some(total_size),
-            kind: TransferKind::Upload,
-        });
+        progress.start();
 
-        let resp = self.client.put_object(builder.build()?).await?;
+        let request = builder.build()?;
+        let _slot = transfer.acquire_slot().await?;
+        transfer.wait_if_paused().await;
+        let resp = self.client.put_object(request).await?;
 
-        self.progress_listener.on_progress(&TransferProgress {
-            bytes_transferred: total_size,
-            total_bytes: Some(total_size),
-            kind: TransferKind::Upload,
-        });
+        progress.advance(total_size);
 
         Ok(TransferUploadResponse {
             etag: resp.etag,
@@ -276,7 +691,8 @@ impl TransferManager {
     async fn multipart_upload(
         &self,
         request: TransferUploadRequest,
-        total_size: u64,
+        transfer: &Arc<Transfer>,
+        progress: &ProgressTracker,
     ) -> Result<TransferUploadResponse> {
         let bucket = request.bucket;
         let key = request.key;
@@ -291,6 +707,21 @@ impl TransferManager {
         if let Some(sc) = request.storage_class {
             init_builder = init_builder.storage_class(sc);
         }
+        if let Some(acl) = request.acl {
+            init_builder = init_builder.acl(acl);
+        }
+        if let Some(sse) = request.server_side_encryption {
+            init_builder = init_builder.server_side_encryption(sse);
+        }
+        if let Some(kms_key_id) = request.kms_key_id {
+            init_builder = init_builder.kms_key_id(kms_key_id);
+        }
+        for (k, v) in request.metadata {
+            init_builder = init_builder.metadata(k, v);
+        }
+        for (k, v) in request.tags {
+            init_builder = init_builder.tag(k, v);
+        }
 
         let init_resp = self
             .client
@@ -298,14 +729,10 @@ impl TransferManager {
             .await?;
         let upload_id = init_resp.upload_id;
 
-        self.progress_listener.on_progress(&TransferProgress {
-            bytes_transferred: 0,
-            total_bytes: Some(total_size),
-            kind: TransferKind::Upload,
-        });
+        progress.start();
 
         match self
-            .upload_parts(&bucket, &key, &upload_id, &request.data, total_size)
+            .upload_parts(transfer, &upload_id, &request.data, progress)
             .await
         {
             Ok((parts, combined_crc)) => {
@@ -317,6 +744,11 @@ impl TransferManager {
                     .build()?;
 
                 let complete_resp = self.client.complete_multipart_upload(complete_req).await?;
+                if let (Some(computed), Some(server)) = (combined_crc, complete_resp.crc64)
+                    && computed != server
+                {
+                    return Err(OssError::ChecksumMismatch { computed, server });
+                }
 
                 Ok(TransferUploadResponse {
                     etag: complete_resp.etag.trim_matches('"').to_string(),
@@ -339,16 +771,14 @@ impl TransferManager {
 
     async fn upload_parts(
         &self,
-        bucket: &BucketName,
-        key: &ObjectKey,
+        transfer: &Arc<Transfer>,
         upload_id: &str,
         data: &[u8],
-        total_size: u64,
+        progress: &ProgressTracker,
     ) -> Result<(Vec<CompletedPart>, Option<u64>)> {
         let part_size = self.part_size as usize;
         let data: Arc<[u8]> = Arc::from(data);
         let semaphore = Arc::new(Semaphore::new(self.concurrency));
-        let bytes_sent = Arc::new(AtomicU64::new(0));
         let mut join_set = JoinSet::new();
 
         let num_parts = data.len().div_ceil(part_size);
@@ -359,47 +789,57 @@ impl TransferManager {
             let offset = i * part_size;
             let chunk_len = chunk_range.len();
 
-            if self.enable_crc64 {
-                let part_crc = crate::crc64::checksum(chunk_range);
+            let part_crc = self
+                .enable_crc64
+                .then(|| crate::crc64::checksum(chunk_range));
+            if let Some(part_crc) = part_crc {
                 part_crcs.push((part_crc, chunk_len as u64));
             }
 
             let client = self.client.clone();
-            let bucket = bucket.clone();
-            let key = key.clone();
+            let transfer = Arc::clone(transfer);
             let upload_id = upload_id.to_string();
             let data = Arc::clone(&data);
             let sem = Arc::clone(&semaphore);
-            let progress = Arc::clone(&bytes_sent);
-            let listener = Arc::clone(&self.progress_listener);
+            let progress = progress.clone();
 
             join_set.spawn(async move {
                 let _permit = sem
                     .acquire()
                     .await
                     .map_err(|_| OssError::Auth("semaphore closed".to_string()))?;
-
-                let chunk = data[offset..offset + chunk_len].to_vec();
-
-                let upload_req = UploadPartRequestBuilder::new()
-                    .bucket(bucket)
-                    .key(key)
-                    .upload_id(&upload_id)
-                    .part_number(part_number)
-                    .body(chunk)
-                    .build()?;
-
-                let resp = client.upload_part(upload_req).await?;
-
-                let sent =
-                    progress.fetch_add(chunk_len as u64, Ordering::Relaxed) + chunk_len as u64;
-                listener.on_progress(&TransferProgress {
-                    bytes_transferred: sent,
-                    total_bytes: Some(total_size),
-                    kind: TransferKind::Upload,
+                let _slot = transfer.acquire_slot().await?;
+
+                let etag = transfer
+                    .run_part(part_number, || async {
+                        let upload_req = UploadPartRequestBuilder::new()
+                            .bucket(transfer.target.bucket.clone())
+                            .key(transfer.target.key.clone())
+                            .upload_id(&upload_id)
+                            .part_number(part_number)
+                            .body(data[offset..offset + chunk_len].to_vec())
+                            .build()?;
+
+                        let resp = client.upload_part(upload_req).await?;
+                        if let (Some(expected), Some(server)) = (part_crc, resp.crc64)
+                            && expected != server
+                        {
+                            return Err(OssError::ChecksumMismatch {
+                                computed: expected,
+                                server,
+                            });
+                        }
+                        Ok(resp.etag)
+                    })
+                    .await?;
+
+                progress.advance(chunk_len as u64);
+                transfer.emit(TransferEvent::PartCompleted {
+                    part_number,
+                    bytes: chunk_len as u64,
                 });
 
-                Ok::<_, OssError>((part_number, resp.etag))
+                Ok::<_, OssError>((part_number, etag))
             });
         }
 
@@ -424,6 +864,252 @@ impl TransferManager {
 
         Ok((parts, combined_crc))
     }
+
+    /// Download an object, fetching it in parallel ranges when it is larger
+    /// than the multipart threshold.
+    ///
+    /// The object size is looked up with `HeadObject` first. Progress is
+    /// reported to the configured listener as body chunks arrive.
+    pub async fn download(
+        &self,
+        request: TransferDownloadRequest,
+    ) -> Result<TransferDownloadResponse> {
+        let mut data = Vec::new();
+        let response = self.download_to_writer(request, &mut data).await?;
+        Ok(TransferDownloadResponse {
+            data,
+            etag: response.etag,
+            ranged: response.ranged,
+        })
+    }
+
+    /// Download an object into `writer`, e.g. a file, a tar builder or an
+    /// HTTP response body.
+    ///
+    /// Large objects are fetched as parallel ranged requests like
+    /// [`download`](Self::download), but written strictly in order. At most
+    /// `concurrency` ranges are fetched or buffered at a time, so memory use
+    /// stays around `concurrency * part_size` whatever the object size.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use rs_ali_oss::*;
+    /// # use rs_ali_oss::ops::transfer::*;
+    /// # async fn example(manager: TransferManager) -> Result<()> {
+    /// let request = TransferDownloadRequestBuilder::new()
+    ///     .bucket(BucketName::new("my-bucket")?)
+    ///     .key(ObjectKey::new("backup.tar")?)
+    ///     .build()?;
+    /// let file = tokio::fs::File::create("backup.tar").await?;
+    /// let response = manager.download_to_writer(request, file).await?;
+    /// println!("wrote {} bytes", response.bytes_written);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_to_writer<W>(
+        &self,
+        request: TransferDownloadRequest,
+        mut writer: W,
+    ) -> Result<TransferWriteResponse>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let transfer = self.transfer(&request.bucket, &request.key, TransferKind::Download);
+        let progress = self.tracker(&transfer, None);
+        let cancelled = progress.cancel_guard();
+
+        let result = self
+            .write_object(request, &mut writer, &transfer, &progress)
+            .await;
+        cancelled.disarm();
+        match &result {
+            Ok(response) => transfer.emit(TransferEvent::DownloadCompleted { response }),
+            Err(error) => {
+                progress.fail(error);
+                transfer.emit(TransferEvent::Failed { error });
+            }
+        }
+        result
+    }
+
+    async fn write_object<W>(
+        &self,
+        request: TransferDownloadRequest,
+        writer: &mut W,
+        transfer: &Arc<Transfer>,
+        progress: &ProgressTracker,
+    ) -> Result<TransferWriteResponse>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let head = self
+            .client
+            .head_object(
+                HeadObjectRequestBuilder::new()
+                    .bucket(request.bucket.clone())
+                    .key(request.key.clone())
+                    .build()?,
+            )
+            .await?;
+        let total_size = head.content_length.unwrap_or(0);
+        transfer.emit(TransferEvent::Started {
+            total_bytes: total_size,
+        });
+        progress.set_total(total_size);
+        progress.start();
+
+        if total_size <= self.multipart_threshold {
+            let _slot = transfer.acquire_slot().await?;
+            transfer.wait_if_paused().await;
+            let response = self
+                .client
+                .get_object(
+                    GetObjectRequestBuilder::new()
+                        .bucket(request.bucket)
+                        .key(request.key)
+                        .build()?,
+                )
+                .await?;
+            let bytes_written = response
+                .body
+                .with_progress(progress.clone())
+                .write_to(writer)
+                .await?;
+            return Ok(TransferWriteResponse {
+                bytes_written,
+                etag: response.etag,
+                ranged: false,
+            });
+        }
+
+        let bytes_written = self
+            .write_ranges(transfer, total_size, progress, writer)
+            .await?;
+        Ok(TransferWriteResponse {
+            bytes_written,
+            etag: head.etag,
+            ranged: true,
+        })
+    }
+
+    /// Fetch the object in ranges of the part size and write them in order.
+    ///
+    /// Ranges are started in order, keeping `concurrency` in flight or
+    /// buffered, and each is written as soon as every range before it has
+    /// been. The range tasks live in a [`JoinSet`], so returning early or
+    /// dropping this future aborts any that are still downloading.
+    async fn write_ranges<W>(
+        &self,
+        transfer: &Arc<Transfer>,
+        total_size: u64,
+        progress: &ProgressTracker,
+        writer: &mut W,
+    ) -> Result<u64>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let num_ranges = total_size.div_ceil(self.part_size);
+        let mut tasks = JoinSet::new();
+        let mut ready = BTreeMap::new();
+        let mut next_range = 0;
+        let mut next_write = 0;
+        let mut written = 0;
+
+        loop {
+            while next_range < num_ranges && tasks.len() + ready.len() < self.concurrency {
+                let start = next_range * self.part_size;
+                let end = (start + self.part_size).min(total_size) - 1;
+                self.spawn_range(&mut tasks, transfer, next_range, start, end, progress);
+                next_range += 1;
+            }
+            let Some(joined) = tasks.join_next().await else {
+                break;
+            };
+            let (index, chunk) = joined
+                .map_err(|e| OssError::Auth(format!("range download task panicked: {e}")))??;
+            ready.insert(index, chunk);
+            while let Some(chunk) = ready.remove(&next_write) {
+                writer.write_all(&chunk).await?;
+                written += chunk.len() as u64;
+                next_write += 1;
+            }
+        }
+        writer.flush().await?;
+        Ok(written)
+    }
+
+    fn spawn_range(
+        &self,
+        tasks: &mut JoinSet<Result<(u64, bytes::Bytes)>>,
+        transfer: &Arc<Transfer>,
+        index: u64,
+        start: u64,
+        end: u64,
+        progress: &ProgressTracker,
+    ) {
+        let client = self.client.clone();
+        let transfer = Arc::clone(transfer);
+        let progress = progress.clone();
+        let part_number = index as u32 + 1;
+        tasks.spawn(async move {
+            let _slot = transfer.acquire_slot().await?;
+            let bytes = transfer
+                .run_part(part_number, || async {
+                    let get_req = GetObjectRequestBuilder::new()
+                        .bucket(transfer.target.bucket.clone())
+                        .key(transfer.target.key.clone())
+                        .range(format!("bytes={start}-{end}"))
+                        .build()?;
+                    let response = client.get_object(get_req).await?;
+                    Ok(response.body.bytes().await?)
+                })
+                .await?;
+            // Only the attempt that succeeded counts; a retried range would
+            // otherwise report its partial bytes twice.
+            progress.advance(bytes.len() as u64);
+            transfer.emit(TransferEvent::PartCompleted {
+                part_number,
+                bytes: bytes.len() as u64,
+            });
+            Ok((index, bytes))
+        });
+    }
+
+    fn transfer(&self, bucket: &BucketName, key: &ObjectKey, kind: TransferKind) -> Arc<Transfer> {
+        Arc::new(Transfer {
+            target: TransferTarget {
+                bucket: bucket.clone(),
+                key: key.clone(),
+                kind,
+            },
+            listener: self.transfer_listener.clone(),
+            paused: self.paused.subscribe(),
+            pause_reported: AtomicBool::new(false),
+            part_retries: self.part_retries,
+            total_parts: self.total_parts.clone(),
+        })
+    }
+
+    /// A progress tracker for one transfer. Throttling state is kept per
+    /// transfer, so concurrent transfers don't suppress each other's events.
+    fn tracker(&self, transfer: &Transfer, total_size: Option<u64>) -> ProgressTracker {
+        let listener: Arc<dyn ProgressListener> =
+            if self.progress_throttle == ProgressThrottle::default() {
+                Arc::clone(&self.progress_listener)
+            } else {
+                Arc::new(ThrottledProgressListener::new(
+                    Arc::clone(&self.progress_listener),
+                    self.progress_throttle,
+                ))
+            };
+        ProgressTracker::new(
+            listener,
+            &transfer.target.bucket,
+            &transfer.target.key,
+            total_size,
+            transfer.target.kind,
+        )
+    }
 }
 
 impl std::fmt::Debug for TransferManager {
@@ -432,7 +1118,10 @@ impl std::fmt::Debug for TransferManager {
             .field("part_size", &self.part_size)
             .field("multipart_threshold", &self.multipart_threshold)
             .field("concurrency", &self.concurrency)
+            .field("max_total_parts_in_flight", &self.max_total_parts_in_flight)
+            .field("part_retries", &self.part_retries)
             .field("enable_crc64", &self.enable_crc64)
+            .field("paused", &self.is_paused())
             .finish_non_exhaustive()
     }
 }
@@ -509,6 +1198,22 @@ mod tests {
         assert_eq!(mgr.concurrency, 1);
     }
 
+    #[test]
+    fn builder_total_parts_limit() {
+        let mgr = TransferManagerBuilder::new(test_client())
+            .max_parts_in_flight_per_transfer(2)
+            .max_total_parts_in_flight(0)
+            .build();
+        assert_eq!(mgr.concurrency, 2);
+        assert_eq!(mgr.max_total_parts_in_flight, Some(1));
+        assert!(
+            TransferManagerBuilder::new(test_client())
+                .build()
+                .total_parts
+                .is_none()
+        );
+    }
+
     #[test]
     fn upload_request_builder() {
         let req = TransferUploadRequestBuilder::new()
@@ -0,0 +1,282 @@
+//! Recursive listing of delimiter-separated "directories".
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::client::OssClient;
+use crate::error::Result;
+use crate::types::common::BucketName;
+use crate::types::request::ListObjectsV2RequestBuilder;
+use crate::types::response::ObjectInfo;
+
+/// Decides whether to skip descending into a directory, given its prefix
+/// and depth.
+type PruneFn = dyn Fn(&str, usize) -> bool + Send + Sync;
+
+/// An entry produced by a [`Walker`].
+#[derive(Debug, Clone)]
+pub enum WalkEntry {
+    /// A common prefix, i.e. a directory.
+    Directory {
+        /// The directory prefix, ending with the delimiter.
+        prefix: String,
+        /// Depth below the walk root; direct children have depth 1.
+        depth: usize,
+    },
+    /// An object.
+    Object {
+        /// The listed object.
+        info: ObjectInfo,
+        /// Depth below the walk root; direct children have depth 1.
+        depth: usize,
+    },
+}
+
+impl WalkEntry {
+    /// The directory prefix or object key.
+    pub fn key(&self) -> &str {
+        match self {
+            Self::Directory { prefix, .. } => prefix,
+            Self::Object { info, .. } => &info.key,
+        }
+    }
+
+    /// Depth below the walk root; direct children have depth 1.
+    pub fn depth(&self) -> usize {
+        match self {
+            Self::Directory { depth, .. } | Self::Object { depth, .. } => *depth,
+        }
+    }
+
+    /// Returns `true` for directories.
+    pub fn is_directory(&self) -> bool {
+        matches!(self, Self::Directory { .. })
+    }
+}
+
+/// A directory being listed.
+struct Frame {
+    prefix: String,
+    depth: usize,
+    continuation_token: Option<String>,
+    buffer: VecDeque<WalkEntry>,
+    done: bool,
+}
+
+impl Frame {
+    fn new(prefix: String, depth: usize) -> Self {
+        Self {
+            prefix,
+            depth,
+            continuation_token: None,
+            buffer: VecDeque::new(),
+            done: false,
+        }
+    }
+}
+
+/// Walks a prefix depth-first, listing one directory level per request.
+///
+/// Each directory is yielded before its contents, and entries within a
+/// directory come in key order. Pages are fetched lazily, so large trees
+/// are walked without holding them in memory.
+///
+/// # Examples
+/// ```no_run
+/// # use rs_ali_oss::*;
+/// # async fn example(client: OssClient) -> Result<()> {
+/// let mut walker = client
+///     .walk(BucketName::new("my-bucket")?, "photos/")
+///     .max_depth(3)
+///     .prune(|prefix, _depth| prefix.ends_with("/thumbnails/"))
+///     .build();
+/// while let Some(entry) = walker.next_entry().await? {
+///     println!("{}{}", "  ".repeat(entry.depth() - 1), entry.key());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Walker {
+    client: OssClient,
+    bucket: BucketName,
+    delimiter: String,
+    max_depth: Option<usize>,
+    max_keys: Option<u32>,
+    prune: Option<Arc<PruneFn>>,
+    stack: Vec<Frame>,
+}
+
+impl Walker {
+    /// Returns the next entry, or `None` when the walk is complete.
+    pub async fn next_entry(&mut self) -> Result<Option<WalkEntry>> {
+        loop {
+            let Some(frame) = self.stack.last_mut() else {
+                return Ok(None);
+            };
+            if let Some(entry) = frame.buffer.pop_front() {
+                if let WalkEntry::Directory { prefix, depth } = &entry
+                    && self.descends_into(prefix, *depth)
+                {
+                    self.stack.push(Frame::new(prefix.clone(), depth + 1));
+                }
+                return Ok(Some(entry));
+            }
+            if frame.done {
+                self.stack.pop();
+                continue;
+            }
+            self.fetch_next_page().await?;
+        }
+    }
+
+    /// Collect every remaining entry into a single Vec.
+    pub async fn collect_all(mut self) -> Result<Vec<WalkEntry>> {
+        let mut all = Vec::new();
+        while let Some(entry) = self.next_entry().await? {
+            all.push(entry);
+        }
+        Ok(all)
+    }
+
+    fn descends_into(&self, prefix: &str, depth: usize) -> bool {
+        self.max_depth.is_none_or(|max| depth < max)
+            && !self
+                .prune
+                .as_ref()
+                .is_some_and(|prune| prune(prefix, depth))
+    }
+
+    async fn fetch_next_page(&mut self) -> Result<()> {
+        let Some(frame) = self.stack.last_mut() else {
+            return Ok(());
+        };
+        let mut builder = ListObjectsV2RequestBuilder::new()
+            .bucket(self.bucket.clone())
+            .prefix(frame.prefix.clone())
+            .delimiter(self.delimiter.clone());
+        if let Some(max_keys) = self.max_keys {
+            builder = builder.max_keys(max_keys);
+        }
+        if let Some(ref token) = frame.continuation_token {
+            builder = builder.continuation_token(token.clone());
+        }
+        let response = self.client.list_objects_v2(builder.build()?).await?;
+
+        let depth = frame.depth;
+        let mut entries: Vec<WalkEntry> = response
+            .contents
+            .into_iter()
+            .map(|info| WalkEntry::Object { info, depth })
+            .chain(
+                response
+                    .common_prefixes
+                    .into_iter()
+                    .map(|p| WalkEntry::Directory {
+                        prefix: p.prefix,
+                        depth,
+                    }),
+            )
+            .collect();
+        entries.sort_by(|a, b| a.key().cmp(b.key()));
+        frame.buffer.extend(entries);
+
+        if response.is_truncated && response.next_continuation_token.is_some() {
+            frame.continuation_token = response.next_continuation_token;
+        } else {
+            frame.done = true;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Walker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Walker")
+            .field("bucket", &self.bucket)
+            .field("delimiter", &self.delimiter)
+            .field("max_depth", &self.max_depth)
+            .field("pending_directories", &self.stack.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl OssClient {
+    /// Create a walker that recursively lists everything under `prefix`.
+    pub fn walk(&self, bucket: BucketName, prefix: impl Into<String>) -> WalkerBuilder {
+        WalkerBuilder {
+            client: self.clone(),
+            bucket,
+            prefix: prefix.into(),
+            delimiter: "/".to_string(),
+            max_depth: None,
+            max_keys: None,
+            prune: None,
+        }
+    }
+}
+
+/// Builder for [`Walker`].
+pub struct WalkerBuilder {
+    client: OssClient,
+    bucket: BucketName,
+    prefix: String,
+    delimiter: String,
+    max_depth: Option<usize>,
+    max_keys: Option<u32>,
+    prune: Option<Arc<PruneFn>>,
+}
+
+impl WalkerBuilder {
+    /// Directory separator (default: `/`).
+    pub fn delimiter(mut self, delimiter: impl Into<String>) -> Self {
+        self.delimiter = delimiter.into();
+        self
+    }
+
+    /// Deepest level to list; `1` lists only the root's direct children.
+    /// Directories at the limit are still yielded, just not descended into.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Maximum keys per page (1-1000).
+    pub fn max_keys(mut self, max_keys: u32) -> Self {
+        self.max_keys = Some(max_keys);
+        self
+    }
+
+    /// Skip descending into directories for which `prune(prefix, depth)`
+    /// returns `true`. Pruned directories are still yielded.
+    pub fn prune(mut self, prune: impl Fn(&str, usize) -> bool + Send + Sync + 'static) -> Self {
+        self.prune = Some(Arc::new(prune));
+        self
+    }
+
+    /// Build the walker.
+    pub fn build(self) -> Walker {
+        Walker {
+            client: self.client,
+            bucket: self.bucket,
+            delimiter: self.delimiter,
+            max_depth: self.max_depth,
+            max_keys: self.max_keys,
+            prune: self.prune,
+            stack: vec![Frame::new(self.prefix, 1)],
+        }
+    }
+}
+
+impl fmt::Debug for WalkerBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WalkerBuilder")
+            .field("bucket", &self.bucket)
+            .field("prefix", &self.prefix)
+            .field("delimiter", &self.delimiter)
+            .field("max_depth", &self.max_depth)
+            .field("max_keys", &self.max_keys)
+            .field("prune", &self.prune.is_some())
+            .finish_non_exhaustive()
+    }
+}
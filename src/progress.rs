@@ -1,6 +1,12 @@
 //! Progress tracking for upload and download operations.
 
-use std::sync::Arc;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::error::OssError;
+use crate::types::common::{BucketName, ObjectKey};
 
 /// Describes the type of transfer being tracked.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -12,8 +18,15 @@ pub enum TransferKind {
 }
 
 /// Snapshot of transfer progress at a point in time.
+///
+/// Carries the object it belongs to, so one listener shared by many
+/// concurrent transfers can tell them apart.
 #[derive(Debug, Clone)]
 pub struct TransferProgress {
+    /// Bucket of the object being transferred.
+    pub bucket: BucketName,
+    /// Key of the object being transferred.
+    pub key: ObjectKey,
     /// Bytes transferred so far.
     pub bytes_transferred: u64,
     /// Total bytes expected (if known).
@@ -34,9 +47,24 @@ impl TransferProgress {
 ///
 /// Implement this trait to display progress bars, log throughput, or
 /// enforce transfer timeouts.
+///
+/// Transfers run by a [`TransferManager`](crate::TransferManager) also end
+/// with a terminal notification when they don't succeed, so a stalled
+/// transfer can be told apart from a dead one: [`on_failed`] or
+/// [`on_cancelled`], called at most once per transfer.
+///
+/// [`on_failed`]: ProgressListener::on_failed
+/// [`on_cancelled`]: ProgressListener::on_cancelled
 pub trait ProgressListener: Send + Sync {
     /// Called periodically as bytes are transferred.
     fn on_progress(&self, progress: &TransferProgress);
+
+    /// Called when the transfer fails, with the progress it had made.
+    fn on_failed(&self, _progress: &TransferProgress, _error: &OssError) {}
+
+    /// Called when the transfer is cancelled, i.e. its future is dropped
+    /// before it finishes.
+    fn on_cancelled(&self, _progress: &TransferProgress) {}
 }
 
 impl<F> ProgressListener for F
@@ -61,12 +89,262 @@ pub fn shared_listener(listener: impl ProgressListener + 'static) -> Arc<dyn Pro
     Arc::new(listener)
 }
 
+/// Limits how often progress events are delivered.
+///
+/// An event is delivered once at least the interval has passed or at least
+/// the byte step has been transferred since the last delivered event. With
+/// neither set, every event is delivered.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+/// use rs_ali_oss::progress::ProgressThrottle;
+///
+/// let throttle = ProgressThrottle::new()
+///     .interval(Duration::from_millis(100))
+///     .bytes(8 * 1024 * 1024);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProgressThrottle {
+    interval: Option<Duration>,
+    bytes: Option<u64>,
+}
+
+impl ProgressThrottle {
+    /// Create a throttle that delivers every event.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Deliver an event once this much time has passed since the last one.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Deliver an event once this many bytes have been transferred since the
+    /// last one.
+    pub fn bytes(mut self, bytes: u64) -> Self {
+        self.bytes = Some(bytes);
+        self
+    }
+
+    fn is_unlimited(&self) -> bool {
+        self.interval.is_none() && self.bytes.is_none()
+    }
+}
+
+/// A [`ProgressListener`] that forwards events to another listener at most
+/// as often as a [`ProgressThrottle`] allows.
+///
+/// The first event and the event completing a transfer of known size are
+/// always delivered, so a progress bar starts at zero and ends full.
+/// Failure and cancellation notifications are never throttled.
+///
+/// The throttle tracks a single stream of events, so use one wrapper per
+/// transfer; [`TransferManager`](crate::TransferManager) does this for you.
+pub struct ThrottledProgressListener {
+    inner: Arc<dyn ProgressListener>,
+    throttle: ProgressThrottle,
+    last: Mutex<Option<(Instant, u64)>>,
+}
+
+impl ThrottledProgressListener {
+    /// Wrap `inner` so it receives events no more often than `throttle` allows.
+    pub fn new(inner: Arc<dyn ProgressListener>, throttle: ProgressThrottle) -> Self {
+        Self {
+            inner,
+            throttle,
+            last: Mutex::new(None),
+        }
+    }
+
+    fn should_deliver(&self, progress: &TransferProgress) -> bool {
+        if self.throttle.is_unlimited() {
+            return true;
+        }
+        let now = Instant::now();
+        let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+        let complete = progress.total_bytes == Some(progress.bytes_transferred);
+        let due = match *last {
+            None => true,
+            Some((at, bytes)) => {
+                complete
+                    || self
+                        .throttle
+                        .interval
+                        .is_some_and(|interval| now.duration_since(at) >= interval)
+                    || self.throttle.bytes.is_some_and(|step| {
+                        progress.bytes_transferred.saturating_sub(bytes) >= step
+                    })
+            }
+        };
+        if due {
+            *last = Some((now, progress.bytes_transferred));
+        }
+        due
+    }
+}
+
+impl ProgressListener for ThrottledProgressListener {
+    fn on_progress(&self, progress: &TransferProgress) {
+        if self.should_deliver(progress) {
+            self.inner.on_progress(progress);
+        }
+    }
+
+    fn on_failed(&self, progress: &TransferProgress, error: &OssError) {
+        self.inner.on_failed(progress, error);
+    }
+
+    fn on_cancelled(&self, progress: &TransferProgress) {
+        self.inner.on_cancelled(progress);
+    }
+}
+
+impl fmt::Debug for ThrottledProgressListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThrottledProgressListener")
+            .field("throttle", &self.throttle)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Counts the bytes moved by one transfer and reports them to a listener.
+///
+/// Clones share the count, so the parts of a parallel transfer report a
+/// single running total.
+#[derive(Clone)]
+pub(crate) struct ProgressTracker {
+    listener: Arc<dyn ProgressListener>,
+    bucket: BucketName,
+    key: ObjectKey,
+    transferred: Arc<AtomicU64>,
+    total_bytes: Arc<OnceLock<u64>>,
+    kind: TransferKind,
+}
+
+impl ProgressTracker {
+    pub(crate) fn new(
+        listener: Arc<dyn ProgressListener>,
+        bucket: &BucketName,
+        key: &ObjectKey,
+        total_bytes: Option<u64>,
+        kind: TransferKind,
+    ) -> Self {
+        let total = OnceLock::new();
+        if let Some(total_bytes) = total_bytes {
+            let _ = total.set(total_bytes);
+        }
+        Self {
+            listener,
+            bucket: bucket.clone(),
+            key: key.clone(),
+            transferred: Arc::new(AtomicU64::new(0)),
+            total_bytes: Arc::new(total),
+            kind,
+        }
+    }
+
+    /// Set the total once it is known, e.g. after a `HeadObject`. Has no
+    /// effect if the total was already set.
+    pub(crate) fn set_total(&self, total_bytes: u64) {
+        let _ = self.total_bytes.set(total_bytes);
+    }
+
+    /// Report the start of the transfer.
+    pub(crate) fn start(&self) {
+        self.report(self.transferred.load(Ordering::Relaxed));
+    }
+
+    /// Record `bytes` more transferred bytes and report the new total.
+    pub(crate) fn advance(&self, bytes: u64) {
+        let transferred = self.transferred.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        self.report(transferred);
+    }
+
+    /// Report that the transfer failed.
+    pub(crate) fn fail(&self, error: &OssError) {
+        self.listener.on_failed(&self.snapshot(), error);
+    }
+
+    /// Report that the transfer was cancelled.
+    pub(crate) fn cancel(&self) {
+        self.listener.on_cancelled(&self.snapshot());
+    }
+
+    /// A guard that reports the transfer as cancelled unless it is disarmed
+    /// before being dropped.
+    pub(crate) fn cancel_guard(&self) -> CancelGuard {
+        CancelGuard(Some(self.clone()))
+    }
+
+    fn report(&self, bytes_transferred: u64) {
+        self.listener.on_progress(&TransferProgress {
+            bucket: self.bucket.clone(),
+            key: self.key.clone(),
+            bytes_transferred,
+            total_bytes: self.total_bytes.get().copied(),
+            kind: self.kind,
+        });
+    }
+
+    fn snapshot(&self) -> TransferProgress {
+        TransferProgress {
+            bucket: self.bucket.clone(),
+            key: self.key.clone(),
+            bytes_transferred: self.transferred.load(Ordering::Relaxed),
+            total_bytes: self.total_bytes.get().copied(),
+            kind: self.kind,
+        }
+    }
+}
+
+/// Reports a transfer as cancelled when dropped, e.g. because the future
+/// running the transfer was dropped.
+pub(crate) struct CancelGuard(Option<ProgressTracker>);
+
+impl CancelGuard {
+    /// The transfer finished, successfully or not; don't report it.
+    pub(crate) fn disarm(mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for CancelGuard {
+    fn drop(&mut self) {
+        if let Some(tracker) = self.0.take() {
+            tracker.cancel();
+        }
+    }
+}
+
+impl fmt::Debug for ProgressTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgressTracker")
+            .field("bucket", &self.bucket)
+            .field("key", &self.key)
+            .field("transferred", &self.transferred.load(Ordering::Relaxed))
+            .field("total_bytes", &self.total_bytes.get())
+            .field("kind", &self.kind)
+            .finish_non_exhaustive()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Mutex;
 
     use super::*;
 
+    fn bucket() -> BucketName {
+        BucketName::new("my-bucket").unwrap()
+    }
+
+    fn key() -> ObjectKey {
+        ObjectKey::new("dir/file.bin").unwrap()
+    }
+
     #[test]
     fn closure_as_listener() {
         let counter = Arc::new(AtomicU64::new(0));
@@ -76,6 +354,8 @@ mod tests {
         };
 
         let progress = TransferProgress {
+            bucket: bucket(),
+            key: key(),
             bytes_transferred: 42,
             total_bytes: Some(100),
             kind: TransferKind::Upload,
@@ -87,6 +367,8 @@ mod tests {
     #[test]
     fn fraction_with_total() {
         let p = TransferProgress {
+            bucket: bucket(),
+            key: key(),
             bytes_transferred: 50,
             total_bytes: Some(100),
             kind: TransferKind::Download,
@@ -97,6 +379,8 @@ mod tests {
     #[test]
     fn fraction_without_total() {
         let p = TransferProgress {
+            bucket: bucket(),
+            key: key(),
             bytes_transferred: 50,
             total_bytes: None,
             kind: TransferKind::Upload,
@@ -108,6 +392,8 @@ mod tests {
     fn noop_listener_compiles() {
         let listener = NoopProgressListener;
         let progress = TransferProgress {
+            bucket: bucket(),
+            key: key(),
             bytes_transferred: 0,
             total_bytes: None,
             kind: TransferKind::Upload,
@@ -126,4 +412,161 @@ mod tests {
         assert_eq!(TransferKind::Upload, TransferKind::Upload);
         assert_ne!(TransferKind::Upload, TransferKind::Download);
     }
+
+    #[test]
+    fn tracker_clones_share_total() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let tracker = ProgressTracker::new(
+            Arc::new(move |p: &TransferProgress| {
+                sink.lock()
+                    .unwrap()
+                    .push((p.bytes_transferred, p.total_bytes, p.kind));
+            }),
+            &bucket(),
+            &key(),
+            Some(10),
+            TransferKind::Download,
+        );
+        tracker.start();
+        tracker.advance(4);
+        tracker.clone().advance(6);
+        assert_eq!(
+            *seen.lock().unwrap(),
+            [
+                (0, Some(10), TransferKind::Download),
+                (4, Some(10), TransferKind::Download),
+                (10, Some(10), TransferKind::Download),
+            ]
+        );
+    }
+
+    #[test]
+    fn tracker_reports_bucket_and_key() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let tracker = ProgressTracker::new(
+            Arc::new(move |p: &TransferProgress| {
+                sink.lock()
+                    .unwrap()
+                    .push(format!("{}/{} {:?}", p.bucket, p.key, p.kind));
+            }),
+            &bucket(),
+            &key(),
+            None,
+            TransferKind::Upload,
+        );
+        tracker.start();
+        assert_eq!(*seen.lock().unwrap(), ["my-bucket/dir/file.bin Upload"]);
+    }
+
+    #[derive(Default)]
+    struct Terminal(Mutex<Vec<String>>);
+
+    impl ProgressListener for Terminal {
+        fn on_progress(&self, _progress: &TransferProgress) {}
+
+        fn on_failed(&self, progress: &TransferProgress, error: &OssError) {
+            self.0.lock().unwrap().push(format!(
+                "failed at {}/{:?}: {error}",
+                progress.bytes_transferred, progress.total_bytes
+            ));
+        }
+
+        fn on_cancelled(&self, progress: &TransferProgress) {
+            self.0.lock().unwrap().push(format!(
+                "cancelled at {}/{:?}",
+                progress.bytes_transferred, progress.total_bytes
+            ));
+        }
+    }
+
+    #[test]
+    fn tracker_reports_failure_and_cancellation() {
+        let listener = Arc::new(Terminal::default());
+        let tracker = ProgressTracker::new(
+            listener.clone(),
+            &bucket(),
+            &key(),
+            None,
+            TransferKind::Download,
+        );
+        tracker.set_total(10);
+        tracker.advance(4);
+
+        tracker.cancel_guard().disarm();
+        tracker.fail(&OssError::Timeout(Duration::from_secs(1)));
+        drop(tracker.cancel_guard());
+        assert_eq!(
+            *listener.0.lock().unwrap(),
+            [
+                "failed at 4/Some(10): operation timed out after 1s",
+                "cancelled at 4/Some(10)"
+            ]
+        );
+    }
+
+    #[test]
+    fn throttle_forwards_terminal_notifications() {
+        let inner = Arc::new(Terminal::default());
+        let listener = ThrottledProgressListener::new(
+            inner.clone(),
+            ProgressThrottle::new().interval(Duration::from_secs(3600)),
+        );
+        listener.on_progress(&upload(0, None));
+        listener.on_cancelled(&upload(5, None));
+        assert_eq!(*inner.0.lock().unwrap(), ["cancelled at 5/None"]);
+    }
+
+    fn recording() -> (Arc<Mutex<Vec<u64>>>, Arc<dyn ProgressListener>) {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let listener = shared_listener(move |p: &TransferProgress| {
+            sink.lock().unwrap().push(p.bytes_transferred);
+        });
+        (seen, listener)
+    }
+
+    fn upload(bytes_transferred: u64, total_bytes: Option<u64>) -> TransferProgress {
+        TransferProgress {
+            bucket: bucket(),
+            key: key(),
+            bytes_transferred,
+            total_bytes,
+            kind: TransferKind::Upload,
+        }
+    }
+
+    #[test]
+    fn throttle_by_bytes_keeps_first_and_last() {
+        let (seen, inner) = recording();
+        let listener = ThrottledProgressListener::new(inner, ProgressThrottle::new().bytes(100));
+        for bytes in (0..=250).step_by(10) {
+            listener.on_progress(&upload(bytes, Some(250)));
+        }
+        assert_eq!(*seen.lock().unwrap(), [0, 100, 200, 250]);
+    }
+
+    #[test]
+    fn throttle_by_interval_drops_bursts() {
+        let (seen, inner) = recording();
+        let listener = ThrottledProgressListener::new(
+            inner,
+            ProgressThrottle::new().interval(Duration::from_secs(3600)),
+        );
+        for bytes in 0..10 {
+            listener.on_progress(&upload(bytes, None));
+        }
+        assert_eq!(*seen.lock().unwrap(), [0]);
+    }
+
+    #[test]
+    fn unlimited_throttle_delivers_everything() {
+        let (seen, inner) = recording();
+        let listener = ThrottledProgressListener::new(inner, ProgressThrottle::new());
+        for bytes in 0..5 {
+            listener.on_progress(&upload(bytes, None));
+        }
+        assert_eq!(seen.lock().unwrap().len(), 5);
+    }
 }
@@ -0,0 +1,417 @@
+//! Pluggable retry classification.
+//!
+//! The client consults a [`RetryClassifier`] after every failed attempt to
+//! decide whether the request should be retried. Backoff and the attempt
+//! budget are still governed by [`RetryConfig`].
+
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::config::RetryConfig;
+use crate::error::{OssError, Result};
+
+/// Whether a failed attempt should be retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryDecision {
+    /// Retry the request after the configured backoff.
+    Retry,
+    /// Fail immediately with the attempt's error.
+    Fail,
+}
+
+/// Information about a failed attempt, passed to [`RetryClassifier::classify`].
+#[derive(Debug)]
+pub struct RetryAttempt<'a> {
+    /// HTTP method (e.g., "GET", "PUT").
+    pub method: &'a str,
+    /// Zero-based attempt number.
+    pub attempt: u32,
+    /// HTTP status code, or `None` for transport errors.
+    pub status: Option<u16>,
+    /// Parsed OSS error code (e.g. `RequestTimeout`), if the response had one.
+    pub code: Option<&'a str>,
+    /// The error the attempt would fail with.
+    pub error: &'a OssError,
+    /// The client's retry configuration, including the retryable status and
+    /// error code sets.
+    pub config: &'a RetryConfig,
+}
+
+/// Decides whether a failed request attempt is retried.
+///
+/// Only consulted while attempts remain and the request body can be replayed.
+///
+/// # Examples
+/// ```
+/// use rs_ali_oss::retry::{DefaultRetryClassifier, RetryAttempt, RetryClassifier, RetryDecision};
+///
+/// /// Also retry a gateway's custom busy code.
+/// struct GatewayAware;
+///
+/// impl RetryClassifier for GatewayAware {
+///     fn classify(&self, attempt: &RetryAttempt<'_>) -> RetryDecision {
+///         match attempt.code {
+///             Some("GatewayBusy") => RetryDecision::Retry,
+///             _ => DefaultRetryClassifier.classify(attempt),
+///         }
+///     }
+/// }
+/// ```
+pub trait RetryClassifier: Send + Sync {
+    /// Classify a failed attempt.
+    fn classify(&self, attempt: &RetryAttempt<'_>) -> RetryDecision;
+}
+
+impl fmt::Debug for dyn RetryClassifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RetryClassifier")
+    }
+}
+
+/// The default policy: retry 5xx responses, the statuses and OSS error codes
+/// listed in [`RetryConfig`] (408/429, timeouts and throttling by default),
+/// and connect/timeout transport errors.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultRetryClassifier;
+
+impl RetryClassifier for DefaultRetryClassifier {
+    fn classify(&self, attempt: &RetryAttempt<'_>) -> RetryDecision {
+        let config = attempt.config;
+        let retryable = match (attempt.status, attempt.error) {
+            (Some(status), _) => {
+                (500..600).contains(&status)
+                    || config.retryable_status_codes.contains(&status)
+                    || attempt
+                        .code
+                        .is_some_and(|code| config.retryable_error_codes.iter().any(|c| c == code))
+            }
+            (None, OssError::Http(e)) => e.is_timeout() || e.is_connect(),
+            (None, _) => false,
+        };
+        if retryable {
+            RetryDecision::Retry
+        } else {
+            RetryDecision::Fail
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct BudgetSlot {
+    second: u64,
+    requests: u64,
+    retries: u64,
+}
+
+/// Caps retries at a fraction of recent requests, shared by every clone of a
+/// client.
+///
+/// During a regional incident every request fails and, without a budget,
+/// each one is retried up to the configured maximum, multiplying the load on
+/// the service. With a budget, retries within the sliding window may not
+/// exceed `ratio` times the requests in that window (plus a small floor so
+/// quiet clients can still retry); once it is spent, failed attempts fail
+/// immediately until traffic recovers.
+///
+/// Register it with [`OssClient::retry_budget`](crate::OssClient::retry_budget).
+///
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+/// use rs_ali_oss::retry::RetryBudget;
+///
+/// # fn example(client: rs_ali_oss::OssClient) -> rs_ali_oss::Result<()> {
+/// // Retries may add at most 10% on top of the regular request rate.
+/// let budget = RetryBudget::builder().ratio(0.1).build()?;
+/// let client = client.retry_budget(Arc::new(budget));
+/// # Ok(())
+/// # }
+/// ```
+pub struct RetryBudget {
+    ratio: f64,
+    min_retries_per_second: u32,
+    started: Instant,
+    slots: Mutex<Vec<BudgetSlot>>,
+}
+
+impl RetryBudget {
+    /// Create a builder.
+    pub fn builder() -> RetryBudgetBuilder {
+        RetryBudgetBuilder::new()
+    }
+
+    /// Retries still allowed in the current window.
+    pub fn available(&self) -> u64 {
+        let now = Instant::now();
+        let mut slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
+        let (allowed, retries) = self.usage(&mut slots, now);
+        allowed.saturating_sub(retries)
+    }
+
+    /// Record an operation (not its retries).
+    pub(crate) fn record_request(&self, now: Instant) {
+        let mut slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
+        self.slot(&mut slots, now).requests += 1;
+    }
+
+    /// Withdraw one retry from the budget, returning `false` when it is spent.
+    pub(crate) fn try_retry(&self, now: Instant) -> bool {
+        let mut slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
+        let (allowed, retries) = self.usage(&mut slots, now);
+        if retries >= allowed {
+            return false;
+        }
+        self.slot(&mut slots, now).retries += 1;
+        true
+    }
+
+    fn second(&self, now: Instant) -> u64 {
+        now.saturating_duration_since(self.started).as_secs()
+    }
+
+    /// The slot for `now`, recycled if it holds an expired second.
+    fn slot<'a>(&self, slots: &'a mut [BudgetSlot], now: Instant) -> &'a mut BudgetSlot {
+        let second = self.second(now);
+        let index = (second % slots.len() as u64) as usize;
+        let slot = &mut slots[index];
+        if slot.second != second {
+            *slot = BudgetSlot {
+                second,
+                ..BudgetSlot::default()
+            };
+        }
+        slot
+    }
+
+    /// Retries allowed and retries used within the window ending at `now`.
+    fn usage(&self, slots: &mut [BudgetSlot], now: Instant) -> (u64, u64) {
+        let second = self.second(now);
+        let window = slots.len() as u64;
+        let (requests, retries) = slots
+            .iter()
+            .filter(|slot| slot.second <= second && second - slot.second < window)
+            .fold((0, 0), |(requests, retries), slot| {
+                (requests + slot.requests, retries + slot.retries)
+            });
+        let allowed =
+            u64::from(self.min_retries_per_second) * window + (requests as f64 * self.ratio) as u64;
+        (allowed, retries)
+    }
+}
+
+impl fmt::Debug for RetryBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let window = self.slots.lock().unwrap_or_else(|e| e.into_inner()).len();
+        f.debug_struct("RetryBudget")
+            .field("ratio", &self.ratio)
+            .field("min_retries_per_second", &self.min_retries_per_second)
+            .field("window", &Duration::from_secs(window as u64))
+            .finish()
+    }
+}
+
+/// Builder for [`RetryBudget`].
+#[derive(Debug, Default)]
+pub struct RetryBudgetBuilder {
+    ratio: Option<f64>,
+    min_retries_per_second: Option<u32>,
+    window: Option<Duration>,
+}
+
+impl RetryBudgetBuilder {
+    /// Create a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maximum retries as a fraction of the requests in the window
+    /// (default: 0.1).
+    pub fn ratio(mut self, ratio: f64) -> Self {
+        self.ratio = Some(ratio);
+        self
+    }
+
+    /// Retries per second always allowed regardless of traffic (default: 1).
+    pub fn min_retries_per_second(mut self, retries: u32) -> Self {
+        self.min_retries_per_second = Some(retries);
+        self
+    }
+
+    /// Length of the sliding window, in whole seconds (default: 10s).
+    pub fn window(mut self, window: Duration) -> Self {
+        self.window = Some(window);
+        self
+    }
+
+    /// Build the budget.
+    pub fn build(self) -> Result<RetryBudget> {
+        let ratio = self.ratio.unwrap_or(0.1);
+        if !(ratio.is_finite() && ratio >= 0.0) {
+            return Err(OssError::InvalidParameter {
+                field: "ratio".into(),
+                reason: "must be a non-negative, finite number".into(),
+            });
+        }
+        let window_secs = self.window.unwrap_or(Duration::from_secs(10)).as_secs();
+        if window_secs == 0 {
+            return Err(OssError::InvalidParameter {
+                field: "window".into(),
+                reason: "must be at least one second".into(),
+            });
+        }
+        let started = Instant::now();
+        Ok(RetryBudget {
+            ratio,
+            min_retries_per_second: self.min_retries_per_second.unwrap_or(1),
+            started,
+            slots: Mutex::new(vec![
+                BudgetSlot {
+                    // Never matches a live second, so slots start out empty.
+                    second: u64::MAX,
+                    ..BudgetSlot::default()
+                };
+                window_secs as usize
+            ]),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server_error(status: u16, code: &str) -> OssError {
+        OssError::ServerError {
+            status,
+            code: code.into(),
+            message: String::new(),
+            request_id: String::new(),
+            host_id: String::new(),
+            ec: None,
+            raw_body: String::new(),
+        }
+    }
+
+    fn classify_with(config: &RetryConfig, status: Option<u16>, error: &OssError) -> RetryDecision {
+        let code = match error {
+            OssError::ServerError { code, .. } => Some(code.as_str()),
+            _ => None,
+        };
+        DefaultRetryClassifier.classify(&RetryAttempt {
+            method: "GET",
+            attempt: 0,
+            status,
+            code,
+            error,
+            config,
+        })
+    }
+
+    fn classify(status: Option<u16>, error: &OssError) -> RetryDecision {
+        classify_with(&RetryConfig::default(), status, error)
+    }
+
+    #[test]
+    fn default_retries_server_errors() {
+        let err = server_error(503, "ServiceUnavailable");
+        assert_eq!(classify(Some(503), &err), RetryDecision::Retry);
+    }
+
+    #[test]
+    fn default_fails_client_errors() {
+        let err = server_error(400, "InvalidArgument");
+        assert_eq!(classify(Some(400), &err), RetryDecision::Fail);
+        let err = server_error(404, "NoSuchKey");
+        assert_eq!(classify(Some(404), &err), RetryDecision::Fail);
+    }
+
+    #[test]
+    fn default_retries_throttling_and_timeouts() {
+        let err = server_error(429, "");
+        assert_eq!(classify(Some(429), &err), RetryDecision::Retry);
+        let err = server_error(408, "");
+        assert_eq!(classify(Some(408), &err), RetryDecision::Retry);
+        let err = server_error(400, "RequestTimeout");
+        assert_eq!(classify(Some(400), &err), RetryDecision::Retry);
+        let err = server_error(403, "Throttling");
+        assert_eq!(classify(Some(403), &err), RetryDecision::Retry);
+    }
+
+    #[test]
+    fn retryable_sets_are_configurable() {
+        let config = RetryConfig {
+            retryable_status_codes: vec![409],
+            retryable_error_codes: vec!["GatewayBusy".into()],
+            ..RetryConfig::default()
+        };
+        let err = server_error(429, "");
+        assert_eq!(classify_with(&config, Some(429), &err), RetryDecision::Fail);
+        let err = server_error(409, "");
+        assert_eq!(
+            classify_with(&config, Some(409), &err),
+            RetryDecision::Retry
+        );
+        let err = server_error(400, "GatewayBusy");
+        assert_eq!(
+            classify_with(&config, Some(400), &err),
+            RetryDecision::Retry
+        );
+        let err = server_error(400, "RequestTimeout");
+        assert_eq!(classify_with(&config, Some(400), &err), RetryDecision::Fail);
+    }
+
+    #[test]
+    fn default_fails_non_transport_errors() {
+        let err = OssError::Auth("nope".into());
+        assert_eq!(classify(None, &err), RetryDecision::Fail);
+    }
+
+    #[test]
+    fn budget_caps_retries_at_ratio() {
+        let budget = RetryBudget::builder()
+            .ratio(0.1)
+            .min_retries_per_second(0)
+            .build()
+            .unwrap();
+        let now = Instant::now();
+        assert!(!budget.try_retry(now));
+        for _ in 0..30 {
+            budget.record_request(now);
+        }
+        for _ in 0..3 {
+            assert!(budget.try_retry(now));
+        }
+        assert!(!budget.try_retry(now));
+    }
+
+    #[test]
+    fn budget_has_floor_and_expires() {
+        let budget = RetryBudget::builder()
+            .min_retries_per_second(1)
+            .window(Duration::from_secs(2))
+            .build()
+            .unwrap();
+        let now = Instant::now();
+        assert!(budget.try_retry(now));
+        assert!(budget.try_retry(now));
+        assert!(!budget.try_retry(now));
+        // Retries fall out of the window and the budget refills.
+        let later = now + Duration::from_secs(2);
+        assert!(budget.try_retry(later));
+    }
+
+    #[test]
+    fn budget_builder_validates() {
+        assert!(matches!(
+            RetryBudget::builder().ratio(-0.5).build(),
+            Err(OssError::InvalidParameter { .. })
+        ));
+        assert!(matches!(
+            RetryBudget::builder()
+                .window(Duration::from_millis(500))
+                .build(),
+            Err(OssError::InvalidParameter { .. })
+        ));
+    }
+}
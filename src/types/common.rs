@@ -1,7 +1,9 @@
 //! Common newtypes and enums shared across OSS operations.
 
 use std::fmt;
+use std::str::FromStr;
 
+use base64::Engine;
 use serde::{Deserialize, Serialize};
 
 use crate::error::{OssError, Result};
@@ -61,7 +63,9 @@ impl fmt::Display for BucketName {
 
 /// An OSS object key, validated on construction.
 ///
-/// Object keys must be 1-1023 bytes long and non-empty.
+/// Object keys must be 1-1023 bytes long, must not start with `/` or `\`,
+/// must not contain carriage returns or line feeds, and must not contain
+/// `..` path segments.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ObjectKey(String);
 
@@ -78,8 +82,71 @@ impl ObjectKey {
                 key.len()
             )));
         }
+        if key.starts_with('/') || key.starts_with('\\') {
+            return Err(OssError::InvalidObjectKey(
+                "must not start with '/' or '\\'".to_string(),
+            ));
+        }
+        if key.contains(['\r', '\n']) {
+            return Err(OssError::InvalidObjectKey(
+                "must not contain carriage returns or line feeds".to_string(),
+            ));
+        }
+        if key.split(['/', '\\']).any(|segment| segment == "..") {
+            return Err(OssError::InvalidObjectKey(
+                "must not contain '..' path segments".to_string(),
+            ));
+        }
         Ok(Self(key))
     }
+
+    /// Append `child` to this key, separated by a single `/`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rs_ali_oss::ObjectKey;
+    /// let dir = ObjectKey::new("photos/2024/")?;
+    /// assert_eq!(dir.join("beach.jpg")?.as_ref(), "photos/2024/beach.jpg");
+    /// # Ok::<(), rs_ali_oss::OssError>(())
+    /// ```
+    pub fn join(&self, child: impl AsRef<str>) -> Result<Self> {
+        let child = Self::new(child.as_ref())?;
+        Self::new(format!("{}/{}", self.0.trim_end_matches('/'), child.0))
+    }
+
+    /// The key of the enclosing "directory", including its trailing `/`, or
+    /// `None` for keys at the bucket root.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rs_ali_oss::ObjectKey;
+    /// let key = ObjectKey::new("photos/2024/beach.jpg")?;
+    /// assert_eq!(key.parent().unwrap().as_ref(), "photos/2024/");
+    /// assert_eq!(ObjectKey::new("photos/")?.parent(), None);
+    /// # Ok::<(), rs_ali_oss::OssError>(())
+    /// ```
+    pub fn parent(&self) -> Option<Self> {
+        let trimmed = self.0.trim_end_matches('/');
+        let end = trimmed.rfind('/')? + 1;
+        Some(Self(self.0[..end].to_string()))
+    }
+
+    /// The last path segment of the key, ignoring a trailing `/`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rs_ali_oss::ObjectKey;
+    /// assert_eq!(ObjectKey::new("photos/2024/beach.jpg")?.file_name(), "beach.jpg");
+    /// assert_eq!(ObjectKey::new("photos/2024/")?.file_name(), "2024");
+    /// # Ok::<(), rs_ali_oss::OssError>(())
+    /// ```
+    pub fn file_name(&self) -> &str {
+        let trimmed = self.0.trim_end_matches('/');
+        match trimmed.rfind('/') {
+            Some(pos) => &trimmed[pos + 1..],
+            None => trimmed,
+        }
+    }
 }
 
 impl AsRef<str> for ObjectKey {
@@ -164,21 +231,92 @@ impl fmt::Display for StorageClass {
     }
 }
 
-/// OSS object or bucket access control level.
+impl FromStr for StorageClass {
+    type Err = OssError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "Standard" => Ok(Self::Standard),
+            "IA" => Ok(Self::InfrequentAccess),
+            "Archive" => Ok(Self::Archive),
+            "ColdArchive" => Ok(Self::ColdArchive),
+            "DeepColdArchive" => Ok(Self::DeepColdArchive),
+            other => Err(OssError::InvalidParameter {
+                field: "storage_class".into(),
+                reason: format!("unknown storage class: {other}"),
+            }),
+        }
+    }
+}
+
+/// How an object was created, from the `x-oss-object-type` header.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ObjectType {
+    /// Uploaded with PutObject or CopyObject.
+    Normal,
+    /// Created with AppendObject.
+    Appendable,
+    /// Assembled by a multipart upload.
+    Multipart,
+    /// A symbolic link to another object.
+    Symlink,
+}
+
+impl fmt::Display for ObjectType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Normal => write!(f, "Normal"),
+            Self::Appendable => write!(f, "Appendable"),
+            Self::Multipart => write!(f, "Multipart"),
+            Self::Symlink => write!(f, "Symlink"),
+        }
+    }
+}
+
+impl FromStr for ObjectType {
+    type Err = OssError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "Normal" => Ok(Self::Normal),
+            "Appendable" => Ok(Self::Appendable),
+            "Multipart" => Ok(Self::Multipart),
+            "Symlink" => Ok(Self::Symlink),
+            other => Err(OssError::InvalidParameter {
+                field: "object_type".into(),
+                reason: format!("unknown object type: {other}"),
+            }),
+        }
+    }
+}
+
+/// OSS object or bucket access control level.
+///
+/// ACLs this SDK does not know about are kept verbatim in
+/// [`ObjectAcl::Unknown`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum ObjectAcl {
     /// Private (owner only).
-    #[serde(rename = "private")]
     Private,
     /// Public read access.
-    #[serde(rename = "public-read")]
     PublicRead,
     /// Public read-write access.
-    #[serde(rename = "public-read-write")]
     PublicReadWrite,
     /// Inherit from bucket (default).
-    #[serde(rename = "default")]
     Default,
+    /// An ACL returned by OSS that this SDK does not know about.
+    Unknown(String),
+}
+
+impl ObjectAcl {
+    /// Returns `true` if the ACL lets anyone read the object.
+    ///
+    /// [`Default`](Self::Default) objects inherit the bucket ACL, so this
+    /// returns `false` for them; check the bucket's ACL as well.
+    pub fn is_public(&self) -> bool {
+        matches!(self, Self::PublicRead | Self::PublicReadWrite)
+    }
 }
 
 impl fmt::Display for ObjectAcl {
@@ -188,10 +326,29 @@ impl fmt::Display for ObjectAcl {
             Self::PublicRead => write!(f, "public-read"),
             Self::PublicReadWrite => write!(f, "public-read-write"),
             Self::Default => write!(f, "default"),
+            Self::Unknown(acl) => write!(f, "{acl}"),
+        }
+    }
+}
+
+impl From<String> for ObjectAcl {
+    fn from(acl: String) -> Self {
+        match acl.as_str() {
+            "private" => Self::Private,
+            "public-read" => Self::PublicRead,
+            "public-read-write" => Self::PublicReadWrite,
+            "default" => Self::Default,
+            _ => Self::Unknown(acl),
         }
     }
 }
 
+impl From<ObjectAcl> for String {
+    fn from(acl: ObjectAcl) -> Self {
+        acl.to_string()
+    }
+}
+
 /// Bucket access control level.
 ///
 /// Defines the access permissions for a bucket. Each level grants
@@ -202,6 +359,7 @@ impl fmt::Display for ObjectAcl {
 /// * `Private` - Only the bucket owner has access
 /// * `PublicRead` - Owner has full access, public has read access
 /// * `PublicReadWrite` - Everyone has full access (use with caution)
+/// * `Unknown` - A value this SDK does not know, kept verbatim
 ///
 /// # Examples
 ///
@@ -209,18 +367,22 @@ impl fmt::Display for ObjectAcl {
 /// # use rs_ali_oss::types::BucketAcl;
 /// let acl = BucketAcl::PublicRead;
 /// assert_eq!(acl.to_string(), "public-read");
+/// assert_eq!(
+///     BucketAcl::from("authenticated-read".to_string()),
+///     BucketAcl::Unknown("authenticated-read".into())
+/// );
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum BucketAcl {
     /// Private access (bucket owner only).
-    #[serde(rename = "private")]
     Private,
     /// Public read access.
-    #[serde(rename = "public-read")]
     PublicRead,
     /// Public read-write access.
-    #[serde(rename = "public-read-write")]
     PublicReadWrite,
+    /// An ACL returned by OSS that this SDK does not know about.
+    Unknown(String),
 }
 
 impl fmt::Display for BucketAcl {
@@ -229,10 +391,28 @@ impl fmt::Display for BucketAcl {
             Self::Private => write!(f, "private"),
             Self::PublicRead => write!(f, "public-read"),
             Self::PublicReadWrite => write!(f, "public-read-write"),
+            Self::Unknown(acl) => write!(f, "{acl}"),
+        }
+    }
+}
+
+impl From<String> for BucketAcl {
+    fn from(acl: String) -> Self {
+        match acl.as_str() {
+            "private" => Self::Private,
+            "public-read" => Self::PublicRead,
+            "public-read-write" => Self::PublicReadWrite,
+            _ => Self::Unknown(acl),
         }
     }
 }
 
+impl From<BucketAcl> for String {
+    fn from(acl: BucketAcl) -> Self {
+        acl.to_string()
+    }
+}
+
 /// Metadata directive for CopyObject operations.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum MetadataDirective {
@@ -356,10 +536,208 @@ impl fmt::Display for ServerSideEncryption {
     }
 }
 
+impl FromStr for ServerSideEncryption {
+    type Err = OssError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "AES256" => Ok(Self::AES256),
+            "KMS" => Ok(Self::KMS),
+            other => Err(OssError::InvalidParameter {
+                field: "server_side_encryption".into(),
+                reason: format!("unknown algorithm: {other}"),
+            }),
+        }
+    }
+}
+
+/// Data redundancy type of a bucket.
+///
+/// A bucket can be converted from locally redundant to zone-redundant
+/// storage with a redundancy transition task, see
+/// [`OssClient::create_bucket_data_redundancy_transition`](crate::OssClient::create_bucket_data_redundancy_transition).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DataRedundancyType {
+    /// Locally redundant storage.
+    #[serde(rename = "LRS")]
+    LRS,
+    /// Zone-redundant storage.
+    #[serde(rename = "ZRS")]
+    ZRS,
+}
+
+impl fmt::Display for DataRedundancyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LRS => write!(f, "LRS"),
+            Self::ZRS => write!(f, "ZRS"),
+        }
+    }
+}
+
+/// State of a bucket data redundancy transition task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RedundancyTransitionStatus {
+    /// Waiting to be processed.
+    Queueing,
+    /// Data is being converted.
+    Processing,
+    /// The bucket has been converted.
+    Finished,
+}
+
+impl fmt::Display for RedundancyTransitionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Queueing => write!(f, "Queueing"),
+            Self::Processing => write!(f, "Processing"),
+            Self::Finished => write!(f, "Finished"),
+        }
+    }
+}
+
+/// An upload callback (`x-oss-callback`).
+///
+/// Once the upload completes, OSS POSTs `body` to `url` and returns the
+/// callback server's response in place of its own. `body` may reference
+/// system variables such as `${bucket}` and `${etag}`, and custom
+/// variables added with [`var`](Self::var).
+///
+/// # Examples
+///
+/// ```
+/// # use rs_ali_oss::types::Callback;
+/// let callback = Callback::new(
+///     "https://example.com/uploaded",
+///     r#"{"bucket":${bucket},"object":${object},"owner":${x:owner}}"#,
+/// )
+/// .body_type("application/json")
+/// .var("x:owner", "alice");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Callback {
+    url: String,
+    host: Option<String>,
+    body: String,
+    body_type: Option<String>,
+    vars: Vec<(String, String)>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CallbackJson<'a> {
+    callback_url: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    callback_host: Option<&'a str>,
+    callback_body: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    callback_body_type: Option<&'a str>,
+}
+
+impl Callback {
+    /// Create a callback that POSTs `body` to `url`.
+    pub fn new(url: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            host: None,
+            body: body.into(),
+            body_type: None,
+            vars: Vec::new(),
+        }
+    }
+
+    /// Set the `Host` header OSS sends to the callback server.
+    pub fn host(mut self, host: impl Into<String>) -> Self {
+        self.host = Some(host.into());
+        self
+    }
+
+    /// Set the content type of the callback body, either
+    /// `application/x-www-form-urlencoded` (the default) or
+    /// `application/json`.
+    pub fn body_type(mut self, body_type: impl Into<String>) -> Self {
+        self.body_type = Some(body_type.into());
+        self
+    }
+
+    /// Add a custom variable. Names must start with `x:`.
+    pub fn var(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.vars.push((name.into(), value.into()));
+        self
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.url.is_empty() {
+            return Err(OssError::InvalidParameter {
+                field: "callback".into(),
+                reason: "callback URL must not be empty".into(),
+            });
+        }
+        if let Some((name, _)) = self.vars.iter().find(|(name, _)| !name.starts_with("x:")) {
+            return Err(OssError::InvalidParameter {
+                field: "callback".into(),
+                reason: format!("variable {name:?} must start with \"x:\""),
+            });
+        }
+        Ok(())
+    }
+
+    /// The base64-encoded `x-oss-callback` value.
+    pub(crate) fn header(&self) -> Result<String> {
+        let json = serde_json::to_vec(&CallbackJson {
+            callback_url: &self.url,
+            callback_host: self.host.as_deref(),
+            callback_body: &self.body,
+            callback_body_type: self.body_type.as_deref(),
+        })
+        .map_err(|e| OssError::JsonParse(e.to_string()))?;
+        Ok(base64::engine::general_purpose::STANDARD.encode(json))
+    }
+
+    /// The base64-encoded `x-oss-callback-var` value, if any variables are set.
+    pub(crate) fn var_header(&self) -> Result<Option<String>> {
+        if self.vars.is_empty() {
+            return Ok(None);
+        }
+        let vars: serde_json::Map<String, serde_json::Value> = self
+            .vars
+            .iter()
+            .map(|(k, v)| (k.clone(), serde_json::Value::String(v.clone())))
+            .collect();
+        let json = serde_json::to_vec(&vars).map_err(|e| OssError::JsonParse(e.to_string()))?;
+        Ok(Some(base64::engine::general_purpose::STANDARD.encode(json)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn storage_class_and_object_type_round_trip_through_strings() {
+        for class in [
+            StorageClass::Standard,
+            StorageClass::InfrequentAccess,
+            StorageClass::Archive,
+            StorageClass::ColdArchive,
+            StorageClass::DeepColdArchive,
+        ] {
+            assert_eq!(class.to_string().parse::<StorageClass>().unwrap(), class);
+        }
+        for object_type in [
+            ObjectType::Normal,
+            ObjectType::Appendable,
+            ObjectType::Multipart,
+            ObjectType::Symlink,
+        ] {
+            assert_eq!(
+                object_type.to_string().parse::<ObjectType>().unwrap(),
+                object_type
+            );
+        }
+        assert!("Glacier".parse::<StorageClass>().is_err());
+    }
+
     #[test]
     fn valid_bucket_names() {
         assert!(BucketName::new("my-bucket").is_ok());
@@ -459,6 +837,17 @@ mod tests {
         assert_eq!(acl, deserialized);
     }
 
+    #[test]
+    fn object_acl_is_public() {
+        assert!(ObjectAcl::PublicRead.is_public());
+        assert!(ObjectAcl::PublicReadWrite.is_public());
+        assert!(!ObjectAcl::Private.is_public());
+        assert!(!ObjectAcl::Default.is_public());
+        let unknown: ObjectAcl = serde_json::from_str(r#""bucket-owner-read""#).unwrap();
+        assert_eq!(unknown, ObjectAcl::Unknown("bucket-owner-read".into()));
+        assert!(!unknown.is_public());
+    }
+
     #[test]
     fn bucket_acl_display() {
         assert_eq!(BucketAcl::Private.to_string(), "private");
@@ -474,6 +863,21 @@ mod tests {
         assert_eq!(acl, deserialized);
     }
 
+    #[test]
+    fn bucket_acl_keeps_unknown_values() {
+        let acl: BucketAcl = serde_json::from_str(r#""authenticated-read""#).unwrap();
+        assert_eq!(acl, BucketAcl::Unknown("authenticated-read".into()));
+        assert_eq!(acl.to_string(), "authenticated-read");
+        assert_eq!(
+            serde_json::to_string(&acl).unwrap(),
+            r#""authenticated-read""#
+        );
+        assert_eq!(
+            serde_json::to_string(&BucketAcl::PublicReadWrite).unwrap(),
+            r#""public-read-write""#
+        );
+    }
+
     #[test]
     fn metadata_directive_display() {
         assert_eq!(MetadataDirective::Copy.to_string(), "COPY");
@@ -604,6 +1008,58 @@ mod tests {
         assert!(ObjectKey::new(&key).is_err());
     }
 
+    #[test]
+    fn object_key_rejects_surprising_keys() {
+        for key in [
+            "/leading-slash",
+            "\\leading-backslash",
+            "line\nbreak",
+            "carriage\rreturn",
+            "..",
+            "a/../b",
+            "../escape",
+            "dir/..",
+            "win\\..\\path",
+        ] {
+            assert!(
+                matches!(ObjectKey::new(key), Err(OssError::InvalidObjectKey(_))),
+                "{key:?}"
+            );
+        }
+        for key in ["a..b", "...", "dir/.hidden", "trailing/"] {
+            assert!(ObjectKey::new(key).is_ok(), "{key:?}");
+        }
+    }
+
+    #[test]
+    fn object_key_join() {
+        let dir = ObjectKey::new("photos").unwrap();
+        assert_eq!(dir.join("a.jpg").unwrap().as_ref(), "photos/a.jpg");
+        let dir = ObjectKey::new("photos//").unwrap();
+        assert_eq!(
+            dir.join("2024/a.jpg").unwrap().as_ref(),
+            "photos/2024/a.jpg"
+        );
+        assert!(dir.join("/a.jpg").is_err());
+        assert!(dir.join("../a.jpg").is_err());
+        assert!(dir.join("").is_err());
+    }
+
+    #[test]
+    fn object_key_parent_and_file_name() {
+        let key = ObjectKey::new("a/b/c.txt").unwrap();
+        let parent = key.parent().unwrap();
+        assert_eq!(parent.as_ref(), "a/b/");
+        assert_eq!(parent.file_name(), "b");
+        assert_eq!(parent.parent().unwrap().as_ref(), "a/");
+        assert_eq!(parent.parent().unwrap().parent(), None);
+        assert_eq!(key.file_name(), "c.txt");
+
+        let root = ObjectKey::new("c.txt").unwrap();
+        assert_eq!(root.parent(), None);
+        assert_eq!(root.file_name(), "c.txt");
+    }
+
     #[test]
     fn object_key_single_byte() {
         assert!(ObjectKey::new("x").is_ok());
@@ -636,4 +1092,43 @@ mod tests {
         assert!(Region::new("us-east-1").is_ok());
         assert!(Region::new("ap-southeast-2").is_ok());
     }
+
+    #[test]
+    fn callback_headers_are_base64_json() {
+        let callback = Callback::new("https://example.com/cb", "bucket=${bucket}")
+            .host("example.com")
+            .var("x:owner", "alice");
+        callback.validate().unwrap();
+
+        let decode = |value: String| {
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(value)
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&bytes).unwrap()
+        };
+        assert_eq!(
+            decode(callback.header().unwrap()),
+            serde_json::json!({
+                "callbackUrl": "https://example.com/cb",
+                "callbackHost": "example.com",
+                "callbackBody": "bucket=${bucket}",
+            })
+        );
+        assert_eq!(
+            decode(callback.var_header().unwrap().unwrap()),
+            serde_json::json!({ "x:owner": "alice" })
+        );
+    }
+
+    #[test]
+    fn callback_vars_need_x_prefix() {
+        let callback = Callback::new("https://example.com/cb", "").var("owner", "alice");
+        assert!(callback.validate().is_err());
+        assert_eq!(
+            Callback::new("https://example.com/cb", "")
+                .var_header()
+                .unwrap(),
+            None
+        );
+    }
 }
@@ -1,66 +1,83 @@
 //! Request and response types for OSS operations.
 
 pub mod common;
+pub mod policy;
 pub mod request;
 pub mod response;
 
 pub use common::{
-    BucketAcl, BucketName, CorsHttpMethod, MetadataDirective, ObjectAcl, ObjectKey, Region,
-    ServerSideEncryption, StorageClass, VersioningStatus,
+    BucketAcl, BucketName, Callback, CorsHttpMethod, DataRedundancyType, MetadataDirective,
+    ObjectAcl, ObjectKey, ObjectType, RedundancyTransitionStatus, Region, ServerSideEncryption,
+    StorageClass, VersioningStatus,
 };
 pub use request::{
     AbortMultipartUploadRequest, AbortMultipartUploadRequestBuilder, AppendObjectRequest,
-    AppendObjectRequestBuilder, CompleteMultipartUploadRequest,
+    AppendObjectRequestBuilder, CnameCertificate, CompleteMultipartUploadRequest,
     CompleteMultipartUploadRequestBuilder, CompleteMultipartUploadXml, CompletedPart,
-    CopyObjectRequest, CopyObjectRequestBuilder, CorsRule, CreateBucketRequest,
+    CopyObjectRequest, CopyObjectRequestBuilder, CorsRule,
+    CreateBucketDataRedundancyTransitionRequest,
+    CreateBucketDataRedundancyTransitionRequestBuilder, CreateBucketRequest,
     CreateBucketRequestBuilder, DeleteBucketCorsRequest, DeleteBucketCorsRequestBuilder,
-    DeleteBucketEncryptionRequest, DeleteBucketEncryptionRequestBuilder,
-    DeleteBucketLifecycleRequest, DeleteBucketLifecycleRequestBuilder, DeleteBucketLoggingRequest,
+    DeleteBucketDataRedundancyTransitionRequest,
+    DeleteBucketDataRedundancyTransitionRequestBuilder, DeleteBucketEncryptionRequest,
+    DeleteBucketEncryptionRequestBuilder, DeleteBucketLifecycleRequest,
+    DeleteBucketLifecycleRequestBuilder, DeleteBucketLoggingRequest,
     DeleteBucketLoggingRequestBuilder, DeleteBucketPolicyRequest, DeleteBucketPolicyRequestBuilder,
     DeleteBucketRequest, DeleteBucketRequestBuilder, DeleteMultipleObjectsRequest,
     DeleteMultipleObjectsRequestBuilder, DeleteObjectRequest, DeleteObjectRequestBuilder,
     DeleteObjectTaggingRequest, DeleteObjectTaggingRequestBuilder, GetBucketAclRequest,
     GetBucketAclRequestBuilder, GetBucketCorsRequest, GetBucketCorsRequestBuilder,
+    GetBucketDataRedundancyTransitionRequest, GetBucketDataRedundancyTransitionRequestBuilder,
     GetBucketEncryptionRequest, GetBucketEncryptionRequestBuilder, GetBucketInfoRequest,
     GetBucketInfoRequestBuilder, GetBucketLifecycleRequest, GetBucketLifecycleRequestBuilder,
     GetBucketLocationRequest, GetBucketLocationRequestBuilder, GetBucketLoggingRequest,
     GetBucketLoggingRequestBuilder, GetBucketPolicyRequest, GetBucketPolicyRequestBuilder,
+    GetBucketTransferAccelerationRequest, GetBucketTransferAccelerationRequestBuilder,
     GetBucketVersioningRequest, GetBucketVersioningRequestBuilder, GetObjectAclRequest,
     GetObjectAclRequestBuilder, GetObjectRequest, GetObjectRequestBuilder, GetObjectTaggingRequest,
     GetObjectTaggingRequestBuilder, HeadObjectRequest, HeadObjectRequestBuilder,
     InitiateMultipartUploadRequest, InitiateMultipartUploadRequestBuilder, LifecycleExpiration,
-    LifecycleRule, LifecycleRuleStatus, LifecycleTransition, ListBucketsRequest,
-    ListBucketsRequestBuilder, ListMultipartUploadsRequest, ListMultipartUploadsRequestBuilder,
-    ListObjectsV2Request, ListObjectsV2RequestBuilder, ListPartsRequest, ListPartsRequestBuilder,
-    PresignedUrlRequest, PresignedUrlRequestBuilder, PutBucketAclRequest,
-    PutBucketAclRequestBuilder, PutBucketCorsRequest, PutBucketCorsRequestBuilder,
-    PutBucketEncryptionRequest, PutBucketEncryptionRequestBuilder, PutBucketLifecycleRequest,
-    PutBucketLifecycleRequestBuilder, PutBucketLoggingRequest, PutBucketLoggingRequestBuilder,
-    PutBucketPolicyRequest, PutBucketPolicyRequestBuilder, PutBucketVersioningRequest,
-    PutBucketVersioningRequestBuilder, PutObjectAclRequest, PutObjectAclRequestBuilder,
-    PutObjectRequest, PutObjectRequestBuilder, PutObjectTaggingRequest,
-    PutObjectTaggingRequestBuilder, RestoreObjectRequest, RestoreObjectRequestBuilder,
-    UploadPartRequest, UploadPartRequestBuilder,
+    LifecycleRule, LifecycleRuleStatus, LifecycleTransition,
+    ListBucketDataRedundancyTransitionRequest, ListBucketDataRedundancyTransitionRequestBuilder,
+    ListBucketsRequest, ListBucketsRequestBuilder, ListMultipartUploadsRequest,
+    ListMultipartUploadsRequestBuilder, ListObjectVersionsRequest,
+    ListObjectVersionsRequestBuilder, ListObjectsV2Request, ListObjectsV2RequestBuilder,
+    ListPartsRequest, ListPartsRequestBuilder, PresignedUrlRequest, PresignedUrlRequestBuilder,
+    PutBucketAclRequest, PutBucketAclRequestBuilder, PutBucketCorsRequest,
+    PutBucketCorsRequestBuilder, PutBucketEncryptionRequest, PutBucketEncryptionRequestBuilder,
+    PutBucketLifecycleRequest, PutBucketLifecycleRequestBuilder, PutBucketLoggingRequest,
+    PutBucketLoggingRequestBuilder, PutBucketPolicyRequest, PutBucketPolicyRequestBuilder,
+    PutBucketTransferAccelerationRequest, PutBucketTransferAccelerationRequestBuilder,
+    PutBucketVersioningRequest, PutBucketVersioningRequestBuilder, PutCnameRequest,
+    PutCnameRequestBuilder, PutObjectAclRequest, PutObjectAclRequestBuilder, PutObjectRequest,
+    PutObjectRequestBuilder, PutObjectTaggingRequest, PutObjectTaggingRequestBuilder,
+    RestoreObjectRequest, RestoreObjectRequestBuilder, UploadPartCopyRequest,
+    UploadPartCopyRequestBuilder, UploadPartRequest, UploadPartRequestBuilder,
 };
 pub use response::{
     AbortMultipartUploadResponse, AccessControlList, AppendObjectResponse,
     ApplyServerSideEncryptionByDefaultResponse, BucketAccessControlList, BucketInfo,
     BucketInfoDetail, BucketOwner, BucketsContainer, CommonPrefix, CompleteMultipartUploadResponse,
-    CopyObjectResponse, CorsRuleResponse, CreateBucketResponse, DeleteBucketCorsResponse,
-    DeleteBucketEncryptionResponse, DeleteBucketLifecycleResponse, DeleteBucketLoggingResponse,
-    DeleteBucketPolicyResponse, DeleteBucketResponse, DeleteMultipleObjectsResponse,
-    DeleteObjectResponse, DeleteObjectTaggingResponse, DeletedObject, EncryptionRuleResponse,
-    GetBucketAclResponse, GetBucketCorsResponse, GetBucketEncryptionResponse,
+    CopyObjectResponse, CorsRuleResponse, CreateBucketDataRedundancyTransitionResponse,
+    CreateBucketResponse, DataRedundancyTransition, DeleteBucketCorsResponse,
+    DeleteBucketDataRedundancyTransitionResponse, DeleteBucketEncryptionResponse,
+    DeleteBucketLifecycleResponse, DeleteBucketLoggingResponse, DeleteBucketPolicyResponse,
+    DeleteBucketResponse, DeleteMarkerEntry, DeleteMultipleObjectsResponse, DeleteObjectResponse,
+    DeleteObjectTaggingResponse, DeletedObject, EncryptionRuleResponse, GetBucketAclResponse,
+    GetBucketCorsResponse, GetBucketDataRedundancyTransitionResponse, GetBucketEncryptionResponse,
     GetBucketInfoResponse, GetBucketLifecycleResponse, GetBucketLocationResponse,
     GetBucketLoggingResponse, GetBucketPolicyResponse, GetBucketRefererResponse,
-    GetBucketVersioningResponse, GetObjectAclResponse, GetObjectResponse, GetObjectTaggingResponse,
-    HeadObjectResponse, InitiateMultipartUploadResponse, LifecycleExpirationResponse,
-    LifecycleRuleResponse, LifecycleTransitionResponse, ListBucketsResponse,
-    ListMultipartUploadsResponse, ListObjectsV2Response, ListPartsResponse, LoggingEnabled,
-    MultipartUploadInfo, ObjectAccessControlList, ObjectBody, ObjectInfo, PartInfo,
-    PutBucketAclResponse, PutBucketCorsResponse, PutBucketEncryptionResponse,
-    PutBucketLifecycleResponse, PutBucketLoggingResponse, PutBucketPolicyResponse,
-    PutBucketRefererResponse, PutBucketVersioningResponse, PutObjectAclResponse, PutObjectResponse,
-    PutObjectTaggingResponse, RefererBlacklist, RefererList, RestoreObjectResponse, Tag, TagSet,
+    GetBucketTransferAccelerationResponse, GetBucketVersioningResponse, GetObjectAclResponse,
+    GetObjectResponse, GetObjectResult, GetObjectTaggingResponse, HeadObjectResponse,
+    InitiateMultipartUploadResponse, LifecycleExpirationResponse, LifecycleRuleResponse,
+    LifecycleTransitionResponse, ListBucketDataRedundancyTransitionResponse, ListBucketsResponse,
+    ListMultipartUploadsResponse, ListObjectVersionsResponse, ListObjectsV2Response,
+    ListPartsResponse, LoggingEnabled, MultipartUploadInfo, ObjectAccessControlList, ObjectBody,
+    ObjectExpiration, ObjectInfo, ObjectVersion, PartInfo, PutBucketAclResponse,
+    PutBucketCorsResponse, PutBucketEncryptionResponse, PutBucketLifecycleResponse,
+    PutBucketLoggingResponse, PutBucketPolicyResponse, PutBucketRefererResponse,
+    PutBucketTransferAccelerationResponse, PutBucketVersioningResponse, PutCnameResponse,
+    PutObjectAclResponse, PutObjectResponse, PutObjectTaggingResponse, RefererBlacklist,
+    RefererList, RestoreObjectResponse, RestoreStatus, Tag, TagSet, UploadPartCopyResponse,
     UploadPartResponse,
 };
@@ -0,0 +1,347 @@
+//! Typed bucket policy documents.
+//!
+//! OSS bucket policies are JSON documents made of statements that allow or
+//! deny actions on resources to principals. [`PolicyDocument`] builds and
+//! parses them, so policies are not assembled from hand-written JSON.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::error::{OssError, Result};
+
+/// Policy language version written by [`PolicyDocumentBuilder`].
+const POLICY_VERSION: &str = "1";
+
+/// Statement conditions, keyed by operator (e.g. `IpAddress`) and then by
+/// condition key (e.g. `acs:SourceIp`).
+pub type Conditions = BTreeMap<String, BTreeMap<String, Vec<String>>>;
+
+/// Whether a statement allows or denies its actions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Effect {
+    /// Grant the actions.
+    Allow,
+    /// Deny the actions, overriding any grant.
+    Deny,
+}
+
+/// A bucket policy document.
+///
+/// # Examples
+/// ```
+/// use rs_ali_oss::types::policy::{Effect, PolicyDocument, Statement};
+///
+/// let policy = PolicyDocument::builder()
+///     .statement(
+///         Statement::builder()
+///             .effect(Effect::Allow)
+///             .principal("20214760404935xxxx")
+///             .action("oss:GetObject")
+///             .resource("acs:oss:*:*:my-bucket/public/*")
+///             .condition("IpAddress", "acs:SourceIp", ["192.168.0.0/16"])
+///             .build()?,
+///     )
+///     .build()?;
+/// let json = policy.to_json()?;
+/// assert_eq!(PolicyDocument::from_json(&json)?, policy);
+/// # Ok::<(), rs_ali_oss::OssError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyDocument {
+    /// Policy language version.
+    #[serde(rename = "Version")]
+    pub version: String,
+    /// The policy statements.
+    #[serde(rename = "Statement", default)]
+    pub statements: Vec<Statement>,
+}
+
+impl PolicyDocument {
+    /// Create a builder.
+    pub fn builder() -> PolicyDocumentBuilder {
+        PolicyDocumentBuilder::new()
+    }
+
+    /// Parse a policy from its JSON form.
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| OssError::JsonParse(e.to_string()))
+    }
+
+    /// Serialize the policy to JSON.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|e| OssError::JsonParse(e.to_string()))
+    }
+}
+
+/// Builder for [`PolicyDocument`].
+#[derive(Debug, Default)]
+pub struct PolicyDocumentBuilder {
+    statements: Vec<Statement>,
+}
+
+impl PolicyDocumentBuilder {
+    /// Create a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a statement.
+    pub fn statement(mut self, statement: Statement) -> Self {
+        self.statements.push(statement);
+        self
+    }
+
+    /// Build the policy document.
+    pub fn build(self) -> Result<PolicyDocument> {
+        if self.statements.is_empty() {
+            return Err(OssError::MissingField("statement".into()));
+        }
+        Ok(PolicyDocument {
+            version: POLICY_VERSION.to_string(),
+            statements: self.statements,
+        })
+    }
+}
+
+/// A single policy statement.
+///
+/// Fields that OSS accepts either as a string or as a list are always
+/// serialized as lists.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Statement {
+    /// Whether the statement allows or denies its actions.
+    #[serde(rename = "Effect")]
+    pub effect: Effect,
+    /// RAM user or account IDs the statement applies to; `*` for everyone.
+    #[serde(
+        rename = "Principal",
+        default,
+        deserialize_with = "one_or_many",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub principals: Vec<String>,
+    /// Actions, e.g. `oss:GetObject` or `oss:*`.
+    #[serde(rename = "Action", deserialize_with = "one_or_many")]
+    pub actions: Vec<String>,
+    /// Resources, e.g. `acs:oss:*:*:my-bucket/*`.
+    #[serde(rename = "Resource", deserialize_with = "one_or_many")]
+    pub resources: Vec<String>,
+    /// Conditions that must hold for the statement to apply.
+    #[serde(
+        rename = "Condition",
+        default,
+        deserialize_with = "conditions",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub conditions: Conditions,
+}
+
+impl Statement {
+    /// Create a builder.
+    pub fn builder() -> StatementBuilder {
+        StatementBuilder::new()
+    }
+}
+
+/// Builder for [`Statement`].
+#[derive(Debug, Default)]
+pub struct StatementBuilder {
+    effect: Option<Effect>,
+    principals: Vec<String>,
+    actions: Vec<String>,
+    resources: Vec<String>,
+    conditions: Conditions,
+}
+
+impl StatementBuilder {
+    /// Create a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the effect.
+    pub fn effect(mut self, effect: Effect) -> Self {
+        self.effect = Some(effect);
+        self
+    }
+
+    /// Add a principal (RAM user or account ID, or `*`).
+    pub fn principal(mut self, principal: impl Into<String>) -> Self {
+        self.principals.push(principal.into());
+        self
+    }
+
+    /// Add an action, e.g. `oss:GetObject`.
+    pub fn action(mut self, action: impl Into<String>) -> Self {
+        self.actions.push(action.into());
+        self
+    }
+
+    /// Add a resource, e.g. `acs:oss:*:*:my-bucket/*`.
+    pub fn resource(mut self, resource: impl Into<String>) -> Self {
+        self.resources.push(resource.into());
+        self
+    }
+
+    /// Add a condition, e.g. `("IpAddress", "acs:SourceIp", ["10.0.0.0/8"])`.
+    ///
+    /// Values for the same operator and key accumulate.
+    pub fn condition<I, V>(
+        mut self,
+        operator: impl Into<String>,
+        key: impl Into<String>,
+        values: I,
+    ) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<String>,
+    {
+        self.conditions
+            .entry(operator.into())
+            .or_default()
+            .entry(key.into())
+            .or_default()
+            .extend(values.into_iter().map(Into::into));
+        self
+    }
+
+    /// Build the statement.
+    pub fn build(self) -> Result<Statement> {
+        let effect = self
+            .effect
+            .ok_or_else(|| OssError::MissingField("effect".into()))?;
+        if self.actions.is_empty() {
+            return Err(OssError::MissingField("action".into()));
+        }
+        if self.resources.is_empty() {
+            return Err(OssError::MissingField("resource".into()));
+        }
+        Ok(Statement {
+            effect,
+            principals: self.principals,
+            actions: self.actions,
+            resources: self.resources,
+            conditions: self.conditions,
+        })
+    }
+}
+
+/// A JSON value that is either a single string or a list of strings.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl From<OneOrMany> for Vec<String> {
+    fn from(value: OneOrMany) -> Self {
+        match value {
+            OneOrMany::One(value) => vec![value],
+            OneOrMany::Many(values) => values,
+        }
+    }
+}
+
+fn one_or_many<'de, D>(deserializer: D) -> std::result::Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    OneOrMany::deserialize(deserializer).map(Into::into)
+}
+
+fn conditions<'de, D>(deserializer: D) -> std::result::Result<Conditions, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw = BTreeMap::<String, BTreeMap<String, OneOrMany>>::deserialize(deserializer)?;
+    Ok(raw
+        .into_iter()
+        .map(|(operator, keys)| {
+            let keys = keys
+                .into_iter()
+                .map(|(key, values)| (key, values.into()))
+                .collect();
+            (operator, keys)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_and_serializes_policy() {
+        let policy = PolicyDocument::builder()
+            .statement(
+                Statement::builder()
+                    .effect(Effect::Deny)
+                    .principal("*")
+                    .action("oss:DeleteObject")
+                    .resource("acs:oss:*:*:bucket/*")
+                    .condition("IpAddress", "acs:SourceIp", ["10.0.0.0/8"])
+                    .condition("IpAddress", "acs:SourceIp", ["172.16.0.0/12"])
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            policy.to_json().unwrap(),
+            r#"{"Version":"1","Statement":[{"Effect":"Deny","Principal":["*"],"Action":["oss:DeleteObject"],"Resource":["acs:oss:*:*:bucket/*"],"Condition":{"IpAddress":{"acs:SourceIp":["10.0.0.0/8","172.16.0.0/12"]}}}]}"#
+        );
+    }
+
+    #[test]
+    fn parses_single_string_fields() {
+        let json = r#"{
+            "Version": "1",
+            "Statement": [{
+                "Effect": "Allow",
+                "Principal": "1234",
+                "Action": "oss:GetObject",
+                "Resource": ["acs:oss:*:*:bucket/a/*", "acs:oss:*:*:bucket/b/*"],
+                "Condition": {"StringEquals": {"acs:UserAgent": "curl"}}
+            }]
+        }"#;
+        let policy = PolicyDocument::from_json(json).unwrap();
+        let statement = &policy.statements[0];
+        assert_eq!(statement.effect, Effect::Allow);
+        assert_eq!(statement.principals, ["1234"]);
+        assert_eq!(statement.actions, ["oss:GetObject"]);
+        assert_eq!(statement.resources.len(), 2);
+        assert_eq!(
+            statement.conditions["StringEquals"]["acs:UserAgent"],
+            ["curl"]
+        );
+    }
+
+    #[test]
+    fn builders_require_fields() {
+        assert!(matches!(
+            PolicyDocument::builder().build(),
+            Err(OssError::MissingField(_))
+        ));
+        assert!(matches!(
+            Statement::builder().action("oss:*").resource("r").build(),
+            Err(OssError::MissingField(_))
+        ));
+        assert!(matches!(
+            Statement::builder()
+                .effect(Effect::Allow)
+                .resource("r")
+                .build(),
+            Err(OssError::MissingField(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_json_is_reported() {
+        assert!(matches!(
+            PolicyDocument::from_json("{not json"),
+            Err(OssError::JsonParse(_))
+        ));
+    }
+}
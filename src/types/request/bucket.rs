@@ -1,9 +1,14 @@
-//! Bucket operation request types: Create, Delete, List, GetInfo, ACL, CORS, Referer, Policy, Versioning, Lifecycle, Encryption, Logging.
+//! Bucket operation request types: Create, Delete, List, GetInfo, ACL, CORS, Referer, Policy, Versioning, Lifecycle, Encryption, Logging, TransferAcceleration, DataRedundancyTransition, Cname.
+
+use std::fmt;
 
 use serde::{Deserialize, Serialize};
 
 use crate::error::{OssError, Result};
-use crate::types::common::{BucketAcl, BucketName, ServerSideEncryption, StorageClass};
+use crate::types::common::{
+    BucketAcl, BucketName, DataRedundancyType, ServerSideEncryption, StorageClass,
+};
+use crate::types::policy::PolicyDocument;
 
 /// Request to create a new bucket.
 #[derive(Debug)]
@@ -340,6 +345,7 @@ impl PutBucketCorsRequestBuilder {
                 reason: "at least one CORS rule is required".into(),
             });
         }
+        validate_cors_rules(&self.cors_rules)?;
         Ok(PutBucketCorsRequest {
             bucket: self
                 .bucket
@@ -386,6 +392,80 @@ impl CorsRule {
     }
 }
 
+/// Maximum number of CORS rules on a bucket.
+const MAX_CORS_RULES: usize = 10;
+
+/// Check CORS rules against the limits OSS enforces.
+fn validate_cors_rules(rules: &[CorsRule]) -> Result<()> {
+    let invalid = |reason: String| OssError::InvalidParameter {
+        field: "cors_rules".into(),
+        reason,
+    };
+    if rules.len() > MAX_CORS_RULES {
+        return Err(invalid(format!(
+            "{} rules given; a bucket allows at most {MAX_CORS_RULES}",
+            rules.len()
+        )));
+    }
+    for (index, rule) in rules.iter().enumerate() {
+        let n = index + 1;
+        if rule.allowed_origins.is_empty() {
+            return Err(invalid(format!("rule #{n} has no allowed origins")));
+        }
+        if rule.allowed_methods.is_empty() {
+            return Err(invalid(format!("rule #{n} has no allowed methods")));
+        }
+        for origin in &rule.allowed_origins {
+            if let Some(problem) = cors_origin_problem(origin) {
+                return Err(invalid(format!(
+                    "rule #{n} has allowed origin '{origin}', which {problem}"
+                )));
+            }
+        }
+        for header in rule.allowed_headers.iter().flatten() {
+            if header.matches('*').count() > 1 {
+                return Err(invalid(format!(
+                    "rule #{n} has allowed header '{header}', which has more than one '*'"
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Why `origin` is not a valid CORS origin, if it is not.
+///
+/// OSS accepts `*` or `scheme://host[:port]`, with at most one `*` wildcard,
+/// e.g. `https://*.example.com`.
+fn cors_origin_problem(origin: &str) -> Option<&'static str> {
+    if origin == "*" {
+        return None;
+    }
+    if origin.matches('*').count() > 1 {
+        return Some("has more than one '*'");
+    }
+    let Some((scheme, authority)) = origin.split_once("://") else {
+        return Some("is not '*' or of the form scheme://host[:port]");
+    };
+    if scheme.is_empty()
+        || !scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+    {
+        return Some("has an invalid scheme");
+    }
+    if authority.is_empty() {
+        return Some("has no host");
+    }
+    if authority.contains('/') {
+        return Some("has a path; origins are scheme://host[:port] only");
+    }
+    if authority.chars().any(char::is_whitespace) {
+        return Some("contains whitespace");
+    }
+    None
+}
+
 /// Request to get the CORS configuration of a bucket.
 #[derive(Debug)]
 pub struct GetBucketCorsRequest {
@@ -586,7 +666,8 @@ impl GetBucketRefererRequestBuilder {
 
 /// Request to set the authorization policy of a bucket.
 ///
-/// The policy is a JSON string containing the bucket policy rules.
+/// The policy is a JSON string containing the bucket policy rules, either
+/// given verbatim or built as a [`PolicyDocument`].
 #[derive(Debug)]
 pub struct PutBucketPolicyRequest {
     pub(crate) bucket: BucketName,
@@ -598,6 +679,8 @@ pub struct PutBucketPolicyRequest {
 pub struct PutBucketPolicyRequestBuilder {
     bucket: Option<BucketName>,
     policy: Option<String>,
+    document: Option<PolicyDocument>,
+    validate: bool,
 }
 
 impl PutBucketPolicyRequestBuilder {
@@ -615,22 +698,81 @@ impl PutBucketPolicyRequestBuilder {
     /// Set the policy JSON string.
     pub fn policy(mut self, policy: impl Into<String>) -> Self {
         self.policy = Some(policy.into());
+        self.document = None;
+        self
+    }
+
+    /// Set the policy from a typed document.
+    pub fn policy_document(mut self, document: PolicyDocument) -> Self {
+        self.document = Some(document);
+        self.policy = None;
+        self
+    }
+
+    /// Check at build time that a policy given with [`policy`](Self::policy)
+    /// is a JSON object with a `Version` string and a non-empty `Statement`
+    /// list (default: off). Without this, a malformed policy is only
+    /// reported by OSS as a generic 400.
+    pub fn validate(mut self, validate: bool) -> Self {
+        self.validate = validate;
         self
     }
 
     /// Build the request.
     pub fn build(self) -> Result<PutBucketPolicyRequest> {
+        let policy = match self.document {
+            Some(document) => Some(document.to_json()?),
+            None => {
+                if self.validate
+                    && let Some(policy) = &self.policy
+                {
+                    validate_policy_json(policy)?;
+                }
+                self.policy
+            }
+        };
         Ok(PutBucketPolicyRequest {
             bucket: self
                 .bucket
                 .ok_or_else(|| OssError::MissingField("bucket".into()))?,
-            policy: self
-                .policy
-                .ok_or_else(|| OssError::MissingField("policy".into()))?,
+            policy: policy.ok_or_else(|| OssError::MissingField("policy".into()))?,
         })
     }
 }
 
+/// Check that `policy` has the top-level shape OSS expects.
+fn validate_policy_json(policy: &str) -> Result<()> {
+    let invalid = |reason: String| OssError::InvalidParameter {
+        field: "policy".into(),
+        reason,
+    };
+    let value: serde_json::Value =
+        serde_json::from_str(policy).map_err(|e| invalid(format!("not valid JSON: {e}")))?;
+    let Some(document) = value.as_object() else {
+        return Err(invalid("must be a JSON object".into()));
+    };
+    match document.get("Version") {
+        Some(serde_json::Value::String(version)) if !version.is_empty() => {}
+        Some(_) => return Err(invalid("\"Version\" must be a non-empty string".into())),
+        None => return Err(invalid("missing \"Version\"".into())),
+    }
+    let statements = match document.get("Statement") {
+        Some(serde_json::Value::Array(statements)) => statements,
+        Some(_) => return Err(invalid("\"Statement\" must be a list".into())),
+        None => return Err(invalid("missing \"Statement\"".into())),
+    };
+    if statements.is_empty() {
+        return Err(invalid("\"Statement\" must not be empty".into()));
+    }
+    if let Some(index) = statements.iter().position(|s| !s.is_object()) {
+        return Err(invalid(format!(
+            "statement #{} is not an object",
+            index + 1
+        )));
+    }
+    Ok(())
+}
+
 /// Request to get the authorization policy of a bucket.
 #[derive(Debug)]
 pub struct GetBucketPolicyRequest {
@@ -927,6 +1069,10 @@ impl PutBucketLifecycleRequestBuilder {
     }
 
     /// Build the request.
+    ///
+    /// The rules are checked for the constraints OSS enforces, so mistakes
+    /// are reported with the offending rule rather than a generic
+    /// `InvalidArgument` from the server.
     pub fn build(self) -> Result<PutBucketLifecycleRequest> {
         if self.lifecycle_rules.is_empty() {
             return Err(OssError::InvalidParameter {
@@ -934,6 +1080,7 @@ impl PutBucketLifecycleRequestBuilder {
                 reason: "at least one lifecycle rule is required".into(),
             });
         }
+        validate_lifecycle_rules(&self.lifecycle_rules)?;
         Ok(PutBucketLifecycleRequest {
             bucket: self
                 .bucket
@@ -943,6 +1090,151 @@ impl PutBucketLifecycleRequestBuilder {
     }
 }
 
+/// Check lifecycle rules for the mistakes OSS rejects: duplicate rule IDs,
+/// malformed expirations, out-of-order transitions and overlapping rules
+/// that would expire objects before transitioning them.
+fn validate_lifecycle_rules(rules: &[LifecycleRule]) -> Result<()> {
+    let invalid = |reason: String| OssError::InvalidParameter {
+        field: "lifecycle_rules".into(),
+        reason,
+    };
+    let name = |index: usize| match &rules[index].id {
+        Some(id) => format!("rule '{id}'"),
+        None => format!("rule #{}", index + 1),
+    };
+
+    let mut ids = std::collections::HashSet::new();
+    for (index, rule) in rules.iter().enumerate() {
+        if let Some(id) = &rule.id {
+            if id.len() > 255 {
+                return Err(invalid(format!("{} has an ID over 255 bytes", name(index))));
+            }
+            if !ids.insert(id.as_str()) {
+                return Err(invalid(format!("rule ID '{id}' is used more than once")));
+            }
+        }
+        if rule.expiration.is_none() && rule.transitions.is_empty() {
+            return Err(invalid(format!(
+                "{} has neither an expiration nor a transition",
+                name(index)
+            )));
+        }
+        match &rule.expiration {
+            Some(LifecycleExpiration::Days(0)) => {
+                return Err(invalid(format!(
+                    "{} expires objects after 0 days; use at least 1",
+                    name(index)
+                )));
+            }
+            Some(LifecycleExpiration::Date(date)) if !is_lifecycle_date(date) => {
+                return Err(invalid(format!(
+                    "{} has expiration date '{date}'; expected YYYY-MM-DD or \
+                     YYYY-MM-DDT00:00:00.000Z",
+                    name(index)
+                )));
+            }
+            _ => {}
+        }
+
+        let mut previous: Option<&LifecycleTransition> = None;
+        for transition in &rule.transitions {
+            let Some(rank) = transition_rank(transition.storage_class) else {
+                return Err(invalid(format!(
+                    "{} cannot transition objects to {}",
+                    name(index),
+                    transition.storage_class
+                )));
+            };
+            if let Some(prev) = previous {
+                let prev_rank = transition_rank(prev.storage_class).unwrap_or(0);
+                if rank <= prev_rank {
+                    return Err(invalid(format!(
+                        "{} lists transitions out of order: {} must come after {}",
+                        name(index),
+                        prev.storage_class,
+                        transition.storage_class
+                    )));
+                }
+                if transition.days <= prev.days {
+                    return Err(invalid(format!(
+                        "{} transitions to {} after {} days, which must be later than \
+                         the transition to {} after {} days",
+                        name(index),
+                        transition.storage_class,
+                        transition.days,
+                        prev.storage_class,
+                        prev.days
+                    )));
+                }
+            }
+            previous = Some(transition);
+        }
+    }
+
+    // An expiration must come after every transition that applies to the
+    // same objects, whether from the same rule or an overlapping one.
+    for (i, expiring) in rules.iter().enumerate() {
+        let Some(LifecycleExpiration::Days(days)) = expiring.expiration else {
+            continue;
+        };
+        for (j, transitioning) in rules.iter().enumerate() {
+            if i != j && !lifecycle_rules_overlap(expiring, transitioning) {
+                continue;
+            }
+            if let Some(transition) = transitioning.transitions.iter().find(|t| t.days >= days) {
+                let reason = if i == j {
+                    format!(
+                        "{} expires objects after {days} days, before its transition to {} \
+                         after {} days",
+                        name(i),
+                        transition.storage_class,
+                        transition.days
+                    )
+                } else {
+                    format!(
+                        "{} expires objects after {days} days, but overlapping {} \
+                         transitions them to {} after {} days",
+                        name(i),
+                        name(j),
+                        transition.storage_class,
+                        transition.days
+                    )
+                };
+                return Err(invalid(reason));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Position of a storage class in the order lifecycle transitions must
+/// follow; `None` for classes that cannot be transitioned to.
+fn transition_rank(storage_class: StorageClass) -> Option<u8> {
+    match storage_class {
+        StorageClass::Standard => None,
+        StorageClass::InfrequentAccess => Some(1),
+        StorageClass::Archive => Some(2),
+        StorageClass::ColdArchive => Some(3),
+        StorageClass::DeepColdArchive => Some(4),
+    }
+}
+
+/// Whether `date` is a midnight date in one of the formats OSS accepts.
+fn is_lifecycle_date(date: &str) -> bool {
+    let day = date.strip_suffix("T00:00:00.000Z").unwrap_or(date);
+    chrono::NaiveDate::parse_from_str(day, "%Y-%m-%d").is_ok()
+}
+
+/// Whether two enabled rules can apply to the same object.
+fn lifecycle_rules_overlap(a: &LifecycleRule, b: &LifecycleRule) -> bool {
+    if a.status == LifecycleRuleStatus::Disabled || b.status == LifecycleRuleStatus::Disabled {
+        return false;
+    }
+    let a = a.prefix.as_deref().unwrap_or("");
+    let b = b.prefix.as_deref().unwrap_or("");
+    a.starts_with(b) || b.starts_with(a)
+}
+
 /// Request to get the lifecycle configuration of a bucket.
 #[derive(Debug)]
 pub struct GetBucketLifecycleRequest {
@@ -1246,6 +1538,359 @@ impl DeleteBucketLoggingRequestBuilder {
     }
 }
 
+/// Request to enable or disable transfer acceleration for a bucket.
+#[derive(Debug)]
+pub struct PutBucketTransferAccelerationRequest {
+    pub(crate) bucket: BucketName,
+    pub(crate) enabled: bool,
+}
+
+/// Builder for [`PutBucketTransferAccelerationRequest`].
+#[derive(Debug, Default)]
+pub struct PutBucketTransferAccelerationRequestBuilder {
+    bucket: Option<BucketName>,
+    enabled: Option<bool>,
+}
+
+impl PutBucketTransferAccelerationRequestBuilder {
+    /// Create a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the bucket name.
+    pub fn bucket(mut self, bucket: BucketName) -> Self {
+        self.bucket = Some(bucket);
+        self
+    }
+
+    /// Enable or disable transfer acceleration.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = Some(enabled);
+        self
+    }
+
+    /// Build the request.
+    pub fn build(self) -> Result<PutBucketTransferAccelerationRequest> {
+        Ok(PutBucketTransferAccelerationRequest {
+            bucket: self
+                .bucket
+                .ok_or_else(|| OssError::MissingField("bucket".into()))?,
+            enabled: self
+                .enabled
+                .ok_or_else(|| OssError::MissingField("enabled".into()))?,
+        })
+    }
+}
+
+/// Request to get the transfer acceleration configuration of a bucket.
+#[derive(Debug)]
+pub struct GetBucketTransferAccelerationRequest {
+    pub(crate) bucket: BucketName,
+}
+
+/// Builder for [`GetBucketTransferAccelerationRequest`].
+#[derive(Debug, Default)]
+pub struct GetBucketTransferAccelerationRequestBuilder {
+    bucket: Option<BucketName>,
+}
+
+impl GetBucketTransferAccelerationRequestBuilder {
+    /// Create a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the bucket name.
+    pub fn bucket(mut self, bucket: BucketName) -> Self {
+        self.bucket = Some(bucket);
+        self
+    }
+
+    /// Build the request.
+    pub fn build(self) -> Result<GetBucketTransferAccelerationRequest> {
+        Ok(GetBucketTransferAccelerationRequest {
+            bucket: self
+                .bucket
+                .ok_or_else(|| OssError::MissingField("bucket".into()))?,
+        })
+    }
+}
+
+/// Request to start converting a bucket to another data redundancy type.
+#[derive(Debug)]
+pub struct CreateBucketDataRedundancyTransitionRequest {
+    pub(crate) bucket: BucketName,
+    pub(crate) target_redundancy_type: DataRedundancyType,
+}
+
+/// Builder for [`CreateBucketDataRedundancyTransitionRequest`].
+#[derive(Debug, Default)]
+pub struct CreateBucketDataRedundancyTransitionRequestBuilder {
+    bucket: Option<BucketName>,
+    target_redundancy_type: Option<DataRedundancyType>,
+}
+
+impl CreateBucketDataRedundancyTransitionRequestBuilder {
+    /// Create a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the bucket name.
+    pub fn bucket(mut self, bucket: BucketName) -> Self {
+        self.bucket = Some(bucket);
+        self
+    }
+
+    /// Set the redundancy type to convert the bucket to.
+    pub fn target_redundancy_type(mut self, target_redundancy_type: DataRedundancyType) -> Self {
+        self.target_redundancy_type = Some(target_redundancy_type);
+        self
+    }
+
+    /// Build the request.
+    pub fn build(self) -> Result<CreateBucketDataRedundancyTransitionRequest> {
+        Ok(CreateBucketDataRedundancyTransitionRequest {
+            bucket: self
+                .bucket
+                .ok_or_else(|| OssError::MissingField("bucket".into()))?,
+            target_redundancy_type: self
+                .target_redundancy_type
+                .ok_or_else(|| OssError::MissingField("target_redundancy_type".into()))?,
+        })
+    }
+}
+
+/// Request to get a data redundancy transition task of a bucket.
+#[derive(Debug)]
+pub struct GetBucketDataRedundancyTransitionRequest {
+    pub(crate) bucket: BucketName,
+    pub(crate) task_id: String,
+}
+
+/// Builder for [`GetBucketDataRedundancyTransitionRequest`].
+#[derive(Debug, Default)]
+pub struct GetBucketDataRedundancyTransitionRequestBuilder {
+    bucket: Option<BucketName>,
+    task_id: Option<String>,
+}
+
+impl GetBucketDataRedundancyTransitionRequestBuilder {
+    /// Create a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the bucket name.
+    pub fn bucket(mut self, bucket: BucketName) -> Self {
+        self.bucket = Some(bucket);
+        self
+    }
+
+    /// Set the ID of the transition task.
+    pub fn task_id(mut self, task_id: impl Into<String>) -> Self {
+        self.task_id = Some(task_id.into());
+        self
+    }
+
+    /// Build the request.
+    pub fn build(self) -> Result<GetBucketDataRedundancyTransitionRequest> {
+        Ok(GetBucketDataRedundancyTransitionRequest {
+            bucket: self
+                .bucket
+                .ok_or_else(|| OssError::MissingField("bucket".into()))?,
+            task_id: self
+                .task_id
+                .ok_or_else(|| OssError::MissingField("task_id".into()))?,
+        })
+    }
+}
+
+/// Request to list the data redundancy transition tasks of a bucket.
+#[derive(Debug)]
+pub struct ListBucketDataRedundancyTransitionRequest {
+    pub(crate) bucket: BucketName,
+}
+
+/// Builder for [`ListBucketDataRedundancyTransitionRequest`].
+#[derive(Debug, Default)]
+pub struct ListBucketDataRedundancyTransitionRequestBuilder {
+    bucket: Option<BucketName>,
+}
+
+impl ListBucketDataRedundancyTransitionRequestBuilder {
+    /// Create a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the bucket name.
+    pub fn bucket(mut self, bucket: BucketName) -> Self {
+        self.bucket = Some(bucket);
+        self
+    }
+
+    /// Build the request.
+    pub fn build(self) -> Result<ListBucketDataRedundancyTransitionRequest> {
+        Ok(ListBucketDataRedundancyTransitionRequest {
+            bucket: self
+                .bucket
+                .ok_or_else(|| OssError::MissingField("bucket".into()))?,
+        })
+    }
+}
+
+/// Request to delete a data redundancy transition task of a bucket.
+#[derive(Debug)]
+pub struct DeleteBucketDataRedundancyTransitionRequest {
+    pub(crate) bucket: BucketName,
+    pub(crate) task_id: String,
+}
+
+/// Builder for [`DeleteBucketDataRedundancyTransitionRequest`].
+#[derive(Debug, Default)]
+pub struct DeleteBucketDataRedundancyTransitionRequestBuilder {
+    bucket: Option<BucketName>,
+    task_id: Option<String>,
+}
+
+impl DeleteBucketDataRedundancyTransitionRequestBuilder {
+    /// Create a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the bucket name.
+    pub fn bucket(mut self, bucket: BucketName) -> Self {
+        self.bucket = Some(bucket);
+        self
+    }
+
+    /// Set the ID of the transition task.
+    pub fn task_id(mut self, task_id: impl Into<String>) -> Self {
+        self.task_id = Some(task_id.into());
+        self
+    }
+
+    /// Build the request.
+    pub fn build(self) -> Result<DeleteBucketDataRedundancyTransitionRequest> {
+        Ok(DeleteBucketDataRedundancyTransitionRequest {
+            bucket: self
+                .bucket
+                .ok_or_else(|| OssError::MissingField("bucket".into()))?,
+            task_id: self
+                .task_id
+                .ok_or_else(|| OssError::MissingField("task_id".into()))?,
+        })
+    }
+}
+
+/// Request to map a custom domain (CNAME) to a bucket.
+#[derive(Debug)]
+pub struct PutCnameRequest {
+    pub(crate) bucket: BucketName,
+    pub(crate) domain: String,
+    pub(crate) certificate: Option<CnameCertificate>,
+}
+
+/// HTTPS certificate settings of a CNAME.
+///
+/// To upload a new certificate set `certificate` and `private_key`; to bind
+/// a certificate already held by Certificate Management Service set
+/// `cert_id`. Set `delete_certificate` to unbind the current certificate.
+#[derive(Clone, Default)]
+pub struct CnameCertificate {
+    /// ID of the certificate in Certificate Management Service.
+    pub cert_id: Option<String>,
+    /// PEM-encoded public certificate (chain).
+    pub certificate: Option<String>,
+    /// PEM-encoded private key of the certificate.
+    pub private_key: Option<String>,
+    /// ID of the currently bound certificate, required when replacing it
+    /// without `force`.
+    pub previous_cert_id: Option<String>,
+    /// Replace the bound certificate even if `previous_cert_id` does not
+    /// match it.
+    pub force: bool,
+    /// Unbind the certificate from the domain.
+    pub delete_certificate: bool,
+}
+
+impl fmt::Debug for CnameCertificate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CnameCertificate")
+            .field("cert_id", &self.cert_id)
+            .field("certificate", &self.certificate.as_ref().map(|_| "..."))
+            .field("private_key", &self.private_key.as_ref().map(|_| "****"))
+            .field("previous_cert_id", &self.previous_cert_id)
+            .field("force", &self.force)
+            .field("delete_certificate", &self.delete_certificate)
+            .finish()
+    }
+}
+
+/// Builder for [`PutCnameRequest`].
+#[derive(Debug, Default)]
+pub struct PutCnameRequestBuilder {
+    bucket: Option<BucketName>,
+    domain: Option<String>,
+    certificate: Option<CnameCertificate>,
+}
+
+impl PutCnameRequestBuilder {
+    /// Create a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the bucket name.
+    pub fn bucket(mut self, bucket: BucketName) -> Self {
+        self.bucket = Some(bucket);
+        self
+    }
+
+    /// Set the custom domain, e.g. `static.example.com`.
+    pub fn domain(mut self, domain: impl Into<String>) -> Self {
+        self.domain = Some(domain.into());
+        self
+    }
+
+    /// Upload, bind or unbind the domain's HTTPS certificate.
+    pub fn certificate(mut self, certificate: CnameCertificate) -> Self {
+        self.certificate = Some(certificate);
+        self
+    }
+
+    /// Build the request.
+    pub fn build(self) -> Result<PutCnameRequest> {
+        if let Some(ref cert) = self.certificate {
+            if cert.certificate.is_some() != cert.private_key.is_some() {
+                return Err(OssError::InvalidParameter {
+                    field: "certificate".into(),
+                    reason: "certificate and private_key must be set together".into(),
+                });
+            }
+            if cert.delete_certificate && (cert.cert_id.is_some() || cert.certificate.is_some()) {
+                return Err(OssError::InvalidParameter {
+                    field: "certificate".into(),
+                    reason: "delete_certificate cannot be combined with a certificate to bind"
+                        .into(),
+                });
+            }
+        }
+        Ok(PutCnameRequest {
+            bucket: self
+                .bucket
+                .ok_or_else(|| OssError::MissingField("bucket".into()))?,
+            domain: self
+                .domain
+                .ok_or_else(|| OssError::MissingField("domain".into()))?,
+            certificate: self.certificate,
+        })
+    }
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename = "CORSConfiguration")]
 pub(crate) struct CorsConfigurationXml {
@@ -1379,6 +2024,51 @@ pub(crate) struct LoggingEnabledXml {
     pub target_prefix: String,
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename = "TransferAccelerationConfiguration")]
+pub(crate) struct TransferAccelerationConfigurationXml {
+    #[serde(rename = "Enabled")]
+    pub enabled: bool,
+}
+
+// No `Debug` on the CNAME XML types: they carry the certificate private key.
+#[derive(Serialize)]
+#[serde(rename = "BucketCnameConfiguration")]
+pub(crate) struct CnameConfigurationXml {
+    #[serde(rename = "Cname")]
+    pub cname: CnameXml,
+}
+
+#[derive(Serialize)]
+pub(crate) struct CnameXml {
+    #[serde(rename = "Domain")]
+    pub domain: String,
+    #[serde(
+        rename = "CertificateConfiguration",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub certificate_configuration: Option<CertificateConfigurationXml>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct CertificateConfigurationXml {
+    #[serde(rename = "CertId", skip_serializing_if = "Option::is_none")]
+    pub cert_id: Option<String>,
+    #[serde(rename = "Certificate", skip_serializing_if = "Option::is_none")]
+    pub certificate: Option<String>,
+    #[serde(rename = "PrivateKey", skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<String>,
+    #[serde(rename = "PreviousCertId", skip_serializing_if = "Option::is_none")]
+    pub previous_cert_id: Option<String>,
+    #[serde(rename = "Force", skip_serializing_if = "std::ops::Not::not")]
+    pub force: bool,
+    #[serde(
+        rename = "DeleteCertificate",
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub delete_certificate: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1495,6 +2185,60 @@ mod tests {
         assert_eq!(req.cors_rules.len(), 1);
     }
 
+    fn cors_error(rules: Vec<CorsRule>) -> String {
+        match PutBucketCorsRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .rules(rules)
+            .build()
+        {
+            Err(OssError::InvalidParameter { field, reason }) => {
+                assert_eq!(field, "cors_rules");
+                reason
+            }
+            other => panic!("expected InvalidParameter, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn put_bucket_cors_validates_rules() {
+        use crate::types::common::CorsHttpMethod;
+
+        let rule = |origin: &str| {
+            CorsRule::new()
+                .add_allowed_origin(origin)
+                .add_allowed_method(CorsHttpMethod::Get)
+        };
+        for origin in [
+            "*",
+            "https://example.com",
+            "http://*.example.com",
+            "http://10.0.0.1:8080",
+        ] {
+            let req = PutBucketCorsRequestBuilder::new()
+                .bucket(BucketName::new("test-bucket").unwrap())
+                .add_rule(rule(origin))
+                .build();
+            assert!(req.is_ok(), "{origin}");
+        }
+
+        let reason = cors_error(vec![rule("https://*.*.example.com")]);
+        assert!(reason.contains("more than one '*'"), "{reason}");
+        let reason = cors_error(vec![rule("example.com")]);
+        assert!(reason.contains("scheme://host[:port]"), "{reason}");
+        let reason = cors_error(vec![rule("https://example.com/app")]);
+        assert!(reason.contains("has a path"), "{reason}");
+        let reason = cors_error(vec![rule("*"), CorsRule::new().add_allowed_origin("*")]);
+        assert_eq!(reason, "rule #2 has no allowed methods");
+        let reason = cors_error(vec![
+            CorsRule::new().add_allowed_method(CorsHttpMethod::Get),
+        ]);
+        assert_eq!(reason, "rule #1 has no allowed origins");
+        let reason = cors_error(vec![rule("*").allowed_headers(vec!["x-*-*".into()])]);
+        assert!(reason.contains("allowed header 'x-*-*'"), "{reason}");
+        let reason = cors_error(vec![rule("*"); 11]);
+        assert!(reason.contains("at most 10"), "{reason}");
+    }
+
     #[test]
     fn put_bucket_cors_missing_bucket() {
         let rule = CorsRule::new()
@@ -1566,6 +2310,65 @@ mod tests {
         assert_eq!(req.policy, policy_json);
     }
 
+    #[test]
+    fn put_bucket_policy_from_document() {
+        use crate::types::policy::{Effect, Statement};
+
+        let document = PolicyDocument::builder()
+            .statement(
+                Statement::builder()
+                    .effect(Effect::Allow)
+                    .principal("*")
+                    .action("oss:GetObject")
+                    .resource("acs:oss:*:*:test-bucket/*")
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let req = PutBucketPolicyRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .policy_document(document.clone())
+            .build()
+            .unwrap();
+        assert_eq!(PolicyDocument::from_json(&req.policy).unwrap(), document);
+    }
+
+    #[test]
+    fn put_bucket_policy_validates_json_when_asked() {
+        let build = |policy: &str, validate: bool| {
+            PutBucketPolicyRequestBuilder::new()
+                .bucket(BucketName::new("test-bucket").unwrap())
+                .policy(policy)
+                .validate(validate)
+                .build()
+        };
+        let valid =
+            r#"{"Version":"1","Statement":[{"Effect":"Allow","Action":["oss:GetObject"]}]}"#;
+        assert!(build(valid, true).is_ok());
+        // Validation is opt-in.
+        assert!(build("not json", false).is_ok());
+
+        for (policy, expected) in [
+            ("not json", "not valid JSON"),
+            ("[]", "must be a JSON object"),
+            (r#"{"Statement":[{}]}"#, "missing \"Version\""),
+            (r#"{"Version":1,"Statement":[{}]}"#, "\"Version\" must be"),
+            (r#"{"Version":"1"}"#, "missing \"Statement\""),
+            (r#"{"Version":"1","Statement":{}}"#, "must be a list"),
+            (r#"{"Version":"1","Statement":[]}"#, "must not be empty"),
+            (r#"{"Version":"1","Statement":[{},"x"]}"#, "statement #2"),
+        ] {
+            match build(policy, true) {
+                Err(OssError::InvalidParameter { field, reason }) => {
+                    assert_eq!(field, "policy");
+                    assert!(reason.contains(expected), "{policy}: {reason}");
+                }
+                other => panic!("{policy}: expected InvalidParameter, got: {other:?}"),
+            }
+        }
+    }
+
     #[test]
     fn put_bucket_policy_missing_bucket() {
         let req = PutBucketPolicyRequestBuilder::new()
@@ -1721,6 +2524,137 @@ mod tests {
         assert_eq!(rule.transitions.len(), 2);
     }
 
+    fn lifecycle_error(rules: Vec<LifecycleRule>) -> String {
+        match PutBucketLifecycleRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .rules(rules)
+            .build()
+        {
+            Err(OssError::InvalidParameter { field, reason }) => {
+                assert_eq!(field, "lifecycle_rules");
+                reason
+            }
+            other => panic!("expected InvalidParameter, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn put_bucket_lifecycle_accepts_valid_rules() {
+        let req = PutBucketLifecycleRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .add_rule(
+                LifecycleRule::new()
+                    .id("tier-logs")
+                    .prefix("logs/")
+                    .add_transition(LifecycleTransition::new(StorageClass::InfrequentAccess, 30))
+                    .add_transition(LifecycleTransition::new(StorageClass::Archive, 90))
+                    .expiration(LifecycleExpiration::Days(365)),
+            )
+            .add_rule(
+                LifecycleRule::new()
+                    .id("expire-tmp")
+                    .prefix("tmp/")
+                    .expiration(LifecycleExpiration::Date("2030-01-01".into())),
+            )
+            .build();
+        assert!(req.is_ok());
+    }
+
+    #[test]
+    fn put_bucket_lifecycle_rejects_duplicate_ids() {
+        let rule = LifecycleRule::new()
+            .id("dup")
+            .expiration(LifecycleExpiration::Days(1));
+        let reason = lifecycle_error(vec![rule.clone(), rule.prefix("other/")]);
+        assert!(reason.contains("'dup' is used more than once"), "{reason}");
+    }
+
+    #[test]
+    fn put_bucket_lifecycle_rejects_unordered_transitions() {
+        let reason = lifecycle_error(vec![
+            LifecycleRule::new()
+                .id("r")
+                .add_transition(LifecycleTransition::new(StorageClass::Archive, 30))
+                .add_transition(LifecycleTransition::new(StorageClass::InfrequentAccess, 60)),
+        ]);
+        assert!(reason.contains("out of order"), "{reason}");
+
+        let reason = lifecycle_error(vec![
+            LifecycleRule::new()
+                .add_transition(LifecycleTransition::new(StorageClass::InfrequentAccess, 90))
+                .add_transition(LifecycleTransition::new(StorageClass::ColdArchive, 90)),
+        ]);
+        assert!(
+            reason.starts_with("rule #1 transitions to ColdArchive"),
+            "{reason}"
+        );
+
+        let reason = lifecycle_error(vec![
+            LifecycleRule::new()
+                .add_transition(LifecycleTransition::new(StorageClass::Standard, 1)),
+        ]);
+        assert!(
+            reason.contains("cannot transition objects to Standard"),
+            "{reason}"
+        );
+    }
+
+    #[test]
+    fn put_bucket_lifecycle_rejects_bad_expirations() {
+        let reason = lifecycle_error(vec![
+            LifecycleRule::new().expiration(LifecycleExpiration::Date("01/02/2030".into())),
+        ]);
+        assert!(reason.contains("expiration date '01/02/2030'"), "{reason}");
+
+        let reason = lifecycle_error(vec![
+            LifecycleRule::new()
+                .id("r")
+                .add_transition(LifecycleTransition::new(StorageClass::Archive, 30))
+                .expiration(LifecycleExpiration::Days(30)),
+        ]);
+        assert!(
+            reason.contains("before its transition to Archive"),
+            "{reason}"
+        );
+
+        let reason = lifecycle_error(vec![LifecycleRule::new().id("empty")]);
+        assert!(
+            reason.contains("neither an expiration nor a transition"),
+            "{reason}"
+        );
+    }
+
+    #[test]
+    fn put_bucket_lifecycle_rejects_conflicting_overlapping_rules() {
+        let expire = LifecycleRule::new()
+            .id("expire-logs")
+            .prefix("logs/")
+            .expiration(LifecycleExpiration::Days(10));
+        let archive = LifecycleRule::new()
+            .id("archive-app-logs")
+            .prefix("logs/app/")
+            .add_transition(LifecycleTransition::new(StorageClass::Archive, 30));
+        let reason = lifecycle_error(vec![expire.clone(), archive.clone()]);
+        assert!(
+            reason.contains("'expire-logs' expires objects after 10 days, but overlapping rule 'archive-app-logs'"),
+            "{reason}"
+        );
+
+        // Disjoint prefixes and disabled rules do not conflict.
+        let req = PutBucketLifecycleRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .add_rule(expire.clone())
+            .add_rule(archive.clone().prefix("data/"))
+            .build();
+        assert!(req.is_ok());
+        let req = PutBucketLifecycleRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .add_rule(expire)
+            .add_rule(archive.status(LifecycleRuleStatus::Disabled))
+            .build();
+        assert!(req.is_ok());
+    }
+
     #[test]
     fn put_bucket_encryption_request_builder() {
         let req = PutBucketEncryptionRequestBuilder::new()
@@ -1930,4 +2864,132 @@ mod tests {
         // Empty prefix should be skipped
         assert!(!xml.contains("<TargetPrefix>"));
     }
+
+    #[test]
+    fn put_bucket_transfer_acceleration_request_builder() {
+        let req = PutBucketTransferAccelerationRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .enabled(true)
+            .build()
+            .unwrap();
+        assert!(req.enabled);
+    }
+
+    #[test]
+    fn put_bucket_transfer_acceleration_missing_enabled_fails() {
+        let req = PutBucketTransferAccelerationRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .build();
+        assert!(matches!(req, Err(OssError::MissingField(f)) if f == "enabled"));
+    }
+
+    #[test]
+    fn get_bucket_transfer_acceleration_request_builder() {
+        let req = GetBucketTransferAccelerationRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .build();
+        assert!(req.is_ok());
+    }
+
+    #[test]
+    fn create_bucket_data_redundancy_transition_request_builder() {
+        let req = CreateBucketDataRedundancyTransitionRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .target_redundancy_type(DataRedundancyType::ZRS)
+            .build()
+            .unwrap();
+        assert_eq!(req.target_redundancy_type, DataRedundancyType::ZRS);
+    }
+
+    #[test]
+    fn create_bucket_data_redundancy_transition_missing_target_fails() {
+        let req = CreateBucketDataRedundancyTransitionRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .build();
+        assert!(matches!(req, Err(OssError::MissingField(f)) if f == "target_redundancy_type"));
+    }
+
+    #[test]
+    fn get_bucket_data_redundancy_transition_missing_task_id_fails() {
+        let req = GetBucketDataRedundancyTransitionRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .build();
+        assert!(matches!(req, Err(OssError::MissingField(f)) if f == "task_id"));
+    }
+
+    #[test]
+    fn put_cname_request_requires_key_with_certificate() {
+        let req = PutCnameRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .domain("static.example.com")
+            .certificate(CnameCertificate {
+                certificate: Some("-----BEGIN CERTIFICATE-----".into()),
+                ..Default::default()
+            })
+            .build();
+        assert!(
+            matches!(req, Err(OssError::InvalidParameter { field, .. }) if field == "certificate")
+        );
+    }
+
+    #[test]
+    fn put_cname_request_rejects_delete_with_cert_id() {
+        let req = PutCnameRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .domain("static.example.com")
+            .certificate(CnameCertificate {
+                cert_id: Some("123-cn-hangzhou".into()),
+                delete_certificate: true,
+                ..Default::default()
+            })
+            .build();
+        assert!(
+            matches!(req, Err(OssError::InvalidParameter { field, .. }) if field == "certificate")
+        );
+    }
+
+    #[test]
+    fn cname_certificate_debug_hides_private_key() {
+        let cert = CnameCertificate {
+            certificate: Some("PUBLIC".into()),
+            private_key: Some("SECRET".into()),
+            ..Default::default()
+        };
+        let debug = format!("{cert:?}");
+        assert!(!debug.contains("SECRET"));
+    }
+
+    #[test]
+    fn cname_configuration_xml_serializes() {
+        let config = CnameConfigurationXml {
+            cname: CnameXml {
+                domain: "static.example.com".into(),
+                certificate_configuration: Some(CertificateConfigurationXml {
+                    cert_id: Some("123-cn-hangzhou".into()),
+                    certificate: None,
+                    private_key: None,
+                    previous_cert_id: None,
+                    force: true,
+                    delete_certificate: false,
+                }),
+            },
+        };
+        let xml = quick_xml::se::to_string(&config).unwrap();
+        assert_eq!(
+            xml,
+            "<BucketCnameConfiguration><Cname><Domain>static.example.com</Domain>\
+             <CertificateConfiguration><CertId>123-cn-hangzhou</CertId><Force>true</Force>\
+             </CertificateConfiguration></Cname></BucketCnameConfiguration>"
+        );
+    }
+
+    #[test]
+    fn transfer_acceleration_configuration_xml_serializes() {
+        let config = TransferAccelerationConfigurationXml { enabled: true };
+        let xml = quick_xml::se::to_string(&config).unwrap();
+        assert_eq!(
+            xml,
+            "<TransferAccelerationConfiguration><Enabled>true</Enabled></TransferAccelerationConfiguration>"
+        );
+    }
 }
@@ -8,7 +8,7 @@ mod presign;
 use crate::error::{OssError, Result};
 
 /// Validate that a metadata key contains only ASCII alphanumeric, hyphens, and underscores.
-fn validate_metadata_key(key: &str) -> Result<()> {
+pub(crate) fn validate_metadata_key(key: &str) -> Result<()> {
     if key.is_empty() {
         return Err(OssError::InvalidParameter {
             field: "metadata key".into(),
@@ -30,41 +30,94 @@ fn validate_metadata_key(key: &str) -> Result<()> {
     Ok(())
 }
 
+/// Maximum number of tags on an object.
+const MAX_OBJECT_TAGS: usize = 10;
+
+/// Validate object tags against the OSS tagging rules: at most 10 tags,
+/// unique keys of 1-128 characters, values of at most 256 characters, and
+/// only letters, digits, spaces and `+ - = . _ : / @` in both.
+pub(crate) fn validate_tags(tags: &[(String, String)]) -> Result<()> {
+    let invalid = |reason: String| OssError::InvalidParameter {
+        field: "tags".into(),
+        reason,
+    };
+    let allowed = |c: char| c.is_alphanumeric() || " +-=._:/@".contains(c);
+    if tags.len() > MAX_OBJECT_TAGS {
+        return Err(invalid(format!(
+            "cannot set more than {MAX_OBJECT_TAGS} tags per object"
+        )));
+    }
+    let mut seen = std::collections::HashSet::new();
+    for (key, value) in tags {
+        let key_len = key.chars().count();
+        if key_len == 0 || key_len > 128 {
+            return Err(invalid(format!(
+                "tag key '{key}' must be 1-128 characters long"
+            )));
+        }
+        if value.chars().count() > 256 {
+            return Err(invalid(format!(
+                "value of tag '{key}' must be at most 256 characters long"
+            )));
+        }
+        if !key.chars().all(allowed) || !value.chars().all(allowed) {
+            return Err(invalid(format!(
+                "tag '{key}' contains characters other than letters, digits, spaces and + - = . _ : / @"
+            )));
+        }
+        if !seen.insert(key.as_str()) {
+            return Err(invalid(format!("duplicate tag key '{key}'")));
+        }
+    }
+    Ok(())
+}
+
 pub(crate) use bucket::{
-    ApplyServerSideEncryptionByDefaultXml, CorsConfigurationXml, CorsRuleXml,
-    EncryptionConfigurationXml, EncryptionRuleXml, LifecycleConfigurationXml,
-    LifecycleExpirationXml, LifecycleRuleXml, LifecycleTransitionXml, LoggingConfigurationXml,
-    LoggingEnabledXml, RefererBlacklistXml, RefererConfigurationXml, RefererListXml,
-    VersioningConfigurationXml,
+    ApplyServerSideEncryptionByDefaultXml, CertificateConfigurationXml, CnameConfigurationXml,
+    CnameXml, CorsConfigurationXml, CorsRuleXml, EncryptionConfigurationXml, EncryptionRuleXml,
+    LifecycleConfigurationXml, LifecycleExpirationXml, LifecycleRuleXml, LifecycleTransitionXml,
+    LoggingConfigurationXml, LoggingEnabledXml, RefererBlacklistXml, RefererConfigurationXml,
+    RefererListXml, TransferAccelerationConfigurationXml, VersioningConfigurationXml,
 };
 pub use bucket::{
-    CorsRule, CreateBucketRequest, CreateBucketRequestBuilder, DeleteBucketCorsRequest,
-    DeleteBucketCorsRequestBuilder, DeleteBucketEncryptionRequest,
+    CnameCertificate, CorsRule, CreateBucketDataRedundancyTransitionRequest,
+    CreateBucketDataRedundancyTransitionRequestBuilder, CreateBucketRequest,
+    CreateBucketRequestBuilder, DeleteBucketCorsRequest, DeleteBucketCorsRequestBuilder,
+    DeleteBucketDataRedundancyTransitionRequest,
+    DeleteBucketDataRedundancyTransitionRequestBuilder, DeleteBucketEncryptionRequest,
     DeleteBucketEncryptionRequestBuilder, DeleteBucketLifecycleRequest,
     DeleteBucketLifecycleRequestBuilder, DeleteBucketLoggingRequest,
     DeleteBucketLoggingRequestBuilder, DeleteBucketPolicyRequest, DeleteBucketPolicyRequestBuilder,
     DeleteBucketRequest, DeleteBucketRequestBuilder, GetBucketAclRequest,
     GetBucketAclRequestBuilder, GetBucketCorsRequest, GetBucketCorsRequestBuilder,
+    GetBucketDataRedundancyTransitionRequest, GetBucketDataRedundancyTransitionRequestBuilder,
     GetBucketEncryptionRequest, GetBucketEncryptionRequestBuilder, GetBucketInfoRequest,
     GetBucketInfoRequestBuilder, GetBucketLifecycleRequest, GetBucketLifecycleRequestBuilder,
     GetBucketLocationRequest, GetBucketLocationRequestBuilder, GetBucketLoggingRequest,
     GetBucketLoggingRequestBuilder, GetBucketPolicyRequest, GetBucketPolicyRequestBuilder,
-    GetBucketRefererRequest, GetBucketRefererRequestBuilder, GetBucketVersioningRequest,
+    GetBucketRefererRequest, GetBucketRefererRequestBuilder, GetBucketTransferAccelerationRequest,
+    GetBucketTransferAccelerationRequestBuilder, GetBucketVersioningRequest,
     GetBucketVersioningRequestBuilder, LifecycleExpiration, LifecycleRule, LifecycleRuleStatus,
-    LifecycleTransition, ListBucketsRequest, ListBucketsRequestBuilder, PutBucketAclRequest,
-    PutBucketAclRequestBuilder, PutBucketCorsRequest, PutBucketCorsRequestBuilder,
-    PutBucketEncryptionRequest, PutBucketEncryptionRequestBuilder, PutBucketLifecycleRequest,
-    PutBucketLifecycleRequestBuilder, PutBucketLoggingRequest, PutBucketLoggingRequestBuilder,
-    PutBucketPolicyRequest, PutBucketPolicyRequestBuilder, PutBucketRefererRequest,
-    PutBucketRefererRequestBuilder, PutBucketVersioningRequest, PutBucketVersioningRequestBuilder,
+    LifecycleTransition, ListBucketDataRedundancyTransitionRequest,
+    ListBucketDataRedundancyTransitionRequestBuilder, ListBucketsRequest,
+    ListBucketsRequestBuilder, PutBucketAclRequest, PutBucketAclRequestBuilder,
+    PutBucketCorsRequest, PutBucketCorsRequestBuilder, PutBucketEncryptionRequest,
+    PutBucketEncryptionRequestBuilder, PutBucketLifecycleRequest, PutBucketLifecycleRequestBuilder,
+    PutBucketLoggingRequest, PutBucketLoggingRequestBuilder, PutBucketPolicyRequest,
+    PutBucketPolicyRequestBuilder, PutBucketRefererRequest, PutBucketRefererRequestBuilder,
+    PutBucketTransferAccelerationRequest, PutBucketTransferAccelerationRequestBuilder,
+    PutBucketVersioningRequest, PutBucketVersioningRequestBuilder, PutCnameRequest,
+    PutCnameRequestBuilder,
 };
+pub(crate) use multipart::MIN_PART_SIZE;
 pub use multipart::{
     AbortMultipartUploadRequest, AbortMultipartUploadRequestBuilder,
     CompleteMultipartUploadRequest, CompleteMultipartUploadRequestBuilder,
     CompleteMultipartUploadXml, CompletedPart, InitiateMultipartUploadRequest,
     InitiateMultipartUploadRequestBuilder, ListMultipartUploadsRequest,
     ListMultipartUploadsRequestBuilder, ListPartsRequest, ListPartsRequestBuilder,
-    UploadPartRequest, UploadPartRequestBuilder,
+    UploadPartCopyRequest, UploadPartCopyRequestBuilder, UploadPartRequest,
+    UploadPartRequestBuilder,
 };
 pub use object::{
     AppendObjectRequest, AppendObjectRequestBuilder, CopyObjectRequest, CopyObjectRequestBuilder,
@@ -72,10 +125,10 @@ pub use object::{
     DeleteObjectRequestBuilder, DeleteObjectTaggingRequest, DeleteObjectTaggingRequestBuilder,
     GetObjectAclRequest, GetObjectAclRequestBuilder, GetObjectRequest, GetObjectRequestBuilder,
     GetObjectTaggingRequest, GetObjectTaggingRequestBuilder, HeadObjectRequest,
-    HeadObjectRequestBuilder, ListObjectsV2Request, ListObjectsV2RequestBuilder,
-    PutObjectAclRequest, PutObjectAclRequestBuilder, PutObjectRequest, PutObjectRequestBuilder,
-    PutObjectTaggingRequest, PutObjectTaggingRequestBuilder, RestoreObjectRequest,
-    RestoreObjectRequestBuilder,
+    HeadObjectRequestBuilder, ListObjectVersionsRequest, ListObjectVersionsRequestBuilder,
+    ListObjectsV2Request, ListObjectsV2RequestBuilder, PutObjectAclRequest,
+    PutObjectAclRequestBuilder, PutObjectRequest, PutObjectRequestBuilder, PutObjectTaggingRequest,
+    PutObjectTaggingRequestBuilder, RestoreObjectRequest, RestoreObjectRequestBuilder,
 };
 pub(crate) use object::{DeleteMultipleObjectsXml, DeleteObjectXmlEntry};
 pub use presign::{PresignedUrlRequest, PresignedUrlRequestBuilder};
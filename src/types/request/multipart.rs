@@ -1,9 +1,25 @@
 //! Multipart upload request types: Initiate, UploadPart, Complete, Abort, ListParts.
 
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use base64::Engine;
+use bytes::Bytes;
+use futures_util::TryStream;
+use md5::{Digest, Md5};
 use serde::Serialize;
 
 use crate::error::{OssError, Result};
-use crate::types::common::{BucketName, ObjectKey, StorageClass};
+use crate::progress::ProgressListener;
+use crate::types::common::{
+    BucketName, Callback, ObjectAcl, ObjectKey, ServerSideEncryption, StorageClass,
+};
+
+use super::{validate_metadata_key, validate_tags};
+
+/// Smallest size OSS accepts for every part but the last.
+pub(crate) const MIN_PART_SIZE: u64 = 100 * 1024;
 
 /// Request to initiate a multipart upload.
 #[derive(Debug)]
@@ -11,16 +27,41 @@ pub struct InitiateMultipartUploadRequest {
     pub(crate) bucket: BucketName,
     pub(crate) key: ObjectKey,
     pub(crate) content_type: Option<String>,
+    pub(crate) cache_control: Option<String>,
+    pub(crate) content_disposition: Option<String>,
+    pub(crate) content_encoding: Option<String>,
+    pub(crate) expires: Option<String>,
     pub(crate) storage_class: Option<StorageClass>,
+    pub(crate) acl: Option<ObjectAcl>,
+    pub(crate) server_side_encryption: Option<ServerSideEncryption>,
+    pub(crate) kms_key_id: Option<String>,
+    pub(crate) forbid_overwrite: bool,
+    pub(crate) sequential: bool,
+    pub(crate) metadata: HashMap<String, String>,
+    pub(crate) tags: Vec<(String, String)>,
 }
 
 /// Builder for [`InitiateMultipartUploadRequest`].
+///
+/// Everything set here is applied to the object that
+/// CompleteMultipartUpload eventually creates.
 #[derive(Debug, Default)]
 pub struct InitiateMultipartUploadRequestBuilder {
     bucket: Option<BucketName>,
     key: Option<ObjectKey>,
     content_type: Option<String>,
+    cache_control: Option<String>,
+    content_disposition: Option<String>,
+    content_encoding: Option<String>,
+    expires: Option<String>,
     storage_class: Option<StorageClass>,
+    acl: Option<ObjectAcl>,
+    server_side_encryption: Option<ServerSideEncryption>,
+    kms_key_id: Option<String>,
+    forbid_overwrite: bool,
+    sequential: bool,
+    metadata: HashMap<String, String>,
+    tags: Vec<(String, String)>,
 }
 
 impl InitiateMultipartUploadRequestBuilder {
@@ -47,14 +88,95 @@ impl InitiateMultipartUploadRequestBuilder {
         self
     }
 
+    /// Set the `Cache-Control` header stored with the object.
+    pub fn cache_control(mut self, cache_control: impl Into<String>) -> Self {
+        self.cache_control = Some(cache_control.into());
+        self
+    }
+
+    /// Set the `Content-Disposition` header stored with the object.
+    pub fn content_disposition(mut self, content_disposition: impl Into<String>) -> Self {
+        self.content_disposition = Some(content_disposition.into());
+        self
+    }
+
+    /// Set the `Content-Encoding` header stored with the object.
+    pub fn content_encoding(mut self, content_encoding: impl Into<String>) -> Self {
+        self.content_encoding = Some(content_encoding.into());
+        self
+    }
+
+    /// Set the `Expires` header stored with the object.
+    pub fn expires(mut self, expires: impl Into<String>) -> Self {
+        self.expires = Some(expires.into());
+        self
+    }
+
     /// Set the storage class.
     pub fn storage_class(mut self, storage_class: StorageClass) -> Self {
         self.storage_class = Some(storage_class);
         self
     }
 
+    /// Set the object ACL.
+    pub fn acl(mut self, acl: ObjectAcl) -> Self {
+        self.acl = Some(acl);
+        self
+    }
+
+    /// Encrypt the object at rest (`x-oss-server-side-encryption`).
+    pub fn server_side_encryption(mut self, encryption: ServerSideEncryption) -> Self {
+        self.server_side_encryption = Some(encryption);
+        self
+    }
+
+    /// Set the KMS key used with [`ServerSideEncryption::KMS`].
+    pub fn kms_key_id(mut self, kms_key_id: impl Into<String>) -> Self {
+        self.kms_key_id = Some(kms_key_id.into());
+        self
+    }
+
+    /// Fail the upload instead of replacing an existing object with the
+    /// same key (`x-oss-forbid-overwrite`).
+    pub fn forbid_overwrite(mut self, forbid: bool) -> Self {
+        self.forbid_overwrite = forbid;
+        self
+    }
+
+    /// Keep the parts of the upload in sequential order (the `sequential`
+    /// query parameter), as required by OSS-HDFS and append-style readers.
+    pub fn sequential(mut self, sequential: bool) -> Self {
+        self.sequential = sequential;
+        self
+    }
+
+    /// Add a custom metadata entry (x-oss-meta-*), applied to the completed
+    /// object.
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Add a tag (sent as `x-oss-tagging`), applied to the completed object.
+    pub fn tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.tags.push((key.into(), value.into()));
+        self
+    }
+
     /// Build the request.
     pub fn build(self) -> Result<InitiateMultipartUploadRequest> {
+        for key in self.metadata.keys() {
+            validate_metadata_key(key)?;
+        }
+        validate_tags(&self.tags)?;
+        if self.kms_key_id.is_some()
+            && self.server_side_encryption != Some(ServerSideEncryption::KMS)
+        {
+            return Err(OssError::InvalidParameter {
+                field: "kms_key_id".into(),
+                reason: "a KMS key requires KMS server-side encryption".into(),
+            });
+        }
         Ok(InitiateMultipartUploadRequest {
             bucket: self
                 .bucket
@@ -63,29 +185,77 @@ impl InitiateMultipartUploadRequestBuilder {
                 .key
                 .ok_or_else(|| OssError::MissingField("key".into()))?,
             content_type: self.content_type,
+            cache_control: self.cache_control,
+            content_disposition: self.content_disposition,
+            content_encoding: self.content_encoding,
+            expires: self.expires,
             storage_class: self.storage_class,
+            acl: self.acl,
+            server_side_encryption: self.server_side_encryption,
+            kms_key_id: self.kms_key_id,
+            forbid_overwrite: self.forbid_overwrite,
+            sequential: self.sequential,
+            metadata: self.metadata,
+            tags: self.tags,
         })
     }
 }
 
 /// Request to upload a single part in a multipart upload.
-#[derive(Debug)]
 pub struct UploadPartRequest {
     pub(crate) bucket: BucketName,
     pub(crate) key: ObjectKey,
     pub(crate) upload_id: String,
     pub(crate) part_number: u32,
     pub(crate) body: reqwest::Body,
+    pub(crate) content_length: Option<u64>,
+    pub(crate) content_md5: Option<String>,
+    pub(crate) progress_listener: Option<Arc<dyn ProgressListener>>,
+}
+
+impl fmt::Debug for UploadPartRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UploadPartRequest")
+            .field("bucket", &self.bucket)
+            .field("key", &self.key)
+            .field("upload_id", &self.upload_id)
+            .field("part_number", &self.part_number)
+            .field("body", &self.body)
+            .field("content_length", &self.content_length)
+            .field("content_md5", &self.content_md5)
+            .field("progress_listener", &self.progress_listener.is_some())
+            .finish()
+    }
 }
 
 /// Builder for [`UploadPartRequest`].
-#[derive(Debug, Default)]
+#[derive(Default)]
 pub struct UploadPartRequestBuilder {
     bucket: Option<BucketName>,
     key: Option<ObjectKey>,
     upload_id: Option<String>,
     part_number: Option<u32>,
     body: Option<reqwest::Body>,
+    content_length: Option<u64>,
+    content_md5: Option<String>,
+    compute_md5: bool,
+    progress_listener: Option<Arc<dyn ProgressListener>>,
+}
+
+impl fmt::Debug for UploadPartRequestBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UploadPartRequestBuilder")
+            .field("bucket", &self.bucket)
+            .field("key", &self.key)
+            .field("upload_id", &self.upload_id)
+            .field("part_number", &self.part_number)
+            .field("body", &self.body)
+            .field("content_length", &self.content_length)
+            .field("content_md5", &self.content_md5)
+            .field("compute_md5", &self.compute_md5)
+            .field("progress_listener", &self.progress_listener.is_some())
+            .finish()
+    }
 }
 
 impl UploadPartRequestBuilder {
@@ -121,6 +291,52 @@ impl UploadPartRequestBuilder {
     /// Set the part body.
     pub fn body(mut self, body: impl Into<reqwest::Body>) -> Self {
         self.body = Some(body.into());
+        self.content_length = None;
+        self
+    }
+
+    /// Set an in-memory part body without copying it. The buffer is shared
+    /// with the request, including across retries.
+    pub fn body_bytes(self, bytes: Bytes) -> Self {
+        self.body(bytes)
+    }
+
+    /// Stream the part body from `stream`, declaring its length up front so
+    /// it is sent with `Content-Length` instead of chunked transfer
+    /// encoding. The stream must yield exactly `content_length` bytes.
+    pub fn body_stream<S>(mut self, stream: S, content_length: u64) -> Self
+    where
+        S: TryStream + Send + 'static,
+        S::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+        Bytes: From<S::Ok>,
+    {
+        self.body = Some(reqwest::Body::wrap_stream(stream));
+        self.content_length = Some(content_length);
+        self
+    }
+
+    /// Set the base64-encoded MD5 of the part (`Content-MD5`). OSS rejects
+    /// the part with `InvalidDigest` if the received bytes do not match.
+    pub fn content_md5(mut self, content_md5: impl Into<String>) -> Self {
+        self.content_md5 = Some(content_md5.into());
+        self
+    }
+
+    /// Compute `Content-MD5` from the body when the request is built.
+    /// Requires an in-memory body; ignored if
+    /// [`content_md5`](Self::content_md5) is set.
+    pub fn compute_md5(mut self, compute: bool) -> Self {
+        self.compute_md5 = compute;
+        self
+    }
+
+    /// Report progress for this part only.
+    ///
+    /// Streaming bodies report every chunk as it is sent. In-memory bodies
+    /// report once when the part starts and once when OSS acknowledges it,
+    /// so the part can still be signed and retried as a whole.
+    pub fn progress_listener(mut self, listener: Arc<dyn ProgressListener>) -> Self {
+        self.progress_listener = Some(listener);
         self
     }
 
@@ -135,6 +351,20 @@ impl UploadPartRequestBuilder {
                 reason: "must be between 1 and 10000".into(),
             });
         }
+        let body = self
+            .body
+            .ok_or_else(|| OssError::MissingField("body".into()))?;
+        let content_md5 = match self.content_md5 {
+            Some(content_md5) => Some(content_md5),
+            None if self.compute_md5 => {
+                let bytes = body.as_bytes().ok_or_else(|| OssError::InvalidParameter {
+                    field: "body".into(),
+                    reason: "Content-MD5 can only be computed for an in-memory body".into(),
+                })?;
+                Some(base64::engine::general_purpose::STANDARD.encode(Md5::digest(bytes)))
+            }
+            None => None,
+        };
         Ok(UploadPartRequest {
             bucket: self
                 .bucket
@@ -146,9 +376,135 @@ impl UploadPartRequestBuilder {
                 .upload_id
                 .ok_or_else(|| OssError::MissingField("upload_id".into()))?,
             part_number,
-            body: self
-                .body
-                .ok_or_else(|| OssError::MissingField("body".into()))?,
+            body,
+            content_length: self.content_length,
+            content_md5,
+            progress_listener: self.progress_listener,
+        })
+    }
+}
+
+/// Request to copy a byte range of an existing object into a part of a
+/// multipart upload (UploadPartCopy).
+#[derive(Debug)]
+pub struct UploadPartCopyRequest {
+    pub(crate) bucket: BucketName,
+    pub(crate) key: ObjectKey,
+    pub(crate) upload_id: String,
+    pub(crate) part_number: u32,
+    pub(crate) source_bucket: BucketName,
+    pub(crate) source_key: ObjectKey,
+    pub(crate) source_range: Option<(u64, u64)>,
+    pub(crate) source_if_match: Option<String>,
+}
+
+/// Builder for [`UploadPartCopyRequest`].
+#[derive(Debug, Default)]
+pub struct UploadPartCopyRequestBuilder {
+    bucket: Option<BucketName>,
+    key: Option<ObjectKey>,
+    upload_id: Option<String>,
+    part_number: Option<u32>,
+    source_bucket: Option<BucketName>,
+    source_key: Option<ObjectKey>,
+    source_range: Option<(u64, u64)>,
+    source_if_match: Option<String>,
+}
+
+impl UploadPartCopyRequestBuilder {
+    /// Create a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the destination bucket.
+    pub fn bucket(mut self, bucket: BucketName) -> Self {
+        self.bucket = Some(bucket);
+        self
+    }
+
+    /// Set the destination object key.
+    pub fn key(mut self, key: ObjectKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Set the upload ID from InitiateMultipartUpload.
+    pub fn upload_id(mut self, upload_id: impl Into<String>) -> Self {
+        self.upload_id = Some(upload_id.into());
+        self
+    }
+
+    /// Set the part number (1-10000).
+    pub fn part_number(mut self, part_number: u32) -> Self {
+        self.part_number = Some(part_number);
+        self
+    }
+
+    /// Set the source bucket.
+    pub fn source_bucket(mut self, bucket: BucketName) -> Self {
+        self.source_bucket = Some(bucket);
+        self
+    }
+
+    /// Set the source object key.
+    pub fn source_key(mut self, key: ObjectKey) -> Self {
+        self.source_key = Some(key);
+        self
+    }
+
+    /// Copy only bytes `start..=end` of the source (default: the whole
+    /// source).
+    pub fn source_range(mut self, start: u64, end: u64) -> Self {
+        self.source_range = Some((start, end));
+        self
+    }
+
+    /// Only copy if the source ETag still matches, so every part of a copy
+    /// comes from the same version of the source.
+    pub fn source_if_match(mut self, etag: impl Into<String>) -> Self {
+        self.source_if_match = Some(etag.into());
+        self
+    }
+
+    /// Build the request.
+    pub fn build(self) -> Result<UploadPartCopyRequest> {
+        let part_number = self
+            .part_number
+            .ok_or_else(|| OssError::MissingField("part_number".into()))?;
+        if !(1..=10000).contains(&part_number) {
+            return Err(OssError::InvalidParameter {
+                field: "part_number".into(),
+                reason: "must be between 1 and 10000".into(),
+            });
+        }
+        if let Some((start, end)) = self.source_range
+            && start > end
+        {
+            return Err(OssError::InvalidParameter {
+                field: "source_range".into(),
+                reason: format!("start {start} is after end {end}"),
+            });
+        }
+        Ok(UploadPartCopyRequest {
+            bucket: self
+                .bucket
+                .ok_or_else(|| OssError::MissingField("bucket".into()))?,
+            key: self
+                .key
+                .ok_or_else(|| OssError::MissingField("key".into()))?,
+            upload_id: self
+                .upload_id
+                .ok_or_else(|| OssError::MissingField("upload_id".into()))?,
+            part_number,
+            source_bucket: self
+                .source_bucket
+                .ok_or_else(|| OssError::MissingField("source_bucket".into()))?,
+            source_key: self
+                .source_key
+                .ok_or_else(|| OssError::MissingField("source_key".into()))?,
+            source_range: self.source_range,
+            source_if_match: self.source_if_match,
         })
     }
 }
@@ -172,6 +528,8 @@ pub struct CompleteMultipartUploadRequest {
     pub(crate) key: ObjectKey,
     pub(crate) upload_id: String,
     pub(crate) parts: Vec<CompletedPart>,
+    pub(crate) complete_all: bool,
+    pub(crate) callback: Option<Callback>,
 }
 
 /// Builder for [`CompleteMultipartUploadRequest`].
@@ -181,6 +539,9 @@ pub struct CompleteMultipartUploadRequestBuilder {
     key: Option<ObjectKey>,
     upload_id: Option<String>,
     parts: Vec<CompletedPart>,
+    part_sizes: HashMap<u32, u64>,
+    complete_all: bool,
+    callback: Option<Callback>,
 }
 
 impl CompleteMultipartUploadRequestBuilder {
@@ -213,14 +574,51 @@ impl CompleteMultipartUploadRequestBuilder {
         self
     }
 
+    /// Add a completed part along with its size in bytes, so `build` can
+    /// reject parts OSS would refuse as too small.
+    pub fn sized_part(mut self, part: CompletedPart, size: u64) -> Self {
+        self.part_sizes.insert(part.part_number, size);
+        self.parts.push(part);
+        self
+    }
+
     /// Set all completed parts at once.
     pub fn parts(mut self, parts: Vec<CompletedPart>) -> Self {
         self.parts = parts;
+        self.part_sizes.clear();
+        self
+    }
+
+    /// Let OSS assemble every part uploaded so far, in part-number order
+    /// (`x-oss-complete-all: yes`), instead of sending a part list. Useful
+    /// when parts are uploaded by many workers and nobody holds the full
+    /// list of ETags.
+    pub fn complete_all(mut self) -> Self {
+        self.complete_all = true;
+        self
+    }
+
+    /// Attach an upload callback. Its result is returned in
+    /// [`CompleteMultipartUploadResponse::callback_result`](crate::types::response::CompleteMultipartUploadResponse::callback_result).
+    pub fn callback(mut self, callback: Callback) -> Self {
+        self.callback = Some(callback);
         self
     }
 
     /// Build the request.
     pub fn build(self) -> Result<CompleteMultipartUploadRequest> {
+        if let Some(ref callback) = self.callback {
+            callback.validate()?;
+        }
+        if self.complete_all && !self.parts.is_empty() {
+            return Err(OssError::InvalidParameter {
+                field: "parts".into(),
+                reason: "must be empty when completing all uploaded parts".into(),
+            });
+        }
+        if !self.complete_all {
+            validate_completed_parts(&self.parts, &self.part_sizes)?;
+        }
         Ok(CompleteMultipartUploadRequest {
             bucket: self
                 .bucket
@@ -232,10 +630,52 @@ impl CompleteMultipartUploadRequestBuilder {
                 .upload_id
                 .ok_or_else(|| OssError::MissingField("upload_id".into()))?,
             parts: self.parts,
+            complete_all: self.complete_all,
+            callback: self.callback,
         })
     }
 }
 
+/// Check a part list for the mistakes OSS answers with `InvalidPartOrder`,
+/// `InvalidPart` or `EntityTooSmall`.
+fn validate_completed_parts(parts: &[CompletedPart], sizes: &HashMap<u32, u64>) -> Result<()> {
+    let invalid = |reason: String| OssError::InvalidParameter {
+        field: "parts".into(),
+        reason,
+    };
+    let Some(last) = parts.last() else {
+        return Err(invalid(
+            "at least one part is required, or use complete_all".into(),
+        ));
+    };
+    let mut previous = 0;
+    for part in parts {
+        if !(1..=10000).contains(&part.part_number) {
+            return Err(invalid(format!(
+                "part number {} is not between 1 and 10000",
+                part.part_number
+            )));
+        }
+        if part.part_number <= previous {
+            return Err(invalid(format!(
+                "part numbers must be unique and ascending, but {} follows {previous}",
+                part.part_number
+            )));
+        }
+        previous = part.part_number;
+        if part.part_number != last.part_number
+            && let Some(&size) = sizes.get(&part.part_number)
+            && size < MIN_PART_SIZE
+        {
+            return Err(invalid(format!(
+                "part {} is {size} bytes; all parts but the last must be at least {MIN_PART_SIZE} bytes",
+                part.part_number
+            )));
+        }
+    }
+    Ok(())
+}
+
 /// Request to abort a multipart upload.
 #[derive(Debug)]
 pub struct AbortMultipartUploadRequest {
@@ -476,6 +916,19 @@ mod tests {
         assert!(req.is_ok());
     }
 
+    #[test]
+    fn initiate_multipart_upload_kms_key_requires_kms() {
+        let req = InitiateMultipartUploadRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .key(ObjectKey::new("large-file.bin").unwrap())
+            .server_side_encryption(ServerSideEncryption::AES256)
+            .kms_key_id("key-1")
+            .build();
+        assert!(
+            matches!(req, Err(OssError::InvalidParameter { ref field, .. }) if field == "kms_key_id")
+        );
+    }
+
     #[test]
     fn upload_part_request_builder() {
         let req = UploadPartRequestBuilder::new()
@@ -488,6 +941,39 @@ mod tests {
         assert!(req.is_ok());
     }
 
+    #[test]
+    fn upload_part_compute_md5() {
+        let req = UploadPartRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .key(ObjectKey::new("large-file.bin").unwrap())
+            .upload_id("test-upload-id")
+            .part_number(1)
+            .body(b"hello".to_vec())
+            .compute_md5(true)
+            .build()
+            .unwrap();
+        assert_eq!(req.content_md5.as_deref(), Some("XUFAKrxLKna5cZ2REBfFkg=="));
+    }
+
+    #[test]
+    fn upload_part_compute_md5_requires_buffered_body() {
+        let chunks: Vec<std::result::Result<bytes::Bytes, std::io::Error>> =
+            vec![Ok(bytes::Bytes::from_static(b"hello"))];
+        let req = UploadPartRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .key(ObjectKey::new("large-file.bin").unwrap())
+            .upload_id("test-upload-id")
+            .part_number(1)
+            .body(reqwest::Body::wrap_stream(futures_util::stream::iter(
+                chunks,
+            )))
+            .compute_md5(true)
+            .build();
+        assert!(
+            matches!(req, Err(OssError::InvalidParameter { ref field, .. }) if field == "body")
+        );
+    }
+
     #[test]
     fn upload_part_request_missing_upload_id() {
         let req = UploadPartRequestBuilder::new()
@@ -499,6 +985,93 @@ mod tests {
         assert!(req.is_err());
     }
 
+    #[test]
+    fn upload_part_copy_rejects_inverted_range() {
+        let req = UploadPartCopyRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .key(ObjectKey::new("copy.bin").unwrap())
+            .upload_id("test-upload-id")
+            .part_number(1)
+            .source_bucket(BucketName::new("src-bucket").unwrap())
+            .source_key(ObjectKey::new("large-file.bin").unwrap())
+            .source_range(10, 9)
+            .build();
+        assert!(
+            matches!(req, Err(OssError::InvalidParameter { ref field, .. }) if field == "source_range")
+        );
+    }
+
+    #[test]
+    fn complete_all_rejects_explicit_parts() {
+        let req = CompleteMultipartUploadRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .key(ObjectKey::new("large-file.bin").unwrap())
+            .upload_id("test-upload-id")
+            .part(CompletedPart {
+                part_number: 1,
+                etag: "\"etag1\"".to_string(),
+            })
+            .complete_all()
+            .build();
+        assert!(
+            matches!(req, Err(OssError::InvalidParameter { ref field, .. }) if field == "parts")
+        );
+    }
+
+    fn complete_builder() -> CompleteMultipartUploadRequestBuilder {
+        CompleteMultipartUploadRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .key(ObjectKey::new("large-file.bin").unwrap())
+            .upload_id("test-upload-id")
+    }
+
+    fn part(part_number: u32) -> CompletedPart {
+        CompletedPart {
+            part_number,
+            etag: format!("etag{part_number}"),
+        }
+    }
+
+    #[test]
+    fn complete_multipart_upload_requires_parts() {
+        let req = complete_builder().build();
+        assert!(
+            matches!(req, Err(OssError::InvalidParameter { ref field, .. }) if field == "parts")
+        );
+    }
+
+    #[test]
+    fn complete_multipart_upload_rejects_unordered_parts() {
+        for parts in [
+            vec![part(2), part(1)],
+            vec![part(1), part(1)],
+            vec![part(0)],
+        ] {
+            let req = complete_builder().parts(parts).build();
+            assert!(
+                matches!(req, Err(OssError::InvalidParameter { ref field, .. }) if field == "parts")
+            );
+        }
+    }
+
+    #[test]
+    fn complete_multipart_upload_rejects_small_intermediate_parts() {
+        let req = complete_builder()
+            .sized_part(part(1), MIN_PART_SIZE - 1)
+            .sized_part(part(2), 10)
+            .build();
+        assert!(
+            matches!(req, Err(OssError::InvalidParameter { ref reason, .. }) if reason.contains("part 1 "))
+        );
+
+        // Only the last part may be small.
+        let req = complete_builder()
+            .sized_part(part(1), MIN_PART_SIZE)
+            .sized_part(part(2), 10)
+            .build();
+        assert!(req.is_ok());
+    }
+
     #[test]
     fn complete_multipart_upload_request_builder() {
         let req = CompleteMultipartUploadRequestBuilder::new()
@@ -1,24 +1,37 @@
 //! Object operation request types: Put, Get, Delete, Head, ListV2, Copy, DeleteMultiple.
 
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
 
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures_util::TryStream;
 use serde::Serialize;
 
+use crate::body::{ReplayableBody, RequestBody};
 use crate::error::{OssError, Result};
-use crate::types::common::{BucketName, MetadataDirective, ObjectAcl, ObjectKey, StorageClass};
+use crate::progress::ProgressListener;
+use crate::types::common::{
+    BucketName, MetadataDirective, ObjectAcl, ObjectKey, ServerSideEncryption, StorageClass,
+};
 
-use super::validate_metadata_key;
+use super::{validate_metadata_key, validate_tags};
 
 /// Request to upload an object to OSS.
 #[derive(Debug)]
 pub struct PutObjectRequest {
     pub(crate) bucket: BucketName,
     pub(crate) key: ObjectKey,
-    pub(crate) body: reqwest::Body,
+    pub(crate) body: RequestBody,
+    pub(crate) content_length: Option<u64>,
     pub(crate) content_type: Option<String>,
     pub(crate) storage_class: Option<StorageClass>,
     pub(crate) acl: Option<ObjectAcl>,
+    pub(crate) server_side_encryption: Option<ServerSideEncryption>,
+    pub(crate) kms_key_id: Option<String>,
     pub(crate) metadata: HashMap<String, String>,
+    pub(crate) tags: Vec<(String, String)>,
 }
 
 /// Builder for [`PutObjectRequest`].
@@ -26,11 +39,15 @@ pub struct PutObjectRequest {
 pub struct PutObjectRequestBuilder {
     bucket: Option<BucketName>,
     key: Option<ObjectKey>,
-    body: Option<reqwest::Body>,
+    body: Option<RequestBody>,
+    content_length: Option<u64>,
     content_type: Option<String>,
     storage_class: Option<StorageClass>,
     acl: Option<ObjectAcl>,
+    server_side_encryption: Option<ServerSideEncryption>,
+    kms_key_id: Option<String>,
     metadata: HashMap<String, String>,
+    tags: Vec<(String, String)>,
 }
 
 impl PutObjectRequestBuilder {
@@ -52,8 +69,43 @@ impl PutObjectRequestBuilder {
     }
 
     /// Set the request body.
+    ///
+    /// Streaming bodies are sent once and not retried unless they fit in
+    /// [`ClientBuilder::max_replay_buffer_size`](crate::config::ClientBuilder::max_replay_buffer_size);
+    /// use [`replayable_body`](Self::replayable_body) to retry larger streams.
     pub fn body(mut self, body: impl Into<reqwest::Body>) -> Self {
-        self.body = Some(body.into());
+        self.body = Some(RequestBody::Once(body.into()));
+        self.content_length = None;
+        self
+    }
+
+    /// Set an in-memory body without copying it. The buffer is shared with
+    /// the request, including across retries.
+    pub fn body_bytes(self, bytes: Bytes) -> Self {
+        self.body(bytes)
+    }
+
+    /// Stream the body from `stream`, declaring its length up front so it is
+    /// sent with `Content-Length` instead of chunked transfer encoding.
+    ///
+    /// The stream must yield exactly `content_length` bytes. Like any
+    /// streaming [`body`](Self::body), it is sent once and not retried.
+    pub fn body_stream<S>(mut self, stream: S, content_length: u64) -> Self
+    where
+        S: TryStream + Send + 'static,
+        S::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+        Bytes: From<S::Ok>,
+    {
+        self.body = Some(RequestBody::Once(reqwest::Body::wrap_stream(stream)));
+        self.content_length = Some(content_length);
+        self
+    }
+
+    /// Set a body that is recreated for every attempt, so the upload is
+    /// retried even when it streams. Replaces any [`body`](Self::body).
+    pub fn replayable_body(mut self, body: ReplayableBody) -> Self {
+        self.body = Some(RequestBody::Replayable(body));
+        self.content_length = None;
         self
     }
 
@@ -75,17 +127,44 @@ impl PutObjectRequestBuilder {
         self
     }
 
+    /// Encrypt the object at rest (`x-oss-server-side-encryption`).
+    pub fn server_side_encryption(mut self, encryption: ServerSideEncryption) -> Self {
+        self.server_side_encryption = Some(encryption);
+        self
+    }
+
+    /// Set the KMS key used with [`ServerSideEncryption::KMS`].
+    pub fn kms_key_id(mut self, kms_key_id: impl Into<String>) -> Self {
+        self.kms_key_id = Some(kms_key_id.into());
+        self
+    }
+
     /// Add a custom metadata entry (x-oss-meta-*).
     pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
         self.metadata.insert(key.into(), value.into());
         self
     }
 
+    /// Add a tag (sent as `x-oss-tagging`), set atomically with the upload.
+    pub fn tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.tags.push((key.into(), value.into()));
+        self
+    }
+
     /// Build the request.
     pub fn build(self) -> Result<PutObjectRequest> {
         for key in self.metadata.keys() {
             validate_metadata_key(key)?;
         }
+        validate_tags(&self.tags)?;
+        if self.kms_key_id.is_some()
+            && self.server_side_encryption != Some(ServerSideEncryption::KMS)
+        {
+            return Err(OssError::InvalidParameter {
+                field: "kms_key_id".into(),
+                reason: "a KMS key requires KMS server-side encryption".into(),
+            });
+        }
         Ok(PutObjectRequest {
             bucket: self
                 .bucket
@@ -96,28 +175,83 @@ impl PutObjectRequestBuilder {
             body: self
                 .body
                 .ok_or_else(|| OssError::MissingField("body".into()))?,
+            content_length: self.content_length,
             content_type: self.content_type,
             storage_class: self.storage_class,
             acl: self.acl,
+            server_side_encryption: self.server_side_encryption,
+            kms_key_id: self.kms_key_id,
             metadata: self.metadata,
+            tags: self.tags,
         })
     }
 }
 
 /// Request to download an object from OSS.
-#[derive(Debug)]
 pub struct GetObjectRequest {
     pub(crate) bucket: BucketName,
     pub(crate) key: ObjectKey,
     pub(crate) range: Option<String>,
+    pub(crate) if_match: Option<String>,
+    pub(crate) if_none_match: Option<String>,
+    pub(crate) if_modified_since: Option<DateTime<Utc>>,
+    pub(crate) if_unmodified_since: Option<DateTime<Utc>>,
+    pub(crate) max_resumes: u32,
+    #[cfg(feature = "decompression")]
+    pub(crate) decode_content: bool,
+    pub(crate) progress_listener: Option<Arc<dyn ProgressListener>>,
+}
+
+impl fmt::Debug for GetObjectRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut f = f.debug_struct("GetObjectRequest");
+        f.field("bucket", &self.bucket)
+            .field("key", &self.key)
+            .field("range", &self.range)
+            .field("if_match", &self.if_match)
+            .field("if_none_match", &self.if_none_match)
+            .field("if_modified_since", &self.if_modified_since)
+            .field("if_unmodified_since", &self.if_unmodified_since)
+            .field("max_resumes", &self.max_resumes);
+        #[cfg(feature = "decompression")]
+        f.field("decode_content", &self.decode_content);
+        f.field("progress_listener", &self.progress_listener.is_some())
+            .finish()
+    }
 }
 
 /// Builder for [`GetObjectRequest`].
-#[derive(Debug, Default)]
+#[derive(Default)]
 pub struct GetObjectRequestBuilder {
     bucket: Option<BucketName>,
     key: Option<ObjectKey>,
     range: Option<String>,
+    if_match: Option<String>,
+    if_none_match: Option<String>,
+    if_modified_since: Option<DateTime<Utc>>,
+    if_unmodified_since: Option<DateTime<Utc>>,
+    max_resumes: u32,
+    #[cfg(feature = "decompression")]
+    decode_content: bool,
+    progress_listener: Option<Arc<dyn ProgressListener>>,
+}
+
+impl fmt::Debug for GetObjectRequestBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut f = f.debug_struct("GetObjectRequestBuilder");
+        f.field("bucket", &self.bucket)
+            .field("key", &self.key)
+            .field("range", &self.range)
+            .field("if_match", &self.if_match)
+            .field("if_none_match", &self.if_none_match)
+            .field("if_modified_since", &self.if_modified_since)
+            .field("if_unmodified_since", &self.if_unmodified_since)
+            .field("max_resumes", &self.max_resumes);
+        #[cfg(feature = "decompression")]
+        f.field("decode_content", &self.decode_content);
+        f.field("progress_listener", &self.progress_listener.is_some())
+            .finish()
+    }
 }
 
 impl GetObjectRequestBuilder {
@@ -144,8 +278,79 @@ impl GetObjectRequestBuilder {
         self
     }
 
+    /// Only return the object if its ETag matches; otherwise OSS answers
+    /// `412 Precondition Failed`.
+    pub fn if_match(mut self, etag: impl Into<String>) -> Self {
+        self.if_match = Some(etag.into());
+        self
+    }
+
+    /// Only return the object if its ETag differs, e.g. from a cached copy.
+    ///
+    /// Use [`OssClient::get_object_conditional`](crate::OssClient::get_object_conditional)
+    /// to receive an unchanged object as
+    /// [`GetObjectResult::NotModified`](crate::types::response::GetObjectResult::NotModified)
+    /// instead of an error.
+    pub fn if_none_match(mut self, etag: impl Into<String>) -> Self {
+        self.if_none_match = Some(etag.into());
+        self
+    }
+
+    /// Only return the object if it was modified after `time`.
+    pub fn if_modified_since(mut self, time: DateTime<Utc>) -> Self {
+        self.if_modified_since = Some(time);
+        self
+    }
+
+    /// Only return the object if it was not modified after `time`; otherwise
+    /// OSS answers `412 Precondition Failed`.
+    pub fn if_unmodified_since(mut self, time: DateTime<Utc>) -> Self {
+        self.if_unmodified_since = Some(time);
+        self
+    }
+
+    /// When the connection breaks while the body is being read, re-request
+    /// the rest of the object from the last received byte, up to
+    /// `max_resumes` times (default: 0, disabled).
+    ///
+    /// Each resumed request is conditional on the object's ETag, so a body
+    /// is never stitched together from two versions of an object. If
+    /// resuming fails, the original read error is returned.
+    pub fn resume_on_error(mut self, max_resumes: u32) -> Self {
+        self.max_resumes = max_resumes;
+        self
+    }
+
+    /// Decompress the body if the object is stored with
+    /// `Content-Encoding: gzip` or `deflate` (default: false).
+    ///
+    /// `content_length` and download progress still count the encoded
+    /// bytes. When OSS returns the object's CRC64, it is checked against
+    /// the encoded bytes as they arrive, and a mismatch fails the read.
+    /// Cannot be combined with [`range`](Self::range).
+    #[cfg(feature = "decompression")]
+    pub fn decode_content(mut self, decode: bool) -> Self {
+        self.decode_content = decode;
+        self
+    }
+
+    /// Report download progress to `listener` as the body is read.
+    ///
+    /// The total is the response's `Content-Length`, if present.
+    pub fn progress_listener(mut self, listener: Arc<dyn ProgressListener>) -> Self {
+        self.progress_listener = Some(listener);
+        self
+    }
+
     /// Build the request.
     pub fn build(self) -> Result<GetObjectRequest> {
+        #[cfg(feature = "decompression")]
+        if self.decode_content && self.range.is_some() {
+            return Err(OssError::InvalidParameter {
+                field: "decode_content".into(),
+                reason: "a byte range of an encoded object cannot be decoded".into(),
+            });
+        }
         Ok(GetObjectRequest {
             bucket: self
                 .bucket
@@ -154,6 +359,14 @@ impl GetObjectRequestBuilder {
                 .key
                 .ok_or_else(|| OssError::MissingField("key".into()))?,
             range: self.range,
+            if_match: self.if_match,
+            if_none_match: self.if_none_match,
+            if_modified_since: self.if_modified_since,
+            if_unmodified_since: self.if_unmodified_since,
+            max_resumes: self.max_resumes,
+            #[cfg(feature = "decompression")]
+            decode_content: self.decode_content,
+            progress_listener: self.progress_listener,
         })
     }
 }
@@ -163,6 +376,7 @@ impl GetObjectRequestBuilder {
 pub struct DeleteObjectRequest {
     pub(crate) bucket: BucketName,
     pub(crate) key: ObjectKey,
+    pub(crate) version_id: Option<String>,
 }
 
 /// Builder for [`DeleteObjectRequest`].
@@ -170,6 +384,7 @@ pub struct DeleteObjectRequest {
 pub struct DeleteObjectRequestBuilder {
     bucket: Option<BucketName>,
     key: Option<ObjectKey>,
+    version_id: Option<String>,
 }
 
 impl DeleteObjectRequestBuilder {
@@ -190,6 +405,13 @@ impl DeleteObjectRequestBuilder {
         self
     }
 
+    /// Permanently delete this version instead of adding a delete marker
+    /// (versioned buckets only).
+    pub fn version_id(mut self, version_id: impl Into<String>) -> Self {
+        self.version_id = Some(version_id.into());
+        self
+    }
+
     /// Build the request.
     pub fn build(self) -> Result<DeleteObjectRequest> {
         Ok(DeleteObjectRequest {
@@ -199,6 +421,7 @@ impl DeleteObjectRequestBuilder {
             key: self
                 .key
                 .ok_or_else(|| OssError::MissingField("key".into()))?,
+            version_id: self.version_id,
         })
     }
 }
@@ -335,6 +558,94 @@ impl ListObjectsV2RequestBuilder {
     }
 }
 
+/// Request to list object versions in a versioned bucket.
+#[derive(Debug)]
+pub struct ListObjectVersionsRequest {
+    pub(crate) bucket: BucketName,
+    pub(crate) prefix: Option<String>,
+    pub(crate) delimiter: Option<String>,
+    pub(crate) max_keys: Option<u32>,
+    pub(crate) key_marker: Option<String>,
+    pub(crate) version_id_marker: Option<String>,
+}
+
+/// Builder for [`ListObjectVersionsRequest`].
+#[derive(Debug, Default)]
+pub struct ListObjectVersionsRequestBuilder {
+    bucket: Option<BucketName>,
+    prefix: Option<String>,
+    delimiter: Option<String>,
+    max_keys: Option<u32>,
+    key_marker: Option<String>,
+    version_id_marker: Option<String>,
+}
+
+impl ListObjectVersionsRequestBuilder {
+    /// Create a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the target bucket.
+    pub fn bucket(mut self, bucket: BucketName) -> Self {
+        self.bucket = Some(bucket);
+        self
+    }
+
+    /// Filter results to keys beginning with this prefix.
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Group keys that share a common prefix ending with this delimiter.
+    pub fn delimiter(mut self, delimiter: impl Into<String>) -> Self {
+        self.delimiter = Some(delimiter.into());
+        self
+    }
+
+    /// Set the maximum number of versions and delete markers to return (1-1000).
+    pub fn max_keys(mut self, max_keys: u32) -> Self {
+        self.max_keys = Some(max_keys);
+        self
+    }
+
+    /// List versions of keys after this one (from `next_key_marker`).
+    pub fn key_marker(mut self, key_marker: impl Into<String>) -> Self {
+        self.key_marker = Some(key_marker.into());
+        self
+    }
+
+    /// Together with [`key_marker`](Self::key_marker), list versions after
+    /// this one (from `next_version_id_marker`).
+    pub fn version_id_marker(mut self, version_id_marker: impl Into<String>) -> Self {
+        self.version_id_marker = Some(version_id_marker.into());
+        self
+    }
+
+    /// Build the request.
+    pub fn build(self) -> Result<ListObjectVersionsRequest> {
+        if let Some(max_keys) = self.max_keys
+            && !(1..=1000).contains(&max_keys)
+        {
+            return Err(OssError::InvalidParameter {
+                field: "max_keys".into(),
+                reason: "must be between 1 and 1000".into(),
+            });
+        }
+        Ok(ListObjectVersionsRequest {
+            bucket: self
+                .bucket
+                .ok_or_else(|| OssError::MissingField("bucket".into()))?,
+            prefix: self.prefix,
+            delimiter: self.delimiter,
+            max_keys: self.max_keys,
+            key_marker: self.key_marker,
+            version_id_marker: self.version_id_marker,
+        })
+    }
+}
+
 /// Request to copy an object within OSS.
 #[derive(Debug)]
 pub struct CopyObjectRequest {
@@ -454,7 +765,7 @@ impl CopyObjectRequestBuilder {
 #[derive(Debug)]
 pub struct DeleteMultipleObjectsRequest {
     pub(crate) bucket: BucketName,
-    pub(crate) keys: Vec<ObjectKey>,
+    pub(crate) keys: Vec<(ObjectKey, Option<String>)>,
     pub(crate) quiet: bool,
 }
 
@@ -462,7 +773,7 @@ pub struct DeleteMultipleObjectsRequest {
 #[derive(Debug, Default)]
 pub struct DeleteMultipleObjectsRequestBuilder {
     bucket: Option<BucketName>,
-    keys: Vec<ObjectKey>,
+    keys: Vec<(ObjectKey, Option<String>)>,
     quiet: bool,
 }
 
@@ -483,13 +794,20 @@ impl DeleteMultipleObjectsRequestBuilder {
 
     /// Add an object key to delete.
     pub fn key(mut self, key: ObjectKey) -> Self {
-        self.keys.push(key);
+        self.keys.push((key, None));
+        self
+    }
+
+    /// Add a specific version of an object to delete permanently, or a
+    /// delete marker to remove.
+    pub fn version(mut self, key: ObjectKey, version_id: impl Into<String>) -> Self {
+        self.keys.push((key, Some(version_id.into())));
         self
     }
 
     /// Set all object keys to delete.
     pub fn keys(mut self, keys: Vec<ObjectKey>) -> Self {
-        self.keys = keys;
+        self.keys = keys.into_iter().map(|key| (key, None)).collect();
         self
     }
 
@@ -594,9 +912,18 @@ pub struct AppendObjectRequest {
     pub(crate) position: u64,
     pub(crate) body: reqwest::Body,
     pub(crate) content_type: Option<String>,
+    pub(crate) cache_control: Option<String>,
+    pub(crate) storage_class: Option<StorageClass>,
+    pub(crate) acl: Option<ObjectAcl>,
+    pub(crate) server_side_encryption: Option<ServerSideEncryption>,
+    pub(crate) metadata: HashMap<String, String>,
 }
 
 /// Builder for [`AppendObjectRequest`].
+///
+/// Cache-Control, storage class, ACL, encryption and metadata only take
+/// effect on the append that creates the object (position 0); OSS ignores
+/// them on later appends.
 #[derive(Debug, Default)]
 pub struct AppendObjectRequestBuilder {
     bucket: Option<BucketName>,
@@ -604,6 +931,11 @@ pub struct AppendObjectRequestBuilder {
     position: Option<u64>,
     body: Option<reqwest::Body>,
     content_type: Option<String>,
+    cache_control: Option<String>,
+    storage_class: Option<StorageClass>,
+    acl: Option<ObjectAcl>,
+    server_side_encryption: Option<ServerSideEncryption>,
+    metadata: HashMap<String, String>,
 }
 
 impl AppendObjectRequestBuilder {
@@ -642,8 +974,41 @@ impl AppendObjectRequestBuilder {
         self
     }
 
+    /// Set the `Cache-Control` header stored with the object.
+    pub fn cache_control(mut self, cache_control: impl Into<String>) -> Self {
+        self.cache_control = Some(cache_control.into());
+        self
+    }
+
+    /// Set the storage class.
+    pub fn storage_class(mut self, storage_class: StorageClass) -> Self {
+        self.storage_class = Some(storage_class);
+        self
+    }
+
+    /// Set the object ACL.
+    pub fn acl(mut self, acl: ObjectAcl) -> Self {
+        self.acl = Some(acl);
+        self
+    }
+
+    /// Encrypt the object at rest (`x-oss-server-side-encryption`).
+    pub fn server_side_encryption(mut self, encryption: ServerSideEncryption) -> Self {
+        self.server_side_encryption = Some(encryption);
+        self
+    }
+
+    /// Add a custom metadata entry (x-oss-meta-*).
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
     /// Build the request.
     pub fn build(self) -> Result<AppendObjectRequest> {
+        for key in self.metadata.keys() {
+            validate_metadata_key(key)?;
+        }
         Ok(AppendObjectRequest {
             bucket: self
                 .bucket
@@ -658,6 +1023,11 @@ impl AppendObjectRequestBuilder {
                 .body
                 .ok_or_else(|| OssError::MissingField("body".into()))?,
             content_type: self.content_type,
+            cache_control: self.cache_control,
+            storage_class: self.storage_class,
+            acl: self.acl,
+            server_side_encryption: self.server_side_encryption,
+            metadata: self.metadata,
         })
     }
 }
@@ -855,12 +1225,7 @@ impl PutObjectTaggingRequestBuilder {
                 "tags (at least one tag required)".into(),
             ));
         }
-        if self.tags.len() > 10 {
-            return Err(OssError::InvalidParameter {
-                field: "tags".into(),
-                reason: "cannot set more than 10 tags per object".into(),
-            });
-        }
+        validate_tags(&self.tags)?;
         Ok(PutObjectTaggingRequest {
             bucket: self
                 .bucket
@@ -936,12 +1301,29 @@ pub(crate) struct DeleteObjectXmlEntry {
     /// The object key.
     #[serde(rename = "Key")]
     pub key: String,
+    /// The version to delete, if any.
+    #[serde(rename = "VersionId", skip_serializing_if = "Option::is_none")]
+    pub version_id: Option<String>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[cfg(feature = "decompression")]
+    #[test]
+    fn get_object_request_rejects_decoding_a_range() {
+        let req = GetObjectRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .key(ObjectKey::new("app.log.gz").unwrap())
+            .range("bytes=0-99")
+            .decode_content(true)
+            .build();
+        assert!(
+            matches!(req, Err(OssError::InvalidParameter { field, .. }) if field == "decode_content")
+        );
+    }
+
     #[test]
     fn put_object_request_builder() {
         let req = PutObjectRequestBuilder::new()
@@ -1094,16 +1476,18 @@ mod tests {
             objects: vec![
                 DeleteObjectXmlEntry {
                     key: "key1".to_string(),
+                    version_id: None,
                 },
                 DeleteObjectXmlEntry {
                     key: "key2".to_string(),
+                    version_id: Some("v2".to_string()),
                 },
             ],
         };
         let xml = quick_xml::se::to_string(&xml_body).unwrap();
         assert!(xml.contains("<Quiet>true</Quiet>"));
-        assert!(xml.contains("<Key>key1</Key>"));
-        assert!(xml.contains("<Key>key2</Key>"));
+        assert!(xml.contains("<Object><Key>key1</Key></Object>"));
+        assert!(xml.contains("<Key>key2</Key><VersionId>v2</VersionId>"));
     }
 
     #[test]
@@ -1171,6 +1555,18 @@ mod tests {
         assert!(req.is_ok());
     }
 
+    #[test]
+    fn append_object_invalid_metadata_key_fails() {
+        let req = AppendObjectRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .key(ObjectKey::new("append.log").unwrap())
+            .position(0)
+            .body(b"log line".to_vec())
+            .metadata("bad key", "value")
+            .build();
+        assert!(req.is_err());
+    }
+
     #[test]
     fn append_object_missing_body() {
         let req = AppendObjectRequestBuilder::new()
@@ -1267,4 +1663,56 @@ mod tests {
             .build();
         assert!(req.is_ok());
     }
+
+    fn tagging_builder() -> PutObjectTaggingRequestBuilder {
+        PutObjectTaggingRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .key(ObjectKey::new("file.txt").unwrap())
+    }
+
+    #[test]
+    fn tagging_accepts_valid_tags() {
+        let req = tagging_builder()
+            .tag("project", "alpha team")
+            .tag("path", "a/b:c=d+e@f.g_h-i")
+            .tag("empty", "")
+            .tag("项目", "测试")
+            .build();
+        assert!(req.is_ok());
+    }
+
+    #[test]
+    fn tagging_rejects_invalid_tags() {
+        let long_key = "k".repeat(129);
+        let long_value = "v".repeat(257);
+        let cases = [
+            tagging_builder().tag("", "v"),
+            tagging_builder().tag(long_key.as_str(), "v"),
+            tagging_builder().tag("k", long_value.as_str()),
+            tagging_builder().tag("k", "semi;colon"),
+            tagging_builder().tag("a&b", "v"),
+            tagging_builder().tag("k", "1").tag("k", "2"),
+        ];
+        for builder in cases {
+            assert!(matches!(
+                builder.build(),
+                Err(OssError::InvalidParameter { .. })
+            ));
+        }
+        assert!(tagging_builder().tag("k", "v".repeat(256)).build().is_ok());
+        assert!(tagging_builder().tag("k".repeat(128), "v").build().is_ok());
+    }
+
+    #[test]
+    fn put_object_validates_tags() {
+        let builder = || {
+            PutObjectRequestBuilder::new()
+                .bucket(BucketName::new("test-bucket").unwrap())
+                .key(ObjectKey::new("file.txt").unwrap())
+                .body(b"x".to_vec())
+        };
+        let req = builder().tag("team", "storage").build().unwrap();
+        assert_eq!(req.tags, [("team".to_string(), "storage".to_string())]);
+        assert!(builder().tag("k", "1").tag("k", "2").build().is_err());
+    }
 }
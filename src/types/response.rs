@@ -2,47 +2,242 @@
 
 use std::collections::HashMap;
 use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
 
 use chrono::{DateTime, Utc};
+use futures_util::Stream;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncWrite, AsyncWriteExt, ReadBuf};
 
-use super::common::{ServerSideEncryption, StorageClass};
+use super::common::{ObjectType, RedundancyTransitionStatus, ServerSideEncryption, StorageClass};
+use super::policy::PolicyDocument;
+use crate::progress::ProgressTracker;
 
 /// Response from a PutObject operation.
 #[derive(Debug)]
 pub struct PutObjectResponse {
     /// ETag of the uploaded object.
     pub etag: String,
+    /// CRC64 of the object as computed by OSS (`x-oss-hash-crc64ecma`).
+    pub crc64: Option<u64>,
+    /// Version ID assigned to the new object, in a versioned bucket.
+    pub version_id: Option<String>,
+    /// Server-side encryption algorithm, if the object is encrypted.
+    pub server_side_encryption: Option<ServerSideEncryption>,
+    /// KMS key ID, for objects encrypted with KMS.
+    pub kms_key_id: Option<String>,
+    /// When a lifecycle rule will delete the object.
+    pub expiration: Option<ObjectExpiration>,
     /// OSS request ID.
     pub request_id: Option<String>,
 }
 
+/// Rewrites body chunks as they are read, e.g. to decrypt them.
+pub(crate) type BodyTransform = Box<dyn FnMut(bytes::Bytes) -> bytes::Bytes + Send + Sync>;
+
+/// The raw chunks of a response body.
+pub(crate) type ChunkStream =
+    Pin<Box<dyn Stream<Item = std::result::Result<bytes::Bytes, reqwest::Error>> + Send + Sync>>;
+
 /// A wrapper around the response body that hides the underlying HTTP library.
 ///
 /// Provides methods to consume the body as bytes, text, or a streaming byte stream.
-pub struct ObjectBody(reqwest::Response);
+/// It also implements [`AsyncRead`] and [`AsyncBufRead`], so it can be handed
+/// to decoders that take a reader.
+/// When the request carried a progress listener, every chunk read through
+/// any of them is reported to it.
+pub struct ObjectBody {
+    stream: ChunkStream,
+    /// Part of a chunk that was buffered by `poll_fill_buf` but not consumed.
+    chunk: bytes::Bytes,
+    range_start: u64,
+    progress: Option<ProgressTracker>,
+    transform: Option<BodyTransform>,
+}
 
 impl ObjectBody {
     /// Create a new `ObjectBody` from a `reqwest::Response`.
     pub(crate) fn new(response: reqwest::Response) -> Self {
-        Self(response)
+        let range_start = response
+            .headers()
+            .get("content-range")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("bytes "))
+            .and_then(|v| v.split_once('-'))
+            .and_then(|(start, _)| start.parse().ok())
+            .unwrap_or(0);
+        Self {
+            stream: Box::pin(response.bytes_stream()),
+            chunk: bytes::Bytes::new(),
+            range_start,
+            progress: None,
+            transform: None,
+        }
+    }
+
+    /// Report chunks read from the body to `tracker`.
+    pub(crate) fn with_progress(mut self, tracker: ProgressTracker) -> Self {
+        self.progress = Some(tracker);
+        self
+    }
+
+    /// Replace the raw chunk stream, e.g. to resume it after the connection
+    /// drops. Progress and the transform apply to the new stream.
+    pub(crate) fn map_stream(mut self, map: impl FnOnce(ChunkStream) -> ChunkStream) -> Self {
+        self.stream = map(self.stream);
+        self
+    }
+
+    /// Pass every chunk through `transform` before handing it to the caller.
+    /// Progress still counts the bytes received.
+    #[cfg_attr(not(feature = "cse"), allow(dead_code))]
+    pub(crate) fn with_transform(mut self, transform: BodyTransform) -> Self {
+        self.transform = Some(transform);
+        self
+    }
+
+    /// Offset of the first body byte within the object, from `Content-Range`.
+    pub(crate) fn range_start(&self) -> u64 {
+        self.range_start
+    }
+
+    /// Read the next chunk, reporting progress and applying the transform.
+    fn poll_chunk(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<std::result::Result<bytes::Bytes, reqwest::Error>>> {
+        if !self.chunk.is_empty() {
+            return Poll::Ready(Some(Ok(std::mem::take(&mut self.chunk))));
+        }
+        let chunk = match ready!(self.stream.as_mut().poll_next(cx)) {
+            Some(Ok(chunk)) => chunk,
+            other => return Poll::Ready(other),
+        };
+        if let Some(progress) = &self.progress {
+            progress.advance(chunk.len() as u64);
+        }
+        Poll::Ready(Some(Ok(match &mut self.transform {
+            Some(transform) => transform(chunk),
+            None => chunk,
+        })))
+    }
+
+    /// Read the next chunk, reporting progress and applying the transform.
+    pub(crate) async fn chunk(
+        &mut self,
+    ) -> std::result::Result<Option<bytes::Bytes>, reqwest::Error> {
+        std::future::poll_fn(|cx| self.poll_chunk(cx))
+            .await
+            .transpose()
     }
 
     /// Consume the body and return all bytes.
-    pub async fn bytes(self) -> std::result::Result<bytes::Bytes, reqwest::Error> {
-        self.0.bytes().await
+    pub async fn bytes(mut self) -> std::result::Result<bytes::Bytes, reqwest::Error> {
+        let mut all = bytes::BytesMut::new();
+        while let Some(chunk) = self.chunk().await? {
+            all.extend_from_slice(&chunk);
+        }
+        Ok(all.freeze())
     }
 
-    /// Consume the body and return it as a UTF-8 string.
+    /// Consume the body and return it as a UTF-8 string (lossy).
     pub async fn text(self) -> std::result::Result<String, reqwest::Error> {
-        self.0.text().await
+        let bytes = self.bytes().await?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Consume the body and deserialize it from JSON.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use rs_ali_oss::*;
+    /// # use rs_ali_oss::types::request::GetObjectRequestBuilder;
+    /// # async fn example(client: OssClient, request: GetObjectRequestBuilder) -> Result<()> {
+    /// #[derive(serde::Deserialize)]
+    /// struct Settings {
+    ///     theme: String,
+    /// }
+    ///
+    /// let response = client.get_object(request.build()?).await?;
+    /// let settings: Settings = response.body.json().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn json<T: DeserializeOwned>(self) -> crate::error::Result<T> {
+        let bytes = self.bytes().await?;
+        serde_json::from_slice(&bytes).map_err(|e| crate::error::OssError::JsonParse(e.to_string()))
+    }
+
+    /// Consume the body, copying it into `writer` chunk by chunk, and return
+    /// the number of bytes written.
+    ///
+    /// Each chunk is written before the next is read, so a slow writer slows
+    /// the download instead of the body piling up in memory. The writer is
+    /// flushed at the end.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use rs_ali_oss::*;
+    /// # use rs_ali_oss::types::request::GetObjectRequestBuilder;
+    /// # async fn example(client: OssClient, request: GetObjectRequestBuilder) -> Result<()> {
+    /// let response = client.get_object(request.build()?).await?;
+    /// let mut stdout = tokio::io::stdout();
+    /// let written = response.body.write_to(&mut stdout).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_to<W>(mut self, mut writer: W) -> crate::error::Result<u64>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let mut written = 0;
+        while let Some(chunk) = self.chunk().await? {
+            writer.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        writer.flush().await?;
+        Ok(written)
     }
 
     /// Return a streaming byte stream for incremental reading.
     pub fn bytes_stream(
-        self,
+        mut self,
     ) -> impl futures_util::Stream<Item = std::result::Result<bytes::Bytes, reqwest::Error>> {
-        self.0.bytes_stream()
+        futures_util::stream::poll_fn(move |cx| self.poll_chunk(cx))
+    }
+}
+
+impl AsyncRead for ObjectBody {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let available = ready!(self.as_mut().poll_fill_buf(cx))?;
+        let n = available.len().min(buf.remaining());
+        buf.put_slice(&available[..n]);
+        self.consume(n);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncBufRead for ObjectBody {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        let this = self.get_mut();
+        while this.chunk.is_empty() {
+            match ready!(this.poll_chunk(cx)) {
+                Some(Ok(chunk)) => this.chunk = chunk,
+                Some(Err(e)) => return Poll::Ready(Err(std::io::Error::other(e))),
+                None => break,
+            }
+        }
+        Poll::Ready(Ok(&this.chunk))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        bytes::Buf::advance(&mut self.get_mut().chunk, amt);
     }
 }
 
@@ -52,6 +247,60 @@ impl fmt::Debug for ObjectBody {
     }
 }
 
+/// Restore state of an archived object, from the `x-oss-restore` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestoreStatus {
+    /// Whether a restore is still in progress.
+    pub ongoing: bool,
+    /// When the restored copy expires, once the restore has finished.
+    pub expiry_date: Option<DateTime<Utc>>,
+}
+
+impl RestoreStatus {
+    /// Returns `true` if the object has been restored and can be read.
+    pub fn is_restored(&self) -> bool {
+        !self.ongoing
+    }
+
+    /// Parse a header value such as
+    /// `ongoing-request="false", expiry-date="Sun, 16 Apr 2017 08:12:33 GMT"`.
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        Some(Self {
+            ongoing: quoted_field(value, "ongoing-request")? == "true",
+            expiry_date: quoted_field(value, "expiry-date")
+                .and_then(crate::client::parse_http_date),
+        })
+    }
+}
+
+/// When a lifecycle rule will delete an object, from the `x-oss-expiration`
+/// header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectExpiration {
+    /// When the object expires.
+    pub expiry_date: DateTime<Utc>,
+    /// ID of the lifecycle rule that expires it.
+    pub rule_id: String,
+}
+
+impl ObjectExpiration {
+    /// Parse a header value such as
+    /// `expiry-date="Sat, 01 Jan 2022 00:00:00 GMT", rule-id="expire-logs"`.
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        Some(Self {
+            expiry_date: crate::client::parse_http_date(quoted_field(value, "expiry-date")?)?,
+            rule_id: quoted_field(value, "rule-id")?.to_string(),
+        })
+    }
+}
+
+/// The value of `name="..."` in a header holding several such fields.
+fn quoted_field<'a>(value: &'a str, name: &str) -> Option<&'a str> {
+    let start = value.find(&format!("{name}=\""))? + name.len() + 2;
+    let len = value[start..].find('"')?;
+    Some(&value[start..start + len])
+}
+
 /// Response from a GetObject operation.
 ///
 /// Use the [`ObjectBody`] methods (`.bytes()`, `.text()`, `.bytes_stream()`)
@@ -65,25 +314,73 @@ pub struct GetObjectResponse {
     pub content_length: Option<u64>,
     /// ETag of the object.
     pub etag: Option<String>,
+    /// Storage class of the object.
+    pub storage_class: Option<StorageClass>,
+    /// How the object was created.
+    pub object_type: Option<ObjectType>,
+    /// Number of tags on the object (`x-oss-tagging-count`).
+    pub tagging_count: Option<u32>,
+    /// Restore state, for archived objects that have had a restore requested.
+    pub restore: Option<RestoreStatus>,
+    /// When a lifecycle rule will delete the object.
+    pub expiration: Option<ObjectExpiration>,
+    /// Custom metadata (x-oss-meta-* headers).
+    pub metadata: HashMap<String, String>,
     /// OSS request ID.
     pub request_id: Option<String>,
 }
 
+impl GetObjectResponse {
+    /// Copy the body into `writer`. See [`ObjectBody::write_to`].
+    pub async fn save_to_writer<W>(self, writer: W) -> crate::error::Result<u64>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        self.body.write_to(writer).await
+    }
+}
+
 impl fmt::Debug for GetObjectResponse {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("GetObjectResponse")
             .field("content_type", &self.content_type)
             .field("content_length", &self.content_length)
             .field("etag", &self.etag)
+            .field("storage_class", &self.storage_class)
+            .field("object_type", &self.object_type)
+            .field("tagging_count", &self.tagging_count)
+            .field("restore", &self.restore)
+            .field("expiration", &self.expiration)
+            .field("metadata", &self.metadata)
             .field("request_id", &self.request_id)
             .field("body", &self.body)
             .finish()
     }
 }
 
-/// Response from a DeleteObject operation.
+/// Outcome of [`OssClient::get_object_conditional`](crate::OssClient::get_object_conditional).
 #[derive(Debug)]
+pub enum GetObjectResult {
+    /// The object was downloaded. Boxed to keep the unchanged case small.
+    Modified(Box<GetObjectResponse>),
+    /// The object is unchanged (`304 Not Modified`).
+    NotModified {
+        /// ETag of the object.
+        etag: Option<String>,
+        /// OSS request ID.
+        request_id: Option<String>,
+    },
+}
+
+/// Response from a DeleteObject operation.
+#[derive(Debug, Default)]
 pub struct DeleteObjectResponse {
+    /// Whether the delete created a delete marker (`x-oss-delete-marker`),
+    /// or removed one when a delete marker's version was deleted.
+    pub delete_marker: bool,
+    /// In a versioned bucket, the ID of the delete marker that was created,
+    /// or of the version that was permanently removed.
+    pub version_id: Option<String>,
     /// OSS request ID.
     pub request_id: Option<String>,
 }
@@ -95,10 +392,34 @@ pub struct HeadObjectResponse {
     pub content_type: Option<String>,
     /// Content length in bytes.
     pub content_length: Option<u64>,
+    /// `Cache-Control` header stored with the object.
+    pub cache_control: Option<String>,
+    /// `Content-Disposition` header stored with the object.
+    pub content_disposition: Option<String>,
+    /// `Content-Encoding` header stored with the object.
+    pub content_encoding: Option<String>,
+    /// `Expires` header stored with the object.
+    pub expires: Option<String>,
     /// ETag of the object.
     pub etag: Option<String>,
     /// Last modified timestamp (parsed from HTTP header).
     pub last_modified: Option<DateTime<Utc>>,
+    /// CRC64 of the object as computed by OSS (`x-oss-hash-crc64ecma`).
+    pub crc64: Option<u64>,
+    /// Storage class of the object.
+    pub storage_class: Option<StorageClass>,
+    /// How the object was created.
+    pub object_type: Option<ObjectType>,
+    /// Version ID, in a versioned bucket.
+    pub version_id: Option<String>,
+    /// Server-side encryption algorithm, if the object is encrypted.
+    pub server_side_encryption: Option<ServerSideEncryption>,
+    /// KMS key ID, for objects encrypted with KMS.
+    pub kms_key_id: Option<String>,
+    /// Restore state, for archived objects that have had a restore requested.
+    pub restore: Option<RestoreStatus>,
+    /// When a lifecycle rule will delete the object.
+    pub expiration: Option<ObjectExpiration>,
     /// Custom metadata (x-oss-meta-* headers).
     pub metadata: HashMap<String, String>,
     /// OSS request ID.
@@ -135,6 +456,85 @@ pub struct ListObjectsV2Response {
     pub common_prefixes: Vec<CommonPrefix>,
 }
 
+/// Response from a ListObjectVersions operation (XML-deserialized).
+///
+/// Versions and delete markers are interleaved in key order by OSS and
+/// split into two lists here.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename = "ListVersionsResult")]
+pub struct ListObjectVersionsResponse {
+    /// Bucket name.
+    #[serde(rename = "Name")]
+    pub name: String,
+    /// The prefix used to filter results.
+    #[serde(rename = "Prefix", default)]
+    pub prefix: String,
+    /// Maximum number of entries returned.
+    #[serde(rename = "MaxKeys")]
+    pub max_keys: u32,
+    /// Whether the results are truncated (more pages available).
+    #[serde(rename = "IsTruncated")]
+    pub is_truncated: bool,
+    /// Key marker for the next page.
+    #[serde(rename = "NextKeyMarker", default)]
+    pub next_key_marker: Option<String>,
+    /// Version ID marker for the next page.
+    #[serde(rename = "NextVersionIdMarker", default)]
+    pub next_version_id_marker: Option<String>,
+    /// Object versions in this page.
+    #[serde(rename = "Version", default)]
+    pub versions: Vec<ObjectVersion>,
+    /// Delete markers in this page.
+    #[serde(rename = "DeleteMarker", default)]
+    pub delete_markers: Vec<DeleteMarkerEntry>,
+    /// Common prefix entries (when delimiter is used).
+    #[serde(rename = "CommonPrefixes", default)]
+    pub common_prefixes: Vec<CommonPrefix>,
+}
+
+/// A single object version in a version listing.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ObjectVersion {
+    /// The object key.
+    #[serde(rename = "Key")]
+    pub key: String,
+    /// The version ID.
+    #[serde(rename = "VersionId")]
+    pub version_id: String,
+    /// Whether this is the current version.
+    #[serde(rename = "IsLatest")]
+    pub is_latest: bool,
+    /// Last modified timestamp.
+    #[serde(rename = "LastModified")]
+    pub last_modified: DateTime<Utc>,
+    /// ETag of the version.
+    #[serde(rename = "ETag")]
+    pub etag: String,
+    /// Size in bytes.
+    #[serde(rename = "Size")]
+    pub size: u64,
+    /// Storage class of the version.
+    #[serde(rename = "StorageClass")]
+    pub storage_class: StorageClass,
+}
+
+/// A delete marker in a version listing.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeleteMarkerEntry {
+    /// The object key.
+    #[serde(rename = "Key")]
+    pub key: String,
+    /// The version ID of the delete marker.
+    #[serde(rename = "VersionId")]
+    pub version_id: String,
+    /// Whether the marker is the current version of the key.
+    #[serde(rename = "IsLatest")]
+    pub is_latest: bool,
+    /// When the marker was created.
+    #[serde(rename = "LastModified")]
+    pub last_modified: DateTime<Utc>,
+}
+
 /// Metadata for a single object in a listing.
 #[derive(Debug, Clone, Deserialize)]
 pub struct ObjectInfo {
@@ -275,7 +675,7 @@ pub struct BucketInfoDetail {
 pub struct AccessControlList {
     /// The grant permission.
     #[serde(rename = "Grant")]
-    pub grant: String,
+    pub grant: crate::types::common::BucketAcl,
 }
 
 /// Response from a CopyObject operation (XML-deserialized).
@@ -288,6 +688,21 @@ pub struct CopyObjectResponse {
     /// ETag of the copied object.
     #[serde(rename = "ETag")]
     pub etag: String,
+    /// CRC64 of the copy as computed by OSS (`x-oss-hash-crc64ecma`).
+    #[serde(skip)]
+    pub crc64: Option<u64>,
+    /// Version ID assigned to the copy, in a versioned destination bucket.
+    #[serde(skip)]
+    pub version_id: Option<String>,
+    /// Version ID of the source object that was copied.
+    #[serde(skip)]
+    pub source_version_id: Option<String>,
+    /// Server-side encryption algorithm, if the copy is encrypted.
+    #[serde(skip)]
+    pub server_side_encryption: Option<ServerSideEncryption>,
+    /// KMS key ID, for copies encrypted with KMS.
+    #[serde(skip)]
+    pub kms_key_id: Option<String>,
 }
 
 /// Response from an InitiateMultipartUpload operation (XML-deserialized).
@@ -310,6 +725,20 @@ pub struct InitiateMultipartUploadResponse {
 pub struct UploadPartResponse {
     /// ETag of the uploaded part.
     pub etag: String,
+    /// CRC64 of the part as computed by OSS (`x-oss-hash-crc64ecma`).
+    pub crc64: Option<u64>,
+}
+
+/// Response from an UploadPartCopy operation (XML-deserialized).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename = "CopyPartResult")]
+pub struct UploadPartCopyResponse {
+    /// Last modified timestamp of the part.
+    #[serde(rename = "LastModified")]
+    pub last_modified: DateTime<Utc>,
+    /// ETag of the copied part.
+    #[serde(rename = "ETag")]
+    pub etag: String,
 }
 
 /// Response from a CompleteMultipartUpload operation (XML-deserialized).
@@ -328,6 +757,25 @@ pub struct CompleteMultipartUploadResponse {
     /// ETag of the completed object.
     #[serde(rename = "ETag")]
     pub etag: String,
+    /// CRC64 of the assembled object as computed by OSS
+    /// (`x-oss-hash-crc64ecma`).
+    #[serde(skip)]
+    pub crc64: Option<u64>,
+    /// Version ID assigned to the completed object, in a versioned bucket.
+    #[serde(skip)]
+    pub version_id: Option<String>,
+    /// Server-side encryption algorithm, if the object is encrypted.
+    #[serde(skip)]
+    pub server_side_encryption: Option<ServerSideEncryption>,
+    /// KMS key ID, for objects encrypted with KMS.
+    #[serde(skip)]
+    pub kms_key_id: Option<String>,
+    /// The callback server's response, when the request carried a
+    /// [`Callback`](super::common::Callback). OSS returns it in place of the
+    /// XML result, so `location` is then the object URL and `etag` comes
+    /// from the `ETag` header.
+    #[serde(skip)]
+    pub callback_result: Option<String>,
 }
 
 /// Response from an AbortMultipartUpload operation.
@@ -396,6 +844,15 @@ pub struct DeletedObject {
     /// The key of the deleted object.
     #[serde(rename = "Key")]
     pub key: String,
+    /// The version that was deleted, when one was specified.
+    #[serde(rename = "VersionId", default)]
+    pub version_id: Option<String>,
+    /// Whether the deletion created or removed a delete marker.
+    #[serde(rename = "DeleteMarker", default)]
+    pub delete_marker: bool,
+    /// Version ID of the delete marker created or removed.
+    #[serde(rename = "DeleteMarkerVersionId", default)]
+    pub delete_marker_version_id: Option<String>,
 }
 
 /// Response from a ListMultipartUploads operation (XML-deserialized).
@@ -408,6 +865,15 @@ pub struct ListMultipartUploadsResponse {
     /// The prefix used to filter results.
     #[serde(rename = "Prefix", default)]
     pub prefix: String,
+    /// The delimiter used to group keys into common prefixes.
+    #[serde(rename = "Delimiter", default)]
+    pub delimiter: String,
+    /// The key marker this page started after.
+    #[serde(rename = "KeyMarker", default)]
+    pub key_marker: String,
+    /// The upload ID marker this page started after.
+    #[serde(rename = "UploadIdMarker", default)]
+    pub upload_id_marker: String,
     /// Maximum number of uploads returned.
     #[serde(rename = "MaxUploads")]
     pub max_uploads: u32,
@@ -423,6 +889,10 @@ pub struct ListMultipartUploadsResponse {
     /// In-progress multipart uploads.
     #[serde(rename = "Upload", default)]
     pub uploads: Vec<MultipartUploadInfo>,
+    /// Common prefix entries (when delimiter is used); uploads under these
+    /// prefixes are not listed individually.
+    #[serde(rename = "CommonPrefixes", default)]
+    pub common_prefixes: Vec<CommonPrefix>,
 }
 
 /// Metadata for a single in-progress multipart upload.
@@ -471,8 +941,9 @@ pub struct RestoreObjectResponse {
 pub struct AppendObjectResponse {
     /// The position for the next append operation.
     pub next_append_position: u64,
-    /// CRC64 checksum of the object.
-    pub crc64: Option<String>,
+    /// CRC64 of the whole object after the append, as computed by OSS
+    /// (`x-oss-hash-crc64ecma`).
+    pub crc64: Option<u64>,
     /// OSS request ID.
     pub request_id: Option<String>,
 }
@@ -489,9 +960,9 @@ pub struct GetObjectAclResponse {
 /// Access control list from GetObjectAcl.
 #[derive(Debug, Clone, Deserialize)]
 pub struct ObjectAccessControlList {
-    /// The grant permission (e.g., "private", "public-read").
+    /// The grant permission.
     #[serde(rename = "Grant")]
-    pub grant: String,
+    pub grant: crate::types::common::ObjectAcl,
 }
 
 /// Response from a PutObjectAcl operation.
@@ -657,6 +1128,13 @@ pub struct GetBucketPolicyResponse {
     pub request_id: Option<String>,
 }
 
+impl GetBucketPolicyResponse {
+    /// Parse the policy into a typed [`PolicyDocument`].
+    pub fn parsed(&self) -> crate::error::Result<PolicyDocument> {
+        PolicyDocument::from_json(&self.policy)
+    }
+}
+
 /// Response from a DeleteBucketPolicy operation.
 #[derive(Debug)]
 pub struct DeleteBucketPolicyResponse {
@@ -838,6 +1316,101 @@ pub struct DeleteBucketLoggingResponse {
     pub request_id: Option<String>,
 }
 
+/// Response from a PutBucketTransferAcceleration operation.
+#[derive(Debug)]
+pub struct PutBucketTransferAccelerationResponse {
+    /// OSS request ID.
+    pub request_id: Option<String>,
+}
+
+/// Response from a GetBucketTransferAcceleration operation (XML-deserialized).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename = "TransferAccelerationConfiguration")]
+pub struct GetBucketTransferAccelerationResponse {
+    /// Whether transfer acceleration is enabled.
+    #[serde(rename = "Enabled", default)]
+    pub enabled: bool,
+    /// OSS request ID.
+    #[serde(skip)]
+    pub request_id: Option<String>,
+}
+
+/// Response from a CreateBucketDataRedundancyTransition operation (XML-deserialized).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename = "BucketDataRedundancyTransition")]
+pub struct CreateBucketDataRedundancyTransitionResponse {
+    /// ID of the created transition task.
+    #[serde(rename = "TaskId")]
+    pub task_id: String,
+    /// OSS request ID.
+    #[serde(skip)]
+    pub request_id: Option<String>,
+}
+
+/// A bucket data redundancy transition task.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DataRedundancyTransition {
+    /// Bucket being converted.
+    #[serde(rename = "Bucket", default)]
+    pub bucket: String,
+    /// Task ID.
+    #[serde(rename = "TaskId")]
+    pub task_id: String,
+    /// Current state of the task.
+    #[serde(rename = "Status")]
+    pub status: RedundancyTransitionStatus,
+    /// When the task was created.
+    #[serde(rename = "CreateTime", default)]
+    pub create_time: Option<String>,
+    /// When conversion started; absent while queueing.
+    #[serde(rename = "StartTime", default)]
+    pub start_time: Option<String>,
+    /// When conversion finished; only present once finished.
+    #[serde(rename = "EndTime", default)]
+    pub end_time: Option<String>,
+    /// Progress in percent; absent while queueing.
+    #[serde(rename = "ProcessPercentage", default)]
+    pub process_percentage: Option<u32>,
+    /// Estimated remaining time in hours; absent while queueing.
+    #[serde(rename = "EstimatedRemainingTime", default)]
+    pub estimated_remaining_time: Option<u64>,
+}
+
+/// Response from a GetBucketDataRedundancyTransition operation.
+#[derive(Debug, Clone)]
+pub struct GetBucketDataRedundancyTransitionResponse {
+    /// The transition task.
+    pub transition: DataRedundancyTransition,
+    /// OSS request ID.
+    pub request_id: Option<String>,
+}
+
+/// Response from a ListBucketDataRedundancyTransition operation (XML-deserialized).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename = "ListBucketDataRedundancyTransition")]
+pub struct ListBucketDataRedundancyTransitionResponse {
+    /// Transition tasks of the bucket.
+    #[serde(rename = "BucketDataRedundancyTransition", default)]
+    pub transitions: Vec<DataRedundancyTransition>,
+    /// OSS request ID.
+    #[serde(skip)]
+    pub request_id: Option<String>,
+}
+
+/// Response from a DeleteBucketDataRedundancyTransition operation.
+#[derive(Debug)]
+pub struct DeleteBucketDataRedundancyTransitionResponse {
+    /// OSS request ID.
+    pub request_id: Option<String>,
+}
+
+/// Response from a PutCname operation.
+#[derive(Debug)]
+pub struct PutCnameResponse {
+    /// OSS request ID.
+    pub request_id: Option<String>,
+}
+
 /// Response from a GetObjectTagging operation (XML-deserialized).
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename = "Tagging")]
@@ -1041,7 +1614,7 @@ mod tests {
         assert_eq!(resp.bucket.location, "oss-cn-hangzhou");
         assert_eq!(resp.bucket.storage_class, StorageClass::Standard);
         let acl = resp.bucket.access_control_list.unwrap();
-        assert_eq!(acl.grant, "private");
+        assert_eq!(acl.grant, crate::types::common::BucketAcl::Private);
     }
 
     #[test]
@@ -1138,6 +1711,86 @@ mod tests {
         assert_eq!(resp.deleted[1].key, "file2.txt");
     }
 
+    #[test]
+    fn parse_restore_status() {
+        let ongoing = RestoreStatus::parse(r#"ongoing-request="true""#).unwrap();
+        assert!(ongoing.ongoing);
+        assert!(!ongoing.is_restored());
+        assert_eq!(ongoing.expiry_date, None);
+
+        let done = RestoreStatus::parse(
+            r#"ongoing-request="false", expiry-date="Sun, 16 Apr 2017 08:12:33 GMT""#,
+        )
+        .unwrap();
+        assert!(done.is_restored());
+        assert!(done.expiry_date.is_some());
+
+        assert_eq!(RestoreStatus::parse("garbage"), None);
+    }
+
+    #[test]
+    fn parse_object_expiration() {
+        let expiration = ObjectExpiration::parse(
+            r#"expiry-date="Sat, 01 Jan 2022 00:00:00 GMT", rule-id="expire-logs""#,
+        )
+        .unwrap();
+        assert_eq!(expiration.rule_id, "expire-logs");
+        assert_eq!(
+            expiration.expiry_date.to_rfc3339(),
+            "2022-01-01T00:00:00+00:00"
+        );
+        assert_eq!(ObjectExpiration::parse(r#"rule-id="r""#), None);
+    }
+
+    #[test]
+    fn deserialize_list_object_versions_with_interleaved_markers() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListVersionsResult>
+    <Name>bucket</Name>
+    <Prefix>logs/</Prefix>
+    <KeyMarker></KeyMarker>
+    <VersionIdMarker></VersionIdMarker>
+    <MaxKeys>3</MaxKeys>
+    <IsTruncated>true</IsTruncated>
+    <NextKeyMarker>logs/b</NextKeyMarker>
+    <NextVersionIdMarker>v-b1</NextVersionIdMarker>
+    <DeleteMarker>
+        <Key>logs/a</Key>
+        <VersionId>v-a2</VersionId>
+        <IsLatest>true</IsLatest>
+        <LastModified>2025-01-02T00:00:00.000Z</LastModified>
+    </DeleteMarker>
+    <Version>
+        <Key>logs/a</Key>
+        <VersionId>v-a1</VersionId>
+        <IsLatest>false</IsLatest>
+        <LastModified>2025-01-01T00:00:00.000Z</LastModified>
+        <ETag>"etag-a"</ETag>
+        <Size>10</Size>
+        <StorageClass>Standard</StorageClass>
+    </Version>
+    <DeleteMarker>
+        <Key>logs/b</Key>
+        <VersionId>v-b1</VersionId>
+        <IsLatest>true</IsLatest>
+        <LastModified>2025-01-03T00:00:00.000Z</LastModified>
+    </DeleteMarker>
+</ListVersionsResult>"#;
+        let resp: ListObjectVersionsResponse = quick_xml::de::from_str(xml).unwrap();
+        assert!(resp.is_truncated);
+        assert_eq!(resp.next_key_marker.as_deref(), Some("logs/b"));
+        assert_eq!(resp.next_version_id_marker.as_deref(), Some("v-b1"));
+        assert_eq!(resp.versions.len(), 1);
+        assert_eq!(resp.versions[0].version_id, "v-a1");
+        assert!(!resp.versions[0].is_latest);
+        let markers: Vec<&str> = resp
+            .delete_markers
+            .iter()
+            .map(|m| m.version_id.as_str())
+            .collect();
+        assert_eq!(markers, ["v-a2", "v-b1"]);
+    }
+
     #[test]
     fn deserialize_list_multipart_uploads_response() {
         let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
@@ -1172,6 +1825,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deserialize_list_multipart_uploads_with_common_prefixes() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListMultipartUploadsResult>
+    <Bucket>test-bucket</Bucket>
+    <KeyMarker>backups/</KeyMarker>
+    <UploadIdMarker>upload-id-000</UploadIdMarker>
+    <NextKeyMarker>videos/</NextKeyMarker>
+    <NextUploadIdMarker></NextUploadIdMarker>
+    <Delimiter>/</Delimiter>
+    <Prefix></Prefix>
+    <MaxUploads>3</MaxUploads>
+    <IsTruncated>true</IsTruncated>
+    <CommonPrefixes>
+        <Prefix>photos/</Prefix>
+    </CommonPrefixes>
+    <Upload>
+        <Key>root.bin</Key>
+        <UploadId>upload-id-001</UploadId>
+        <Initiated>2024-03-15T10:30:00.000Z</Initiated>
+        <StorageClass>Standard</StorageClass>
+    </Upload>
+    <CommonPrefixes>
+        <Prefix>videos/</Prefix>
+    </CommonPrefixes>
+</ListMultipartUploadsResult>"#;
+        let resp: ListMultipartUploadsResponse = quick_xml::de::from_str(xml).unwrap();
+        assert_eq!(resp.delimiter, "/");
+        assert_eq!(resp.key_marker, "backups/");
+        assert_eq!(resp.upload_id_marker, "upload-id-000");
+        assert_eq!(resp.next_key_marker.as_deref(), Some("videos/"));
+        assert_eq!(resp.uploads.len(), 1);
+        let prefixes: Vec<&str> = resp
+            .common_prefixes
+            .iter()
+            .map(|p| p.prefix.as_str())
+            .collect();
+        assert_eq!(prefixes, ["photos/", "videos/"]);
+    }
+
     #[test]
     fn deserialize_list_multipart_uploads_empty() {
         let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
@@ -1183,6 +1876,8 @@ mod tests {
 </ListMultipartUploadsResult>"#;
         let resp: ListMultipartUploadsResponse = quick_xml::de::from_str(xml).unwrap();
         assert!(resp.uploads.is_empty());
+        assert!(resp.common_prefixes.is_empty());
+        assert_eq!(resp.delimiter, "");
     }
 
     #[test]
@@ -1194,7 +1889,11 @@ mod tests {
     </AccessControlList>
 </AccessControlPolicy>"#;
         let resp: GetObjectAclResponse = quick_xml::de::from_str(xml).unwrap();
-        assert_eq!(resp.access_control_list.grant, "public-read");
+        assert_eq!(
+            resp.access_control_list.grant,
+            crate::types::common::ObjectAcl::PublicRead
+        );
+        assert!(resp.access_control_list.grant.is_public());
     }
 
     #[test]
@@ -1237,6 +1936,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deserialize_get_bucket_acl_unknown_grant() {
+        let xml = r#"<AccessControlPolicy>
+    <Owner><ID>1</ID><DisplayName>1</DisplayName></Owner>
+    <AccessControlList><Grant>authenticated-read</Grant></AccessControlList>
+</AccessControlPolicy>"#;
+        let resp: GetBucketAclResponse = quick_xml::de::from_str(xml).unwrap();
+        assert_eq!(
+            resp.access_control_list.grant,
+            crate::types::common::BucketAcl::Unknown("authenticated-read".into())
+        );
+    }
+
     #[test]
     fn deserialize_get_object_tagging_response() {
         let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
@@ -1591,6 +2303,16 @@ mod tests {
         assert_eq!(logging.target_prefix, "logs/");
     }
 
+    #[test]
+    fn deserialize_get_bucket_transfer_acceleration_response() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<TransferAccelerationConfiguration>
+    <Enabled>true</Enabled>
+</TransferAccelerationConfiguration>"#;
+        let resp: GetBucketTransferAccelerationResponse = quick_xml::de::from_str(xml).unwrap();
+        assert!(resp.enabled);
+    }
+
     #[test]
     fn deserialize_get_bucket_logging_response_no_prefix() {
         let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
@@ -1613,4 +2335,15 @@ mod tests {
         let resp: GetBucketLoggingResponse = quick_xml::de::from_str(xml).unwrap();
         assert!(resp.logging_enabled.is_none());
     }
+
+    #[test]
+    fn get_bucket_policy_response_parsed() {
+        let response = GetBucketPolicyResponse {
+            policy: r#"{"Version":"1","Statement":[{"Effect":"Allow","Action":"oss:*","Principal":"*","Resource":"acs:oss:*:*:b/*"}]}"#.to_string(),
+            request_id: None,
+        };
+        let document = response.parsed().unwrap();
+        assert_eq!(document.version, "1");
+        assert_eq!(document.statements[0].actions, ["oss:*"]);
+    }
 }
@@ -0,0 +1,108 @@
+//! Integration tests for the blocking client using wiremock.
+#![cfg(feature = "blocking")]
+
+use std::io::Read;
+
+use rs_ali_oss::blocking::OssBlockingClient;
+use rs_ali_oss::config::ClientBuilder;
+use rs_ali_oss::error::OssError;
+use rs_ali_oss::types::common::{BucketName, ObjectKey};
+use rs_ali_oss::types::request::{GetObjectRequestBuilder, PutObjectRequestBuilder};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Start a mock server on a separate runtime; the blocking client must not
+/// run inside one.
+fn start_server(mocks: Vec<Mock>) -> (tokio::runtime::Runtime, MockServer) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let server = runtime.block_on(async {
+        let server = MockServer::start().await;
+        for mock in mocks {
+            mock.mount(&server).await;
+        }
+        server
+    });
+    (runtime, server)
+}
+
+fn mock_client(server: &MockServer) -> OssBlockingClient {
+    OssBlockingClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .max_retries(0),
+    )
+    .unwrap()
+}
+
+#[test]
+fn blocking_put_and_get_object() {
+    let (_runtime, server) = start_server(vec![
+        Mock::given(method("PUT"))
+            .and(path("/hello.txt"))
+            .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"abc\""))
+            .expect(1),
+        Mock::given(method("GET"))
+            .and(path("/hello.txt"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/plain")
+                    .set_body_bytes(b"Hello, OSS!"),
+            )
+            .expect(2),
+    ]);
+    let client = mock_client(&server);
+    let bucket = BucketName::new("my-bucket").unwrap();
+    let key = ObjectKey::new("hello.txt").unwrap();
+
+    let request = PutObjectRequestBuilder::new()
+        .bucket(bucket.clone())
+        .key(key.clone())
+        .body(b"Hello, OSS!".to_vec())
+        .build()
+        .unwrap();
+    assert_eq!(client.put_object(request).unwrap().etag, "abc");
+
+    let get = || {
+        GetObjectRequestBuilder::new()
+            .bucket(bucket.clone())
+            .key(key.clone())
+            .build()
+            .unwrap()
+    };
+    let response = client.get_object(get()).unwrap();
+    assert_eq!(response.content_type.as_deref(), Some("text/plain"));
+    assert_eq!(response.body.text().unwrap(), "Hello, OSS!");
+
+    let mut body = client.get_object(get()).unwrap().body;
+    let mut first = [0u8; 5];
+    body.read_exact(&mut first).unwrap();
+    assert_eq!(&first, b"Hello");
+    let mut rest = String::new();
+    body.read_to_string(&mut rest).unwrap();
+    assert_eq!(rest, ", OSS!");
+}
+
+#[test]
+fn blocking_client_surfaces_server_errors() {
+    let (_runtime, server) = start_server(vec![
+        Mock::given(method("GET"))
+            .and(path("/missing.txt"))
+            .respond_with(ResponseTemplate::new(404).set_body_string(
+                r#"<Error><Code>NoSuchKey</Code><Message>missing</Message>
+                <RequestId>R1</RequestId><HostId>H1</HostId></Error>"#,
+            )),
+    ]);
+    let client = mock_client(&server);
+    let request = GetObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("missing.txt").unwrap())
+        .build()
+        .unwrap();
+
+    let err = client.get_object(request).unwrap_err();
+    assert!(matches!(err, OssError::ServerError { status: 404, .. }));
+}
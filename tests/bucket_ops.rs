@@ -1,13 +1,17 @@
 //! Integration tests for bucket operations using wiremock.
 
 use rs_ali_oss::OssClient;
-use rs_ali_oss::config::ClientBuilder;
-use rs_ali_oss::types::common::BucketName;
+use rs_ali_oss::config::{ClientBuilder, OperationOptions};
+use rs_ali_oss::types::common::{BucketAcl, BucketName};
+use rs_ali_oss::types::common::{DataRedundancyType, RedundancyTransitionStatus};
 use rs_ali_oss::types::request::{
-    CreateBucketRequestBuilder, DeleteBucketRequestBuilder, GetBucketInfoRequestBuilder,
-    ListBucketsRequestBuilder,
+    CnameCertificate, CreateBucketDataRedundancyTransitionRequestBuilder,
+    CreateBucketRequestBuilder, DeleteBucketRequestBuilder,
+    GetBucketDataRedundancyTransitionRequestBuilder, GetBucketInfoRequestBuilder,
+    ListBucketDataRedundancyTransitionRequestBuilder, ListBucketsRequestBuilder,
+    PutCnameRequestBuilder,
 };
-use wiremock::matchers::{method, path, query_param};
+use wiremock::matchers::{body_string_contains, header, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 /// Create an `OssClient` that points at the given mock server.
@@ -237,7 +241,171 @@ async fn get_bucket_info_parses_xml_response() {
         rs_ali_oss::StorageClass::Standard
     );
     let acl = response.bucket.access_control_list.unwrap();
-    assert_eq!(acl.grant, "private");
+    assert_eq!(acl.grant, BucketAcl::Private);
+}
+
+// ---- BucketDataRedundancyTransition ----
+
+#[tokio::test]
+async fn create_bucket_data_redundancy_transition_returns_task_id() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(query_param("redundancyTransition", ""))
+        .and(query_param("x-oss-target-redundancy-type", "ZRS"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("x-oss-request-id", "DRT-001")
+                .set_body_string(
+                    "<BucketDataRedundancyTransition><TaskId>task-1</TaskId></BucketDataRedundancyTransition>",
+                ),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = CreateBucketDataRedundancyTransitionRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .target_redundancy_type(DataRedundancyType::ZRS)
+        .build()
+        .unwrap();
+
+    let response = client
+        .create_bucket_data_redundancy_transition(request)
+        .await
+        .unwrap();
+    assert_eq!(response.task_id, "task-1");
+    assert_eq!(response.request_id.as_deref(), Some("DRT-001"));
+}
+
+#[tokio::test]
+async fn get_bucket_data_redundancy_transition_parses_progress() {
+    let server = MockServer::start().await;
+
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<BucketDataRedundancyTransition>
+    <Bucket>my-bucket</Bucket>
+    <TaskId>task-1</TaskId>
+    <Status>Processing</Status>
+    <CreateTime>2023-11-17T09:14:39.000Z</CreateTime>
+    <StartTime>2023-11-17T09:15:39.000Z</StartTime>
+    <ProcessPercentage>42</ProcessPercentage>
+    <EstimatedRemainingTime>8</EstimatedRemainingTime>
+</BucketDataRedundancyTransition>"#;
+
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .and(query_param("redundancyTransition", ""))
+        .and(query_param("x-oss-redundancy-transition-taskid", "task-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(xml))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = GetBucketDataRedundancyTransitionRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .task_id("task-1")
+        .build()
+        .unwrap();
+
+    let transition = client
+        .get_bucket_data_redundancy_transition(request)
+        .await
+        .unwrap()
+        .transition;
+    assert_eq!(transition.status, RedundancyTransitionStatus::Processing);
+    assert_eq!(transition.process_percentage, Some(42));
+    assert_eq!(transition.estimated_remaining_time, Some(8));
+    assert!(transition.end_time.is_none());
+}
+
+#[tokio::test]
+async fn list_bucket_data_redundancy_transition_parses_tasks() {
+    let server = MockServer::start().await;
+
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketDataRedundancyTransition>
+    <BucketDataRedundancyTransition>
+        <Bucket>my-bucket</Bucket>
+        <TaskId>task-1</TaskId>
+        <Status>Finished</Status>
+        <CreateTime>2023-11-17T09:14:39.000Z</CreateTime>
+        <StartTime>2023-11-17T09:15:39.000Z</StartTime>
+        <ProcessPercentage>100</ProcessPercentage>
+        <EstimatedRemainingTime>0</EstimatedRemainingTime>
+        <EndTime>2023-11-18T09:14:39.000Z</EndTime>
+    </BucketDataRedundancyTransition>
+    <BucketDataRedundancyTransition>
+        <Bucket>my-bucket</Bucket>
+        <TaskId>task-2</TaskId>
+        <Status>Queueing</Status>
+        <CreateTime>2023-11-19T09:14:39.000Z</CreateTime>
+    </BucketDataRedundancyTransition>
+</ListBucketDataRedundancyTransition>"#;
+
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .and(query_param("redundancyTransition", ""))
+        .respond_with(ResponseTemplate::new(200).set_body_string(xml))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = ListBucketDataRedundancyTransitionRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .build()
+        .unwrap();
+
+    let response = client
+        .list_bucket_data_redundancy_transition(request)
+        .await
+        .unwrap();
+    assert_eq!(response.transitions.len(), 2);
+    assert_eq!(
+        response.transitions[0].status,
+        RedundancyTransitionStatus::Finished
+    );
+    assert_eq!(response.transitions[1].task_id, "task-2");
+    assert!(response.transitions[1].process_percentage.is_none());
+}
+
+// ---- PutCname ----
+
+#[tokio::test]
+async fn put_cname_uploads_certificate() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(query_param("cname", ""))
+        .and(query_param("comp", "add"))
+        .and(body_string_contains("<Domain>static.example.com</Domain>"))
+        .and(body_string_contains("<PrivateKey>KEY</PrivateKey>"))
+        .and(body_string_contains("<Force>true</Force>"))
+        .respond_with(ResponseTemplate::new(200).insert_header("x-oss-request-id", "CNAME-001"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = PutCnameRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .domain("static.example.com")
+        .certificate(CnameCertificate {
+            certificate: Some("CERT".into()),
+            private_key: Some("KEY".into()),
+            force: true,
+            ..Default::default()
+        })
+        .build()
+        .unwrap();
+
+    let response = client.put_cname(request).await.unwrap();
+    assert_eq!(response.request_id.as_deref(), Some("CNAME-001"));
 }
 
 // ---- Error handling ----
@@ -309,3 +477,63 @@ async fn delete_bucket_not_empty_returns_error() {
     let err_str = err.to_string();
     assert!(err_str.contains("BucketNotEmpty"), "error: {err_str}");
 }
+
+// ---- Warm-up ----
+
+#[tokio::test]
+async fn warm_up_sends_unsigned_head_and_ignores_status() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("HEAD"))
+        .and(path("/"))
+        .and(header("x-gateway-route", "blue"))
+        .and(header("x-request-tag", "warm-up"))
+        .respond_with(ResponseTemplate::new(403))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .default_header("x-gateway-route", "blue")
+            .max_retries(0),
+    )
+    .unwrap();
+    let options = OperationOptions::builder()
+        .header("x-request-tag", "warm-up")
+        .build()
+        .unwrap();
+    let bucket = BucketName::new("test-bucket").unwrap();
+    client.with_options(options).warm_up(&bucket).await.unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    assert!(!requests[0].headers.contains_key("authorization"));
+}
+
+#[tokio::test]
+async fn warm_up_reports_unreachable_endpoint() {
+    // Nothing listens on a port once its listener is dropped.
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(format!("http://127.0.0.1:{port}"))
+            .allow_insecure(true),
+    )
+    .unwrap();
+
+    let bucket = BucketName::new("test-bucket").unwrap();
+    let err = client.warm_up(&bucket).await.unwrap_err();
+    assert!(matches!(err, rs_ali_oss::OssError::Http(_)), "{err:?}");
+}
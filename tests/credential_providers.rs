@@ -0,0 +1,355 @@
+//! Integration tests for network-backed credential providers using wiremock.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use rs_ali_oss::OssClient;
+use rs_ali_oss::config::{ClientBuilder, Credentials};
+use rs_ali_oss::credential::{
+    AssumeRoleProvider, CredentialProvider, OidcRoleArnProvider, ProfileProvider, StaticProvider,
+};
+use rs_ali_oss::error::{OssError, Result};
+use rs_ali_oss::types::common::{BucketName, ObjectKey};
+use rs_ali_oss::types::request::DeleteObjectRequestBuilder;
+use wiremock::matchers::{body_string_contains, header_regex, method};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const STS_RESPONSE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<AssumeRoleWithOIDCResponse>
+  <RequestId>3D57EAD2-8723-1F26-B69C-F8707D8B565D</RequestId>
+  <Credentials>
+    <AccessKeyId>STS.test-id</AccessKeyId>
+    <AccessKeySecret>test-secret</AccessKeySecret>
+    <SecurityToken>test-token</SecurityToken>
+    <Expiration>2099-01-01T00:00:00Z</Expiration>
+  </Credentials>
+</AssumeRoleWithOIDCResponse>"#;
+
+fn write_token(name: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("rs-ali-oss-{name}-{}", std::process::id()));
+    std::fs::write(&path, "eyJhbGciOiJSUzI1NiJ9.oidc-token\n").unwrap();
+    path
+}
+
+fn provider(server: &MockServer, token_file: std::path::PathBuf) -> OidcRoleArnProvider {
+    OidcRoleArnProvider::builder()
+        .role_arn("acs:ram::123:role/app")
+        .oidc_provider_arn("acs:ram::123:oidc-provider/ack")
+        .oidc_token_file(token_file)
+        .role_session_name("test-session")
+        .sts_endpoint(server.uri())
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn oidc_provider_exchanges_token_and_caches() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("Action=AssumeRoleWithOIDC"))
+        .and(body_string_contains(
+            "OIDCToken=eyJhbGciOiJSUzI1NiJ9.oidc-token",
+        ))
+        .and(body_string_contains("RoleSessionName=test-session"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(STS_RESPONSE))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = provider(&server, write_token("oidc-async"));
+    let creds = provider.resolve_async().await.unwrap();
+    assert_eq!(creds.access_key_id(), "STS.test-id");
+    assert_eq!(creds.access_key_secret(), "test-secret");
+    assert_eq!(creds.security_token(), Some("test-token"));
+    assert!(creds.expiration().is_some());
+
+    // Second resolution is served from the cache.
+    let creds = provider.resolve_async().await.unwrap();
+    assert_eq!(creds.access_key_id(), "STS.test-id");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn oidc_provider_sync_resolve() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(STS_RESPONSE))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = provider(&server, write_token("oidc-sync"));
+    let creds = tokio::task::block_in_place(|| provider.resolve()).unwrap();
+    assert_eq!(creds.access_key_id(), "STS.test-id");
+}
+
+#[tokio::test]
+async fn oidc_provider_surfaces_sts_error() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(400).set_body_string(
+            r#"<Error><RequestId>R1</RequestId><HostId>sts.aliyuncs.com</HostId>
+            <Code>AuthenticationFail.OIDCToken.Invalid</Code><Message>invalid token</Message></Error>"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let provider = provider(&server, write_token("oidc-error"));
+    match provider.resolve_async().await.unwrap_err() {
+        OssError::ServerError { status, code, .. } => {
+            assert_eq!(status, 400);
+            assert_eq!(code, "AuthenticationFail.OIDCToken.Invalid");
+        }
+        other => panic!("expected ServerError, got: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn profile_ram_role_arn_assumes_role_with_signed_request() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("Action=AssumeRole"))
+        .and(body_string_contains("AccessKeyId=base-id"))
+        .and(body_string_contains("SignatureMethod=HMAC-SHA1"))
+        .and(body_string_contains("Signature="))
+        .respond_with(ResponseTemplate::new(200).set_body_string(STS_RESPONSE))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let path = std::env::temp_dir().join(format!("rs-ali-oss-profile-{}", std::process::id()));
+    std::fs::write(
+        &path,
+        "[deploy]\ntype = ram_role_arn\naccess_key_id = base-id\n\
+         access_key_secret = base-secret\nrole_arn = acs:ram::123:role/deployer\n",
+    )
+    .unwrap();
+
+    let provider = ProfileProvider::builder()
+        .path(&path)
+        .profile("deploy")
+        .sts_endpoint(server.uri())
+        .build()
+        .unwrap();
+    let creds = provider.resolve_async().await.unwrap();
+    assert_eq!(creds.access_key_id(), "STS.test-id");
+    assert_eq!(creds.security_token(), Some("test-token"));
+
+    // Served from the cache on the second call.
+    provider.resolve_async().await.unwrap();
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn assume_role_provider_uses_source_credentials() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(body_string_contains("Action=AssumeRole"))
+        .and(body_string_contains("AccessKeyId=source-id"))
+        .and(body_string_contains("SecurityToken=source-token"))
+        .and(body_string_contains("ExternalId=ext-42"))
+        .and(body_string_contains("DurationSeconds=900"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(STS_RESPONSE))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = AssumeRoleProvider::builder(StaticProvider::with_security_token(
+        "source-id",
+        "source-secret",
+        "source-token",
+    ))
+    .role_arn("acs:ram::123:role/scoped")
+    .external_id("ext-42")
+    .duration(std::time::Duration::from_secs(900))
+    .refresh_before(std::time::Duration::from_secs(60))
+    .sts_endpoint(server.uri())
+    .build()
+    .unwrap();
+
+    let creds = provider.resolve_async().await.unwrap();
+    assert_eq!(creds.access_key_id(), "STS.test-id");
+    let again = provider.resolve_async().await.unwrap();
+    assert_eq!(again.access_key_id(), "STS.test-id");
+}
+
+/// Issues a new access key id on every resolution, like a rotating STS source.
+#[derive(Debug, Default)]
+struct RotatingProvider {
+    calls: AtomicUsize,
+    invalidations: AtomicUsize,
+}
+
+impl CredentialProvider for RotatingProvider {
+    fn resolve(&self) -> Result<Credentials> {
+        let n = self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(Credentials::with_security_token(
+            format!("STS.key-{n}"),
+            "secret",
+            format!("token-{n}"),
+        ))
+    }
+
+    fn provider_name(&self) -> &str {
+        "rotating"
+    }
+
+    fn invalidate(&self) {
+        self.invalidations.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn rotating_client(server: &MockServer, provider: Arc<RotatingProvider>) -> OssClient {
+    OssClient::from_builder(
+        ClientBuilder::new()
+            .credential_provider(provider)
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .max_retries(0),
+    )
+    .unwrap()
+}
+
+fn delete_request() -> rs_ali_oss::types::request::DeleteObjectRequest {
+    DeleteObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("rotated.txt").unwrap())
+        .build()
+        .unwrap()
+}
+
+const EXPIRED_TOKEN: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Error>
+  <Code>SecurityTokenExpired</Code>
+  <Message>The security token you provided has expired.</Message>
+  <RequestId>REQ-EXPIRED</RequestId>
+  <HostId>my-bucket.oss-cn-hangzhou.aliyuncs.com</HostId>
+</Error>"#;
+
+#[tokio::test]
+async fn client_resolves_provider_credentials_per_request() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("DELETE"))
+        .and(header_regex("authorization", "Credential=STS.key-0/"))
+        .and(header_regex("x-oss-security-token", "^token-0$"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(header_regex("authorization", "Credential=STS.key-1/"))
+        .and(header_regex("x-oss-security-token", "^token-1$"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = Arc::new(RotatingProvider::default());
+    let client = rotating_client(&server, provider.clone());
+
+    for _ in 0..2 {
+        client.delete_object(delete_request()).await.unwrap();
+    }
+    assert_eq!(provider.calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn client_refreshes_credentials_and_retries_once_on_expired_token() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("DELETE"))
+        .and(header_regex("x-oss-security-token", "^token-0$"))
+        .respond_with(ResponseTemplate::new(403).set_body_string(EXPIRED_TOKEN))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(header_regex("x-oss-security-token", "^token-1$"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = Arc::new(RotatingProvider::default());
+    let client = rotating_client(&server, provider.clone());
+    client.delete_object(delete_request()).await.unwrap();
+    assert_eq!(provider.invalidations.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn credential_refresh_is_not_delayed_by_backoff() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("DELETE"))
+        .and(header_regex("x-oss-security-token", "^token-0$"))
+        .respond_with(ResponseTemplate::new(403).set_body_string(EXPIRED_TOKEN))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(header_regex("x-oss-security-token", "^token-1$"))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&server)
+        .await;
+
+    let provider = Arc::new(RotatingProvider::default());
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .credential_provider(provider)
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .base_retry_delay(Duration::from_secs(30))
+            .max_retry_delay(Duration::from_secs(30)),
+    )
+    .unwrap();
+    let start = Instant::now();
+    client.delete_object(delete_request()).await.unwrap();
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn client_gives_up_after_one_credential_refresh() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(403).set_body_string(EXPIRED_TOKEN))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let provider = Arc::new(RotatingProvider::default());
+    let client = rotating_client(&server, provider.clone());
+    let err = client.delete_object(delete_request()).await.unwrap_err();
+    assert!(
+        matches!(err, OssError::ServerError { ref code, .. } if code == "SecurityTokenExpired")
+    );
+    assert_eq!(provider.invalidations.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn client_surfaces_provider_errors() {
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .credential_provider(Arc::new(rs_ali_oss::credential::ProviderChain::new()))
+            .region("cn-hangzhou")
+            .endpoint("http://127.0.0.1:1")
+            .allow_insecure(true)
+            .max_retries(0),
+    )
+    .unwrap();
+
+    let request = DeleteObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("k").unwrap())
+        .build()
+        .unwrap();
+    let err = client.delete_object(request).await.unwrap_err();
+    assert!(matches!(err, OssError::Auth(_)));
+}
@@ -0,0 +1,371 @@
+//! Integration tests for the in-memory mock backend.
+#![cfg(feature = "test-util")]
+
+use std::sync::Arc;
+
+use rs_ali_oss::mock::MockOss;
+use rs_ali_oss::types::common::{BucketName, ObjectKey};
+use rs_ali_oss::types::request::{
+    CompleteMultipartUploadRequestBuilder, CompletedPart, CopyObjectRequestBuilder,
+    CreateBucketRequestBuilder, DeleteBucketRequestBuilder, DeleteObjectRequestBuilder,
+    GetObjectRequestBuilder, HeadObjectRequestBuilder, InitiateMultipartUploadRequestBuilder,
+    ListObjectsV2RequestBuilder, ListPartsRequestBuilder, PutObjectRequestBuilder,
+    UploadPartRequestBuilder,
+};
+use rs_ali_oss::{OssApi, OssError};
+
+fn bucket() -> BucketName {
+    BucketName::new("test-bucket").unwrap()
+}
+
+fn key(key: &str) -> ObjectKey {
+    ObjectKey::new(key).unwrap()
+}
+
+async fn put(api: &dyn OssApi, name: &str, data: &[u8]) -> String {
+    let request = PutObjectRequestBuilder::new()
+        .bucket(bucket())
+        .key(key(name))
+        .body(data.to_vec())
+        .content_type("text/plain")
+        .metadata("author", "alice")
+        .build()
+        .unwrap();
+    api.put_object(request).await.unwrap().etag
+}
+
+fn code(err: &OssError) -> Option<String> {
+    err.error_code().map(|code| code.to_string())
+}
+
+#[tokio::test]
+async fn objects_round_trip_with_etags_and_ranges() {
+    let oss = MockOss::new().with_bucket("test-bucket");
+    let api: Arc<dyn OssApi> = Arc::new(oss.clone());
+
+    let etag = put(api.as_ref(), "hello.txt", b"Hello World").await;
+    assert_eq!(etag, "B10A8DB164E0754105B7A99BE72E3FE5");
+
+    let head = api
+        .head_object(
+            HeadObjectRequestBuilder::new()
+                .bucket(bucket())
+                .key(key("hello.txt"))
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(head.content_length, Some(11));
+    assert_eq!(head.etag.as_deref(), Some(etag.as_str()));
+    assert_eq!(head.metadata["author"], "alice");
+
+    let response = api
+        .get_object(
+            GetObjectRequestBuilder::new()
+                .bucket(bucket())
+                .key(key("hello.txt"))
+                .range("bytes=6-")
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.content_type.as_deref(), Some("text/plain"));
+    assert_eq!(response.body.text().await.unwrap(), "World");
+
+    api.delete_object(
+        DeleteObjectRequestBuilder::new()
+            .bucket(bucket())
+            .key(key("hello.txt"))
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    assert!(oss.object("test-bucket", "hello.txt").is_none());
+
+    let err = api
+        .get_object(
+            GetObjectRequestBuilder::new()
+                .bucket(bucket())
+                .key(key("hello.txt"))
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap_err();
+    assert!(err.is_not_found());
+    assert_eq!(code(&err).as_deref(), Some("NoSuchKey"));
+}
+
+#[tokio::test]
+async fn listing_paginates_and_groups_common_prefixes() {
+    let oss = MockOss::new().with_bucket("test-bucket");
+    for name in ["a.txt", "b.txt", "dir/1", "dir/2", "other/x", "z.txt"] {
+        put(&oss, name, b"x").await;
+    }
+
+    let mut pages = Vec::new();
+    let mut token = None;
+    loop {
+        let mut request = ListObjectsV2RequestBuilder::new()
+            .bucket(bucket())
+            .delimiter("/")
+            .max_keys(2);
+        if let Some(token) = token.take() {
+            request = request.continuation_token(token);
+        }
+        let page = oss.list_objects_v2(request.build().unwrap()).await.unwrap();
+        let mut entries: Vec<String> = page.contents.iter().map(|o| o.key.clone()).collect();
+        entries.extend(page.common_prefixes.iter().map(|p| p.prefix.clone()));
+        pages.push(entries);
+        match page.next_continuation_token {
+            Some(next) if page.is_truncated => token = Some(next),
+            _ => break,
+        }
+    }
+    assert_eq!(
+        pages,
+        [
+            vec!["a.txt", "b.txt"],
+            vec!["dir/", "other/"],
+            vec!["z.txt"]
+        ]
+    );
+
+    let page = oss
+        .list_objects_v2(
+            ListObjectsV2RequestBuilder::new()
+                .bucket(bucket())
+                .prefix("dir/")
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(page.key_count, 2);
+    assert!(!page.is_truncated);
+}
+
+#[tokio::test]
+async fn buckets_must_exist_and_be_empty_to_delete() {
+    let oss = MockOss::new();
+    let err = put_err(&oss).await;
+    assert_eq!(code(&err).as_deref(), Some("NoSuchBucket"));
+
+    oss.create_bucket(
+        CreateBucketRequestBuilder::new()
+            .bucket(bucket())
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    put(&oss, "keep", b"x").await;
+
+    let err = oss
+        .delete_bucket(
+            DeleteBucketRequestBuilder::new()
+                .bucket(bucket())
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(code(&err).as_deref(), Some("BucketNotEmpty"));
+}
+
+async fn put_err(api: &dyn OssApi) -> OssError {
+    let request = PutObjectRequestBuilder::new()
+        .bucket(bucket())
+        .key(key("k"))
+        .body(b"x".to_vec())
+        .build()
+        .unwrap();
+    api.put_object(request).await.unwrap_err()
+}
+
+#[tokio::test]
+async fn copy_keeps_content() {
+    let oss = MockOss::new().with_bucket("test-bucket");
+    let etag = put(&oss, "src", b"payload").await;
+    let copy = oss
+        .copy_object(
+            CopyObjectRequestBuilder::new()
+                .bucket(bucket())
+                .key(key("dst"))
+                .source_bucket(bucket())
+                .source_key(key("src"))
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(copy.etag, etag);
+    assert_eq!(oss.keys("test-bucket"), ["dst", "src"]);
+}
+
+#[tokio::test]
+async fn multipart_upload_assembles_parts_in_order() {
+    let oss = MockOss::new().with_bucket("test-bucket");
+    let upload_id = oss
+        .initiate_multipart_upload(
+            InitiateMultipartUploadRequestBuilder::new()
+                .bucket(bucket())
+                .key(key("big.bin"))
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .upload_id;
+
+    let mut parts = Vec::new();
+    for (part_number, data) in [(2u32, &b"world"[..]), (1, &b"hello "[..])] {
+        let response = oss
+            .upload_part(
+                UploadPartRequestBuilder::new()
+                    .bucket(bucket())
+                    .key(key("big.bin"))
+                    .upload_id(&upload_id)
+                    .part_number(part_number)
+                    .body(data.to_vec())
+                    .build()
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.crc64, Some(rs_ali_oss::crc64::checksum(data)));
+        parts.push(CompletedPart {
+            part_number,
+            etag: response.etag,
+        });
+    }
+
+    let listed = oss
+        .list_parts(
+            ListPartsRequestBuilder::new()
+                .bucket(bucket())
+                .key(key("big.bin"))
+                .upload_id(&upload_id)
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        listed
+            .parts
+            .iter()
+            .map(|p| p.part_number)
+            .collect::<Vec<_>>(),
+        [1, 2]
+    );
+
+    let complete = |parts: Vec<CompletedPart>| {
+        CompleteMultipartUploadRequestBuilder::new()
+            .bucket(bucket())
+            .key(key("big.bin"))
+            .upload_id(&upload_id)
+            .parts(parts)
+            .build()
+    };
+    // Out-of-order parts are rejected before reaching OSS.
+    assert!(matches!(
+        complete(parts.clone()),
+        Err(OssError::InvalidParameter { field, .. }) if field == "parts"
+    ));
+
+    parts.reverse();
+    let response = oss
+        .complete_multipart_upload(complete(parts).unwrap())
+        .await
+        .unwrap();
+    assert!(response.etag.ends_with("-2"));
+    assert_eq!(
+        oss.object("test-bucket", "big.bin").as_deref(),
+        Some(&b"hello world"[..])
+    );
+}
+
+#[tokio::test]
+async fn upload_part_rejects_bad_content_md5() {
+    let oss = MockOss::new().with_bucket("test-bucket");
+    let upload_id = oss
+        .initiate_multipart_upload(
+            InitiateMultipartUploadRequestBuilder::new()
+                .bucket(bucket())
+                .key(key("big.bin"))
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .upload_id;
+
+    let err = oss
+        .upload_part(
+            UploadPartRequestBuilder::new()
+                .bucket(bucket())
+                .key(key("big.bin"))
+                .upload_id(&upload_id)
+                .part_number(1)
+                .body(b"hello".to_vec())
+                .content_md5("1B2M2Y8AsgTpgAmY7PhCfg==")
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(code(&err).as_deref(), Some("InvalidDigest"));
+}
+
+#[tokio::test]
+async fn complete_all_assembles_every_uploaded_part() {
+    let oss = MockOss::new().with_bucket("test-bucket");
+    let upload_id = oss
+        .initiate_multipart_upload(
+            InitiateMultipartUploadRequestBuilder::new()
+                .bucket(bucket())
+                .key(key("big.bin"))
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .upload_id;
+
+    for (part_number, data) in [(2u32, &b"world"[..]), (1, &b"hello "[..])] {
+        oss.upload_part(
+            UploadPartRequestBuilder::new()
+                .bucket(bucket())
+                .key(key("big.bin"))
+                .upload_id(&upload_id)
+                .part_number(part_number)
+                .body(data.to_vec())
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    }
+
+    let response = oss
+        .complete_multipart_upload(
+            CompleteMultipartUploadRequestBuilder::new()
+                .bucket(bucket())
+                .key(key("big.bin"))
+                .upload_id(&upload_id)
+                .complete_all()
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert!(response.etag.ends_with("-2"));
+    assert_eq!(
+        oss.object("test-bucket", "big.bin").as_deref(),
+        Some(&b"hello world"[..])
+    );
+}
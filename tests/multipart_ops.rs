@@ -1,14 +1,25 @@
 //! Integration tests for multipart upload operations using wiremock.
 
+use std::sync::{Arc, Mutex};
+
 use rs_ali_oss::OssClient;
 use rs_ali_oss::config::ClientBuilder;
-use rs_ali_oss::types::common::{BucketName, ObjectKey};
+use rs_ali_oss::crc64;
+use rs_ali_oss::error::{ErrorCode, OssError};
+use rs_ali_oss::ops::transfer::{
+    TransferDownloadRequestBuilder, TransferEvent, TransferListener, TransferManager,
+    TransferManagerBuilder, TransferTarget, TransferUploadRequest, TransferUploadRequestBuilder,
+};
+use rs_ali_oss::progress::{ProgressListener, ProgressThrottle, TransferKind, TransferProgress};
+use rs_ali_oss::types::common::{BucketName, Callback, ObjectAcl, ObjectKey, ServerSideEncryption};
 use rs_ali_oss::types::request::{
     AbortMultipartUploadRequestBuilder, CompleteMultipartUploadRequestBuilder, CompletedPart,
     InitiateMultipartUploadRequestBuilder, ListPartsRequestBuilder, UploadPartRequestBuilder,
 };
-use wiremock::matchers::{method, path, query_param};
-use wiremock::{Mock, MockServer, ResponseTemplate};
+use wiremock::matchers::{
+    body_string, header, header_exists, method, path, query_param, query_param_is_missing,
+};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
 
 /// Create an `OssClient` that points at the given mock server.
 fn mock_client(server: &MockServer) -> OssClient {
@@ -63,6 +74,85 @@ async fn initiate_multipart_upload_parses_xml() {
     assert_eq!(response.upload_id, "UPLOAD-ID-12345");
 }
 
+#[tokio::test]
+async fn initiate_multipart_upload_sends_sequential() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/large-file.bin"))
+        .and(query_param("uploads", ""))
+        .and(query_param("sequential", ""))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            "<InitiateMultipartUploadResult><Bucket>test-bucket</Bucket>\
+             <Key>large-file.bin</Key><UploadId>SEQ-1</UploadId></InitiateMultipartUploadResult>",
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = InitiateMultipartUploadRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("large-file.bin").unwrap())
+        .sequential(true)
+        .build()
+        .unwrap();
+
+    let response = client.initiate_multipart_upload(request).await.unwrap();
+    assert_eq!(response.upload_id, "SEQ-1");
+}
+
+#[tokio::test]
+async fn initiate_multipart_upload_sends_object_headers() {
+    let server = MockServer::start().await;
+
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<InitiateMultipartUploadResult>
+    <Bucket>test-bucket</Bucket>
+    <Key>large-file.bin</Key>
+    <UploadId>UPLOAD-ID-12345</UploadId>
+</InitiateMultipartUploadResult>"#;
+
+    Mock::given(method("POST"))
+        .and(path("/large-file.bin"))
+        .and(query_param("uploads", ""))
+        .and(header("cache-control", "max-age=60"))
+        .and(header("content-disposition", "attachment"))
+        .and(header("x-oss-object-acl", "public-read"))
+        .and(header("x-oss-server-side-encryption", "KMS"))
+        .and(header("x-oss-server-side-encryption-key-id", "key-1"))
+        .and(header("x-oss-forbid-overwrite", "true"))
+        .and(header("x-oss-meta-owner", "alice"))
+        .and(header("x-oss-tagging", "team=data&stage=raw%20v1"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "application/xml")
+                .set_body_string(xml),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = InitiateMultipartUploadRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("large-file.bin").unwrap())
+        .cache_control("max-age=60")
+        .content_disposition("attachment")
+        .acl(ObjectAcl::PublicRead)
+        .server_side_encryption(ServerSideEncryption::KMS)
+        .kms_key_id("key-1")
+        .forbid_overwrite(true)
+        .metadata("owner", "alice")
+        .tag("team", "data")
+        .tag("stage", "raw v1")
+        .build()
+        .unwrap();
+
+    let response = client.initiate_multipart_upload(request).await.unwrap();
+    assert_eq!(response.upload_id, "UPLOAD-ID-12345");
+}
+
 // ---- UploadPart ----
 
 #[tokio::test]
@@ -92,6 +182,124 @@ async fn upload_part_returns_etag() {
     assert_eq!(response.etag, "part1-etag-abc");
 }
 
+#[tokio::test]
+async fn upload_part_sends_computed_content_md5() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/large-file.bin"))
+        .and(header("content-md5", "XUFAKrxLKna5cZ2REBfFkg=="))
+        .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"part1-etag\""))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = UploadPartRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("large-file.bin").unwrap())
+        .upload_id("UPLOAD-ID-12345")
+        .part_number(1)
+        .body(b"hello".to_vec())
+        .compute_md5(true)
+        .build()
+        .unwrap();
+
+    client.upload_part(request).await.unwrap();
+}
+
+#[tokio::test]
+async fn upload_part_streams_body_with_content_length() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/large-file.bin"))
+        .and(header("content-length", "12"))
+        .and(body_string("hello, world"))
+        .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"part1-etag\""))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let chunks: Vec<Result<bytes::Bytes, std::io::Error>> = vec![
+        Ok(bytes::Bytes::from_static(b"hello, ")),
+        Ok(bytes::Bytes::from_static(b"world")),
+    ];
+    let request = UploadPartRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("large-file.bin").unwrap())
+        .upload_id("UPLOAD-ID-12345")
+        .part_number(1)
+        .body_stream(futures_util::stream::iter(chunks), 12)
+        .build()
+        .unwrap();
+
+    let response = client.upload_part(request).await.unwrap();
+    assert_eq!(response.etag, "part1-etag");
+}
+
+#[tokio::test]
+async fn upload_part_reports_part_progress() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/large-file.bin"))
+        .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"part-etag\""))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let upload = |body: reqwest::Body, seen: Arc<Mutex<Vec<TransferProgress>>>| {
+        UploadPartRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .key(ObjectKey::new("large-file.bin").unwrap())
+            .upload_id("UPLOAD-ID-12345")
+            .part_number(1)
+            .body(body)
+            .progress_listener(Arc::new(move |p: &TransferProgress| {
+                seen.lock().unwrap().push(p.clone());
+            }))
+            .build()
+            .unwrap()
+    };
+
+    let buffered = Arc::new(Mutex::new(Vec::new()));
+    client
+        .upload_part(upload(vec![0u8; 1024].into(), Arc::clone(&buffered)))
+        .await
+        .unwrap();
+    let buffered: Vec<u64> = buffered
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|p| p.bytes_transferred)
+        .collect();
+    assert_eq!(buffered, vec![0, 1024]);
+
+    let chunks: Vec<Result<bytes::Bytes, std::io::Error>> = (0..4)
+        .map(|_| Ok(bytes::Bytes::from_static(&[1u8; 256])))
+        .collect();
+    let streamed = Arc::new(Mutex::new(Vec::new()));
+    client
+        .upload_part(upload(
+            reqwest::Body::wrap_stream(futures_util::stream::iter(chunks)),
+            Arc::clone(&streamed),
+        ))
+        .await
+        .unwrap();
+    let streamed = streamed.lock().unwrap();
+    assert!(streamed.iter().all(|p| p.kind == TransferKind::Upload));
+    assert_eq!(
+        streamed
+            .iter()
+            .map(|p| p.bytes_transferred)
+            .collect::<Vec<_>>(),
+        vec![0, 256, 512, 768, 1024]
+    );
+}
+
 #[tokio::test]
 async fn upload_part_second_part() {
     let server = MockServer::start().await;
@@ -139,6 +347,7 @@ async fn complete_multipart_upload_parses_xml() {
         .respond_with(
             ResponseTemplate::new(200)
                 .insert_header("content-type", "application/xml")
+                .insert_header("x-oss-version-id", "final-version")
                 .set_body_string(xml),
         )
         .expect(1)
@@ -166,6 +375,123 @@ async fn complete_multipart_upload_parses_xml() {
     assert_eq!(response.key, "large-file.bin");
     assert_eq!(response.etag, "\"final-etag-xyz\"");
     assert!(response.location.contains("large-file.bin"));
+    assert_eq!(response.version_id.as_deref(), Some("final-version"));
+}
+
+#[tokio::test]
+async fn complete_multipart_upload_complete_all_sends_empty_body() {
+    let server = MockServer::start().await;
+
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<CompleteMultipartUploadResult>
+    <Location>https://test-bucket.oss-cn-hangzhou.aliyuncs.com/large-file.bin</Location>
+    <Bucket>test-bucket</Bucket>
+    <Key>large-file.bin</Key>
+    <ETag>"final-etag-xyz"</ETag>
+</CompleteMultipartUploadResult>"#;
+
+    Mock::given(method("POST"))
+        .and(path("/large-file.bin"))
+        .and(query_param("uploadId", "UPLOAD-ID-12345"))
+        .and(header("x-oss-complete-all", "yes"))
+        .and(body_string(""))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "application/xml")
+                .set_body_string(xml),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = CompleteMultipartUploadRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("large-file.bin").unwrap())
+        .upload_id("UPLOAD-ID-12345")
+        .complete_all()
+        .build()
+        .unwrap();
+
+    let response = client.complete_multipart_upload(request).await.unwrap();
+    assert_eq!(response.etag, "\"final-etag-xyz\"");
+}
+
+#[tokio::test]
+async fn complete_multipart_upload_returns_callback_result() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/large-file.bin"))
+        .and(query_param("uploadId", "UPLOAD-ID-12345"))
+        .and(header_exists("x-oss-callback"))
+        .and(header_exists("x-oss-callback-var"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "application/json")
+                .insert_header("etag", "\"final-etag-xyz\"")
+                .insert_header("x-oss-version-id", "v1")
+                .set_body_string(r#"{"Status":"OK"}"#),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = CompleteMultipartUploadRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("large-file.bin").unwrap())
+        .upload_id("UPLOAD-ID-12345")
+        .complete_all()
+        .callback(
+            Callback::new("https://example.com/cb", "object=${object}").var("x:owner", "alice"),
+        )
+        .build()
+        .unwrap();
+
+    let response = client.complete_multipart_upload(request).await.unwrap();
+    assert_eq!(
+        response.callback_result.as_deref(),
+        Some(r#"{"Status":"OK"}"#)
+    );
+    assert_eq!(response.etag, "\"final-etag-xyz\"");
+    assert_eq!(response.bucket, "test-bucket");
+    assert_eq!(response.key, "large-file.bin");
+    assert!(response.location.ends_with("/large-file.bin"));
+    assert_eq!(response.version_id.as_deref(), Some("v1"));
+}
+
+#[tokio::test]
+async fn complete_multipart_upload_surfaces_failed_callback() {
+    let server = MockServer::start().await;
+
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Error>
+    <Code>CallbackFailed</Code>
+    <Message>Error status : 502.</Message>
+    <RequestId>REQ-CB</RequestId>
+    <HostId>test-bucket.oss-cn-hangzhou.aliyuncs.com</HostId>
+</Error>"#;
+
+    Mock::given(method("POST"))
+        .and(path("/large-file.bin"))
+        .respond_with(ResponseTemplate::new(203).set_body_string(xml))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = CompleteMultipartUploadRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("large-file.bin").unwrap())
+        .upload_id("UPLOAD-ID-12345")
+        .complete_all()
+        .callback(Callback::new("https://example.com/cb", "object=${object}"))
+        .build()
+        .unwrap();
+
+    let err = client.complete_multipart_upload(request).await.unwrap_err();
+    assert_eq!(err.error_code(), Some(ErrorCode::CallbackFailed));
 }
 
 // ---- AbortMultipartUpload ----
@@ -312,6 +638,59 @@ async fn list_parts_with_pagination_params() {
     assert_eq!(response.parts[1].part_number, 4);
 }
 
+fn list_parts_page(parts: &[u32], next_marker: Option<u32>) -> String {
+    let parts: String = parts
+        .iter()
+        .map(|n| {
+            format!(
+                "<Part><PartNumber>{n}</PartNumber><LastModified>2025-01-15T10:30:00.000Z</LastModified><ETag>\"etag-{n}\"</ETag><Size>100</Size></Part>"
+            )
+        })
+        .collect();
+    let marker = next_marker
+        .map(|m| format!("<NextPartNumberMarker>{m}</NextPartNumberMarker>"))
+        .unwrap_or_default();
+    format!(
+        "<ListPartsResult><Bucket>test-bucket</Bucket><Key>large-file.bin</Key><UploadId>UPLOAD-ID-12345</UploadId><MaxParts>2</MaxParts><IsTruncated>{}</IsTruncated>{marker}{parts}</ListPartsResult>",
+        next_marker.is_some()
+    )
+}
+
+#[tokio::test]
+async fn list_all_parts_follows_markers_and_sorts() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/large-file.bin"))
+        .and(query_param_is_missing("part-number-marker"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(list_parts_page(&[1, 2], Some(2))))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/large-file.bin"))
+        .and(query_param("part-number-marker", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(list_parts_page(&[4, 3], None)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let parts = client
+        .list_all_parts(
+            BucketName::new("test-bucket").unwrap(),
+            ObjectKey::new("large-file.bin").unwrap(),
+            "UPLOAD-ID-12345",
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        parts.iter().map(|p| p.part_number).collect::<Vec<_>>(),
+        [1, 2, 3, 4]
+    );
+    assert_eq!(parts[3].etag, "\"etag-4\"");
+}
+
 // ---- Error handling ----
 
 #[tokio::test]
@@ -349,3 +728,730 @@ async fn initiate_multipart_upload_error_returns_oss_error() {
     let err_str = err.to_string();
     assert!(err_str.contains("InvalidArgument"), "error: {err_str}");
 }
+
+// ---- TransferManager CRC64 ----
+
+/// Answers UploadPart with the part's real CRC64, as OSS does.
+struct PartCrcResponder;
+
+impl Respond for PartCrcResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        ResponseTemplate::new(200)
+            .insert_header("etag", "\"part-etag\"")
+            .insert_header(
+                "x-oss-hash-crc64ecma",
+                crc64::checksum(&request.body).to_string(),
+            )
+    }
+}
+
+async fn mount_crc_multipart(server: &MockServer, object_crc: u64) {
+    let init_xml = r#"<InitiateMultipartUploadResult>
+    <Bucket>test-bucket</Bucket>
+    <Key>big.bin</Key>
+    <UploadId>UPLOAD-CRC</UploadId>
+</InitiateMultipartUploadResult>"#;
+    let complete_xml = r#"<CompleteMultipartUploadResult>
+    <Location>https://test-bucket.oss-cn-hangzhou.aliyuncs.com/big.bin</Location>
+    <Bucket>test-bucket</Bucket>
+    <Key>big.bin</Key>
+    <ETag>"final"</ETag>
+</CompleteMultipartUploadResult>"#;
+
+    Mock::given(method("POST"))
+        .and(path("/big.bin"))
+        .and(query_param("uploads", ""))
+        .respond_with(ResponseTemplate::new(200).set_body_string(init_xml))
+        .mount(server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/big.bin"))
+        .and(query_param("uploadId", "UPLOAD-CRC"))
+        .respond_with(PartCrcResponder)
+        .expect(3)
+        .mount(server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/big.bin"))
+        .and(query_param("uploadId", "UPLOAD-CRC"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("x-oss-hash-crc64ecma", object_crc.to_string())
+                .set_body_string(complete_xml),
+        )
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+fn crc_upload(server: &MockServer, data: Vec<u8>) -> (TransferManager, TransferUploadRequest) {
+    let manager = TransferManagerBuilder::new(mock_client(server))
+        .part_size(100 * 1024)
+        .multipart_threshold(100 * 1024)
+        .enable_crc64(true)
+        .build();
+    let request = TransferUploadRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("big.bin").unwrap())
+        .data(data)
+        .build()
+        .unwrap();
+    (manager, request)
+}
+
+#[tokio::test]
+async fn transfer_upload_verifies_combined_crc64() {
+    let server = MockServer::start().await;
+    let data: Vec<u8> = (0..250 * 1024).map(|i| (i % 251) as u8).collect();
+    mount_crc_multipart(&server, crc64::checksum(&data)).await;
+
+    let (manager, request) = crc_upload(&server, data.clone());
+    let response = manager.upload(request).await.unwrap();
+    assert!(response.multipart);
+    assert_eq!(response.crc64, Some(crc64::checksum(&data)));
+}
+
+#[tokio::test]
+async fn transfer_upload_reports_crc64_mismatch() {
+    let server = MockServer::start().await;
+    let data: Vec<u8> = (0..250 * 1024).map(|i| (i % 251) as u8).collect();
+    // OSS assembled something other than what was sent, e.g. parts out of order.
+    mount_crc_multipart(&server, crc64::checksum(&data) ^ 1).await;
+
+    let (manager, request) = crc_upload(&server, data.clone());
+    let err = manager.upload(request).await.unwrap_err();
+    assert!(
+        matches!(err, OssError::ChecksumMismatch { computed, .. } if computed == crc64::checksum(&data)),
+        "{err:?}"
+    );
+}
+
+fn attributed_upload(key: &str, data: Vec<u8>) -> TransferUploadRequest {
+    TransferUploadRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new(key).unwrap())
+        .data(data)
+        .content_type("application/octet-stream")
+        .acl(ObjectAcl::Private)
+        .server_side_encryption(ServerSideEncryption::KMS)
+        .kms_key_id("key-1")
+        .metadata("owner", "alice")
+        .tag("team", "storage")
+        .build()
+        .unwrap()
+}
+
+fn attribute_mock(method_name: &str, key: &str) -> wiremock::MockBuilder {
+    Mock::given(method(method_name))
+        .and(path(format!("/{key}")))
+        .and(header("content-type", "application/octet-stream"))
+        .and(header("x-oss-object-acl", "private"))
+        .and(header("x-oss-server-side-encryption", "KMS"))
+        .and(header("x-oss-server-side-encryption-key-id", "key-1"))
+        .and(header("x-oss-meta-owner", "alice"))
+        .and(header("x-oss-tagging", "team=storage"))
+}
+
+#[tokio::test]
+async fn transfer_upload_sends_object_attributes() {
+    let server = MockServer::start().await;
+    attribute_mock("PUT", "small.bin")
+        .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"small\""))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let client = mock_client(&server);
+
+    let response = TransferManagerBuilder::new(client.clone())
+        .build()
+        .upload(attributed_upload("small.bin", b"hello".to_vec()))
+        .await
+        .unwrap();
+    assert!(!response.multipart);
+
+    let data: Vec<u8> = (0..250 * 1024).map(|i| (i % 251) as u8).collect();
+    mount_crc_multipart(&server, crc64::checksum(&data)).await;
+    attribute_mock("POST", "big.bin")
+        .and(query_param("uploads", ""))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            "<InitiateMultipartUploadResult><Bucket>test-bucket</Bucket>\
+             <Key>big.bin</Key><UploadId>UPLOAD-CRC</UploadId>\
+             </InitiateMultipartUploadResult>",
+        ))
+        .with_priority(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let response = TransferManagerBuilder::new(client)
+        .part_size(100 * 1024)
+        .multipart_threshold(100 * 1024)
+        .build()
+        .upload(attributed_upload("big.bin", data))
+        .await
+        .unwrap();
+    assert!(response.multipart);
+}
+
+#[test]
+fn transfer_upload_rejects_kms_key_without_kms() {
+    let err = TransferUploadRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("a.bin").unwrap())
+        .data(b"x".to_vec())
+        .kms_key_id("key-1")
+        .build()
+        .unwrap_err();
+    assert!(
+        matches!(err, OssError::InvalidParameter { ref field, .. } if field == "kms_key_id"),
+        "{err:?}"
+    );
+}
+
+// ---- TransferManager lifecycle events ----
+
+/// Records transfer events as short strings.
+fn event_log() -> (Arc<Mutex<Vec<String>>>, Arc<dyn TransferListener>) {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&log);
+    let listener = move |target: &TransferTarget, event: &TransferEvent<'_>| {
+        assert_eq!(target.bucket.to_string(), "test-bucket");
+        let entry = match event {
+            TransferEvent::Started { total_bytes } => format!("started {total_bytes}"),
+            TransferEvent::PartCompleted { part_number, .. } => format!("part {part_number}"),
+            TransferEvent::PartRetried {
+                part_number,
+                attempt,
+                ..
+            } => format!("retry {part_number}/{attempt}"),
+            TransferEvent::Paused => "paused".to_string(),
+            TransferEvent::Resumed => "resumed".to_string(),
+            TransferEvent::Failed { .. } => "failed".to_string(),
+            TransferEvent::UploadCompleted { .. } => "uploaded".to_string(),
+            TransferEvent::DownloadCompleted { .. } => "downloaded".to_string(),
+            _ => "other".to_string(),
+        };
+        sink.lock().unwrap().push(entry);
+    };
+    (log, Arc::new(listener))
+}
+
+#[tokio::test]
+async fn transfer_upload_reports_lifecycle_events_and_retries_parts() {
+    let server = MockServer::start().await;
+    let data: Vec<u8> = (0..250 * 1024).map(|i| (i % 251) as u8).collect();
+    mount_crc_multipart(&server, crc64::checksum(&data)).await;
+    // The first attempt at part 2 hits a transient error.
+    Mock::given(method("PUT"))
+        .and(path("/big.bin"))
+        .and(query_param("partNumber", "2"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+
+    let (log, listener) = event_log();
+    let (_, request) = crc_upload(&server, data);
+    let manager = TransferManagerBuilder::new(mock_client(&server))
+        .part_size(100 * 1024)
+        .multipart_threshold(100 * 1024)
+        .concurrency(1)
+        .part_retries(1)
+        .transfer_listener(listener)
+        .build();
+    manager.upload(request).await.unwrap();
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        [
+            "started 256000",
+            "part 1",
+            "retry 2/1",
+            "part 2",
+            "part 3",
+            "uploaded"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn transfer_waits_while_paused() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path("/small.bin"))
+        .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"small\""))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let (log, listener) = event_log();
+    let manager = TransferManagerBuilder::new(mock_client(&server))
+        .transfer_listener(listener)
+        .build();
+    let request = TransferUploadRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("small.bin").unwrap())
+        .data(b"hello".to_vec())
+        .build()
+        .unwrap();
+
+    manager.pause();
+    assert!(manager.is_paused());
+    let (response, ()) = tokio::join!(manager.upload(request), async {
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        assert!(server.received_requests().await.unwrap().is_empty());
+        manager.resume();
+    });
+    response.unwrap();
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        ["started 5", "paused", "resumed", "uploaded"]
+    );
+}
+
+#[tokio::test]
+async fn transfer_download_reports_failure() {
+    let server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path("/missing.bin"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let (log, listener) = event_log();
+    let manager = TransferManagerBuilder::new(mock_client(&server))
+        .transfer_listener(listener)
+        .build();
+    let request = TransferDownloadRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("missing.bin").unwrap())
+        .build()
+        .unwrap();
+
+    assert!(manager.download(request).await.is_err());
+    assert_eq!(*log.lock().unwrap(), ["failed"]);
+}
+
+/// Answers PutObject after a delay, recording when each request arrived.
+struct SlowPutResponder(Arc<Mutex<Vec<std::time::Instant>>>);
+
+impl Respond for SlowPutResponder {
+    fn respond(&self, _request: &Request) -> ResponseTemplate {
+        self.0.lock().unwrap().push(std::time::Instant::now());
+        ResponseTemplate::new(200)
+            .insert_header("etag", "\"small\"")
+            .set_delay(std::time::Duration::from_millis(200))
+    }
+}
+
+#[tokio::test]
+async fn transfer_manager_limits_parts_in_flight_across_transfers() {
+    let server = MockServer::start().await;
+    let arrivals = Arc::new(Mutex::new(Vec::new()));
+    Mock::given(method("PUT"))
+        .respond_with(SlowPutResponder(Arc::clone(&arrivals)))
+        .expect(4)
+        .mount(&server)
+        .await;
+
+    let manager = TransferManagerBuilder::new(mock_client(&server))
+        .max_total_parts_in_flight(2)
+        .build();
+    let uploads = (0..4).map(|i| {
+        let request = TransferUploadRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .key(ObjectKey::new(format!("small-{i}.bin")).unwrap())
+            .data(b"hello".to_vec())
+            .build()
+            .unwrap();
+        manager.upload(request)
+    });
+    for response in futures_util::future::join_all(uploads).await {
+        response.unwrap();
+    }
+
+    // Two uploads run at once; the other two wait for a free slot.
+    let arrivals = arrivals.lock().unwrap();
+    let since_first = |i: usize| arrivals[i] - arrivals[0];
+    assert!(since_first(1) < std::time::Duration::from_millis(150));
+    assert!(since_first(2) >= std::time::Duration::from_millis(150));
+}
+
+/// Records failure and cancellation notifications.
+#[derive(Default)]
+struct TerminalLog(Mutex<Vec<String>>);
+
+impl ProgressListener for TerminalLog {
+    fn on_progress(&self, _progress: &TransferProgress) {}
+
+    fn on_failed(&self, progress: &TransferProgress, error: &OssError) {
+        self.0.lock().unwrap().push(format!(
+            "failed {:?} {}",
+            progress.total_bytes,
+            error.status().unwrap_or_default()
+        ));
+    }
+
+    fn on_cancelled(&self, progress: &TransferProgress) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("cancelled {:?}", progress.total_bytes));
+    }
+}
+
+#[tokio::test]
+async fn transfer_reports_failure_and_cancellation_to_progress_listener() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path("/denied.bin"))
+        .respond_with(ResponseTemplate::new(403))
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/slow.bin"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("etag", "\"slow\"")
+                .set_delay(std::time::Duration::from_secs(5)),
+        )
+        .mount(&server)
+        .await;
+
+    let log = Arc::new(TerminalLog::default());
+    let manager = TransferManagerBuilder::new(mock_client(&server))
+        .progress_listener(log.clone())
+        .build();
+    let upload = |key: &str| {
+        TransferUploadRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .key(ObjectKey::new(key).unwrap())
+            .data(b"hello".to_vec())
+            .build()
+            .unwrap()
+    };
+
+    assert!(manager.upload(upload("denied.bin")).await.is_err());
+    let timed_out = tokio::time::timeout(
+        std::time::Duration::from_millis(100),
+        manager.upload(upload("slow.bin")),
+    )
+    .await;
+    assert!(timed_out.is_err());
+
+    assert_eq!(
+        *log.0.lock().unwrap(),
+        ["failed Some(5) 403", "cancelled Some(5)"]
+    );
+}
+
+#[tokio::test]
+async fn shared_progress_listener_tells_transfers_apart() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"e\""))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&seen);
+    let manager = TransferManagerBuilder::new(mock_client(&server))
+        .progress_listener(Arc::new(move |p: &TransferProgress| {
+            sink.lock()
+                .unwrap()
+                .push(format!("{}/{} {}", p.bucket, p.key, p.bytes_transferred));
+        }))
+        .build();
+    let upload = |key: &str, data: &[u8]| {
+        TransferUploadRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .key(ObjectKey::new(key).unwrap())
+            .data(data.to_vec())
+            .build()
+            .unwrap()
+    };
+    let (a, b) = tokio::join!(
+        manager.upload(upload("a.txt", b"aaa")),
+        manager.upload(upload("b.txt", b"bbbbb")),
+    );
+    a.unwrap();
+    b.unwrap();
+
+    let mut seen = seen.lock().unwrap().clone();
+    seen.sort();
+    assert_eq!(
+        seen,
+        [
+            "test-bucket/a.txt 0",
+            "test-bucket/a.txt 3",
+            "test-bucket/b.txt 0",
+            "test-bucket/b.txt 5"
+        ]
+    );
+}
+
+// ---- TransferManager download ----
+
+/// Serves byte ranges of a fixed object, as OSS does for ranged GETs.
+struct RangeResponder(Vec<u8>);
+
+impl Respond for RangeResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let range = request
+            .headers
+            .get("range")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("bytes="))
+            .and_then(|v| v.split_once('-'))
+            .map(|(start, end)| (start.parse().unwrap(), end.parse::<usize>().unwrap()));
+        match range {
+            Some((start, end)) => ResponseTemplate::new(206)
+                .insert_header("etag", "\"obj-etag\"")
+                .set_body_bytes(self.0[start..=end].to_vec()),
+            None => ResponseTemplate::new(200)
+                .insert_header("etag", "\"obj-etag\"")
+                .set_body_bytes(self.0.clone()),
+        }
+    }
+}
+
+#[tokio::test]
+async fn transfer_download_fetches_ranges_in_order_with_progress() {
+    let server = MockServer::start().await;
+    let data: Vec<u8> = (0..250 * 1024).map(|i| (i % 251) as u8).collect();
+
+    Mock::given(method("HEAD"))
+        .and(path("/big.bin"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("etag", "\"obj-etag\"")
+                .insert_header("content-length", data.len().to_string()),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/big.bin"))
+        .respond_with(RangeResponder(data.clone()))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&seen);
+    let manager = TransferManagerBuilder::new(mock_client(&server))
+        .part_size(100 * 1024)
+        .multipart_threshold(100 * 1024)
+        .progress_listener(Arc::new(move |p: &TransferProgress| {
+            sink.lock().unwrap().push(p.clone());
+        }))
+        .build();
+    let request = TransferDownloadRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("big.bin").unwrap())
+        .build()
+        .unwrap();
+
+    let response = manager.download(request).await.unwrap();
+    assert!(response.ranged);
+    assert_eq!(response.etag.as_deref(), Some("obj-etag"));
+    assert!(response.data == data);
+
+    let seen = seen.lock().unwrap();
+    assert!(seen.iter().all(|p| p.kind == TransferKind::Download));
+    assert!(seen.iter().all(|p| p.key.as_ref() == "big.bin"));
+    assert!(
+        seen.windows(2)
+            .all(|w| w[0].bytes_transferred <= w[1].bytes_transferred)
+    );
+    let last = seen.last().unwrap();
+    assert_eq!(last.bytes_transferred, data.len() as u64);
+    assert_eq!(last.total_bytes, Some(data.len() as u64));
+}
+
+#[tokio::test]
+async fn transfer_download_throttles_each_transfer_separately() {
+    let server = MockServer::start().await;
+    let data: Vec<u8> = (0..150 * 1024).map(|i| (i % 251) as u8).collect();
+
+    for key in ["/a.bin", "/b.bin"] {
+        Mock::given(method("HEAD"))
+            .and(path(key))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("etag", "\"obj-etag\"")
+                    .insert_header("content-length", data.len().to_string()),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(key))
+            .respond_with(RangeResponder(data.clone()))
+            .mount(&server)
+            .await;
+    }
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&seen);
+    let manager = TransferManagerBuilder::new(mock_client(&server))
+        .part_size(100 * 1024)
+        .multipart_threshold(100 * 1024)
+        .progress_throttle(ProgressThrottle::new().interval(std::time::Duration::from_secs(3600)))
+        .progress_listener(Arc::new(move |p: &TransferProgress| {
+            sink.lock()
+                .unwrap()
+                .push((p.key.to_string(), p.bytes_transferred));
+        }))
+        .build();
+    let request = |key: &str| {
+        TransferDownloadRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .key(ObjectKey::new(key).unwrap())
+            .build()
+            .unwrap()
+    };
+
+    let (a, b) = tokio::join!(
+        manager.download(request("a.bin")),
+        manager.download(request("b.bin"))
+    );
+    a.unwrap();
+    b.unwrap();
+
+    // Each transfer gets its own first and final event; nothing in between.
+    let mut seen = seen.lock().unwrap().clone();
+    seen.sort();
+    let total = data.len() as u64;
+    assert_eq!(
+        seen,
+        vec![
+            ("a.bin".to_string(), 0),
+            ("a.bin".to_string(), total),
+            ("b.bin".to_string(), 0),
+            ("b.bin".to_string(), total),
+        ]
+    );
+}
+
+/// Serves ranges like [`RangeResponder`], but answers the first range last.
+struct SlowFirstRangeResponder(Vec<u8>);
+
+impl Respond for SlowFirstRangeResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let response = RangeResponder(self.0.clone()).respond(request);
+        let first = request
+            .headers
+            .get("range")
+            .is_some_and(|v| v.as_bytes().starts_with(b"bytes=0-"));
+        if first {
+            response.set_delay(std::time::Duration::from_millis(200))
+        } else {
+            response
+        }
+    }
+}
+
+#[tokio::test]
+async fn transfer_download_to_writer_writes_ranges_in_order() {
+    let server = MockServer::start().await;
+    let data: Vec<u8> = (0..450 * 1024).map(|i| (i % 251) as u8).collect();
+
+    Mock::given(method("HEAD"))
+        .and(path("/big.bin"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("etag", "\"obj-etag\"")
+                .insert_header("content-length", data.len().to_string()),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/big.bin"))
+        .respond_with(SlowFirstRangeResponder(data.clone()))
+        .expect(5)
+        .mount(&server)
+        .await;
+
+    let manager = TransferManagerBuilder::new(mock_client(&server))
+        .part_size(100 * 1024)
+        .multipart_threshold(100 * 1024)
+        .concurrency(3)
+        .build();
+    let request = TransferDownloadRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("big.bin").unwrap())
+        .build()
+        .unwrap();
+
+    let (mut reader, writer) = tokio::io::duplex(64 * 1024);
+    let collect = tokio::spawn(async move {
+        let mut received = Vec::new();
+        tokio::io::AsyncReadExt::read_to_end(&mut reader, &mut received)
+            .await
+            .unwrap();
+        received
+    });
+    let response = manager.download_to_writer(request, writer).await.unwrap();
+    assert!(response.ranged);
+    assert_eq!(response.bytes_written, data.len() as u64);
+    assert_eq!(response.etag.as_deref(), Some("obj-etag"));
+    assert!(collect.await.unwrap() == data);
+}
+
+/// Serves ranges like [`RangeResponder`], each after a delay.
+struct DelayedRangeResponder(Vec<u8>);
+
+impl Respond for DelayedRangeResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        RangeResponder(self.0.clone())
+            .respond(request)
+            .set_delay(std::time::Duration::from_millis(300))
+    }
+}
+
+#[tokio::test]
+async fn transfer_download_to_writer_aborts_ranges_when_dropped() {
+    let server = MockServer::start().await;
+    let data: Vec<u8> = (0..250 * 1024).map(|i| (i % 251) as u8).collect();
+
+    Mock::given(method("HEAD"))
+        .and(path("/big.bin"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("etag", "\"obj-etag\"")
+                .insert_header("content-length", data.len().to_string()),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/big.bin"))
+        .respond_with(DelayedRangeResponder(data.clone()))
+        .mount(&server)
+        .await;
+
+    let seen = Arc::new(Mutex::new(0u64));
+    let sink = Arc::clone(&seen);
+    let manager = TransferManagerBuilder::new(mock_client(&server))
+        .part_size(100 * 1024)
+        .multipart_threshold(100 * 1024)
+        .progress_listener(Arc::new(move |p: &TransferProgress| {
+            *sink.lock().unwrap() = p.bytes_transferred;
+        }))
+        .build();
+    let request = TransferDownloadRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("big.bin").unwrap())
+        .build()
+        .unwrap();
+
+    let download = manager.download_to_writer(request, tokio::io::sink());
+    let dropped = tokio::time::timeout(std::time::Duration::from_millis(100), download).await;
+    assert!(dropped.is_err());
+
+    // Detached range tasks would finish their GETs and report progress.
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    assert_eq!(*seen.lock().unwrap(), 0);
+}
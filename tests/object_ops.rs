@@ -1,14 +1,20 @@
 //! Integration tests for object operations using wiremock.
 
+use std::sync::{Arc, Mutex};
+
 use rs_ali_oss::OssClient;
-use rs_ali_oss::config::ClientBuilder;
-use rs_ali_oss::types::common::{BucketName, ObjectKey};
+use rs_ali_oss::config::{ClientBuilder, MetadataEncoding, OperationOptions};
+use rs_ali_oss::progress::{TransferKind, TransferProgress};
+use rs_ali_oss::types::common::{
+    BucketName, ObjectAcl, ObjectKey, ObjectType, ServerSideEncryption, StorageClass,
+};
 use rs_ali_oss::types::request::{
-    CopyObjectRequestBuilder, DeleteMultipleObjectsRequestBuilder, DeleteObjectRequestBuilder,
-    GetObjectRequestBuilder, HeadObjectRequestBuilder, ListObjectsV2RequestBuilder,
-    PutObjectRequestBuilder,
+    AppendObjectRequestBuilder, CopyObjectRequestBuilder, DeleteMultipleObjectsRequestBuilder,
+    DeleteObjectRequestBuilder, GetObjectRequestBuilder, HeadObjectRequestBuilder,
+    ListObjectsV2RequestBuilder, PutObjectRequestBuilder,
 };
-use wiremock::matchers::{method, path, query_param};
+use rs_ali_oss::types::response::GetObjectResult;
+use wiremock::matchers::{header, header_regex, method, path, query_param, query_param_is_missing};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 /// Create an `OssClient` that points at the given mock server.
@@ -28,135 +34,1768 @@ fn mock_client(server: &MockServer) -> OssClient {
 // ---- PutObject ----
 
 #[tokio::test]
-async fn put_object_returns_etag_and_request_id() {
-    let server = MockServer::start().await;
-
-    Mock::given(method("PUT"))
-        .and(path("/hello.txt"))
-        .respond_with(
-            ResponseTemplate::new(200)
-                .insert_header("etag", "\"abc123\"")
-                .insert_header("x-oss-request-id", "REQ-001"),
-        )
-        .expect(1)
-        .mount(&server)
-        .await;
+async fn put_object_returns_etag_and_request_id() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/hello.txt"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("etag", "\"abc123\"")
+                .insert_header("x-oss-request-id", "REQ-001"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = PutObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("hello.txt").unwrap())
+        .body(b"Hello, OSS!".to_vec())
+        .content_type("text/plain")
+        .build()
+        .unwrap();
+
+    let response = client.put_object(request).await.unwrap();
+    assert_eq!(response.etag, "abc123");
+    assert_eq!(response.request_id.as_deref(), Some("REQ-001"));
+}
+
+#[tokio::test]
+async fn put_object_returns_version_crc64_and_encryption() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/versioned.txt"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("etag", "\"abc123\"")
+                .insert_header("x-oss-hash-crc64ecma", "5981764153023615706")
+                .insert_header("x-oss-version-id", "CAEQNhiBgMDJgZCA0BYiIDc4MGZjZGI2")
+                .insert_header("x-oss-server-side-encryption", "KMS")
+                .insert_header("x-oss-server-side-encryption-key-id", "kms-key-1")
+                .insert_header(
+                    "x-oss-expiration",
+                    "expiry-date=\"Sat, 01 Jan 2022 00:00:00 GMT\", rule-id=\"expire-logs\"",
+                ),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = PutObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("versioned.txt").unwrap())
+        .body(b"Hello, OSS!".to_vec())
+        .build()
+        .unwrap();
+
+    let response = client.put_object(request).await.unwrap();
+    assert_eq!(response.crc64, Some(5981764153023615706));
+    assert_eq!(
+        response.version_id.as_deref(),
+        Some("CAEQNhiBgMDJgZCA0BYiIDc4MGZjZGI2")
+    );
+    assert_eq!(
+        response.server_side_encryption,
+        Some(ServerSideEncryption::KMS)
+    );
+    assert_eq!(response.kms_key_id.as_deref(), Some("kms-key-1"));
+    let expiration = response.expiration.unwrap();
+    assert_eq!(expiration.rule_id, "expire-logs");
+    assert_eq!(
+        expiration.expiry_date.to_rfc3339(),
+        "2022-01-01T00:00:00+00:00"
+    );
+}
+
+#[tokio::test]
+async fn put_object_with_v1_signature() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/legacy.txt"))
+        .and(header_regex(
+            "authorization",
+            "^OSS test-key-id:[A-Za-z0-9+/]+=*$",
+        ))
+        .and(header_regex("date", "GMT$"))
+        .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"v1\""))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .signature_version(rs_ali_oss::SignatureVersion::V1)
+            .max_retries(0),
+    )
+    .unwrap();
+    let request = PutObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("legacy.txt").unwrap())
+        .body(b"v1".to_vec())
+        .build()
+        .unwrap();
+
+    let response = client.put_object(request).await.unwrap();
+    assert_eq!(response.etag, "v1");
+}
+
+#[tokio::test]
+async fn put_object_with_custom_signer() {
+    use futures_util::future::BoxFuture;
+    use rs_ali_oss::{Signer, SigningContext, SigningDetails};
+
+    /// Stands in for a signing proxy: signs asynchronously.
+    struct ProxySigner;
+
+    impl Signer for ProxySigner {
+        fn sign(
+            &self,
+            _request: &mut reqwest::Request,
+            _context: &SigningContext<'_>,
+        ) -> rs_ali_oss::Result<Option<SigningDetails>> {
+            unreachable!("the client signs through sign_async")
+        }
+
+        fn sign_async<'a>(
+            &'a self,
+            request: &'a mut reqwest::Request,
+            context: &'a SigningContext<'a>,
+        ) -> BoxFuture<'a, rs_ali_oss::Result<Option<SigningDetails>>> {
+            Box::pin(async move {
+                tokio::task::yield_now().await;
+                let value = format!(
+                    "PROXY {}:{}",
+                    context.credentials.access_key_id(),
+                    context.resource_path
+                );
+                request
+                    .headers_mut()
+                    .insert("authorization", value.parse().unwrap());
+                Ok(None)
+            })
+        }
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path("/proxied.txt"))
+        .and(header(
+            "authorization",
+            "PROXY test-key-id:/my-bucket/proxied.txt",
+        ))
+        .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"p\""))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .signer(Arc::new(ProxySigner))
+            .max_retries(0),
+    )
+    .unwrap();
+    let request = PutObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("proxied.txt").unwrap())
+        .body(b"p".to_vec())
+        .build()
+        .unwrap();
+
+    let response = client.put_object(request).await.unwrap();
+    assert_eq!(response.etag, "p");
+}
+
+#[tokio::test]
+async fn interceptor_receives_signing_details_when_enabled() {
+    use std::sync::{Arc, Mutex};
+
+    use rs_ali_oss::{Interceptor, InterceptorContext, SigningDetails};
+
+    #[derive(Default)]
+    struct Capture(Mutex<Vec<Option<SigningDetails>>>);
+
+    impl Interceptor for Capture {
+        fn name(&self) -> &str {
+            "capture"
+        }
+
+        fn before_request(&self, ctx: &InterceptorContext) -> Result<(), String> {
+            self.0.lock().unwrap().push(ctx.signing.clone());
+            Ok(())
+        }
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&server)
+        .await;
+
+    for expose in [false, true] {
+        let capture = Arc::new(Capture::default());
+        let client = OssClient::from_builder(
+            ClientBuilder::new()
+                .access_key_id("test-key-id")
+                .access_key_secret("test-key-secret")
+                .region("cn-hangzhou")
+                .endpoint(server.uri())
+                .allow_insecure(true)
+                .expose_signing_details(expose)
+                .max_retries(0),
+        )
+        .unwrap()
+        .interceptor(capture.clone());
+        let request = DeleteObjectRequestBuilder::new()
+            .bucket(BucketName::new("my-bucket").unwrap())
+            .key(ObjectKey::new("debug.txt").unwrap())
+            .build()
+            .unwrap();
+        client.delete_object(request).await.unwrap();
+
+        let captured = capture.0.lock().unwrap();
+        match &captured[..] {
+            [None] => assert!(!expose),
+            [Some(details)] => {
+                assert!(expose);
+                assert!(
+                    details
+                        .canonical_request
+                        .starts_with("DELETE\n/my-bucket/debug.txt\n")
+                );
+                assert!(details.string_to_sign.starts_with("OSS4-HMAC-SHA256\n"));
+            }
+            other => panic!("unexpected capture: {other:?}"),
+        }
+    }
+}
+
+#[tokio::test]
+async fn rate_limit_interceptor_paces_requests() {
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    use rs_ali_oss::RateLimitInterceptor;
+
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let limiter = RateLimitInterceptor::builder()
+        .requests_per_second(20.0)
+        .build()
+        .unwrap();
+    let client = mock_client(&server).interceptor(Arc::new(limiter));
+
+    let start = Instant::now();
+    for _ in 0..3 {
+        let request = DeleteObjectRequestBuilder::new()
+            .bucket(BucketName::new("my-bucket").unwrap())
+            .key(ObjectKey::new("paced.txt").unwrap())
+            .build()
+            .unwrap();
+        client.delete_object(request).await.unwrap();
+    }
+    // The first request is free; the next two wait 50ms each.
+    assert!(start.elapsed() >= Duration::from_millis(100));
+}
+
+#[tokio::test]
+async fn interceptor_receives_response_metadata() {
+    use std::sync::{Arc, Mutex};
+
+    use rs_ali_oss::{Interceptor, InterceptorContext, RequestOutcome};
+
+    type Captured = (Option<String>, Option<String>, Option<String>);
+
+    #[derive(Default)]
+    struct Capture(Mutex<Vec<Captured>>);
+
+    impl Interceptor for Capture {
+        fn name(&self) -> &str {
+            "capture"
+        }
+
+        fn after_request(&self, _ctx: &InterceptorContext, outcome: &RequestOutcome) {
+            let server = outcome
+                .headers
+                .get("server")
+                .map(|v| v.to_str().unwrap().to_string());
+            self.0.lock().unwrap().push((
+                outcome.request_id.clone(),
+                outcome.error_code.clone(),
+                server,
+            ));
+        }
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .respond_with(
+            ResponseTemplate::new(204)
+                .insert_header("x-oss-request-id", "REQ-OK")
+                .insert_header("server", "AliyunOSS"),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(404).set_body_string(
+            r#"<Error><Code>NoSuchKey</Code><Message>missing</Message>
+            <RequestId>REQ-ERR</RequestId><HostId>h</HostId></Error>"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let capture = Arc::new(Capture::default());
+    let client = mock_client(&server).interceptor(capture.clone());
+    let request = DeleteObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("meta.txt").unwrap())
+        .build()
+        .unwrap();
+    client.delete_object(request).await.unwrap();
+    let request = GetObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("meta.txt").unwrap())
+        .build()
+        .unwrap();
+    client.get_object(request).await.unwrap_err();
+
+    let captured = capture.0.lock().unwrap();
+    assert_eq!(
+        captured[0],
+        (Some("REQ-OK".into()), None, Some("AliyunOSS".into()))
+    );
+    // Without the header, the request ID comes from the error body.
+    assert_eq!(
+        captured[1],
+        (Some("REQ-ERR".into()), Some("NoSuchKey".into()), None)
+    );
+}
+
+#[tokio::test]
+async fn metrics_interceptor_counts_bytes() {
+    use std::sync::Arc;
+
+    use rs_ali_oss::MetricsInterceptor;
+
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"e\""))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"0123456789".to_vec()))
+        .mount(&server)
+        .await;
+
+    let metrics = Arc::new(MetricsInterceptor::new());
+    let client = mock_client(&server).interceptor(metrics.clone());
+    let request = PutObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("bytes.txt").unwrap())
+        .body(b"Hello, OSS!".to_vec())
+        .build()
+        .unwrap();
+    client.put_object(request).await.unwrap();
+    let request = GetObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("bytes.txt").unwrap())
+        .build()
+        .unwrap();
+    client.get_object(request).await.unwrap();
+
+    let snapshot = metrics.snapshot();
+    assert_eq!(snapshot.operations["PUT"].bytes_sent, 11);
+    assert_eq!(snapshot.operations["PUT"].bytes_received, 0);
+    assert_eq!(snapshot.operations["GET"].bytes_sent, 0);
+    assert_eq!(snapshot.operations["GET"].bytes_received, 10);
+}
+
+#[tokio::test]
+async fn interceptors_can_be_replaced_by_name_and_ordered_by_priority() {
+    use std::sync::Arc;
+
+    use rs_ali_oss::{Interceptor, InterceptorContext, MetricsInterceptor};
+
+    /// Records its label before each attempt, under the name `name`.
+    struct Capture {
+        name: &'static str,
+        label: &'static str,
+        log: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    impl Interceptor for Capture {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn before_request(&self, _ctx: &InterceptorContext) -> Result<(), String> {
+            self.log.lock().unwrap().push(self.label);
+            Ok(())
+        }
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&server)
+        .await;
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let capture = |name, label| {
+        Arc::new(Capture {
+            name,
+            label,
+            log: log.clone(),
+        })
+    };
+    let metrics = Arc::new(MetricsInterceptor::new());
+    let client = mock_client(&server)
+        .interceptor(capture("audit", "audit"))
+        .interceptor(metrics.clone())
+        .interceptor(capture("debug", "debug"))
+        .interceptor_with_priority(capture("auth", "auth"), -1)
+        .replace_interceptor("metrics", capture("metrics", "metrics-capture"))
+        .without_interceptor("debug");
+    assert_eq!(client.interceptor_names(), ["auth", "audit", "metrics"]);
+
+    let request = DeleteObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("file.txt").unwrap())
+        .build()
+        .unwrap();
+    client.delete_object(request).await.unwrap();
+
+    assert_eq!(*log.lock().unwrap(), ["auth", "audit", "metrics-capture"]);
+    assert!(metrics.snapshot().operations.is_empty());
+}
+
+#[tokio::test]
+async fn async_interceptor_hooks_are_awaited() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+
+    use futures_util::future::BoxFuture;
+    use rs_ali_oss::{Interceptor, InterceptorContext, RequestOutcome};
+
+    /// Asks an (async) quota service before each attempt and reports afterwards.
+    #[derive(Default)]
+    struct RemoteQuota {
+        granted: AtomicU32,
+        reported: AtomicU32,
+    }
+
+    impl Interceptor for RemoteQuota {
+        fn name(&self) -> &str {
+            "remote_quota"
+        }
+
+        fn before_request_async<'a>(
+            &'a self,
+            ctx: &'a InterceptorContext,
+        ) -> BoxFuture<'a, Result<(), String>> {
+            Box::pin(async move {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                if ctx.url.contains("denied") {
+                    return Err("quota exceeded".to_string());
+                }
+                self.granted.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            })
+        }
+
+        fn after_request_async<'a>(
+            &'a self,
+            _ctx: &'a InterceptorContext,
+            _outcome: &'a RequestOutcome,
+        ) -> BoxFuture<'a, ()> {
+            Box::pin(async move {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                self.reported.fetch_add(1, Ordering::SeqCst);
+            })
+        }
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let quota = Arc::new(RemoteQuota::default());
+    let client = mock_client(&server).interceptor(quota.clone());
+    let delete = |key: &str| {
+        DeleteObjectRequestBuilder::new()
+            .bucket(BucketName::new("my-bucket").unwrap())
+            .key(ObjectKey::new(key).unwrap())
+            .build()
+            .unwrap()
+    };
+
+    client.delete_object(delete("allowed.txt")).await.unwrap();
+    assert_eq!(quota.granted.load(Ordering::SeqCst), 1);
+    assert_eq!(quota.reported.load(Ordering::SeqCst), 1);
+
+    let err = client
+        .delete_object(delete("denied.txt"))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("quota exceeded"), "error: {err}");
+    assert_eq!(quota.reported.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn resolve_override_pins_host_to_address() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/pinned.txt"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // `oss.invalid` cannot resolve through DNS; the override routes the
+    // virtual-hosted bucket host to the mock server instead.
+    let port = server.address().port();
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(format!("http://oss.invalid:{port}"))
+            .allow_insecure(true)
+            .resolve("my-bucket.oss.invalid", *server.address())
+            .max_retries(0),
+    )
+    .unwrap();
+    let request = DeleteObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("pinned.txt").unwrap())
+        .build()
+        .unwrap();
+    client.delete_object(request).await.unwrap();
+}
+
+#[tokio::test]
+async fn forced_http_versions_reach_server() {
+    use rs_ali_oss::HttpVersion;
+
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    for version in [HttpVersion::Http1Only, HttpVersion::Http2PriorKnowledge] {
+        let client = OssClient::from_builder(
+            ClientBuilder::new()
+                .access_key_id("test-key-id")
+                .access_key_secret("test-key-secret")
+                .region("cn-hangzhou")
+                .endpoint(server.uri())
+                .allow_insecure(true)
+                .http_version(version)
+                .http2_adaptive_window(true)
+                .max_retries(0),
+        )
+        .unwrap();
+        let request = DeleteObjectRequestBuilder::new()
+            .bucket(BucketName::new("my-bucket").unwrap())
+            .key(ObjectKey::new("proto.txt").unwrap())
+            .build()
+            .unwrap();
+        client.delete_object(request).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn get_object_with_options_sends_traffic_limit_and_headers() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/limited.bin"))
+        .and(header("x-oss-traffic-limit", "819200"))
+        .and(header("x-oss-request-payer", "requester"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"data"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let options = OperationOptions::builder()
+        .traffic_limit(819_200)
+        .header("x-oss-request-payer", "requester")
+        .build()
+        .unwrap();
+    let request = GetObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("limited.bin").unwrap())
+        .build()
+        .unwrap();
+
+    let response = client
+        .get_object_with_options(request, options)
+        .await
+        .unwrap();
+    assert_eq!(response.body.bytes().await.unwrap().as_ref(), b"data");
+}
+
+#[tokio::test]
+async fn repeated_headers_send_every_value() {
+    let server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .default_header("x-cost-center", "storage")
+            .default_header("x-cost-center", "analytics")
+            .max_retries(0),
+    )
+    .unwrap();
+    let options = OperationOptions::builder()
+        .header("x-request-tag", "first")
+        .header("x-request-tag", "second")
+        .build()
+        .unwrap();
+    let head = HeadObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("tagged.txt").unwrap())
+        .build()
+        .unwrap();
+    client
+        .with_options(options)
+        .head_object(head)
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let values = |name: &str| -> Vec<String> {
+        requests[0]
+            .headers
+            .get_all(name)
+            .iter()
+            .map(|v| v.to_str().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(values("x-cost-center"), ["storage", "analytics"]);
+    assert_eq!(values("x-request-tag"), ["first", "second"]);
+}
+
+#[tokio::test]
+async fn default_headers_are_sent_with_every_request() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("HEAD"))
+        .and(path("/routed.txt"))
+        .and(header("x-gateway-route", "blue"))
+        .and(header("x-oss-meta-cost-center", "storage"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/routed.txt"))
+        .and(header("x-gateway-route", "green"))
+        .and(header("x-oss-meta-cost-center", "storage"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"data"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .default_header("x-gateway-route", "blue")
+            .default_header("x-oss-meta-cost-center", "storage")
+            .max_retries(0),
+    )
+    .unwrap();
+    let head = HeadObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("routed.txt").unwrap())
+        .build()
+        .unwrap();
+    client.head_object(head).await.unwrap();
+
+    // Per-operation headers take precedence over the defaults.
+    let options = OperationOptions::builder()
+        .header("x-gateway-route", "green")
+        .build()
+        .unwrap();
+    let get = GetObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("routed.txt").unwrap())
+        .build()
+        .unwrap();
+    client.get_object_with_options(get, options).await.unwrap();
+}
+
+#[tokio::test]
+async fn user_agent_suffix_is_sent() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("HEAD"))
+        .and(path("/ua.txt"))
+        .and(header_regex(
+            "user-agent",
+            r"^rs-ali-oss/\d+\.\d+\.\d+ myapp/1\.2\.3$",
+        ))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .user_agent_suffix("myapp/1.2.3")
+            .max_retries(0),
+    )
+    .unwrap();
+    let request = HeadObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("ua.txt").unwrap())
+        .build()
+        .unwrap();
+
+    client.head_object(request).await.unwrap();
+}
+
+#[tokio::test]
+async fn max_in_flight_requests_limits_concurrency() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("HEAD"))
+        .respond_with(ResponseTemplate::new(200).set_delay(std::time::Duration::from_millis(300)))
+        .expect(4)
+        .mount(&server)
+        .await;
+
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .max_in_flight_requests(2)
+            .max_retries(0),
+    )
+    .unwrap();
+
+    let start = std::time::Instant::now();
+    let tasks: Vec<_> = (0..4)
+        .map(|i| {
+            let client = client.clone();
+            tokio::spawn(async move {
+                let request = HeadObjectRequestBuilder::new()
+                    .bucket(BucketName::new("my-bucket").unwrap())
+                    .key(ObjectKey::new(format!("object-{i}")).unwrap())
+                    .build()
+                    .unwrap();
+                client.head_object(request).await.unwrap();
+            })
+        })
+        .collect();
+
+    tokio::time::sleep(std::time::Duration::from_millis(150)).await;
+    assert_eq!(client.in_flight_requests(), 2);
+    for task in tasks {
+        task.await.unwrap();
+    }
+    assert!(start.elapsed() >= std::time::Duration::from_millis(600));
+    assert_eq!(client.in_flight_requests(), 0);
+}
+
+#[tokio::test]
+async fn throttled_requests_do_not_hold_in_flight_slots() {
+    use rs_ali_oss::RateLimitInterceptor;
+
+    let server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let limiter = RateLimitInterceptor::builder()
+        .requests_per_second(2.0)
+        .build()
+        .unwrap();
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .max_in_flight_requests(1)
+            .max_retries(0),
+    )
+    .unwrap()
+    .interceptor(Arc::new(limiter));
+
+    let tasks: Vec<_> = (0..2)
+        .map(|i| {
+            let client = client.clone();
+            tokio::spawn(async move {
+                let request = HeadObjectRequestBuilder::new()
+                    .bucket(BucketName::new("my-bucket").unwrap())
+                    .key(ObjectKey::new(format!("object-{i}")).unwrap())
+                    .build()
+                    .unwrap();
+                client.head_object(request).await.unwrap();
+            })
+        })
+        .collect();
+
+    // One request has finished; the other is waiting out the rate limit.
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    assert_eq!(client.in_flight_requests(), 0);
+    for task in tasks {
+        task.await.unwrap();
+    }
+}
+
+#[cfg(feature = "mime_guess")]
+#[tokio::test]
+async fn put_object_infers_content_type_from_key() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/site/index.html"))
+        .and(header("content-type", "text/html"))
+        .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"h\""))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/site/app.js"))
+        .and(header("content-type", "text/plain"))
+        .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"j\""))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let put = |key: &str| {
+        PutObjectRequestBuilder::new()
+            .bucket(BucketName::new("my-bucket").unwrap())
+            .key(ObjectKey::new(key).unwrap())
+            .body(b"x".to_vec())
+    };
+    client
+        .put_object(put("site/index.html").build().unwrap())
+        .await
+        .unwrap();
+    // An explicit content type always wins.
+    client
+        .put_object(
+            put("site/app.js")
+                .content_type("text/plain")
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn put_object_content_type_detection_can_be_disabled() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/index.html"))
+        .and(header_regex("content-type", ".+"))
+        .respond_with(ResponseTemplate::new(400))
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/index.html"))
+        .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"h\""))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .detect_content_type(false)
+            .max_retries(0),
+    )
+    .unwrap();
+    let request = PutObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("index.html").unwrap())
+        .body(b"<html></html>".to_vec())
+        .build()
+        .unwrap();
+
+    client.put_object(request).await.unwrap();
+}
+
+#[tokio::test]
+async fn non_ascii_metadata_is_rejected_by_default() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = PutObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("menu.txt").unwrap())
+        .body(b"x".to_vec())
+        .metadata("title", "café")
+        .build()
+        .unwrap();
+
+    let err = client.put_object(request).await.unwrap_err();
+    assert!(
+        matches!(err, rs_ali_oss::OssError::InvalidParameter { .. }),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn non_ascii_metadata_round_trips_with_rfc2047() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/menu.txt"))
+        .and(header("x-oss-meta-title", "=?UTF-8?B?Y2Fmw6k=?="))
+        .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"m\""))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("HEAD"))
+        .and(path("/menu.txt"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("x-oss-meta-title", "=?UTF-8?B?Y2Fmw6k=?=")
+                .insert_header("x-oss-meta-plain", "ascii"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .metadata_encoding(MetadataEncoding::Rfc2047)
+            .max_retries(0),
+    )
+    .unwrap();
+    let request = PutObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("menu.txt").unwrap())
+        .body(b"x".to_vec())
+        .metadata("title", "café")
+        .build()
+        .unwrap();
+    client.put_object(request).await.unwrap();
+
+    let request = HeadObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("menu.txt").unwrap())
+        .build()
+        .unwrap();
+    let response = client.head_object(request).await.unwrap();
+    assert_eq!(response.metadata["title"], "café");
+    assert_eq!(response.metadata["plain"], "ascii");
+}
+
+#[tokio::test]
+async fn put_object_sends_tagging_header() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/tagged.txt"))
+        .and(header("x-oss-tagging", "team=storage&note=a%20b%2Fc"))
+        .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"t\""))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = PutObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("tagged.txt").unwrap())
+        .body(b"x".to_vec())
+        .tag("team", "storage")
+        .tag("note", "a b/c")
+        .build()
+        .unwrap();
+
+    client.put_object(request).await.unwrap();
+}
+
+#[tokio::test]
+async fn put_object_with_metadata_sends_request() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/doc.pdf"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("etag", "\"meta-etag\"")
+                .insert_header("x-oss-request-id", "REQ-META"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = PutObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("doc.pdf").unwrap())
+        .body(b"PDF content".to_vec())
+        .content_type("application/pdf")
+        .metadata("author", "alice")
+        .build()
+        .unwrap();
+
+    let response = client.put_object(request).await.unwrap();
+    assert_eq!(response.etag, "meta-etag");
+}
+
+#[tokio::test]
+async fn put_object_sends_bytes_and_sized_streams() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/shared.bin"))
+        .and(header("content-length", "5"))
+        .and(wiremock::matchers::body_bytes(b"bytes".to_vec()))
+        .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"b\""))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/streamed.bin"))
+        .and(header("content-length", "12"))
+        .and(wiremock::matchers::body_bytes(b"hello, world".to_vec()))
+        .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"s\""))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = PutObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("shared.bin").unwrap())
+        .body_bytes(bytes::Bytes::from_static(b"bytes"))
+        .build()
+        .unwrap();
+    assert_eq!(client.put_object(request).await.unwrap().etag, "b");
+
+    let chunks: Vec<Result<bytes::Bytes, std::io::Error>> = vec![
+        Ok(bytes::Bytes::from_static(b"hello, ")),
+        Ok(bytes::Bytes::from_static(b"world")),
+    ];
+    let request = PutObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("streamed.bin").unwrap())
+        .body_stream(futures_util::stream::iter(chunks), 12)
+        .build()
+        .unwrap();
+    assert_eq!(client.put_object(request).await.unwrap().etag, "s");
+}
+
+// ---- AppendObject ----
+
+#[tokio::test]
+async fn append_object_sends_initial_object_headers() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/app.log"))
+        .and(query_param("append", ""))
+        .and(query_param("position", "0"))
+        .and(header("cache-control", "no-cache"))
+        .and(header("x-oss-storage-class", "IA"))
+        .and(header("x-oss-object-acl", "private"))
+        .and(header("x-oss-server-side-encryption", "AES256"))
+        .and(header("x-oss-meta-source", "agent"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("x-oss-next-append-position", "8")
+                .insert_header("x-oss-hash-crc64ecma", "123"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = AppendObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("app.log").unwrap())
+        .position(0)
+        .body(b"log line".to_vec())
+        .cache_control("no-cache")
+        .storage_class(StorageClass::InfrequentAccess)
+        .acl(ObjectAcl::Private)
+        .server_side_encryption(ServerSideEncryption::AES256)
+        .metadata("source", "agent")
+        .build()
+        .unwrap();
+
+    let response = client.append_object(request).await.unwrap();
+    assert_eq!(response.next_append_position, 8);
+    assert_eq!(response.crc64, Some(123));
+}
+// ---- GetObject ----
+
+#[tokio::test]
+async fn put_and_get_json_and_text_objects() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/settings.json"))
+        .and(header("content-type", "application/json"))
+        .and(wiremock::matchers::body_json(
+            serde_json::json!({ "theme": "dark" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"j\""))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/notes.txt"))
+        .and(header_regex("content-type", "^text/plain; charset=utf-8$"))
+        .and(wiremock::matchers::body_bytes(b"hello".to_vec()))
+        .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"t\""))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/settings.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"theme":"dark"}"#))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let bucket = BucketName::new("my-bucket").unwrap();
+    let response = client
+        .put_object_json(
+            bucket.clone(),
+            ObjectKey::new("settings.json").unwrap(),
+            &serde_json::json!({ "theme": "dark" }),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.etag, "j");
+    client
+        .put_object_text(
+            bucket.clone(),
+            ObjectKey::new("notes.txt").unwrap(),
+            "hello",
+        )
+        .await
+        .unwrap();
+
+    let request = GetObjectRequestBuilder::new()
+        .bucket(bucket)
+        .key(ObjectKey::new("settings.json").unwrap())
+        .build()
+        .unwrap();
+    let settings: serde_json::Value = client
+        .get_object(request)
+        .await
+        .unwrap()
+        .body
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(settings["theme"], "dark");
+}
+
+#[tokio::test]
+async fn get_object_returns_body_and_headers() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/hello.txt"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/plain")
+                .insert_header("content-length", "11")
+                .insert_header("etag", "\"get-etag\"")
+                .set_body_bytes(b"Hello World"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = GetObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("hello.txt").unwrap())
+        .build()
+        .unwrap();
+
+    let response = client.get_object(request).await.unwrap();
+    assert_eq!(response.content_type.as_deref(), Some("text/plain"));
+    assert_eq!(response.content_length, Some(11));
+    assert_eq!(response.etag.as_deref(), Some("get-etag"));
+
+    let body = response.body.bytes().await.unwrap();
+    assert_eq!(&body[..], b"Hello World");
+}
+
+#[tokio::test]
+async fn get_object_parses_storage_and_restore_headers() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/frozen.bin"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("x-oss-storage-class", "Archive")
+                .insert_header("x-oss-object-type", "Multipart")
+                .insert_header("x-oss-tagging-count", "3")
+                .insert_header(
+                    "x-oss-restore",
+                    "ongoing-request=\"false\", expiry-date=\"Sun, 16 Apr 2017 08:12:33 GMT\"",
+                )
+                .insert_header(
+                    "x-oss-expiration",
+                    "expiry-date=\"Mon, 01 May 2017 00:00:00 GMT\", rule-id=\"archive-cleanup\"",
+                )
+                .set_body_bytes(b"thawed"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = GetObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("frozen.bin").unwrap())
+        .build()
+        .unwrap();
+
+    let response = client.get_object(request).await.unwrap();
+    assert_eq!(response.storage_class, Some(StorageClass::Archive));
+    assert_eq!(response.object_type, Some(ObjectType::Multipart));
+    assert_eq!(response.tagging_count, Some(3));
+    let restore = response.restore.unwrap();
+    assert!(restore.is_restored());
+    assert_eq!(
+        restore.expiry_date.unwrap().to_rfc3339(),
+        "2017-04-16T08:12:33+00:00"
+    );
+    let expiration = response.expiration.unwrap();
+    assert_eq!(expiration.rule_id, "archive-cleanup");
+    assert_eq!(
+        expiration.expiry_date.to_rfc3339(),
+        "2017-05-01T00:00:00+00:00"
+    );
+}
+
+#[tokio::test]
+async fn get_object_with_range_sends_request() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/big-file.bin"))
+        .respond_with(
+            ResponseTemplate::new(206)
+                .insert_header("content-type", "application/octet-stream")
+                .insert_header("content-length", "100")
+                .set_body_bytes(vec![0u8; 100]),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = GetObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("big-file.bin").unwrap())
+        .range("bytes=0-99")
+        .build()
+        .unwrap();
+
+    let response = client.get_object(request).await.unwrap();
+    assert_eq!(response.content_length, Some(100));
+}
+
+#[tokio::test]
+async fn get_object_reports_download_progress() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/big-file.bin"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![7u8; 4096]))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&seen);
+    let client = mock_client(&server);
+    let request = GetObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("big-file.bin").unwrap())
+        .progress_listener(Arc::new(move |p: &TransferProgress| {
+            sink.lock().unwrap().push(p.clone());
+        }))
+        .build()
+        .unwrap();
+
+    let response = client.get_object(request).await.unwrap();
+    let body = response.body.bytes().await.unwrap();
+    assert_eq!(body.len(), 4096);
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.first().unwrap().bytes_transferred, 0);
+    let last = seen.last().unwrap();
+    assert_eq!(last.bytes_transferred, 4096);
+    assert_eq!(last.total_bytes, Some(4096));
+    assert_eq!(last.kind, TransferKind::Download);
+}
+
+#[tokio::test]
+async fn get_object_body_streams_into_a_writer() {
+    let server = MockServer::start().await;
+
+    let data: Vec<u8> = (0..=255u8).cycle().take(100_000).collect();
+    Mock::given(method("GET"))
+        .and(path("/big-file.bin"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(data.clone()))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = || {
+        GetObjectRequestBuilder::new()
+            .bucket(BucketName::new("my-bucket").unwrap())
+            .key(ObjectKey::new("big-file.bin").unwrap())
+            .build()
+            .unwrap()
+    };
+
+    let mut sink = Vec::new();
+    let response = client.get_object(request()).await.unwrap();
+    let written = response.body.write_to(&mut sink).await.unwrap();
+    assert_eq!(written, data.len() as u64);
+    assert_eq!(sink, data);
+
+    let mut sink = std::io::Cursor::new(Vec::new());
+    let response = client.get_object(request()).await.unwrap();
+    let written = response.save_to_writer(&mut sink).await.unwrap();
+    assert_eq!(written, data.len() as u64);
+    assert_eq!(sink.into_inner(), data);
+}
+
+#[tokio::test]
+async fn get_object_body_is_an_async_reader() {
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt};
+
+    let server = MockServer::start().await;
+
+    let csv: String = (0..1000).map(|i| format!("{i},row-{i}\n")).collect();
+    Mock::given(method("GET"))
+        .and(path("/rows.csv"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(csv.clone()))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = || {
+        GetObjectRequestBuilder::new()
+            .bucket(BucketName::new("my-bucket").unwrap())
+            .key(ObjectKey::new("rows.csv").unwrap())
+            .build()
+            .unwrap()
+    };
+
+    let mut lines = client.get_object(request()).await.unwrap().body.lines();
+    let mut count = 0;
+    while let Some(line) = lines.next_line().await.unwrap() {
+        assert_eq!(line, format!("{count},row-{count}"));
+        count += 1;
+    }
+    assert_eq!(count, 1000);
+
+    // Small reads split chunks; nothing is lost at the seams.
+    let mut body = client.get_object(request()).await.unwrap().body;
+    let mut all = Vec::new();
+    let mut buf = [0u8; 7];
+    loop {
+        let n = body.read(&mut buf).await.unwrap();
+        if n == 0 {
+            break;
+        }
+        all.extend_from_slice(&buf[..n]);
+    }
+    assert_eq!(all, csv.as_bytes());
+}
+
+#[tokio::test]
+async fn get_object_conditional_returns_not_modified() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/index.html"))
+        .and(header("if-none-match", "\"cached-etag\""))
+        .and(header_regex(
+            "if-modified-since",
+            "^Sat, 01 Feb 2025 12:00:00 GMT$",
+        ))
+        .respond_with(
+            ResponseTemplate::new(304)
+                .insert_header("etag", "\"cached-etag\"")
+                .insert_header("x-oss-request-id", "req-304"),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = || {
+        GetObjectRequestBuilder::new()
+            .bucket(BucketName::new("my-bucket").unwrap())
+            .key(ObjectKey::new("index.html").unwrap())
+            .if_none_match("cached-etag")
+            .if_modified_since("2025-02-01T12:00:00Z".parse().unwrap())
+            .build()
+            .unwrap()
+    };
+
+    match client.get_object_conditional(request()).await.unwrap() {
+        GetObjectResult::NotModified { etag, request_id } => {
+            assert_eq!(etag.as_deref(), Some("cached-etag"));
+            assert_eq!(request_id.as_deref(), Some("req-304"));
+        }
+        GetObjectResult::Modified(response) => panic!("unexpected body: {response:?}"),
+    }
+
+    let err = client.get_object(request()).await.unwrap_err();
+    match err {
+        rs_ali_oss::OssError::ServerError {
+            status, request_id, ..
+        } => {
+            assert_eq!(status, 304);
+            assert_eq!(request_id, "req-304");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn get_object_conditional_returns_changed_object() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/index.html"))
+        .and(header("if-none-match", "\"old-etag\""))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("etag", "\"new-etag\"")
+                .set_body_string("fresh"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = GetObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("index.html").unwrap())
+        .if_none_match("\"old-etag\"")
+        .build()
+        .unwrap();
+
+    let GetObjectResult::Modified(response) = client.get_object_conditional(request).await.unwrap()
+    else {
+        panic!("expected the changed object");
+    };
+    assert_eq!(response.etag.as_deref(), Some("new-etag"));
+    assert_eq!(response.body.text().await.unwrap(), "fresh");
+}
+
+#[cfg(feature = "decompression")]
+async fn compress(data: &[u8], gzip: bool) -> Vec<u8> {
+    use async_compression::tokio::bufread::{GzipEncoder, ZlibEncoder};
+    use tokio::io::AsyncReadExt;
+
+    let mut out = Vec::new();
+    if gzip {
+        GzipEncoder::new(data).read_to_end(&mut out).await.unwrap();
+    } else {
+        ZlibEncoder::new(data).read_to_end(&mut out).await.unwrap();
+    }
+    out
+}
+
+#[cfg(feature = "decompression")]
+#[tokio::test]
+async fn get_object_decodes_content_encoding() {
+    let server = MockServer::start().await;
+    let text = "line\n".repeat(10_000);
+    let gzipped = compress(text.as_bytes(), true).await;
+    let deflated = compress(text.as_bytes(), false).await;
+
+    for (key, encoding, body) in [
+        ("app.log.gz", "gzip", &gzipped),
+        ("app.log.z", "deflate", &deflated),
+    ] {
+        Mock::given(method("GET"))
+            .and(path(format!("/{key}")))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-encoding", encoding)
+                    .insert_header(
+                        "x-oss-hash-crc64ecma",
+                        rs_ali_oss::crc64::checksum(body).to_string(),
+                    )
+                    .set_body_bytes(body.clone()),
+            )
+            .mount(&server)
+            .await;
+    }
+
+    let client = mock_client(&server);
+    let get = |key: &str| {
+        GetObjectRequestBuilder::new()
+            .bucket(BucketName::new("my-bucket").unwrap())
+            .key(ObjectKey::new(key).unwrap())
+    };
+    for key in ["app.log.gz", "app.log.z"] {
+        let request = get(key).decode_content(true).build().unwrap();
+        let response = client.get_object(request).await.unwrap();
+        assert_eq!(response.body.text().await.unwrap(), text, "{key}");
+    }
+
+    // Without opting in the stored bytes are returned as-is.
+    let request = get("app.log.gz").build().unwrap();
+    let response = client.get_object(request).await.unwrap();
+    assert_eq!(response.body.bytes().await.unwrap(), gzipped);
+}
+
+#[cfg(feature = "decompression")]
+#[tokio::test]
+async fn get_object_decode_checks_encoded_crc64() {
+    let server = MockServer::start().await;
+    let gzipped = compress(b"hello, oss", true).await;
+
+    Mock::given(method("GET"))
+        .and(path("/hello.gz"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-encoding", "gzip")
+                .insert_header("x-oss-hash-crc64ecma", "12345")
+                .set_body_bytes(gzipped),
+        )
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = GetObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("hello.gz").unwrap())
+        .decode_content(true)
+        .build()
+        .unwrap();
+    let response = client.get_object(request).await.unwrap();
+    assert!(response.body.bytes().await.is_err());
+}
+
+/// Serve one canned HTTP response per connection, then drop it, returning
+/// the endpoint and the request heads received.
+async fn serve_raw(responses: Vec<&'static str>) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    let handle = tokio::spawn(async move {
+        let mut heads = Vec::new();
+        for response in responses {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut head = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !head.ends_with(b"\r\n\r\n") {
+                let n = socket.read(&mut buf).await.unwrap();
+                head.extend_from_slice(&buf[..n]);
+            }
+            heads.push(String::from_utf8(head).unwrap().to_lowercase());
+            socket.write_all(response.as_bytes()).await.unwrap();
+        }
+        heads
+    });
+    (endpoint, handle)
+}
+
+fn raw_client(endpoint: String) -> OssClient {
+    OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(endpoint)
+            .allow_insecure(true)
+            .max_retries(0),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn get_object_resumes_body_after_connection_drop() {
+    let (endpoint, server) = serve_raw(vec![
+        "HTTP/1.1 200 OK\r\ncontent-length: 11\r\netag: \"v1\"\r\n\r\nHello",
+        "HTTP/1.1 206 Partial Content\r\ncontent-length: 6\r\netag: \"v1\"\r\n\
+         content-range: bytes 5-10/11\r\n\r\n World",
+    ])
+    .await;
 
-    let client = mock_client(&server);
-    let request = PutObjectRequestBuilder::new()
+    let request = GetObjectRequestBuilder::new()
         .bucket(BucketName::new("my-bucket").unwrap())
         .key(ObjectKey::new("hello.txt").unwrap())
-        .body(b"Hello, OSS!".to_vec())
-        .content_type("text/plain")
+        .resume_on_error(1)
         .build()
         .unwrap();
+    let response = raw_client(endpoint).get_object(request).await.unwrap();
+    let body = response.body.bytes().await.unwrap();
+    assert_eq!(&body[..], b"Hello World");
 
-    let response = client.put_object(request).await.unwrap();
-    assert_eq!(response.etag, "abc123");
-    assert_eq!(response.request_id.as_deref(), Some("REQ-001"));
+    let heads = server.await.unwrap();
+    assert!(heads[1].contains("range: bytes=5-10\r\n"));
+    assert!(heads[1].contains("if-match: \"v1\"\r\n"));
 }
 
 #[tokio::test]
-async fn put_object_with_metadata_sends_request() {
-    let server = MockServer::start().await;
-
-    Mock::given(method("PUT"))
-        .and(path("/doc.pdf"))
-        .respond_with(
-            ResponseTemplate::new(200)
-                .insert_header("etag", "\"meta-etag\"")
-                .insert_header("x-oss-request-id", "REQ-META"),
-        )
-        .expect(1)
-        .mount(&server)
-        .await;
+async fn get_object_resume_fails_when_object_changed() {
+    let (endpoint, _server) = serve_raw(vec![
+        "HTTP/1.1 200 OK\r\ncontent-length: 11\r\netag: \"v1\"\r\n\r\nHello",
+        "HTTP/1.1 412 Precondition Failed\r\ncontent-length: 0\r\n\r\n",
+    ])
+    .await;
 
-    let client = mock_client(&server);
-    let request = PutObjectRequestBuilder::new()
+    let request = GetObjectRequestBuilder::new()
         .bucket(BucketName::new("my-bucket").unwrap())
-        .key(ObjectKey::new("doc.pdf").unwrap())
-        .body(b"PDF content".to_vec())
-        .content_type("application/pdf")
-        .metadata("author", "alice")
+        .key(ObjectKey::new("hello.txt").unwrap())
+        .resume_on_error(3)
         .build()
         .unwrap();
-
-    let response = client.put_object(request).await.unwrap();
-    assert_eq!(response.etag, "meta-etag");
+    let response = raw_client(endpoint).get_object(request).await.unwrap();
+    assert!(response.body.bytes().await.is_err());
 }
 
-// ---- GetObject ----
+// ---- DeleteObject ----
 
 #[tokio::test]
-async fn get_object_returns_body_and_headers() {
+async fn delete_object_returns_request_id() {
     let server = MockServer::start().await;
 
-    Mock::given(method("GET"))
-        .and(path("/hello.txt"))
-        .respond_with(
-            ResponseTemplate::new(200)
-                .insert_header("content-type", "text/plain")
-                .insert_header("content-length", "11")
-                .insert_header("etag", "\"get-etag\"")
-                .set_body_bytes(b"Hello World"),
-        )
+    Mock::given(method("DELETE"))
+        .and(path("/old-file.txt"))
+        .respond_with(ResponseTemplate::new(204).insert_header("x-oss-request-id", "DEL-001"))
         .expect(1)
         .mount(&server)
         .await;
 
     let client = mock_client(&server);
-    let request = GetObjectRequestBuilder::new()
+    let request = DeleteObjectRequestBuilder::new()
         .bucket(BucketName::new("my-bucket").unwrap())
-        .key(ObjectKey::new("hello.txt").unwrap())
+        .key(ObjectKey::new("old-file.txt").unwrap())
         .build()
         .unwrap();
 
-    let response = client.get_object(request).await.unwrap();
-    assert_eq!(response.content_type.as_deref(), Some("text/plain"));
-    assert_eq!(response.content_length, Some(11));
-    assert_eq!(response.etag.as_deref(), Some("get-etag"));
-
-    let body = response.body.bytes().await.unwrap();
-    assert_eq!(&body[..], b"Hello World");
+    let response = client.delete_object(request).await.unwrap();
+    assert_eq!(response.request_id.as_deref(), Some("DEL-001"));
+    assert!(!response.delete_marker);
+    assert_eq!(response.version_id, None);
 }
 
 #[tokio::test]
-async fn get_object_with_range_sends_request() {
+async fn delete_object_reports_created_delete_marker() {
     let server = MockServer::start().await;
 
-    Mock::given(method("GET"))
-        .and(path("/big-file.bin"))
+    Mock::given(method("DELETE"))
+        .and(path("/old-file.txt"))
+        .and(query_param_is_missing("versionId"))
         .respond_with(
-            ResponseTemplate::new(206)
-                .insert_header("content-type", "application/octet-stream")
-                .insert_header("content-length", "100")
-                .set_body_bytes(vec![0u8; 100]),
+            ResponseTemplate::new(204)
+                .insert_header("x-oss-delete-marker", "true")
+                .insert_header("x-oss-version-id", "marker-v1"),
         )
         .expect(1)
         .mount(&server)
         .await;
 
     let client = mock_client(&server);
-    let request = GetObjectRequestBuilder::new()
+    let request = DeleteObjectRequestBuilder::new()
         .bucket(BucketName::new("my-bucket").unwrap())
-        .key(ObjectKey::new("big-file.bin").unwrap())
-        .range("bytes=0-99")
+        .key(ObjectKey::new("old-file.txt").unwrap())
         .build()
         .unwrap();
 
-    let response = client.get_object(request).await.unwrap();
-    assert_eq!(response.content_length, Some(100));
+    let response = client.delete_object(request).await.unwrap();
+    assert!(response.delete_marker);
+    assert_eq!(response.version_id.as_deref(), Some("marker-v1"));
 }
 
-// ---- DeleteObject ----
-
 #[tokio::test]
-async fn delete_object_returns_request_id() {
+async fn delete_object_version_sends_version_id() {
     let server = MockServer::start().await;
 
     Mock::given(method("DELETE"))
         .and(path("/old-file.txt"))
-        .respond_with(ResponseTemplate::new(204).insert_header("x-oss-request-id", "DEL-001"))
+        .and(query_param("versionId", "v42"))
+        .respond_with(ResponseTemplate::new(204).insert_header("x-oss-version-id", "v42"))
         .expect(1)
         .mount(&server)
         .await;
@@ -165,11 +1804,13 @@ async fn delete_object_returns_request_id() {
     let request = DeleteObjectRequestBuilder::new()
         .bucket(BucketName::new("my-bucket").unwrap())
         .key(ObjectKey::new("old-file.txt").unwrap())
+        .version_id("v42")
         .build()
         .unwrap();
 
     let response = client.delete_object(request).await.unwrap();
-    assert_eq!(response.request_id.as_deref(), Some("DEL-001"));
+    assert!(!response.delete_marker);
+    assert_eq!(response.version_id.as_deref(), Some("v42"));
 }
 
 // ---- HeadObject ----
@@ -186,6 +1827,19 @@ async fn head_object_returns_headers_and_metadata() {
                 .insert_header("content-length", "42")
                 .insert_header("etag", "\"head-etag\"")
                 .insert_header("last-modified", "Sat, 01 Jan 2025 00:00:00 GMT")
+                .insert_header("x-oss-hash-crc64ecma", "5981764153023615706")
+                .insert_header("x-oss-storage-class", "IA")
+                .insert_header("x-oss-object-type", "Appendable")
+                .insert_header(
+                    "x-oss-version-id",
+                    "CAEQNhiBgMDJgZCA0BYiIDc4MGZjZGI2OTBjOTRmNTE5NmU5NmFhZjhjYmY0****",
+                )
+                .insert_header("x-oss-server-side-encryption", "KMS")
+                .insert_header("x-oss-server-side-encryption-key-id", "key-123")
+                .insert_header(
+                    "x-oss-expiration",
+                    "expiry-date=\"Sat, 01 Jan 2022 00:00:00 GMT\", rule-id=\"expire-logs\"",
+                )
                 .insert_header("x-oss-meta-author", "bob")
                 .insert_header("x-oss-meta-project", "demo"),
         )
@@ -205,6 +1859,17 @@ async fn head_object_returns_headers_and_metadata() {
     assert_eq!(response.content_length, Some(42));
     assert_eq!(response.etag.as_deref(), Some("head-etag"));
     assert!(response.last_modified.is_some());
+    assert_eq!(response.crc64, Some(5981764153023615706));
+    assert_eq!(response.storage_class, Some(StorageClass::InfrequentAccess));
+    assert_eq!(response.object_type, Some(ObjectType::Appendable));
+    assert!(response.version_id.unwrap().starts_with("CAEQ"));
+    assert_eq!(
+        response.server_side_encryption,
+        Some(ServerSideEncryption::KMS)
+    );
+    assert_eq!(response.kms_key_id.as_deref(), Some("key-123"));
+    assert_eq!(response.restore, None);
+    assert_eq!(response.expiration.unwrap().rule_id, "expire-logs");
     assert_eq!(
         response.metadata.get("author").map(|s| s.as_str()),
         Some("bob")
@@ -344,6 +2009,10 @@ async fn copy_object_parses_xml_response() {
         .respond_with(
             ResponseTemplate::new(200)
                 .insert_header("content-type", "application/xml")
+                .insert_header("x-oss-hash-crc64ecma", "42")
+                .insert_header("x-oss-version-id", "dest-v2")
+                .insert_header("x-oss-copy-source-version-id", "src-v1")
+                .insert_header("x-oss-server-side-encryption", "AES256")
                 .set_body_string(xml),
         )
         .expect(1)
@@ -367,6 +2036,272 @@ async fn copy_object_parses_xml_response() {
             .unwrap()
     );
     assert_eq!(response.etag, "\"copy-etag-abc\"");
+    assert_eq!(response.crc64, Some(42));
+    assert_eq!(response.version_id.as_deref(), Some("dest-v2"));
+    assert_eq!(response.source_version_id.as_deref(), Some("src-v1"));
+    assert_eq!(
+        response.server_side_encryption,
+        Some(ServerSideEncryption::AES256)
+    );
+    assert_eq!(response.kms_key_id, None);
+}
+
+/// Mount a `CopyObject` response for `dest-key.txt` copied from
+/// `src-bucket/src-key.txt`.
+async fn mount_single_copy(server: &MockServer) {
+    Mock::given(method("PUT"))
+        .and(path("/dest-key.txt"))
+        .and(header("x-oss-copy-source", "/src-bucket/src-key.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            "<CopyObjectResult><LastModified>2025-02-01T12:00:00.000Z</LastModified>\
+             <ETag>\"e\"</ETag></CopyObjectResult>",
+        ))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+fn single_copy_request() -> rs_ali_oss::types::request::CopyObjectRequest {
+    CopyObjectRequestBuilder::new()
+        .bucket(BucketName::new("dest-bucket").unwrap())
+        .key(ObjectKey::new("dest-key.txt").unwrap())
+        .source_bucket(BucketName::new("src-bucket").unwrap())
+        .source_key(ObjectKey::new("src-key.txt").unwrap())
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn copy_object_skips_head_by_default() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("HEAD"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+    mount_single_copy(&server).await;
+
+    mock_client(&server)
+        .copy_object(single_copy_request())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn copy_object_copies_directly_when_source_lookup_fails() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("HEAD"))
+        .and(path("/src-key.txt"))
+        .respond_with(ResponseTemplate::new(403))
+        .expect(1)
+        .mount(&server)
+        .await;
+    mount_single_copy(&server).await;
+
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .multipart_copy(true)
+            .max_retries(0),
+    )
+    .unwrap();
+    client.copy_object(single_copy_request()).await.unwrap();
+}
+
+#[tokio::test]
+async fn copy_object_falls_back_to_multipart_above_threshold() {
+    const MIB: u64 = 1024 * 1024;
+    let server = MockServer::start().await;
+
+    Mock::given(method("HEAD"))
+        .and(path("/big.bin"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-length", (300 * MIB).to_string())
+                .insert_header("content-type", "video/mp4")
+                .insert_header("cache-control", "max-age=60")
+                .insert_header("content-disposition", "attachment")
+                .insert_header("content-encoding", "identity")
+                .insert_header("expires", "Wed, 21 Oct 2026 07:28:00 GMT")
+                .insert_header("etag", "\"src-etag\"")
+                .insert_header("x-oss-server-side-encryption", "KMS")
+                .insert_header("x-oss-server-side-encryption-key-id", "key-1")
+                .insert_header("x-oss-meta-owner", "alice"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/big.bin"))
+        .and(query_param("tagging", ""))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            "<Tagging><TagSet><Tag><Key>team</Key><Value>media</Value></Tag></TagSet></Tagging>",
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/big-copy.bin"))
+        .and(query_param("uploads", ""))
+        .and(header("content-type", "video/mp4"))
+        .and(header("cache-control", "max-age=60"))
+        .and(header("content-disposition", "attachment"))
+        .and(header("content-encoding", "identity"))
+        .and(header_regex("expires", "^Wed, 21 Oct 2026 07:28:00 GMT$"))
+        .and(header("x-oss-server-side-encryption", "KMS"))
+        .and(header("x-oss-server-side-encryption-key-id", "key-1"))
+        .and(header("x-oss-tagging", "team=media"))
+        .and(header("x-oss-meta-owner", "alice"))
+        .and(header("x-oss-storage-class", "IA"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            "<InitiateMultipartUploadResult><Bucket>dest-bucket</Bucket>\
+             <Key>big-copy.bin</Key><UploadId>UP-1</UploadId></InitiateMultipartUploadResult>",
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let ranges = [
+        (1, 0, 128 * MIB - 1),
+        (2, 128 * MIB, 256 * MIB - 1),
+        (3, 256 * MIB, 300 * MIB - 1),
+    ];
+    for (part_number, start, end) in ranges {
+        Mock::given(method("PUT"))
+            .and(path("/big-copy.bin"))
+            .and(query_param("uploadId", "UP-1"))
+            .and(query_param("partNumber", part_number.to_string()))
+            .and(header("x-oss-copy-source", "/src-bucket/big.bin"))
+            .and(header(
+                "x-oss-copy-source-range",
+                format!("bytes={start}-{end}"),
+            ))
+            .and(header("x-oss-copy-source-if-match", "src-etag"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                "<CopyPartResult><LastModified>2025-02-01T12:00:0{part_number}.000Z</LastModified>\
+                 <ETag>\"part-{part_number}\"</ETag></CopyPartResult>"
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("POST"))
+        .and(path("/big-copy.bin"))
+        .and(query_param("uploadId", "UP-1"))
+        .and(wiremock::matchers::body_string_contains(
+            "<PartNumber>3</PartNumber><ETag>\"part-3\"</ETag>",
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("x-oss-hash-crc64ecma", "7")
+                .set_body_string(
+                    "<CompleteMultipartUploadResult><Location>loc</Location>\
+                     <Bucket>dest-bucket</Bucket><Key>big-copy.bin</Key>\
+                     <ETag>\"final-etag\"</ETag></CompleteMultipartUploadResult>",
+                ),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .multipart_copy(true)
+            .multipart_copy_threshold(200 * MIB)
+            .max_retries(0),
+    )
+    .unwrap();
+    let request = CopyObjectRequestBuilder::new()
+        .bucket(BucketName::new("dest-bucket").unwrap())
+        .key(ObjectKey::new("big-copy.bin").unwrap())
+        .source_bucket(BucketName::new("src-bucket").unwrap())
+        .source_key(ObjectKey::new("big.bin").unwrap())
+        .storage_class(StorageClass::InfrequentAccess)
+        .build()
+        .unwrap();
+
+    let response = client.copy_object(request).await.unwrap();
+    assert_eq!(response.etag, "\"final-etag\"");
+    assert_eq!(response.crc64, Some(7));
+    assert_eq!(
+        response.last_modified,
+        "2025-02-01T12:00:03Z"
+            .parse::<chrono::DateTime<chrono::Utc>>()
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn copy_object_aborts_multipart_copy_on_part_failure() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("HEAD"))
+        .and(path("/big.bin"))
+        .respond_with(ResponseTemplate::new(200).insert_header("content-length", "2048"))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(query_param("tagging", ""))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string("<Tagging><TagSet></TagSet></Tagging>"),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(query_param("uploads", ""))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            "<InitiateMultipartUploadResult><Bucket>dest-bucket</Bucket>\
+             <Key>big-copy.bin</Key><UploadId>UP-1</UploadId></InitiateMultipartUploadResult>",
+        ))
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(query_param("uploadId", "UP-1"))
+        .respond_with(ResponseTemplate::new(412).set_body_string(
+            "<Error><Code>PreconditionFailed</Code><Message>changed</Message></Error>",
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/big-copy.bin"))
+        .and(query_param("uploadId", "UP-1"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .multipart_copy(true)
+            .multipart_copy_threshold(1024)
+            .max_retries(0),
+    )
+    .unwrap();
+    let request = CopyObjectRequestBuilder::new()
+        .bucket(BucketName::new("dest-bucket").unwrap())
+        .key(ObjectKey::new("big-copy.bin").unwrap())
+        .source_bucket(BucketName::new("src-bucket").unwrap())
+        .source_key(ObjectKey::new("big.bin").unwrap())
+        .build()
+        .unwrap();
+
+    assert!(client.copy_object(request).await.is_err());
 }
 
 // ---- DeleteMultipleObjects ----
@@ -476,6 +2411,37 @@ async fn server_error_404_returns_oss_error() {
     );
 }
 
+#[tokio::test]
+async fn head_object_error_takes_request_id_from_headers() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("HEAD"))
+        .and(path("/missing.txt"))
+        .respond_with(
+            ResponseTemplate::new(404)
+                .insert_header("x-oss-request-id", "HEAD-404-REQ")
+                .insert_header("x-oss-ec", "0026-00000001"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let request = HeadObjectRequestBuilder::new()
+        .bucket(BucketName::new("my-bucket").unwrap())
+        .key(ObjectKey::new("missing.txt").unwrap())
+        .build()
+        .unwrap();
+
+    let err = client.head_object(request).await.unwrap_err();
+    let err_str = err.to_string();
+    assert!(
+        err_str.contains("request id: HEAD-404-REQ"),
+        "error: {err_str}"
+    );
+    assert!(err_str.contains("EC: 0026-00000001"), "error: {err_str}");
+}
+
 #[tokio::test]
 async fn server_error_403_returns_access_denied() {
     let server = MockServer::start().await;
@@ -0,0 +1,216 @@
+//! Integration tests for the listing paginators.
+
+use rs_ali_oss::config::ClientBuilder;
+use rs_ali_oss::types::common::BucketName;
+use rs_ali_oss::{OssClient, ResumeState};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+fn mock_client(server: &MockServer) -> OssClient {
+    OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .max_retries(0),
+    )
+    .unwrap()
+}
+
+fn bucket() -> BucketName {
+    BucketName::new("test-bucket").unwrap()
+}
+
+/// Answers ListObjectsV2 with `count` keys named `key-000`, `key-001`, ...,
+/// honouring `max-keys` and `continuation-token`.
+struct KeysResponder(usize);
+
+impl Respond for KeysResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let param = |name: &str| {
+            request
+                .url
+                .query_pairs()
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v.into_owned())
+        };
+        let max_keys: usize = param("max-keys").map_or(1000, |v| v.parse().unwrap());
+        let start: usize = param("continuation-token").map_or(0, |v| v.parse().unwrap());
+        let end = (start + max_keys).min(self.0);
+
+        let mut xml = format!(
+            "<ListBucketResult><Name>test-bucket</Name><MaxKeys>{max_keys}</MaxKeys>\
+             <KeyCount>{}</KeyCount><IsTruncated>{}</IsTruncated>",
+            end - start,
+            end < self.0
+        );
+        if end < self.0 {
+            xml.push_str(&format!(
+                "<NextContinuationToken>{end}</NextContinuationToken>"
+            ));
+        }
+        for i in start..end {
+            xml.push_str(&format!(
+                "<Contents><Key>key-{i:03}</Key>\
+                 <LastModified>2025-01-01T00:00:00.000Z</LastModified>\
+                 <ETag>\"etag\"</ETag><Size>1</Size>\
+                 <StorageClass>Standard</StorageClass></Contents>"
+            ));
+        }
+        xml.push_str("</ListBucketResult>");
+        ResponseTemplate::new(200).set_body_string(xml)
+    }
+}
+
+async fn mount_keys(server: &MockServer, count: usize) {
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .and(query_param("list-type", "2"))
+        .respond_with(KeysResponder(count))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn paginator_collects_every_page() {
+    let server = MockServer::start().await;
+    mount_keys(&server, 25).await;
+
+    let objects = mock_client(&server)
+        .list_objects_v2_paginator(bucket())
+        .max_keys(10)
+        .build()
+        .collect_all()
+        .await
+        .unwrap();
+    assert_eq!(objects.len(), 25);
+    assert_eq!(objects[24].key, "key-024");
+}
+
+#[tokio::test]
+async fn paginator_stops_at_max_pages() {
+    let server = MockServer::start().await;
+    mount_keys(&server, 25).await;
+
+    let mut paginator = mock_client(&server)
+        .list_objects_v2_paginator(bucket())
+        .max_keys(10)
+        .max_pages(2)
+        .build();
+    let mut pages = 0;
+    while let Some(page) = paginator.next_page().await.unwrap() {
+        assert_eq!(page.contents.len(), 10);
+        pages += 1;
+    }
+    assert_eq!(pages, 2);
+    assert_eq!(paginator.pages_fetched(), 2);
+    assert_eq!(paginator.items_fetched(), 20);
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn paginator_stops_at_max_items() {
+    let server = MockServer::start().await;
+    mount_keys(&server, 25).await;
+
+    let objects = mock_client(&server)
+        .list_objects_v2_paginator(bucket())
+        .max_keys(10)
+        .max_items(13)
+        .build()
+        .collect_all()
+        .await
+        .unwrap();
+    assert_eq!(objects.len(), 13);
+    assert_eq!(objects[12].key, "key-012");
+
+    // The last page only asks for the objects still needed.
+    let requests = server.received_requests().await.unwrap();
+    let max_keys: Vec<String> = requests
+        .iter()
+        .filter_map(|r| {
+            r.url
+                .query_pairs()
+                .find(|(k, _)| k == "max-keys")
+                .map(|(_, v)| v.into_owned())
+        })
+        .collect();
+    assert_eq!(max_keys, ["10", "3"]);
+}
+
+#[tokio::test]
+async fn bucket_paginator_stops_at_max_items() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            "<ListAllMyBucketsResult><Prefix></Prefix><Marker></Marker>\
+             <MaxKeys>2</MaxKeys><IsTruncated>true</IsTruncated>\
+             <NextMarker>bucket-two</NextMarker><Buckets>\
+             <Bucket><Name>bucket-one</Name><Location>oss-cn-hangzhou</Location>\
+             <CreationDate>2025-01-01T00:00:00.000Z</CreationDate>\
+             <StorageClass>Standard</StorageClass></Bucket>\
+             <Bucket><Name>bucket-two</Name><Location>oss-cn-hangzhou</Location>\
+             <CreationDate>2025-01-01T00:00:00.000Z</CreationDate>\
+             <StorageClass>Standard</StorageClass></Bucket>\
+             </Buckets></ListAllMyBucketsResult>",
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut paginator = mock_client(&server)
+        .list_buckets_paginator()
+        .max_items(1)
+        .build();
+    let page = paginator.next_page().await.unwrap().unwrap();
+    assert_eq!(page.buckets.bucket.len(), 1);
+    assert_eq!(page.buckets.bucket[0].name, "bucket-one");
+    assert!(paginator.next_page().await.unwrap().is_none());
+    assert_eq!(paginator.pages_fetched(), 1);
+    assert_eq!(paginator.items_fetched(), 1);
+}
+
+#[tokio::test]
+async fn paginator_resumes_from_saved_state() {
+    let server = MockServer::start().await;
+    mount_keys(&server, 25).await;
+    let client = mock_client(&server);
+
+    let mut paginator = client
+        .list_objects_v2_paginator(bucket())
+        .max_keys(10)
+        .build();
+    paginator.next_page().await.unwrap().unwrap();
+    let json = serde_json::to_string(&paginator.resume_state()).unwrap();
+    drop(paginator);
+
+    let state: ResumeState = serde_json::from_str(&json).unwrap();
+    assert_eq!(state.token.as_deref(), Some("10"));
+    assert_eq!(state.pages_fetched, 1);
+    let mut paginator = client
+        .list_objects_v2_paginator(bucket())
+        .max_keys(10)
+        .resume_from(state)
+        .build();
+    let mut keys = Vec::new();
+    while let Some(page) = paginator.next_page().await.unwrap() {
+        keys.extend(page.contents.into_iter().map(|o| o.key));
+    }
+    assert_eq!(keys.len(), 15);
+    assert_eq!(keys[0], "key-010");
+    assert_eq!(paginator.pages_fetched(), 3);
+    assert_eq!(paginator.items_fetched(), 25);
+
+    // A finished listing stays finished.
+    let state = paginator.resume_state();
+    assert!(state.done);
+    let mut paginator = client
+        .list_objects_v2_paginator(bucket())
+        .resume_from(state)
+        .build();
+    assert!(paginator.next_page().await.unwrap().is_none());
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+}
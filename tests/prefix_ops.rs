@@ -0,0 +1,788 @@
+//! Integration tests for helpers that operate on every object under a prefix.
+
+use rs_ali_oss::OssClient;
+use rs_ali_oss::config::ClientBuilder;
+use rs_ali_oss::crc64;
+use rs_ali_oss::ops::bulk::VersionedDeletion;
+use rs_ali_oss::ops::manifest::{Manifest, VerifyIssueKind};
+use rs_ali_oss::types::common::{BucketName, ObjectAcl, StorageClass};
+use wiremock::matchers::{header, header_exists, method, path, path_regex, query_param};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+fn mock_client(server: &MockServer) -> OssClient {
+    OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .max_retries(0),
+    )
+    .unwrap()
+}
+
+fn bucket() -> BucketName {
+    BucketName::new("test-bucket").unwrap()
+}
+
+/// Answers ListObjectsV2 from a fixed, sorted set of keys, honouring
+/// `prefix`, `delimiter`, `max-keys` and `continuation-token` like OSS.
+struct ListingResponder(Vec<&'static str>);
+
+impl Respond for ListingResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let param = |name: &str| {
+            request
+                .url
+                .query_pairs()
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v.into_owned())
+        };
+        let prefix = param("prefix").unwrap_or_default();
+        let delimiter = param("delimiter").filter(|d| !d.is_empty());
+        let max_keys: usize = param("max-keys").map_or(1000, |v| v.parse().unwrap());
+        let after = param("continuation-token").unwrap_or_default();
+
+        let mut entries: Vec<(String, bool)> = Vec::new();
+        for key in self.0.iter().filter(|k| k.starts_with(&prefix)) {
+            let rest = &key[prefix.len()..];
+            let entry = match delimiter
+                .as_deref()
+                .and_then(|d| rest.find(d).map(|i| (i, d)))
+            {
+                Some((i, d)) => (format!("{prefix}{}", &rest[..i + d.len()]), true),
+                None => (key.to_string(), false),
+            };
+            if entry.0 > after && entries.last() != Some(&entry) {
+                entries.push(entry);
+            }
+        }
+        let truncated = entries.len() > max_keys;
+        entries.truncate(max_keys);
+
+        let mut xml = format!(
+            "<ListBucketResult><Name>test-bucket</Name><Prefix>{prefix}</Prefix>\
+             <MaxKeys>{max_keys}</MaxKeys><KeyCount>{}</KeyCount>\
+             <IsTruncated>{truncated}</IsTruncated>",
+            entries.len()
+        );
+        if truncated && let Some((last, _)) = entries.last() {
+            xml.push_str(&format!(
+                "<NextContinuationToken>{last}</NextContinuationToken>"
+            ));
+        }
+        // OSS lists all objects before all common prefixes.
+        for (key, _) in entries.iter().filter(|(_, is_prefix)| !is_prefix) {
+            xml.push_str(&format!(
+                "<Contents><Key>{key}</Key>\
+                 <LastModified>2025-01-01T00:00:00.000Z</LastModified>\
+                 <ETag>\"etag-{key}\"</ETag><Size>{}</Size>\
+                 <StorageClass>Standard</StorageClass></Contents>",
+                key.len()
+            ));
+        }
+        for (key, _) in entries.iter().filter(|(_, is_prefix)| *is_prefix) {
+            xml.push_str(&format!(
+                "<CommonPrefixes><Prefix>{key}</Prefix></CommonPrefixes>"
+            ));
+        }
+        xml.push_str("</ListBucketResult>");
+        ResponseTemplate::new(200).set_body_string(xml)
+    }
+}
+
+async fn mount_listing(server: &MockServer, keys: Vec<&'static str>) {
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .and(query_param("list-type", "2"))
+        .respond_with(ListingResponder(keys))
+        .mount(server)
+        .await;
+}
+
+/// Answers DeleteMultipleObjects by reporting every requested entry as
+/// deleted, except keys containing `locked`.
+struct DeleteResponder;
+
+impl Respond for DeleteResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body = String::from_utf8_lossy(&request.body);
+        let mut xml = String::from("<DeleteResult>");
+        for entry in body.split("<Object>").skip(1) {
+            let field = |name: &str| {
+                let start = entry.find(&format!("<{name}>"))? + name.len() + 2;
+                let end = entry.find(&format!("</{name}>"))?;
+                Some(entry[start..end].to_string())
+            };
+            let key = field("Key").unwrap();
+            if key.contains("locked") {
+                continue;
+            }
+            xml.push_str(&format!("<Deleted><Key>{key}</Key>"));
+            if let Some(version_id) = field("VersionId") {
+                xml.push_str(&format!("<VersionId>{version_id}</VersionId>"));
+            }
+            xml.push_str("</Deleted>");
+        }
+        xml.push_str("</DeleteResult>");
+        ResponseTemplate::new(200).set_body_string(xml)
+    }
+}
+
+const TREE: &[&str] = &[
+    "photos/2024/a.jpg",
+    "photos/2024/b.jpg",
+    "photos/2024/raw/a.cr2",
+    "photos/cover.jpg",
+    "photos/thumbnails/a.jpg",
+    "videos/v.mp4",
+];
+
+// ---- walk ----
+
+#[tokio::test]
+async fn walk_yields_directories_before_their_contents() {
+    let server = MockServer::start().await;
+    mount_listing(&server, TREE.to_vec()).await;
+
+    let entries = mock_client(&server)
+        .walk(bucket(), "photos/")
+        .max_keys(2)
+        .build()
+        .collect_all()
+        .await
+        .unwrap();
+    let listed: Vec<(&str, usize, bool)> = entries
+        .iter()
+        .map(|e| (e.key(), e.depth(), e.is_directory()))
+        .collect();
+    assert_eq!(
+        listed,
+        [
+            ("photos/2024/", 1, true),
+            ("photos/2024/a.jpg", 2, false),
+            ("photos/2024/b.jpg", 2, false),
+            ("photos/2024/raw/", 2, true),
+            ("photos/2024/raw/a.cr2", 3, false),
+            ("photos/cover.jpg", 1, false),
+            ("photos/thumbnails/", 1, true),
+            ("photos/thumbnails/a.jpg", 2, false),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn walk_honours_max_depth_and_pruning() {
+    let server = MockServer::start().await;
+    mount_listing(&server, TREE.to_vec()).await;
+    let client = mock_client(&server);
+
+    let shallow = client
+        .walk(bucket(), "")
+        .max_depth(1)
+        .build()
+        .collect_all()
+        .await
+        .unwrap();
+    let keys: Vec<&str> = shallow.iter().map(|e| e.key()).collect();
+    assert_eq!(keys, ["photos/", "videos/"]);
+
+    let mut walker = client
+        .walk(bucket(), "photos/")
+        .prune(|prefix, _| prefix.ends_with("/thumbnails/") || prefix.ends_with("/raw/"))
+        .build();
+    let mut keys = Vec::new();
+    while let Some(entry) = walker.next_entry().await.unwrap() {
+        keys.push(entry.key().to_string());
+    }
+    assert_eq!(
+        keys,
+        [
+            "photos/2024/",
+            "photos/2024/a.jpg",
+            "photos/2024/b.jpg",
+            "photos/2024/raw/",
+            "photos/cover.jpg",
+            "photos/thumbnails/",
+        ]
+    );
+}
+
+// ---- delete_prefix ----
+
+#[tokio::test]
+async fn delete_prefix_pages_listing_and_batches_deletes() {
+    let server = MockServer::start().await;
+    let keys: Vec<&'static str> = (0..2500)
+        .map(|i| &*Box::leak(format!("logs/{i:05}.log").into_boxed_str()))
+        .chain(["other/keep.txt"])
+        .collect();
+    mount_listing(&server, keys).await;
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(query_param("delete", ""))
+        .respond_with(DeleteResponder)
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let response = mock_client(&server)
+        .delete_prefix(bucket(), "logs/")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.deleted, 2500);
+    assert!(response.is_complete());
+}
+
+#[tokio::test]
+async fn delete_prefix_reports_failures_and_carries_on() {
+    let server = MockServer::start().await;
+    mount_listing(&server, vec!["tmp/a", "tmp/locked", "tmp/b"]).await;
+    Mock::given(method("POST"))
+        .and(query_param("delete", ""))
+        .respond_with(DeleteResponder)
+        .mount(&server)
+        .await;
+
+    let response = mock_client(&server)
+        .delete_prefix(bucket(), "tmp/")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.deleted, 2);
+    assert_eq!(response.failures.len(), 1);
+    assert_eq!(response.failures[0].key, "tmp/locked");
+    assert_eq!(response.failures[0].version_id, None);
+}
+
+#[tokio::test]
+async fn delete_prefix_removes_every_version_and_delete_marker() {
+    let server = MockServer::start().await;
+    let page = |truncated: bool, entries: &str| {
+        ResponseTemplate::new(200).set_body_string(format!(
+            "<ListVersionsResult><Name>test-bucket</Name><Prefix>tmp/</Prefix>\
+             <MaxKeys>1000</MaxKeys><IsTruncated>{truncated}</IsTruncated>\
+             <NextKeyMarker>tmp/a</NextKeyMarker>\
+             <NextVersionIdMarker>v1</NextVersionIdMarker>{entries}</ListVersionsResult>"
+        ))
+    };
+    Mock::given(method("GET"))
+        .and(query_param("versions", ""))
+        .and(query_param("key-marker", "tmp/a"))
+        .and(query_param("version-id-marker", "v1"))
+        .respond_with(page(
+            false,
+            "<DeleteMarker><Key>tmp/b</Key><VersionId>v3</VersionId>\
+             <IsLatest>true</IsLatest>\
+             <LastModified>2025-01-01T00:00:00.000Z</LastModified></DeleteMarker>",
+        ))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(query_param("versions", ""))
+        .respond_with(page(
+            true,
+            "<Version><Key>tmp/a</Key><VersionId>v2</VersionId><IsLatest>true</IsLatest>\
+             <LastModified>2025-01-01T00:00:00.000Z</LastModified>\
+             <ETag>\"e2\"</ETag><Size>1</Size><StorageClass>Standard</StorageClass></Version>\
+             <Version><Key>tmp/a</Key><VersionId>v1</VersionId><IsLatest>false</IsLatest>\
+             <LastModified>2025-01-01T00:00:00.000Z</LastModified>\
+             <ETag>\"e1\"</ETag><Size>1</Size><StorageClass>Standard</StorageClass></Version>",
+        ))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(query_param("delete", ""))
+        .respond_with(DeleteResponder)
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let response = mock_client(&server)
+        .delete_prefix(bucket(), "tmp/")
+        .versions(VersionedDeletion::AllVersions)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.deleted, 3);
+    assert!(response.is_complete());
+
+    let requests = server.received_requests().await.unwrap();
+    let delete = requests
+        .iter()
+        .find(|r| r.method.as_str() == "POST")
+        .unwrap();
+    let body = String::from_utf8_lossy(&delete.body);
+    for version_id in ["v1", "v2", "v3"] {
+        assert!(body.contains(&format!("<VersionId>{version_id}</VersionId>")));
+    }
+}
+
+#[tokio::test]
+async fn delete_prefix_records_failed_batches() {
+    let server = MockServer::start().await;
+    mount_listing(&server, vec!["tmp/a", "tmp/b"]).await;
+    Mock::given(method("POST"))
+        .and(query_param("delete", ""))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+
+    let response = mock_client(&server)
+        .delete_prefix(bucket(), "tmp/")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.deleted, 0);
+    let failed: Vec<&str> = response.failures.iter().map(|f| f.key.as_str()).collect();
+    assert_eq!(failed, ["tmp/a", "tmp/b"]);
+}
+
+#[tokio::test]
+async fn delete_bucket_force_empties_and_deletes_the_bucket() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(query_param("uploads", ""))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            "<ListMultipartUploadsResult><Bucket>test-bucket</Bucket>\
+             <MaxUploads>1000</MaxUploads><IsTruncated>false</IsTruncated>\
+             <Upload><Key>big.bin</Key><UploadId>u1</UploadId>\
+             <Initiated>2025-01-01T00:00:00.000Z</Initiated>\
+             <StorageClass>Standard</StorageClass></Upload>\
+             <Upload><Key>gone.bin</Key><UploadId>u2</UploadId>\
+             <Initiated>2025-01-01T00:00:00.000Z</Initiated>\
+             <StorageClass>Standard</StorageClass></Upload>\
+             <Upload><Key>a/../b</Key><UploadId>u3</UploadId>\
+             <Initiated>2025-01-01T00:00:00.000Z</Initiated>\
+             <StorageClass>Standard</StorageClass></Upload>\
+             </ListMultipartUploadsResult>",
+        ))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/big.bin"))
+        .and(query_param("uploadId", "u1"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/gone.bin"))
+        .and(query_param("uploadId", "u2"))
+        .respond_with(
+            ResponseTemplate::new(404)
+                .set_body_string("<Error><Code>NoSuchUpload</Code><Message>gone</Message></Error>"),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(query_param("versioning", ""))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            "<VersioningConfiguration><Status>Suspended</Status></VersioningConfiguration>",
+        ))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(query_param("versions", ""))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            "<ListVersionsResult><Name>test-bucket</Name><MaxKeys>1000</MaxKeys>\
+             <IsTruncated>false</IsTruncated>\
+             <Version><Key>a</Key><VersionId>v1</VersionId><IsLatest>true</IsLatest>\
+             <LastModified>2025-01-01T00:00:00.000Z</LastModified>\
+             <ETag>\"e\"</ETag><Size>1</Size><StorageClass>Standard</StorageClass></Version>\
+             <DeleteMarker><Key>b</Key><VersionId>v2</VersionId><IsLatest>true</IsLatest>\
+             <LastModified>2025-01-01T00:00:00.000Z</LastModified></DeleteMarker>\
+             </ListVersionsResult>",
+        ))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(query_param("delete", ""))
+        .respond_with(DeleteResponder)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/"))
+        .respond_with(ResponseTemplate::new(204).insert_header("x-oss-request-id", "req-1"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let response = mock_client(&server)
+        .delete_bucket_force(bucket())
+        .await
+        .unwrap();
+    assert_eq!(response.aborted_uploads, 1);
+    let skipped: Vec<_> = response
+        .abort_failures
+        .iter()
+        .map(|f| (f.key.as_str(), f.upload_id.as_str()))
+        .collect();
+    assert_eq!(skipped, [("a/../b", "u3")]);
+    assert_eq!(response.deleted, 2);
+    assert_eq!(response.request_id.as_deref(), Some("req-1"));
+
+    let requests = server.received_requests().await.unwrap();
+    let last = requests.last().unwrap();
+    assert_eq!((last.method.as_str(), last.url.path()), ("DELETE", "/"));
+}
+
+// ---- copy_prefix ----
+
+const COPY_RESULT: &str = "<CopyObjectResult>\
+    <LastModified>2025-01-01T00:00:00.000Z</LastModified>\
+    <ETag>\"etag\"</ETag></CopyObjectResult>";
+
+#[tokio::test]
+async fn copy_prefix_rewrites_keys_and_storage_class() {
+    let server = MockServer::start().await;
+    mount_listing(&server, TREE.to_vec()).await;
+    Mock::given(method("PUT"))
+        .and(path_regex("^/archive/2024/"))
+        .and(header_exists("x-oss-copy-source"))
+        .and(header("x-oss-storage-class", "Archive"))
+        .and(header("x-oss-object-acl", "private"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(COPY_RESULT))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let response = mock_client(&server)
+        .copy_prefix(bucket(), "photos/2024/", bucket(), "archive/2024/")
+        .storage_class(StorageClass::Archive)
+        .acl(ObjectAcl::Private)
+        .concurrency(2)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.copied, 3);
+    assert_eq!(
+        response.bytes,
+        [
+            "photos/2024/a.jpg",
+            "photos/2024/b.jpg",
+            "photos/2024/raw/a.cr2"
+        ]
+        .iter()
+        .map(|k| k.len() as u64)
+        .sum::<u64>()
+    );
+    assert!(response.is_complete());
+    assert!(response.planned.is_empty());
+
+    let requests = server.received_requests().await.unwrap();
+    let mut sources: Vec<String> = requests
+        .iter()
+        .filter(|r| r.method.as_str() == "PUT")
+        .map(|r| {
+            let source = r.headers.get("x-oss-copy-source").unwrap();
+            format!("{} <- {}", r.url.path(), source.to_str().unwrap())
+        })
+        .collect();
+    sources.sort();
+    assert_eq!(
+        sources,
+        [
+            "/archive/2024/a.jpg <- /test-bucket/photos/2024/a.jpg",
+            "/archive/2024/b.jpg <- /test-bucket/photos/2024/b.jpg",
+            "/archive/2024/raw/a.cr2 <- /test-bucket/photos/2024/raw/a.cr2",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn copy_prefix_dry_run_only_plans() {
+    let server = MockServer::start().await;
+    mount_listing(&server, TREE.to_vec()).await;
+    Mock::given(method("PUT"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(COPY_RESULT))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let other = BucketName::new("other-bucket").unwrap();
+    let response = mock_client(&server)
+        .copy_prefix(bucket(), "photos/thumbnails/", other, "")
+        .dry_run(true)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.copied, 1);
+    assert_eq!(response.planned.len(), 1);
+    assert_eq!(response.planned[0].source_key, "photos/thumbnails/a.jpg");
+    assert_eq!(response.planned[0].destination_key, "a.jpg");
+}
+
+#[tokio::test]
+async fn copy_prefix_records_failed_copies() {
+    let server = MockServer::start().await;
+    mount_listing(&server, TREE.to_vec()).await;
+    Mock::given(method("PUT"))
+        .and(path("/backup/cover.jpg"))
+        .respond_with(ResponseTemplate::new(403))
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(COPY_RESULT))
+        .mount(&server)
+        .await;
+
+    let response = mock_client(&server)
+        .copy_prefix(bucket(), "photos/", bucket(), "backup/")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.copied, 4);
+    assert_eq!(response.failures.len(), 1);
+    assert_eq!(response.failures[0].source_key, "photos/cover.jpg");
+}
+
+#[tokio::test]
+async fn copy_prefix_rejects_destination_inside_source() {
+    let server = MockServer::start().await;
+    let result = mock_client(&server)
+        .copy_prefix(bucket(), "photos/", bucket(), "photos/backup/")
+        .send()
+        .await;
+    assert!(matches!(
+        result,
+        Err(rs_ali_oss::OssError::InvalidParameter { field, .. }) if field == "destination_prefix"
+    ));
+}
+
+// ---- manifest ----
+
+/// Answers HeadObject with the CRC64 of the key itself, matching the
+/// object bodies written by `write_tree`.
+struct HeadResponder;
+
+impl Respond for HeadResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let key = &request.url.path()[1..];
+        ResponseTemplate::new(200)
+            .insert_header("content-length", key.len().to_string())
+            .insert_header(
+                "x-oss-hash-crc64ecma",
+                crc64::checksum(key.as_bytes()).to_string(),
+            )
+    }
+}
+
+/// Write each key under `prefix` to `dir`, with the key as its contents.
+fn write_tree(dir: &std::path::Path, prefix: &str) {
+    for key in TREE.iter().filter(|k| k.starts_with(prefix)) {
+        let path = dir.join(&key[prefix.len()..]);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, key).unwrap();
+    }
+}
+
+#[tokio::test]
+async fn manifest_records_objects_and_verifies_a_local_copy() {
+    let server = MockServer::start().await;
+    mount_listing(&server, TREE.to_vec()).await;
+    Mock::given(method("HEAD"))
+        .respond_with(HeadResponder)
+        .expect(5)
+        .mount(&server)
+        .await;
+
+    let manifest = mock_client(&server)
+        .manifest(bucket(), "photos/")
+        .concurrency(2)
+        .send()
+        .await
+        .unwrap();
+    let keys: Vec<&str> = manifest.entries.iter().map(|e| e.key.as_str()).collect();
+    assert_eq!(keys, TREE[..5]);
+    assert_eq!(manifest.entries[0].etag, "etag-photos/2024/a.jpg");
+    assert_eq!(
+        manifest.entries[0].crc64,
+        Some(crc64::checksum(b"photos/2024/a.jpg"))
+    );
+
+    let mut restored = Manifest::from_csv(&manifest.to_csv()).unwrap();
+    assert_eq!(restored.entries, manifest.entries);
+    restored.prefix = "photos/".into();
+
+    let dir = std::env::temp_dir().join(format!("rs-ali-oss-manifest-{}", std::process::id()));
+    write_tree(&dir, "photos/");
+    let report = restored.verify_dir(&dir).unwrap();
+    assert_eq!(report.matched, 5);
+    assert!(report.is_clean());
+
+    std::fs::write(dir.join("cover.jpg"), "photos/cover.JPG").unwrap();
+    std::fs::remove_file(dir.join("2024/b.jpg")).unwrap();
+    std::fs::write(dir.join("thumbnails/a.jpg"), "short").unwrap();
+    std::fs::write(dir.join("notes.txt"), "stray").unwrap();
+    let mut issues = restored.verify_dir(&dir).unwrap().issues;
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    issues.sort_by(|a, b| a.key.cmp(&b.key));
+    let issues: Vec<(&str, VerifyIssueKind)> =
+        issues.iter().map(|i| (i.key.as_str(), i.kind)).collect();
+    assert_eq!(issues.len(), 4);
+    assert_eq!(issues[0], ("photos/2024/b.jpg", VerifyIssueKind::Missing));
+    assert!(matches!(
+        issues[1],
+        ("photos/cover.jpg", VerifyIssueKind::Crc64Mismatch { .. })
+    ));
+    assert_eq!(issues[2], ("photos/notes.txt", VerifyIssueKind::Unexpected));
+    assert_eq!(
+        issues[3],
+        (
+            "photos/thumbnails/a.jpg",
+            VerifyIssueKind::SizeMismatch {
+                expected: 23,
+                actual: 5
+            }
+        )
+    );
+}
+
+#[tokio::test]
+async fn manifest_without_crc64_skips_head_requests() {
+    let server = MockServer::start().await;
+    mount_listing(&server, TREE.to_vec()).await;
+    Mock::given(method("HEAD"))
+        .respond_with(HeadResponder)
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let manifest = mock_client(&server)
+        .manifest(bucket(), "")
+        .crc64(false)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(manifest.entries.len(), TREE.len());
+    assert!(manifest.entries.iter().all(|e| e.crc64.is_none()));
+    assert_eq!(
+        manifest.total_size(),
+        TREE.iter().map(|k| k.len() as u64).sum::<u64>()
+    );
+}
+
+// ---- restore_prefix ----
+
+#[tokio::test]
+async fn restore_prefix_skips_restored_and_standard_objects() {
+    let server = MockServer::start().await;
+    let contents: String = [
+        ("dt=2024-06-01/a.parquet", "Standard"),
+        ("dt=2024-06-01/b.parquet", "Archive"),
+        ("dt=2024-06-01/c.parquet", "ColdArchive"),
+        ("dt=2024-06-01/d.parquet", "Archive"),
+        ("dt=2024-06-01/e.parquet", "Archive"),
+    ]
+    .iter()
+    .map(|(key, class)| {
+        format!(
+            "<Contents><Key>{key}</Key>\
+             <LastModified>2025-01-01T00:00:00.000Z</LastModified>\
+             <ETag>\"etag\"</ETag><Size>1</Size>\
+             <StorageClass>{class}</StorageClass></Contents>"
+        )
+    })
+    .collect();
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .and(query_param("list-type", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+            "<ListBucketResult><Name>test-bucket</Name><Prefix>dt=2024-06-01/</Prefix>\
+             <MaxKeys>1000</MaxKeys><KeyCount>5</KeyCount>\
+             <IsTruncated>false</IsTruncated>{contents}</ListBucketResult>"
+        )))
+        .mount(&server)
+        .await;
+    Mock::given(method("HEAD"))
+        .and(path("/dt=2024-06-01/c.parquet"))
+        .respond_with(
+            ResponseTemplate::new(200).insert_header("x-oss-restore", "ongoing-request=\"true\""),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("HEAD"))
+        .and(path("/dt=2024-06-01/d.parquet"))
+        .respond_with(ResponseTemplate::new(200).insert_header(
+            "x-oss-restore",
+            "ongoing-request=\"false\", expiry-date=\"Sun, 16 Apr 2017 08:12:33 GMT\"",
+        ))
+        .mount(&server)
+        .await;
+    Mock::given(method("HEAD"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/dt=2024-06-01/e.parquet"))
+        .and(query_param("restore", ""))
+        .respond_with(ResponseTemplate::new(409).set_body_string(
+            "<Error><Code>RestoreAlreadyInProgress</Code>\
+             <Message>The restore operation is in progress.</Message></Error>",
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/dt=2024-06-01/b.parquet"))
+        .and(query_param("restore", ""))
+        .and(wiremock::matchers::body_string_contains("<Days>3</Days>"))
+        .respond_with(ResponseTemplate::new(202))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let response = mock_client(&server)
+        .restore_prefix(bucket(), "dt=2024-06-01/")
+        .days(3)
+        .concurrency(2)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.requested, 1);
+    assert_eq!(response.in_progress, 2);
+    assert_eq!(response.already_restored, 1);
+    assert_eq!(response.not_archived, 1);
+    assert!(response.is_complete());
+}
+
+#[tokio::test]
+async fn restore_prefix_records_failed_restores() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .and(query_param("list-type", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            "<ListBucketResult><Name>test-bucket</Name><Prefix>cold/</Prefix>\
+             <MaxKeys>1000</MaxKeys><KeyCount>1</KeyCount><IsTruncated>false</IsTruncated>\
+             <Contents><Key>cold/x.bin</Key>\
+             <LastModified>2025-01-01T00:00:00.000Z</LastModified>\
+             <ETag>\"etag\"</ETag><Size>1</Size>\
+             <StorageClass>Archive</StorageClass></Contents></ListBucketResult>",
+        ))
+        .mount(&server)
+        .await;
+    Mock::given(method("HEAD"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(403))
+        .mount(&server)
+        .await;
+
+    let response = mock_client(&server)
+        .restore_prefix(bucket(), "cold/")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.requested, 0);
+    assert_eq!(response.failures.len(), 1);
+    assert_eq!(response.failures[0].key, "cold/x.bin");
+    assert!(!response.is_complete());
+}
@@ -1,11 +1,17 @@
 //! Integration tests for retry behavior using wiremock.
 
-use rs_ali_oss::OssClient;
-use rs_ali_oss::config::ClientBuilder;
-use rs_ali_oss::error::OssError;
+use std::sync::Arc;
+
+use rs_ali_oss::config::{ClientBuilder, OperationOptions};
+use rs_ali_oss::error::{OssError, TimeoutKind};
+use rs_ali_oss::middleware::{CircuitBreaker, CircuitState, Hedging};
+use rs_ali_oss::retry::{
+    DefaultRetryClassifier, RetryAttempt, RetryBudget, RetryClassifier, RetryDecision,
+};
 use rs_ali_oss::types::common::{BucketName, ObjectKey};
 use rs_ali_oss::types::request::{GetObjectRequestBuilder, PutObjectRequestBuilder};
-use wiremock::matchers::{method, path};
+use rs_ali_oss::{OssClient, ReplayableBody};
+use wiremock::matchers::{body_string, header, method, path};
 use wiremock::{Mock, MockServer, Respond, ResponseTemplate};
 
 fn mock_client_with_retries(server: &MockServer, max_retries: u32) -> OssClient {
@@ -186,6 +192,117 @@ async fn retry_with_buffered_body_succeeds() {
     assert_eq!(response.etag, "retry-etag");
 }
 
+fn streaming_body(data: &'static [u8]) -> reqwest::Body {
+    let chunks: Vec<Result<bytes::Bytes, std::io::Error>> = data
+        .chunks(4)
+        .map(|c| Ok(bytes::Bytes::from_static(c)))
+        .collect();
+    reqwest::Body::wrap_stream(futures_util::stream::iter(chunks))
+}
+
+fn fail_then_succeed() -> SequentialResponder {
+    SequentialResponder::new(vec![
+        ResponseTemplate::new(500).set_body_string(
+            r#"<Error><Code>InternalError</Code><Message>Oops</Message>
+            <RequestId>R1</RequestId><HostId>H1</HostId></Error>"#,
+        ),
+        ResponseTemplate::new(200).insert_header("etag", "\"stream-etag\""),
+    ])
+}
+
+#[tokio::test]
+async fn streaming_body_is_sent_once_without_retry() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/stream.txt"))
+        .and(body_string("hello streaming"))
+        .respond_with(fail_then_succeed())
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client_with_retries(&server, 3);
+    let request = PutObjectRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("stream.txt").unwrap())
+        .body(streaming_body(b"hello streaming"))
+        .build()
+        .unwrap();
+
+    let err = client.put_object(request).await.unwrap_err();
+    assert!(matches!(err, OssError::ServerError { status: 500, .. }));
+}
+
+#[tokio::test]
+async fn small_streaming_body_is_buffered_and_retried() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/stream.txt"))
+        .and(body_string("hello streaming"))
+        .respond_with(fail_then_succeed())
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .max_retries(1)
+            .base_retry_delay(std::time::Duration::from_millis(1))
+            .max_replay_buffer_size(1024),
+    )
+    .unwrap();
+    let request = PutObjectRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("stream.txt").unwrap())
+        .body(streaming_body(b"hello streaming"))
+        .build()
+        .unwrap();
+
+    let response = client.put_object(request).await.unwrap();
+    assert_eq!(response.etag, "stream-etag");
+}
+
+#[tokio::test]
+async fn replayable_body_is_recreated_for_retry() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/replay.txt"))
+        .and(header("content-length", "15"))
+        .and(body_string("hello streaming"))
+        .respond_with(fail_then_succeed())
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let created = Arc::new(std::sync::atomic::AtomicU32::new(0));
+    let counter = Arc::clone(&created);
+    let body = ReplayableBody::new(move || {
+        counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Ok(streaming_body(b"hello streaming"))
+    })
+    .content_length(15);
+
+    let client = mock_client_with_retries(&server, 1);
+    let request = PutObjectRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("replay.txt").unwrap())
+        .replayable_body(body)
+        .build()
+        .unwrap();
+
+    let response = client.put_object(request).await.unwrap();
+    assert_eq!(response.etag, "stream-etag");
+    assert_eq!(created.load(std::sync::atomic::Ordering::SeqCst), 2);
+}
+
 #[tokio::test]
 async fn no_retry_when_max_retries_is_zero() {
     let server = MockServer::start().await;
@@ -210,3 +327,447 @@ async fn no_retry_when_max_retries_is_zero() {
     let err = client.get_object(request).await.unwrap_err();
     assert!(matches!(err, OssError::ServerError { .. }));
 }
+
+#[tokio::test]
+async fn operation_options_override_max_retries() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/override.txt"))
+        .respond_with(ResponseTemplate::new(500).set_body_string(
+            r#"<Error><Code>InternalError</Code><Message>fail</Message>
+            <RequestId>R1</RequestId><HostId>H1</HostId></Error>"#,
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client_with_retries(&server, 3);
+    let options = OperationOptions::builder().max_retries(0).build().unwrap();
+    let request = GetObjectRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("override.txt").unwrap())
+        .build()
+        .unwrap();
+
+    let err = client
+        .get_object_with_options(request, options)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OssError::ServerError { .. }));
+}
+
+#[tokio::test]
+async fn operation_options_override_timeout() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/slow.txt"))
+        .respond_with(ResponseTemplate::new(200).set_delay(std::time::Duration::from_secs(5)))
+        .mount(&server)
+        .await;
+
+    let client = mock_client_with_retries(&server, 0);
+    let options = OperationOptions::builder()
+        .timeout(std::time::Duration::from_millis(50))
+        .build()
+        .unwrap();
+    let request = GetObjectRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("slow.txt").unwrap())
+        .build()
+        .unwrap();
+
+    let err = client
+        .with_options(options)
+        .get_object(request)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OssError::Timeout(_)));
+    assert_eq!(err.timeout_kind(), Some(TimeoutKind::Request));
+}
+
+#[tokio::test]
+async fn read_timeout_is_reported_as_read() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/stalled.txt"))
+        .respond_with(ResponseTemplate::new(200).set_delay(std::time::Duration::from_secs(5)))
+        .mount(&server)
+        .await;
+
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .read_timeout(std::time::Duration::from_millis(50))
+            .max_retries(0),
+    )
+    .unwrap();
+    let request = GetObjectRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("stalled.txt").unwrap())
+        .build()
+        .unwrap();
+
+    let err = client.get_object(request).await.unwrap_err();
+    assert_eq!(err.timeout_kind(), Some(TimeoutKind::Read), "{err:?}");
+}
+
+#[tokio::test]
+async fn retry_429_then_200_succeeds() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/throttled.txt"))
+        .respond_with(SequentialResponder::new(vec![
+            ResponseTemplate::new(429),
+            ResponseTemplate::new(200).set_body_bytes(b"ok"),
+        ]))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = mock_client_with_retries(&server, 1);
+    let request = GetObjectRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("throttled.txt").unwrap())
+        .build()
+        .unwrap();
+
+    let response = client.get_object(request).await.unwrap();
+    assert_eq!(response.body.bytes().await.unwrap().as_ref(), b"ok");
+}
+
+#[tokio::test]
+async fn retryable_status_codes_can_be_narrowed() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/throttled.txt"))
+        .respond_with(ResponseTemplate::new(429))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .max_retries(3)
+            .retryable_status_codes([]),
+    )
+    .unwrap();
+    let request = GetObjectRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("throttled.txt").unwrap())
+        .build()
+        .unwrap();
+
+    let err = client.get_object(request).await.unwrap_err();
+    assert!(matches!(err, OssError::ServerError { status: 429, .. }));
+}
+
+struct RetryRequestTimeout;
+
+impl RetryClassifier for RetryRequestTimeout {
+    fn classify(&self, attempt: &RetryAttempt<'_>) -> RetryDecision {
+        if attempt.code == Some("RequestTimeout") {
+            RetryDecision::Retry
+        } else {
+            DefaultRetryClassifier.classify(attempt)
+        }
+    }
+}
+
+#[tokio::test]
+async fn custom_classifier_retries_request_timeout() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/slow.txt"))
+        .respond_with(SequentialResponder::new(vec![
+            ResponseTemplate::new(400).set_body_string(
+                r#"<Error><Code>RequestTimeout</Code><Message>timeout</Message>
+                <RequestId>R1</RequestId><HostId>H1</HostId></Error>"#,
+            ),
+            ResponseTemplate::new(200).set_body_string("ok"),
+        ]))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .max_retries(2)
+            .base_retry_delay(std::time::Duration::from_millis(1))
+            .retry_classifier(Arc::new(RetryRequestTimeout)),
+    )
+    .unwrap();
+    let request = GetObjectRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("slow.txt").unwrap())
+        .build()
+        .unwrap();
+
+    let response = client.get_object(request).await.unwrap();
+    assert_eq!(response.body.bytes().await.unwrap().as_ref(), b"ok");
+}
+
+#[tokio::test]
+async fn custom_classifier_can_disable_retries() {
+    struct NeverRetry;
+
+    impl RetryClassifier for NeverRetry {
+        fn classify(&self, _attempt: &RetryAttempt<'_>) -> RetryDecision {
+            RetryDecision::Fail
+        }
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .max_retries(3)
+            .retry_classifier(Arc::new(NeverRetry)),
+    )
+    .unwrap();
+    let request = GetObjectRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("busy.txt").unwrap())
+        .build()
+        .unwrap();
+
+    let err = client.get_object(request).await.unwrap_err();
+    assert!(matches!(err, OssError::ServerError { status: 503, .. }));
+}
+
+#[tokio::test]
+async fn circuit_breaker_stops_retry_storm() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let breaker = Arc::new(
+        CircuitBreaker::builder()
+            .failure_threshold(2)
+            .cooldown(std::time::Duration::from_secs(60))
+            .build()
+            .unwrap(),
+    );
+    let client = mock_client_with_retries(&server, 5).circuit_breaker(breaker.clone());
+    let request = || {
+        GetObjectRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .key(ObjectKey::new("outage.txt").unwrap())
+            .build()
+            .unwrap()
+    };
+
+    let err = client.get_object(request()).await.unwrap_err();
+    let host = match err {
+        OssError::CircuitOpen { host, retry_after } => {
+            assert!(retry_after > std::time::Duration::from_secs(50));
+            host
+        }
+        other => panic!("expected CircuitOpen, got: {other:?}"),
+    };
+    assert_eq!(breaker.state(&host), CircuitState::Open);
+
+    // Later requests fail fast without reaching the server.
+    let err = client.get_object(request()).await.unwrap_err();
+    assert!(matches!(err, OssError::CircuitOpen { .. }));
+}
+
+#[tokio::test]
+async fn retry_budget_suppresses_retries_when_spent() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(4)
+        .mount(&server)
+        .await;
+
+    // At most one retry per request: each failing GET is retried once
+    // instead of three times.
+    let budget = Arc::new(
+        RetryBudget::builder()
+            .ratio(1.0)
+            .min_retries_per_second(0)
+            .window(std::time::Duration::from_secs(60))
+            .build()
+            .unwrap(),
+    );
+    let client = mock_client_with_retries(&server, 3).retry_budget(budget);
+    for _ in 0..2 {
+        let request = GetObjectRequestBuilder::new()
+            .bucket(BucketName::new("test-bucket").unwrap())
+            .key(ObjectKey::new("outage.txt").unwrap())
+            .build()
+            .unwrap();
+        assert!(client.get_object(request).await.is_err());
+    }
+}
+
+/// Responds slowly to the first request only.
+struct SlowFirstResponder(std::sync::atomic::AtomicUsize);
+
+impl Respond for SlowFirstResponder {
+    fn respond(&self, _request: &wiremock::Request) -> ResponseTemplate {
+        let n = self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        let response = ResponseTemplate::new(200).set_body_string(format!("response {n}"));
+        if n == 0 {
+            response.set_delay(std::time::Duration::from_secs(5))
+        } else {
+            response
+        }
+    }
+}
+
+#[tokio::test]
+async fn hedged_read_returns_first_success() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/hedged.txt"))
+        .respond_with(SlowFirstResponder(Default::default()))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let hedging = Arc::new(
+        Hedging::builder()
+            .initial_delay(std::time::Duration::from_millis(50))
+            .build()
+            .unwrap(),
+    );
+    let client = mock_client_with_retries(&server, 0).hedging(Arc::clone(&hedging));
+    let request = GetObjectRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("hedged.txt").unwrap())
+        .build()
+        .unwrap();
+
+    let start = std::time::Instant::now();
+    let response = client.get_object(request).await.unwrap();
+    assert_eq!(response.body.text().await.unwrap(), "response 1");
+    assert!(start.elapsed() < std::time::Duration::from_secs(2));
+    assert_eq!(hedging.hedged_requests(), 1);
+}
+
+#[tokio::test]
+async fn writes_are_not_hedged() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/hedged.txt"))
+        .respond_with(ResponseTemplate::new(200).set_delay(std::time::Duration::from_millis(200)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let hedging = Arc::new(
+        Hedging::builder()
+            .initial_delay(std::time::Duration::from_millis(10))
+            .build()
+            .unwrap(),
+    );
+    let client = mock_client_with_retries(&server, 0).hedging(Arc::clone(&hedging));
+    let request = PutObjectRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("hedged.txt").unwrap())
+        .body(b"data".to_vec())
+        .build()
+        .unwrap();
+
+    client.put_object(request).await.unwrap();
+    assert_eq!(hedging.hedged_requests(), 0);
+}
+
+#[tokio::test]
+async fn retry_waits_for_retry_after_hint() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/throttled.txt"))
+        .respond_with(SequentialResponder::new(vec![
+            ResponseTemplate::new(503).insert_header("retry-after", "0.3"),
+            ResponseTemplate::new(200).set_body_bytes(b"ok"),
+        ]))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = OssClient::from_builder(
+        ClientBuilder::new()
+            .access_key_id("test-key-id")
+            .access_key_secret("test-key-secret")
+            .region("cn-hangzhou")
+            .endpoint(server.uri())
+            .allow_insecure(true)
+            .max_retries(1)
+            .base_retry_delay(std::time::Duration::from_millis(1))
+            .max_retry_delay(std::time::Duration::from_secs(1)),
+    )
+    .unwrap();
+    let request = GetObjectRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("throttled.txt").unwrap())
+        .build()
+        .unwrap();
+
+    let start = std::time::Instant::now();
+    client.get_object(request).await.unwrap();
+    // The 300ms hint replaces the 1ms base delay.
+    assert!(start.elapsed() >= std::time::Duration::from_millis(300));
+}
+
+#[tokio::test]
+async fn retry_after_hint_is_capped_by_max_delay() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/throttled.txt"))
+        .respond_with(SequentialResponder::new(vec![
+            ResponseTemplate::new(503).insert_header("x-oss-retry-after", "3600"),
+            ResponseTemplate::new(200).set_body_bytes(b"ok"),
+        ]))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = mock_client_with_retries(&server, 1);
+    let request = GetObjectRequestBuilder::new()
+        .bucket(BucketName::new("test-bucket").unwrap())
+        .key(ObjectKey::new("throttled.txt").unwrap())
+        .build()
+        .unwrap();
+
+    let start = std::time::Instant::now();
+    client.get_object(request).await.unwrap();
+    assert!(start.elapsed() < std::time::Duration::from_secs(5));
+}